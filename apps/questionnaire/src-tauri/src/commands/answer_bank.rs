@@ -1,5 +1,7 @@
+use crate::commands::questionnaire::ColumnProfileDto;
 use crate::error_map::{map_core_error, AppErrorDto};
 use cs_core::answer_bank;
+use cs_core::domain::time::today_utc_date;
 use cs_core::storage::db::SqliteDb;
 use cs_core::storage::vault_db_path;
 use serde::{Deserialize, Serialize};
@@ -21,6 +23,9 @@ pub struct AnswerBankEntryDto {
     pub content_hash: String,
     pub created_at: String,
     pub updated_at: String,
+    pub status: String,
+    pub last_edited_by: Option<String>,
+    pub translations: Vec<LocalizedTextDto>,
 }
 
 impl From<answer_bank::AnswerBankEntry> for AnswerBankEntryDto {
@@ -40,6 +45,9 @@ impl From<answer_bank::AnswerBankEntry> for AnswerBankEntryDto {
             content_hash: value.content_hash,
             created_at: value.created_at,
             updated_at: value.updated_at,
+            status: value.status.as_str().to_string(),
+            last_edited_by: value.last_edited_by,
+            translations: value.translations.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -55,6 +63,8 @@ pub struct AnswerBankCreateInputDto {
     pub last_reviewed_at: Option<String>,
     pub tags: Vec<String>,
     pub source: String,
+    #[serde(default)]
+    pub translations: Vec<LocalizedTextDto>,
 }
 
 impl From<AnswerBankCreateInputDto> for answer_bank::AnswerBankCreateInput {
@@ -69,6 +79,7 @@ impl From<AnswerBankCreateInputDto> for answer_bank::AnswerBankCreateInput {
             last_reviewed_at: value.last_reviewed_at,
             tags: value.tags,
             source: value.source,
+            translations: value.translations.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -84,6 +95,7 @@ pub struct AnswerBankUpdatePatchDto {
     pub last_reviewed_at: Option<Option<String>>,
     pub tags: Option<Vec<String>>,
     pub source: Option<String>,
+    pub translations: Option<Vec<LocalizedTextDto>>,
 }
 
 impl From<AnswerBankUpdatePatchDto> for answer_bank::AnswerBankUpdatePatch {
@@ -98,6 +110,39 @@ impl From<AnswerBankUpdatePatchDto> for answer_bank::AnswerBankUpdatePatch {
             last_reviewed_at: value.last_reviewed_at,
             tags: value.tags,
             source: value.source,
+            translations: value
+                .translations
+                .map(|v| v.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalizedTextDto {
+    pub locale: String,
+    pub question_canonical: String,
+    pub answer_short: String,
+    pub answer_long: String,
+}
+
+impl From<answer_bank::LocalizedText> for LocalizedTextDto {
+    fn from(value: answer_bank::LocalizedText) -> Self {
+        Self {
+            locale: value.locale,
+            question_canonical: value.question_canonical,
+            answer_short: value.answer_short,
+            answer_long: value.answer_long,
+        }
+    }
+}
+
+impl From<LocalizedTextDto> for answer_bank::LocalizedText {
+    fn from(value: LocalizedTextDto) -> Self {
+        Self {
+            locale: value.locale,
+            question_canonical: value.question_canonical,
+            answer_short: value.answer_short,
+            answer_long: value.answer_long,
         }
     }
 }
@@ -117,6 +162,457 @@ impl From<AnswerBankListParamsDto> for answer_bank::ListParams {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerBankAliasDto {
+    pub alias_id: String,
+    pub entry_id: String,
+    pub alias_text: String,
+    pub source_question_id: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+impl From<answer_bank::AnswerBankAlias> for AnswerBankAliasDto {
+    fn from(value: answer_bank::AnswerBankAlias) -> Self {
+        Self {
+            alias_id: value.alias_id,
+            entry_id: value.entry_id,
+            alias_text: value.alias_text,
+            source_question_id: value.source_question_id,
+            created_by: value.created_by,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerBankRevisionDto {
+    pub entry_id: String,
+    pub revision: i64,
+    pub kind: String,
+    pub rolled_back_to: Option<i64>,
+    pub question_canonical: String,
+    pub answer_short: String,
+    pub answer_long: String,
+    pub notes: Option<String>,
+    pub evidence_links: Vec<String>,
+    pub owner: String,
+    pub last_reviewed_at: Option<String>,
+    pub tags: Vec<String>,
+    pub source: String,
+    pub content_hash: String,
+    pub created_by: String,
+    pub created_at: String,
+    pub translations: Vec<LocalizedTextDto>,
+    pub audit_seq: Option<i64>,
+}
+
+impl From<answer_bank::AnswerBankRevision> for AnswerBankRevisionDto {
+    fn from(value: answer_bank::AnswerBankRevision) -> Self {
+        Self {
+            entry_id: value.entry_id,
+            revision: value.revision,
+            kind: value.kind.as_str().to_string(),
+            rolled_back_to: value.rolled_back_to,
+            question_canonical: value.question_canonical,
+            answer_short: value.answer_short,
+            answer_long: value.answer_long,
+            notes: value.notes,
+            evidence_links: value.evidence_links,
+            owner: value.owner,
+            last_reviewed_at: value.last_reviewed_at,
+            tags: value.tags,
+            source: value.source,
+            content_hash: value.content_hash,
+            created_by: value.created_by,
+            created_at: value.created_at,
+            translations: value.translations.into_iter().map(Into::into).collect(),
+            audit_seq: value.audit_seq,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevisionFieldChangeDto {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerBankRevisionDiffDto {
+    pub entry_id: String,
+    pub from_revision: i64,
+    pub to_revision: i64,
+    pub changes: Vec<RevisionFieldChangeDto>,
+}
+
+impl From<answer_bank::AnswerBankRevisionDiff> for AnswerBankRevisionDiffDto {
+    fn from(value: answer_bank::AnswerBankRevisionDiff) -> Self {
+        Self {
+            entry_id: value.entry_id,
+            from_revision: value.from_revision,
+            to_revision: value.to_revision,
+            changes: value
+                .changes
+                .into_iter()
+                .map(|c| RevisionFieldChangeDto {
+                    field: c.field,
+                    before: c.before,
+                    after: c.after,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewPolicyDto {
+    pub scope: String,
+    pub target: String,
+    pub interval_days: i64,
+    pub updated_by: String,
+    pub updated_at: String,
+}
+
+impl From<answer_bank::ReviewPolicy> for ReviewPolicyDto {
+    fn from(value: answer_bank::ReviewPolicy) -> Self {
+        Self {
+            scope: value.scope.as_str().to_string(),
+            target: value.target,
+            interval_days: value.interval_days,
+            updated_by: value.updated_by,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverdueReviewDto {
+    pub entry_id: String,
+    pub question_canonical: String,
+    pub last_reviewed_at: Option<String>,
+    pub interval_days: i64,
+    pub due_on: Option<String>,
+    pub days_overdue: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnerOverdueReviewsDto {
+    pub owner: String,
+    pub entries: Vec<OverdueReviewDto>,
+}
+
+impl From<answer_bank::OwnerOverdueReviews> for OwnerOverdueReviewsDto {
+    fn from(value: answer_bank::OwnerOverdueReviews) -> Self {
+        Self {
+            owner: value.owner,
+            entries: value
+                .entries
+                .into_iter()
+                .map(|e| OverdueReviewDto {
+                    entry_id: e.entry_id,
+                    question_canonical: e.question_canonical,
+                    last_reviewed_at: e.last_reviewed_at,
+                    interval_days: e.interval_days,
+                    due_on: e.due_on,
+                    days_overdue: e.days_overdue,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerBankColumnMapDto {
+    pub question: String,
+    pub answer_long: String,
+    pub answer_short: Option<String>,
+    pub entry_id: Option<String>,
+    pub notes: Option<String>,
+    pub owner: Option<String>,
+    pub tags: Option<String>,
+    pub evidence_links: Option<String>,
+    pub source: Option<String>,
+}
+
+impl From<answer_bank::AnswerBankColumnMap> for AnswerBankColumnMapDto {
+    fn from(value: answer_bank::AnswerBankColumnMap) -> Self {
+        Self {
+            question: value.question,
+            answer_long: value.answer_long,
+            answer_short: value.answer_short,
+            entry_id: value.entry_id,
+            notes: value.notes,
+            owner: value.owner,
+            tags: value.tags,
+            evidence_links: value.evidence_links,
+            source: value.source,
+        }
+    }
+}
+
+impl From<AnswerBankColumnMapDto> for answer_bank::AnswerBankColumnMap {
+    fn from(value: AnswerBankColumnMapDto) -> Self {
+        Self {
+            question: value.question,
+            answer_long: value.answer_long,
+            answer_short: value.answer_short,
+            entry_id: value.entry_id,
+            notes: value.notes,
+            owner: value.owner,
+            tags: value.tags,
+            evidence_links: value.evidence_links,
+            source: value.source,
+        }
+    }
+}
+
+/// Columns of a file to import, plus a map guessed from their labels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerBankImportProfileDto {
+    pub columns: Vec<ColumnProfileDto>,
+    pub suggested_map: Option<AnswerBankColumnMapDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkImportRowDto {
+    pub row_number: i64,
+    pub action: String,
+    pub entry_id: Option<String>,
+    pub question: String,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerBankImportReportDto {
+    pub source_filename: String,
+    pub source_sha256: String,
+    pub dry_run: bool,
+    pub created: i64,
+    pub updated: i64,
+    pub unchanged: i64,
+    pub duplicates: i64,
+    pub conflicts: i64,
+    pub rows: Vec<BulkImportRowDto>,
+}
+
+impl From<answer_bank::AnswerBankImportReport> for AnswerBankImportReportDto {
+    fn from(value: answer_bank::AnswerBankImportReport) -> Self {
+        Self {
+            source_filename: value.source_filename,
+            source_sha256: value.source_sha256,
+            dry_run: value.dry_run,
+            created: value.created,
+            updated: value.updated,
+            unchanged: value.unchanged,
+            duplicates: value.duplicates,
+            conflicts: value.conflicts,
+            rows: value
+                .rows
+                .into_iter()
+                .map(|r| BulkImportRowDto {
+                    row_number: r.row_number,
+                    action: r.action.as_str().to_string(),
+                    entry_id: r.entry_id,
+                    question: r.question,
+                    message: r.message,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerBankExportSummaryDto {
+    pub format: String,
+    pub entry_count: i64,
+    pub sha256: String,
+    pub unresolved_variables: Vec<String>,
+}
+
+impl From<answer_bank::AnswerBankExportSummary> for AnswerBankExportSummaryDto {
+    fn from(value: answer_bank::AnswerBankExportSummary) -> Self {
+        Self {
+            format: value.format,
+            entry_count: value.entry_count,
+            sha256: value.sha256,
+            unresolved_variables: value.unresolved_variables,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroupDto {
+    pub entries: Vec<AnswerBankEntryDto>,
+    pub similarity: f64,
+}
+
+impl From<answer_bank::DuplicateGroup> for DuplicateGroupDto {
+    fn from(value: answer_bank::DuplicateGroup) -> Self {
+        Self {
+            entries: value.entries.into_iter().map(Into::into).collect(),
+            similarity: value.similarity,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultVariableDto {
+    pub name: String,
+    pub value: String,
+    pub updated_by: String,
+    pub updated_at: String,
+}
+
+impl From<answer_bank::VaultVariable> for VaultVariableDto {
+    fn from(value: answer_bank::VaultVariable) -> Self {
+        Self {
+            name: value.name,
+            value: value.value,
+            updated_by: value.updated_by,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderedEntryDto {
+    pub entry_id: String,
+    pub answer_short: String,
+    pub answer_long: String,
+    pub unresolved: Vec<String>,
+}
+
+impl From<answer_bank::RenderedEntry> for RenderedEntryDto {
+    fn from(value: answer_bank::RenderedEntry) -> Self {
+        Self {
+            entry_id: value.entry_id,
+            answer_short: value.answer_short,
+            answer_long: value.answer_long,
+            unresolved: value.unresolved,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedVariablesDto {
+    pub entry_id: String,
+    pub question_canonical: String,
+    pub names: Vec<String>,
+}
+
+impl From<answer_bank::UnresolvedVariables> for UnresolvedVariablesDto {
+    fn from(value: answer_bank::UnresolvedVariables) -> Self {
+        Self {
+            entry_id: value.entry_id,
+            question_canonical: value.question_canonical,
+            names: value.names,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerBankTagDto {
+    pub tag: String,
+    pub description: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+impl From<answer_bank::AnswerBankTag> for AnswerBankTagDto {
+    fn from(value: answer_bank::AnswerBankTag) -> Self {
+        Self {
+            tag: value.tag,
+            description: value.description,
+            created_by: value.created_by,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagUsageDto {
+    pub tag: String,
+    pub parent: Option<String>,
+    pub description: Option<String>,
+    pub defined: bool,
+    pub entry_count: i64,
+    pub total_count: i64,
+}
+
+impl From<answer_bank::TagUsage> for TagUsageDto {
+    fn from(value: answer_bank::TagUsage) -> Self {
+        Self {
+            tag: value.tag,
+            parent: value.parent,
+            description: value.description,
+            defined: value.defined,
+            entry_count: value.entry_count,
+            total_count: value.total_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagRenameReportDto {
+    pub from: String,
+    pub to: String,
+    pub renamed_tags: Vec<String>,
+    pub entry_ids: Vec<String>,
+    pub evidence_ids: Vec<String>,
+}
+
+impl From<answer_bank::TagRenameReport> for TagRenameReportDto {
+    fn from(value: answer_bank::TagRenameReport) -> Self {
+        Self {
+            from: value.from,
+            to: value.to,
+            renamed_tags: value.renamed_tags,
+            entry_ids: value.entry_ids,
+            evidence_ids: value.evidence_ids,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlMappingDto {
+    pub entry_id: String,
+    pub framework: String,
+    pub control_id: String,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+impl From<answer_bank::ControlMapping> for ControlMappingDto {
+    fn from(value: answer_bank::ControlMapping) -> Self {
+        Self {
+            entry_id: value.entry_id,
+            framework: value.framework,
+            control_id: value.control_id,
+            created_by: value.created_by,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlUsageDto {
+    pub framework: String,
+    pub control_id: String,
+    pub entry_count: i64,
+}
+
+impl From<answer_bank::ControlUsage> for ControlUsageDto {
+    fn from(value: answer_bank::ControlUsage) -> Self {
+        Self {
+            framework: value.framework,
+            control_id: value.control_id,
+            entry_count: value.entry_count,
+        }
+    }
+}
+
 pub fn ab_create_entry(
     vault_root: &str,
     input: AnswerBankCreateInputDto,
@@ -196,11 +692,387 @@ pub fn ab_link_evidence(
     Ok(out.into())
 }
 
-// Tauri Command Handlers
-
-use crate::app_state::AppState;
-use tauri::State;
-
+pub fn ab_list_aliases(
+    vault_root: &str,
+    entry_id: &str,
+) -> Result<Vec<AnswerBankAliasDto>, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_list_aliases(&db, entry_id).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+pub fn ab_add_alias(
+    vault_root: &str,
+    entry_id: &str,
+    alias_text: &str,
+    actor: &str,
+) -> Result<AnswerBankAliasDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out =
+        answer_bank::ab_add_alias(&db, entry_id, alias_text, actor).map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_delete_alias(vault_root: &str, alias_id: &str, actor: &str) -> Result<(), AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    answer_bank::ab_delete_alias(&db, alias_id, actor).map_err(map_core_error)?;
+    Ok(())
+}
+
+pub fn ab_list_revisions(
+    vault_root: &str,
+    entry_id: &str,
+) -> Result<Vec<AnswerBankRevisionDto>, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_list_revisions(&db, entry_id).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+pub fn ab_revision_at_audit_seq(
+    vault_root: &str,
+    entry_id: &str,
+    audit_seq: i64,
+) -> Result<AnswerBankRevisionDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out =
+        answer_bank::ab_revision_at_audit_seq(&db, entry_id, audit_seq).map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_diff_revisions(
+    vault_root: &str,
+    entry_id: &str,
+    from_revision: i64,
+    to_revision: i64,
+) -> Result<AnswerBankRevisionDiffDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_diff_revisions(&db, entry_id, from_revision, to_revision)
+        .map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_rollback_entry(
+    vault_root: &str,
+    entry_id: &str,
+    revision: i64,
+    actor: &str,
+) -> Result<AnswerBankEntryDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out =
+        answer_bank::ab_rollback_entry(&db, entry_id, revision, actor).map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_list_review_policies(vault_root: &str) -> Result<Vec<ReviewPolicyDto>, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_list_review_policies(&db).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+pub fn ab_set_review_policy(
+    vault_root: &str,
+    scope: &str,
+    target: &str,
+    interval_days: i64,
+    actor: &str,
+) -> Result<ReviewPolicyDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let scope = answer_bank::ReviewPolicyScope::parse(scope).map_err(map_core_error)?;
+    let out = answer_bank::ab_set_review_policy(&db, scope, target, interval_days, actor)
+        .map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_delete_review_policy(
+    vault_root: &str,
+    scope: &str,
+    target: &str,
+    actor: &str,
+) -> Result<(), AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let scope = answer_bank::ReviewPolicyScope::parse(scope).map_err(map_core_error)?;
+    answer_bank::ab_delete_review_policy(&db, scope, target, actor).map_err(map_core_error)?;
+    Ok(())
+}
+
+pub fn ab_mark_reviewed(
+    vault_root: &str,
+    entry_id: &str,
+    reviewed_at: &str,
+    actor: &str,
+) -> Result<AnswerBankEntryDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out =
+        answer_bank::ab_mark_reviewed(&db, entry_id, reviewed_at, actor).map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_overdue_reviews(
+    vault_root: &str,
+    as_of: &str,
+) -> Result<Vec<OwnerOverdueReviewsDto>, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_overdue_reviews(&db, as_of).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+pub fn ab_set_entry_status(
+    vault_root: &str,
+    entry_id: &str,
+    status: &str,
+    actor: &str,
+) -> Result<AnswerBankEntryDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let status = answer_bank::EntryStatus::parse(status).map_err(map_core_error)?;
+    let out =
+        answer_bank::ab_set_entry_status(&db, entry_id, status, actor).map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_profile_import_file(
+    source_path: &str,
+) -> Result<AnswerBankImportProfileDto, AppErrorDto> {
+    let cols =
+        answer_bank::ab_profile_import_file(Path::new(source_path)).map_err(map_core_error)?;
+    let suggested_map = answer_bank::ab_guess_column_map(&cols).map(Into::into);
+    Ok(AnswerBankImportProfileDto {
+        columns: cols.into_iter().map(Into::into).collect(),
+        suggested_map,
+    })
+}
+
+pub fn ab_bulk_import(
+    vault_root: &str,
+    source_path: &str,
+    map: AnswerBankColumnMapDto,
+    dry_run: bool,
+    actor: &str,
+) -> Result<AnswerBankImportReportDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_bulk_import(&db, Path::new(source_path), &map.into(), dry_run, actor)
+        .map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_export_entries(
+    vault_root: &str,
+    out_path: &str,
+    render_variables: bool,
+) -> Result<AnswerBankExportSummaryDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_export_entries(&db, Path::new(out_path), render_variables)
+        .map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_find_duplicates(
+    vault_root: &str,
+    threshold: f64,
+) -> Result<Vec<DuplicateGroupDto>, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_find_duplicates(&db, threshold).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+pub fn ab_merge_entries(
+    vault_root: &str,
+    keep_entry_id: &str,
+    merge_entry_ids: &[String],
+    actor: &str,
+) -> Result<AnswerBankEntryDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_merge_entries(&db, keep_entry_id, merge_entry_ids, actor)
+        .map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_list_variables(vault_root: &str) -> Result<Vec<VaultVariableDto>, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_list_variables(&db).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+pub fn ab_set_variable(
+    vault_root: &str,
+    name: &str,
+    value: &str,
+    actor: &str,
+) -> Result<VaultVariableDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_set_variable(&db, name, value, actor).map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_delete_variable(vault_root: &str, name: &str, actor: &str) -> Result<(), AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    answer_bank::ab_delete_variable(&db, name, actor).map_err(map_core_error)
+}
+
+pub fn ab_render_entry(vault_root: &str, entry_id: &str) -> Result<RenderedEntryDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_render_entry(&db, entry_id).map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_unresolved_variables(
+    vault_root: &str,
+) -> Result<Vec<UnresolvedVariablesDto>, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_unresolved_variables(&db).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+pub fn ab_list_tags(vault_root: &str) -> Result<Vec<TagUsageDto>, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_list_tags(&db).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+pub fn ab_define_tag(
+    vault_root: &str,
+    tag: &str,
+    description: Option<&str>,
+    actor: &str,
+) -> Result<AnswerBankTagDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_define_tag(&db, tag, description, actor).map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_rename_tag(
+    vault_root: &str,
+    from: &str,
+    to: &str,
+    actor: &str,
+) -> Result<TagRenameReportDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_rename_tag(&db, from, to, actor).map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_delete_tag(vault_root: &str, tag: &str, actor: &str) -> Result<(), AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    answer_bank::ab_delete_tag(&db, tag, actor).map_err(map_core_error)
+}
+
+pub fn ab_map_control(
+    vault_root: &str,
+    entry_id: &str,
+    framework: &str,
+    control_id: &str,
+    actor: &str,
+) -> Result<ControlMappingDto, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_map_control(&db, entry_id, framework, control_id, actor)
+        .map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+pub fn ab_unmap_control(
+    vault_root: &str,
+    entry_id: &str,
+    framework: &str,
+    control_id: &str,
+    actor: &str,
+) -> Result<(), AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    answer_bank::ab_unmap_control(&db, entry_id, framework, control_id, actor)
+        .map_err(map_core_error)
+}
+
+pub fn ab_list_entry_controls(
+    vault_root: &str,
+    entry_id: &str,
+) -> Result<Vec<ControlMappingDto>, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_list_entry_controls(&db, entry_id).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+pub fn ab_list_controls(vault_root: &str) -> Result<Vec<ControlUsageDto>, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out = answer_bank::ab_list_controls(&db).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+pub fn ab_entries_for_control(
+    vault_root: &str,
+    framework: &str,
+    control_id: Option<&str>,
+) -> Result<Vec<AnswerBankEntryDto>, AppErrorDto> {
+    let root = Path::new(vault_root);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+    let out =
+        answer_bank::ab_entries_for_control(&db, framework, control_id).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+// Tauri Command Handlers
+
+use crate::app_state::AppState;
+use tauri::State;
+
 #[tauri::command]
 pub async fn answer_bank_create(
     input: AnswerBankCreateInputDto,
@@ -256,3 +1128,488 @@ pub async fn answer_bank_list(
     let entries = ab_list_entries(&vault_path, params).map_err(|e| e.to_string())?;
     Ok(entries)
 }
+
+#[tauri::command]
+pub async fn answer_bank_search(
+    query: String,
+    limit: i64,
+    offset: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<AnswerBankEntryDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let params = AnswerBankListParamsDto { limit, offset };
+    let entries = ab_search_entries(&vault_path, &query, params).map_err(|e| e.to_string())?;
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn answer_bank_list_aliases(
+    entry_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<AnswerBankAliasDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let aliases = ab_list_aliases(&vault_path, &entry_id).map_err(|e| e.to_string())?;
+    Ok(aliases)
+}
+
+#[tauri::command]
+pub async fn answer_bank_add_alias(
+    entry_id: String,
+    alias_text: String,
+    state: State<'_, AppState>,
+) -> Result<AnswerBankAliasDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let alias = ab_add_alias(&vault_path, &entry_id, &alias_text, &state.actor)
+        .map_err(|e| e.to_string())?;
+    Ok(alias)
+}
+
+#[tauri::command]
+pub async fn answer_bank_delete_alias(
+    alias_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    ab_delete_alias(&vault_path, &alias_id, &state.actor).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn answer_bank_list_revisions(
+    entry_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<AnswerBankRevisionDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let revisions = ab_list_revisions(&vault_path, &entry_id).map_err(|e| e.to_string())?;
+    Ok(revisions)
+}
+
+#[tauri::command]
+pub async fn answer_bank_revision_at_audit_seq(
+    entry_id: String,
+    audit_seq: i64,
+    state: State<'_, AppState>,
+) -> Result<AnswerBankRevisionDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let revision =
+        ab_revision_at_audit_seq(&vault_path, &entry_id, audit_seq).map_err(|e| e.to_string())?;
+    Ok(revision)
+}
+
+#[tauri::command]
+pub async fn answer_bank_diff_revisions(
+    entry_id: String,
+    from_revision: i64,
+    to_revision: i64,
+    state: State<'_, AppState>,
+) -> Result<AnswerBankRevisionDiffDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let diff = ab_diff_revisions(&vault_path, &entry_id, from_revision, to_revision)
+        .map_err(|e| e.to_string())?;
+    Ok(diff)
+}
+
+#[tauri::command]
+pub async fn answer_bank_rollback(
+    entry_id: String,
+    revision: i64,
+    state: State<'_, AppState>,
+) -> Result<AnswerBankEntryDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let entry = ab_rollback_entry(&vault_path, &entry_id, revision, &state.actor)
+        .map_err(|e| e.to_string())?;
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn answer_bank_list_review_policies(
+    state: State<'_, AppState>,
+) -> Result<Vec<ReviewPolicyDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let policies = ab_list_review_policies(&vault_path).map_err(|e| e.to_string())?;
+    Ok(policies)
+}
+
+#[tauri::command]
+pub async fn answer_bank_set_review_policy(
+    scope: String,
+    target: Option<String>,
+    interval_days: i64,
+    state: State<'_, AppState>,
+) -> Result<ReviewPolicyDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let policy = ab_set_review_policy(
+        &vault_path,
+        &scope,
+        target.as_deref().unwrap_or(""),
+        interval_days,
+        &state.actor,
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(policy)
+}
+
+#[tauri::command]
+pub async fn answer_bank_delete_review_policy(
+    scope: String,
+    target: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    ab_delete_review_policy(
+        &vault_path,
+        &scope,
+        target.as_deref().unwrap_or(""),
+        &state.actor,
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// `reviewed_at` defaults to today (UTC).
+#[tauri::command]
+pub async fn answer_bank_mark_reviewed(
+    entry_id: String,
+    reviewed_at: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<AnswerBankEntryDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let reviewed_at = reviewed_at.unwrap_or_else(today_utc_date);
+    let entry = ab_mark_reviewed(&vault_path, &entry_id, &reviewed_at, &state.actor)
+        .map_err(|e| e.to_string())?;
+    Ok(entry)
+}
+
+/// `as_of` defaults to today (UTC).
+#[tauri::command]
+pub async fn answer_bank_overdue_reviews(
+    as_of: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<OwnerOverdueReviewsDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let as_of = as_of.unwrap_or_else(today_utc_date);
+    let overdue = ab_overdue_reviews(&vault_path, &as_of).map_err(|e| e.to_string())?;
+    Ok(overdue)
+}
+
+#[tauri::command]
+pub async fn answer_bank_set_status(
+    entry_id: String,
+    status: String,
+    state: State<'_, AppState>,
+) -> Result<AnswerBankEntryDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let entry = ab_set_entry_status(&vault_path, &entry_id, &status, &state.actor)
+        .map_err(|e| e.to_string())?;
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn answer_bank_profile_import(
+    source_path: String,
+) -> Result<AnswerBankImportProfileDto, String> {
+    ab_profile_import_file(&source_path).map_err(|e| e.to_string())
+}
+
+/// With `dry_run` nothing is written; the report previews the import.
+#[tauri::command]
+pub async fn answer_bank_bulk_import(
+    source_path: String,
+    map: AnswerBankColumnMapDto,
+    dry_run: bool,
+    state: State<'_, AppState>,
+) -> Result<AnswerBankImportReportDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let report = ab_bulk_import(&vault_path, &source_path, map, dry_run, &state.actor)
+        .map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+/// Answers are exported as stored unless `render_variables` is set.
+#[tauri::command]
+pub async fn answer_bank_export(
+    out_path: String,
+    render_variables: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<AnswerBankExportSummaryDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let summary = ab_export_entries(&vault_path, &out_path, render_variables.unwrap_or(false))
+        .map_err(|e| e.to_string())?;
+    Ok(summary)
+}
+
+/// `threshold` defaults to `answer_bank::DEFAULT_DUPLICATE_THRESHOLD`.
+#[tauri::command]
+pub async fn answer_bank_find_duplicates(
+    threshold: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<DuplicateGroupDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let threshold = threshold.unwrap_or(answer_bank::DEFAULT_DUPLICATE_THRESHOLD);
+    let groups = ab_find_duplicates(&vault_path, threshold).map_err(|e| e.to_string())?;
+    Ok(groups)
+}
+
+#[tauri::command]
+pub async fn answer_bank_merge(
+    keep_entry_id: String,
+    merge_entry_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<AnswerBankEntryDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let entry = ab_merge_entries(&vault_path, &keep_entry_id, &merge_entry_ids, &state.actor)
+        .map_err(|e| e.to_string())?;
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn answer_bank_list_variables(
+    state: State<'_, AppState>,
+) -> Result<Vec<VaultVariableDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let variables = ab_list_variables(&vault_path).map_err(|e| e.to_string())?;
+    Ok(variables)
+}
+
+#[tauri::command]
+pub async fn answer_bank_set_variable(
+    name: String,
+    value: String,
+    state: State<'_, AppState>,
+) -> Result<VaultVariableDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let variable =
+        ab_set_variable(&vault_path, &name, &value, &state.actor).map_err(|e| e.to_string())?;
+    Ok(variable)
+}
+
+#[tauri::command]
+pub async fn answer_bank_delete_variable(
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    ab_delete_variable(&vault_path, &name, &state.actor).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn answer_bank_render_entry(
+    entry_id: String,
+    state: State<'_, AppState>,
+) -> Result<RenderedEntryDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let rendered = ab_render_entry(&vault_path, &entry_id).map_err(|e| e.to_string())?;
+    Ok(rendered)
+}
+
+#[tauri::command]
+pub async fn answer_bank_unresolved_variables(
+    state: State<'_, AppState>,
+) -> Result<Vec<UnresolvedVariablesDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let entries = ab_unresolved_variables(&vault_path).map_err(|e| e.to_string())?;
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn answer_bank_list_tags(state: State<'_, AppState>) -> Result<Vec<TagUsageDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let tags = ab_list_tags(&vault_path).map_err(|e| e.to_string())?;
+    Ok(tags)
+}
+
+#[tauri::command]
+pub async fn answer_bank_define_tag(
+    tag: String,
+    description: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<AnswerBankTagDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let tag = ab_define_tag(&vault_path, &tag, description.as_deref(), &state.actor)
+        .map_err(|e| e.to_string())?;
+    Ok(tag)
+}
+
+#[tauri::command]
+pub async fn answer_bank_rename_tag(
+    from: String,
+    to: String,
+    state: State<'_, AppState>,
+) -> Result<TagRenameReportDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let report = ab_rename_tag(&vault_path, &from, &to, &state.actor).map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn answer_bank_delete_tag(tag: String, state: State<'_, AppState>) -> Result<(), String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    ab_delete_tag(&vault_path, &tag, &state.actor).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn answer_bank_map_control(
+    entry_id: String,
+    framework: String,
+    control_id: String,
+    state: State<'_, AppState>,
+) -> Result<ControlMappingDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let mapping = ab_map_control(
+        &vault_path,
+        &entry_id,
+        &framework,
+        &control_id,
+        &state.actor,
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(mapping)
+}
+
+#[tauri::command]
+pub async fn answer_bank_unmap_control(
+    entry_id: String,
+    framework: String,
+    control_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    ab_unmap_control(
+        &vault_path,
+        &entry_id,
+        &framework,
+        &control_id,
+        &state.actor,
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn answer_bank_list_entry_controls(
+    entry_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ControlMappingDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let mappings = ab_list_entry_controls(&vault_path, &entry_id).map_err(|e| e.to_string())?;
+    Ok(mappings)
+}
+
+#[tauri::command]
+pub async fn answer_bank_list_controls(
+    state: State<'_, AppState>,
+) -> Result<Vec<ControlUsageDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let controls = ab_list_controls(&vault_path).map_err(|e| e.to_string())?;
+    Ok(controls)
+}
+
+#[tauri::command]
+pub async fn answer_bank_entries_for_control(
+    framework: String,
+    control_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<AnswerBankEntryDto>, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let entries = ab_entries_for_control(&vault_path, &framework, control_id.as_deref())
+        .map_err(|e| e.to_string())?;
+    Ok(entries)
+}
@@ -1,6 +1,10 @@
 use crate::app_state::AppState;
 use crate::error_map::map_core_error;
-use cs_core::export::pack;
+use cs_core::export::scope::{ExportScope, ScopeControl};
+use cs_core::export::{pack, redaction, signing};
+use cs_core::storage::db::SqliteDb;
+use cs_core::storage::vault_db_path;
+use cs_core::util::redact::{RedactionProfile, RedactionRule, RedactionRuleKind};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use tauri::State;
@@ -10,11 +14,162 @@ pub struct ExportPackDto {
     pub zip_path: String,
     pub manifest_version: i64,
     pub file_count: usize,
+    pub sections: Vec<ExportSectionDto>,
+    pub signer_public_key_hex: String,
+    pub audit_head_seq: Option<i64>,
+    pub audit_head_hash: Option<String>,
+    pub scope: ExportScopeDto,
+    pub redaction_profile: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSectionDto {
+    pub name: String,
+    pub version: i64,
+    pub file_count: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportScopeDto {
+    #[serde(default)]
+    pub import_ids: Vec<String>,
+    #[serde(default)]
+    pub answer_bank_tags: Vec<String>,
+    #[serde(default)]
+    pub evidence_tags: Vec<String>,
+    #[serde(default)]
+    pub controls: Vec<ScopeControlDto>,
+    /// Audit `seq` range of the `EvidenceAdded` events in scope.
+    #[serde(default)]
+    pub evidence_added_from_seq: Option<i64>,
+    #[serde(default)]
+    pub evidence_added_to_seq: Option<i64>,
+    /// Include the whole audit log in a scoped pack.
+    #[serde(default)]
+    pub full_audit_log: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScopeControlDto {
+    pub framework: String,
+    pub control_id: Option<String>,
+}
+
+impl From<ExportScopeDto> for ExportScope {
+    fn from(value: ExportScopeDto) -> Self {
+        Self {
+            import_ids: value.import_ids,
+            answer_bank_tags: value.answer_bank_tags,
+            evidence_tags: value.evidence_tags,
+            controls: value
+                .controls
+                .into_iter()
+                .map(|c| ScopeControl {
+                    framework: c.framework,
+                    control_id: c.control_id,
+                })
+                .collect(),
+            evidence_added_from_seq: value.evidence_added_from_seq,
+            evidence_added_to_seq: value.evidence_added_to_seq,
+            full_audit_log: value.full_audit_log,
+        }
+    }
+}
+
+impl From<ExportScope> for ExportScopeDto {
+    fn from(value: ExportScope) -> Self {
+        Self {
+            import_ids: value.import_ids,
+            answer_bank_tags: value.answer_bank_tags,
+            evidence_tags: value.evidence_tags,
+            controls: value
+                .controls
+                .into_iter()
+                .map(|c| ScopeControlDto {
+                    framework: c.framework,
+                    control_id: c.control_id,
+                })
+                .collect(),
+            evidence_added_from_seq: value.evidence_added_from_seq,
+            evidence_added_to_seq: value.evidence_added_to_seq,
+            full_audit_log: value.full_audit_log,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionProfileDto {
+    pub name: String,
+    #[serde(default)]
+    pub rules: Vec<RedactionRuleDto>,
+    #[serde(default)]
+    pub excluded_evidence_tags: Vec<String>,
+    #[serde(default)]
+    pub remove_notes: bool,
+    #[serde(default)]
+    pub remove_owners: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionRuleDto {
+    /// `keyword` or `regex`.
+    pub kind: String,
+    pub pattern: String,
+    pub replacement: String,
+}
+
+impl TryFrom<RedactionProfileDto> for RedactionProfile {
+    type Error = String;
+
+    fn try_from(value: RedactionProfileDto) -> Result<Self, Self::Error> {
+        let rules = value
+            .rules
+            .into_iter()
+            .map(|r| -> Result<RedactionRule, String> {
+                Ok(RedactionRule {
+                    kind: RedactionRuleKind::parse(&r.kind).map_err(map_core_error)?,
+                    pattern: r.pattern,
+                    replacement: r.replacement,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self {
+            name: value.name,
+            rules,
+            excluded_evidence_tags: value.excluded_evidence_tags,
+            remove_notes: value.remove_notes,
+            remove_owners: value.remove_owners,
+        })
+    }
+}
+
+impl From<RedactionProfile> for RedactionProfileDto {
+    fn from(value: RedactionProfile) -> Self {
+        Self {
+            name: value.name,
+            rules: value
+                .rules
+                .into_iter()
+                .map(|r| RedactionRuleDto {
+                    kind: r.kind.as_str().to_string(),
+                    pattern: r.pattern,
+                    replacement: r.replacement,
+                })
+                .collect(),
+            excluded_evidence_tags: value.excluded_evidence_tags,
+            remove_notes: value.remove_notes,
+            remove_owners: value.remove_owners,
+        }
+    }
+}
+
+/// Generate a signed pack; `scope` limits what it contains (everything when
+/// omitted) and `redaction_profile` names the profile to redact it with.
 #[tauri::command]
 pub async fn generate_export_pack(
     output_path: String,
+    scope: Option<ExportScopeDto>,
+    redaction_profile: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<ExportPackDto, String> {
     let vault_path = state
@@ -31,11 +186,103 @@ pub async fn generate_export_pack(
         return Err(e.to_string());
     }
 
-    let export_pack = pack::generate_pack(vault_root, out_zip).map_err(map_core_error)?;
+    let scope: ExportScope = scope.unwrap_or_default().into();
+    let export_pack = pack::generate_pack(
+        vault_root,
+        out_zip,
+        &scope,
+        redaction_profile.as_deref(),
+        &state.actor,
+    )
+    .map_err(map_core_error)?;
 
     Ok(ExportPackDto {
         zip_path: export_pack.zip_path.to_string_lossy().to_string(),
         manifest_version: export_pack.manifest.version,
         file_count: export_pack.manifest.files.len(),
+        sections: export_pack
+            .manifest
+            .sections
+            .iter()
+            .map(|s| ExportSectionDto {
+                name: s.name.clone(),
+                version: s.version,
+                file_count: s.files.len(),
+            })
+            .collect(),
+        signer_public_key_hex: export_pack.signature.public_key_hex,
+        audit_head_seq: export_pack.manifest.audit_head.as_ref().map(|h| h.seq),
+        audit_head_hash: export_pack.manifest.audit_head.map(|h| h.hash),
+        scope: export_pack.manifest.scope.unwrap_or_default().into(),
+        redaction_profile: export_pack.manifest.redaction.map(|r| r.profile),
     })
 }
+
+fn open_vault_db(state: &State<'_, AppState>) -> Result<SqliteDb, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+    let db = SqliteDb::new(&vault_db_path(Path::new(&vault_path)));
+    db.migrate().map_err(map_core_error)?;
+    Ok(db)
+}
+
+#[tauri::command]
+pub async fn list_redaction_profiles(
+    state: State<'_, AppState>,
+) -> Result<Vec<RedactionProfileDto>, String> {
+    let db = open_vault_db(&state)?;
+    let profiles = redaction::redaction_list_profiles(&db).map_err(map_core_error)?;
+    Ok(profiles.into_iter().map(Into::into).collect())
+}
+
+/// Save a redaction profile, replacing any profile with the same name.
+#[tauri::command]
+pub async fn save_redaction_profile(
+    profile: RedactionProfileDto,
+    state: State<'_, AppState>,
+) -> Result<RedactionProfileDto, String> {
+    let db = open_vault_db(&state)?;
+    let profile = RedactionProfile::try_from(profile)?;
+    let saved =
+        redaction::redaction_save_profile(&db, &profile, &state.actor).map_err(map_core_error)?;
+    Ok(saved.into())
+}
+
+#[tauri::command]
+pub async fn delete_redaction_profile(
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let db = open_vault_db(&state)?;
+    redaction::redaction_delete_profile(&db, &name, &state.actor).map_err(map_core_error)?;
+    Ok(())
+}
+
+/// Public key the open vault signs export packs with, once it has exported.
+#[tauri::command]
+pub async fn get_export_signing_key(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    let db = open_vault_db(&state)?;
+    Ok(signing::vault_signing_public_key(&db).map_err(map_core_error)?)
+}
+
+/// Replace the open vault's signing key and return the new public key.
+/// Recipients must be given the new key; packs signed before stay valid
+/// for those that still trust the old one.
+#[tauri::command]
+pub async fn rotate_export_signing_key(state: State<'_, AppState>) -> Result<String, String> {
+    let db = open_vault_db(&state)?;
+    Ok(signing::rotate_signing_key(&db, &state.actor).map_err(map_core_error)?)
+}
+
+/// Validate a pack against the given trusted signer keys and return the
+/// key it was signed with.
+#[tauri::command]
+pub async fn validate_export_pack(
+    zip_path: String,
+    trusted_keys: Vec<String>,
+) -> Result<String, String> {
+    let signature =
+        pack::validate_pack(Path::new(&zip_path), &trusted_keys).map_err(map_core_error)?;
+    Ok(signature.public_key_hex)
+}
@@ -1,13 +1,95 @@
 use crate::app_state::AppState;
 use crate::error_map::map_core_error;
 use cs_core::answer_bank;
-use cs_core::questionnaire::matching::{MatchSuggestion, MatchingEngine};
+use cs_core::questionnaire::explain;
+use cs_core::questionnaire::matching::{AnswerBankMatcher, MatchSuggestion, RankingModel};
+use cs_core::questionnaire::suggestions;
+use cs_core::questionnaire::synonyms;
 use cs_core::storage::db::SqliteDb;
 use cs_core::storage::vault_db_path;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use tauri::State;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextSpanDto {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+    pub origin: String,
+}
+
+impl From<explain::TextSpan> for TextSpanDto {
+    fn from(value: explain::TextSpan) -> Self {
+        Self {
+            start: value.start,
+            end: value.end,
+            text: value.text,
+            origin: value.origin.as_str().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldSpanDto {
+    pub field: String,
+    pub alias_index: Option<usize>,
+    pub span: TextSpanDto,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermMatchDto {
+    pub term: String,
+    pub question_spans: Vec<TextSpanDto>,
+    pub field_spans: Vec<FieldSpanDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldScoreDto {
+    pub field: String,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchExplanationDto {
+    pub summary: String,
+    pub terms: Vec<TermMatchDto>,
+    pub field_scores: Vec<FieldScoreDto>,
+}
+
+impl From<explain::MatchExplanation> for MatchExplanationDto {
+    fn from(value: explain::MatchExplanation) -> Self {
+        Self {
+            summary: value.summary,
+            terms: value
+                .terms
+                .into_iter()
+                .map(|t| TermMatchDto {
+                    term: t.term,
+                    question_spans: t.question_spans.into_iter().map(Into::into).collect(),
+                    field_spans: t
+                        .field_spans
+                        .into_iter()
+                        .map(|f| FieldSpanDto {
+                            field: f.field.as_str().to_string(),
+                            alias_index: f.alias_index,
+                            span: f.span.into(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            field_scores: value
+                .field_scores
+                .into_iter()
+                .map(|f| FieldScoreDto {
+                    field: f.field.as_str().to_string(),
+                    score: f.score,
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchSuggestionDto {
     pub answer_bank_entry_id: String,
@@ -15,6 +97,13 @@ pub struct MatchSuggestionDto {
     pub normalized_question: String,
     pub normalized_answer: String,
     pub confidence_explanation: String,
+    pub explanation: MatchExplanationDto,
+    pub scorer_name: String,
+    pub scorer_version: String,
+    pub stale: bool,
+    pub answer_short: String,
+    pub answer_long: String,
+    pub unresolved_variables: Vec<String>,
 }
 
 impl From<MatchSuggestion> for MatchSuggestionDto {
@@ -25,6 +114,13 @@ impl From<MatchSuggestion> for MatchSuggestionDto {
             normalized_question: value.normalized_question,
             normalized_answer: value.normalized_answer,
             confidence_explanation: value.confidence_explanation,
+            explanation: value.explanation.into(),
+            scorer_name: value.scorer_name,
+            scorer_version: value.scorer_version,
+            stale: value.stale,
+            answer_short: value.answer_short,
+            answer_long: value.answer_long,
+            unresolved_variables: value.unresolved_variables,
         }
     }
 }
@@ -33,6 +129,8 @@ impl From<MatchSuggestion> for MatchSuggestionDto {
 pub async fn get_matching_suggestions(
     question: String,
     top_n: Option<usize>,
+    model: Option<String>,
+    locale: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<Vec<MatchSuggestionDto>, String> {
     let vault_path = state
@@ -43,23 +141,227 @@ pub async fn get_matching_suggestions(
     let db = SqliteDb::new(&vault_db_path(root));
     db.migrate().map_err(map_core_error)?;
 
-    // Load all answer bank entries for the vault
-    // TODO: This approach does not scale. For a production system, consider using
-    // a full-text search index (e.g., SQLite FTS5) or a dedicated search service
-    // to avoid loading all entries into memory for every request.
-    let params = answer_bank::ListParams {
-        limit: 10000, // Large limit to get all entries
-        offset: 0,
+    // Candidates come from the answer bank full-text index and are reranked
+    // (BM25 unless another model is asked for)
+    let model = match model.as_deref() {
+        Some(m) => RankingModel::parse(m).map_err(map_core_error)?,
+        None => RankingModel::default(),
     };
-    let entries = answer_bank::ab_list_entries(&db, params).map_err(map_core_error)?;
-
-    // Create matching engine with answer bank
-    let engine = MatchingEngine::new(entries);
+    let mut matcher = AnswerBankMatcher::load(&db, model).map_err(map_core_error)?;
+    if let Some(locale) = locale {
+        let locale = answer_bank::normalize_locale(&locale).map_err(map_core_error)?;
+        matcher = matcher.with_locale(&locale);
+    }
 
     // Get suggestions (default to top 5 if not specified)
-    let suggestions = engine
-        .get_suggestions(&question, top_n.unwrap_or(5))
+    let suggestions = matcher
+        .get_suggestions(&db, &question, top_n.unwrap_or(5))
         .map_err(map_core_error)?;
 
     Ok(suggestions.into_iter().map(Into::into).collect())
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionSuggestionDto {
+    pub suggestion_id: String,
+    pub question_id: String,
+    pub answer_bank_entry_id: String,
+    pub score: f64,
+    pub normalized_question: String,
+    pub normalized_answer: String,
+    pub confidence_explanation: String,
+    pub explanation: Option<MatchExplanationDto>,
+    pub scorer_name: Option<String>,
+    pub scorer_version: Option<String>,
+    pub accepted: bool,
+    pub accepted_at: Option<String>,
+    pub rejected_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<suggestions::QuestionSuggestion> for QuestionSuggestionDto {
+    fn from(value: suggestions::QuestionSuggestion) -> Self {
+        Self {
+            suggestion_id: value.suggestion_id,
+            question_id: value.question_id,
+            answer_bank_entry_id: value.answer_bank_entry_id,
+            score: value.score,
+            normalized_question: value.normalized_question,
+            normalized_answer: value.normalized_answer,
+            confidence_explanation: value.confidence_explanation,
+            explanation: value.explanation.map(Into::into),
+            scorer_name: value.scorer_name,
+            scorer_version: value.scorer_version,
+            accepted: value.accepted,
+            accepted_at: value.accepted_at,
+            rejected_at: value.rejected_at,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+fn open_vault_db(state: &State<'_, AppState>) -> Result<SqliteDb, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let db = SqliteDb::new(&vault_db_path(Path::new(&vault_path)));
+    db.migrate().map_err(map_core_error)?;
+    Ok(db)
+}
+
+#[tauri::command]
+pub async fn generate_import_suggestions(
+    import_id: String,
+    top_n: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<QuestionSuggestionDto>, String> {
+    let db = open_vault_db(&state)?;
+    let out = suggestions::generate_suggestions(&db, &import_id, top_n.unwrap_or(5), &state.actor)
+        .map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn list_import_suggestions(
+    import_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<QuestionSuggestionDto>, String> {
+    let db = open_vault_db(&state)?;
+    let out = suggestions::list_import_suggestions(&db, &import_id).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn accept_suggestion(
+    suggestion_id: String,
+    state: State<'_, AppState>,
+) -> Result<QuestionSuggestionDto, String> {
+    let db = open_vault_db(&state)?;
+    let out = suggestions::accept_suggestion(&db, &suggestion_id, &state.actor)
+        .map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+#[tauri::command]
+pub async fn reject_suggestion(
+    suggestion_id: String,
+    state: State<'_, AppState>,
+) -> Result<QuestionSuggestionDto, String> {
+    let db = open_vault_db(&state)?;
+    let out = suggestions::reject_suggestion(&db, &suggestion_id, &state.actor)
+        .map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoAnswerItemDto {
+    pub question_id: String,
+    pub row_number: i64,
+    pub outcome: String,
+    pub best_score: Option<f64>,
+    pub best_entry_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoAnswerReportDto {
+    pub import_id: String,
+    pub threshold: f64,
+    pub auto_filled: i64,
+    pub needs_review: i64,
+    pub no_match: i64,
+    pub skipped: i64,
+    pub items: Vec<AutoAnswerItemDto>,
+}
+
+impl From<suggestions::AutoAnswerReport> for AutoAnswerReportDto {
+    fn from(value: suggestions::AutoAnswerReport) -> Self {
+        Self {
+            import_id: value.import_id,
+            threshold: value.threshold,
+            auto_filled: value.auto_filled,
+            needs_review: value.needs_review,
+            no_match: value.no_match,
+            skipped: value.skipped,
+            items: value
+                .items
+                .into_iter()
+                .map(|i| AutoAnswerItemDto {
+                    question_id: i.question_id,
+                    row_number: i.row_number,
+                    outcome: i.outcome.as_str().to_string(),
+                    best_score: i.best_score,
+                    best_entry_id: i.best_entry_id,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn auto_answer_import(
+    import_id: String,
+    threshold: f64,
+    top_n: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<AutoAnswerReportDto, String> {
+    let db = open_vault_db(&state)?;
+    let options = suggestions::AutoAnswerOptions {
+        threshold,
+        top_n: top_n.unwrap_or(5),
+    };
+    let report = suggestions::auto_answer_import(&db, &import_id, &options, &state.actor)
+        .map_err(map_core_error)?;
+    Ok(report.into())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchSynonymDto {
+    pub term: String,
+    pub canonical: String,
+    pub source: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<synonyms::MatchSynonym> for MatchSynonymDto {
+    fn from(value: synonyms::MatchSynonym) -> Self {
+        Self {
+            term: value.term,
+            canonical: value.canonical,
+            source: value.source,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn list_match_synonyms(
+    state: State<'_, AppState>,
+) -> Result<Vec<MatchSynonymDto>, String> {
+    let db = open_vault_db(&state)?;
+    let out = synonyms::list_synonyms(&db).map_err(map_core_error)?;
+    Ok(out.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn set_match_synonym(
+    term: String,
+    canonical: String,
+    state: State<'_, AppState>,
+) -> Result<MatchSynonymDto, String> {
+    let db = open_vault_db(&state)?;
+    let out =
+        synonyms::set_synonym(&db, &term, &canonical, &state.actor).map_err(map_core_error)?;
+    Ok(out.into())
+}
+
+#[tauri::command]
+pub async fn delete_match_synonym(term: String, state: State<'_, AppState>) -> Result<(), String> {
+    let db = open_vault_db(&state)?;
+    synonyms::delete_synonym(&db, &term, &state.actor).map_err(map_core_error)?;
+    Ok(())
+}
@@ -4,3 +4,4 @@ pub mod license;
 pub mod matching;
 pub mod questionnaire;
 pub mod vault;
+pub mod workflow;
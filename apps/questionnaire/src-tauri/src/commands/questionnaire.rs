@@ -1,5 +1,6 @@
 use crate::error_map::{map_core_error, AppErrorDto};
 use cs_core::questionnaire;
+use cs_core::questionnaire::revision;
 use cs_core::storage::db::SqliteDb;
 use cs_core::storage::vault_db_path;
 use serde::{Deserialize, Serialize};
@@ -32,6 +33,8 @@ pub struct QuestionnaireImportDto {
     pub format: String,
     pub status: String,
     pub column_map: Option<ColumnMapDto>,
+    pub parent_import_id: Option<String>,
+    pub language: String,
 }
 
 impl From<questionnaire::QuestionnaireImport> for QuestionnaireImportDto {
@@ -49,6 +52,8 @@ impl From<questionnaire::QuestionnaireImport> for QuestionnaireImportDto {
                 answer: m.answer,
                 notes: m.notes,
             }),
+            parent_import_id: value.parent_import_id,
+            language: value.language,
         }
     }
 }
@@ -197,3 +202,148 @@ pub async fn save_column_mapping(
 
     Ok(import.into())
 }
+
+#[tauri::command]
+pub async fn set_import_language(
+    import_id: String,
+    language: String,
+    state: State<'_, AppState>,
+) -> Result<QuestionnaireImportDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let root = Path::new(&vault_path);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+
+    let import = questionnaire::set_import_language(&db, &import_id, &language, &state.actor)
+        .map_err(map_core_error)?;
+
+    Ok(import.into())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevisionDiffItemDto {
+    pub change: String,
+    pub question_id: Option<String>,
+    pub row_number: Option<i64>,
+    pub question_text: Option<String>,
+    pub parent_question_id: Option<String>,
+    pub parent_row_number: Option<i64>,
+    pub parent_question_text: Option<String>,
+}
+
+impl From<revision::RevisionDiffItem> for RevisionDiffItemDto {
+    fn from(value: revision::RevisionDiffItem) -> Self {
+        Self {
+            change: value.change.as_str().to_string(),
+            question_id: value.question_id,
+            row_number: value.row_number,
+            question_text: value.question_text,
+            parent_question_id: value.parent_question_id,
+            parent_row_number: value.parent_row_number,
+            parent_question_text: value.parent_question_text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevisionDiffDto {
+    pub import_id: String,
+    pub parent_import_id: String,
+    pub added: i64,
+    pub removed: i64,
+    pub changed: i64,
+    pub unchanged: i64,
+    pub items: Vec<RevisionDiffItemDto>,
+}
+
+impl From<revision::RevisionDiff> for RevisionDiffDto {
+    fn from(value: revision::RevisionDiff) -> Self {
+        Self {
+            import_id: value.import_id,
+            parent_import_id: value.parent_import_id,
+            added: value.added,
+            removed: value.removed,
+            changed: value.changed,
+            unchanged: value.unchanged,
+            items: value.items.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarryOverReportDto {
+    pub import_id: String,
+    pub parent_import_id: String,
+    pub carried_over: i64,
+}
+
+impl From<revision::CarryOverReport> for CarryOverReportDto {
+    fn from(value: revision::CarryOverReport) -> Self {
+        Self {
+            import_id: value.import_id,
+            parent_import_id: value.parent_import_id,
+            carried_over: value.carried_over,
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn import_questionnaire_revision(
+    parent_import_id: String,
+    file_path: String,
+    state: State<'_, AppState>,
+) -> Result<QuestionnaireImportDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let root = Path::new(&vault_path);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+
+    let import =
+        revision::import_revision(&db, &parent_import_id, Path::new(&file_path), &state.actor)
+            .map_err(map_core_error)?;
+
+    Ok(import.into())
+}
+
+#[tauri::command]
+pub async fn diff_questionnaire_revision(
+    import_id: String,
+    state: State<'_, AppState>,
+) -> Result<RevisionDiffDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let root = Path::new(&vault_path);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+
+    let diff = revision::diff_revision(&db, &import_id).map_err(map_core_error)?;
+
+    Ok(diff.into())
+}
+
+#[tauri::command]
+pub async fn carry_over_revision(
+    import_id: String,
+    state: State<'_, AppState>,
+) -> Result<CarryOverReportDto, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let root = Path::new(&vault_path);
+    let db = SqliteDb::new(&vault_db_path(root));
+    db.migrate().map_err(map_core_error)?;
+
+    let report =
+        revision::carry_over_revision(&db, &import_id, &state.actor).map_err(map_core_error)?;
+
+    Ok(report.into())
+}
@@ -0,0 +1,213 @@
+use crate::app_state::AppState;
+use crate::error_map::map_core_error;
+use cs_core::questionnaire::workflow::{self, QuestionAnswer, QuestionStatus};
+use cs_core::storage::db::SqliteDb;
+use cs_core::storage::vault_db_path;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionnaireQuestionDto {
+    pub question_id: String,
+    pub vault_id: String,
+    pub import_id: String,
+    pub row_number: i64,
+    pub question_text: String,
+    pub source_answer: Option<String>,
+    pub source_notes: Option<String>,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub answer_bank_entry_id: Option<String>,
+    pub answer_override: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<workflow::QuestionnaireQuestion> for QuestionnaireQuestionDto {
+    fn from(value: workflow::QuestionnaireQuestion) -> Self {
+        let (answer_bank_entry_id, answer_override) = match value.answer {
+            Some(QuestionAnswer::Entry(id)) => (Some(id), None),
+            Some(QuestionAnswer::Override(text)) => (None, Some(text)),
+            None => (None, None),
+        };
+        Self {
+            question_id: value.question_id,
+            vault_id: value.vault_id,
+            import_id: value.import_id,
+            row_number: value.row_number,
+            question_text: value.question_text,
+            source_answer: value.source_answer,
+            source_notes: value.source_notes,
+            status: value.status.as_str().to_string(),
+            assignee: value.assignee,
+            answer_bank_entry_id,
+            answer_override,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionCommentDto {
+    pub comment_id: String,
+    pub question_id: String,
+    pub author: String,
+    pub body: String,
+    pub created_at: String,
+}
+
+impl From<workflow::QuestionComment> for QuestionCommentDto {
+    fn from(value: workflow::QuestionComment) -> Self {
+        Self {
+            comment_id: value.comment_id,
+            question_id: value.question_id,
+            author: value.author,
+            body: value.body,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportProgressDto {
+    pub import_id: String,
+    pub total: i64,
+    pub unanswered: i64,
+    pub suggested: i64,
+    pub drafted: i64,
+    pub approved: i64,
+    pub not_applicable: i64,
+    pub completion_percent: f64,
+    pub answered_percent: f64,
+}
+
+impl From<workflow::ImportProgress> for ImportProgressDto {
+    fn from(value: workflow::ImportProgress) -> Self {
+        Self {
+            import_id: value.import_id,
+            total: value.total,
+            unanswered: value.unanswered,
+            suggested: value.suggested,
+            drafted: value.drafted,
+            approved: value.approved,
+            not_applicable: value.not_applicable,
+            completion_percent: value.completion_percent,
+            answered_percent: value.answered_percent,
+        }
+    }
+}
+
+fn open_vault_db(state: &State<'_, AppState>) -> Result<SqliteDb, String> {
+    let vault_path = state
+        .get_vault_path()
+        .ok_or_else(|| "No vault open".to_string())?;
+
+    let db = SqliteDb::new(&vault_db_path(Path::new(&vault_path)));
+    db.migrate().map_err(map_core_error)?;
+    Ok(db)
+}
+
+#[tauri::command]
+pub async fn list_questions(
+    import_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<QuestionnaireQuestionDto>, String> {
+    let db = open_vault_db(&state)?;
+    let questions = workflow::list_questions(&db, &import_id).map_err(map_core_error)?;
+    Ok(questions.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn list_questions_for_control(
+    import_id: String,
+    framework: String,
+    control_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<QuestionnaireQuestionDto>, String> {
+    let db = open_vault_db(&state)?;
+    let questions =
+        workflow::list_questions_for_control(&db, &import_id, &framework, control_id.as_deref())
+            .map_err(map_core_error)?;
+    Ok(questions.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn set_question_status(
+    question_id: String,
+    status: String,
+    state: State<'_, AppState>,
+) -> Result<QuestionnaireQuestionDto, String> {
+    let db = open_vault_db(&state)?;
+    let status = QuestionStatus::parse(&status).map_err(map_core_error)?;
+    let question = workflow::set_question_status(&db, &question_id, status, &state.actor)
+        .map_err(map_core_error)?;
+    Ok(question.into())
+}
+
+#[tauri::command]
+pub async fn set_question_answer(
+    question_id: String,
+    answer_bank_entry_id: Option<String>,
+    answer_override: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<QuestionnaireQuestionDto, String> {
+    let db = open_vault_db(&state)?;
+    let answer = match (answer_bank_entry_id, answer_override) {
+        (Some(id), None) => QuestionAnswer::Entry(id),
+        (None, Some(text)) => QuestionAnswer::Override(text),
+        _ => {
+            return Err(
+                "Provide exactly one of answer_bank_entry_id or answer_override".to_string(),
+            )
+        }
+    };
+    let question = workflow::set_question_answer(&db, &question_id, &answer, &state.actor)
+        .map_err(map_core_error)?;
+    Ok(question.into())
+}
+
+#[tauri::command]
+pub async fn assign_question(
+    question_id: String,
+    assignee: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<QuestionnaireQuestionDto, String> {
+    let db = open_vault_db(&state)?;
+    let question = workflow::assign_question(&db, &question_id, assignee.as_deref(), &state.actor)
+        .map_err(map_core_error)?;
+    Ok(question.into())
+}
+
+#[tauri::command]
+pub async fn add_question_comment(
+    question_id: String,
+    body: String,
+    state: State<'_, AppState>,
+) -> Result<QuestionCommentDto, String> {
+    let db = open_vault_db(&state)?;
+    let comment = workflow::add_question_comment(&db, &question_id, &body, &state.actor)
+        .map_err(map_core_error)?;
+    Ok(comment.into())
+}
+
+#[tauri::command]
+pub async fn list_question_comments(
+    question_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<QuestionCommentDto>, String> {
+    let db = open_vault_db(&state)?;
+    let comments = workflow::list_question_comments(&db, &question_id).map_err(map_core_error)?;
+    Ok(comments.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn get_import_progress(
+    import_id: String,
+    state: State<'_, AppState>,
+) -> Result<ImportProgressDto, String> {
+    let db = open_vault_db(&state)?;
+    let progress = workflow::import_progress(&db, &import_id).map_err(map_core_error)?;
+    Ok(progress.into())
+}
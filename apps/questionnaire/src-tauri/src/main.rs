@@ -33,15 +33,75 @@ fn main() {
             commands::questionnaire::import_questionnaire,
             commands::questionnaire::get_column_profiles,
             commands::questionnaire::save_column_mapping,
+            commands::questionnaire::set_import_language,
+            commands::questionnaire::import_questionnaire_revision,
+            commands::questionnaire::diff_questionnaire_revision,
+            commands::questionnaire::carry_over_revision,
+            // Question workflow commands
+            commands::workflow::list_questions,
+            commands::workflow::list_questions_for_control,
+            commands::workflow::set_question_status,
+            commands::workflow::set_question_answer,
+            commands::workflow::assign_question,
+            commands::workflow::add_question_comment,
+            commands::workflow::list_question_comments,
+            commands::workflow::get_import_progress,
             // Answer bank commands
             commands::answer_bank::answer_bank_create,
             commands::answer_bank::answer_bank_update,
             commands::answer_bank::answer_bank_delete,
             commands::answer_bank::answer_bank_list,
+            commands::answer_bank::answer_bank_search,
+            commands::answer_bank::answer_bank_list_aliases,
+            commands::answer_bank::answer_bank_add_alias,
+            commands::answer_bank::answer_bank_delete_alias,
+            commands::answer_bank::answer_bank_list_revisions,
+            commands::answer_bank::answer_bank_revision_at_audit_seq,
+            commands::answer_bank::answer_bank_diff_revisions,
+            commands::answer_bank::answer_bank_rollback,
+            commands::answer_bank::answer_bank_list_review_policies,
+            commands::answer_bank::answer_bank_set_review_policy,
+            commands::answer_bank::answer_bank_delete_review_policy,
+            commands::answer_bank::answer_bank_mark_reviewed,
+            commands::answer_bank::answer_bank_overdue_reviews,
+            commands::answer_bank::answer_bank_set_status,
+            commands::answer_bank::answer_bank_profile_import,
+            commands::answer_bank::answer_bank_bulk_import,
+            commands::answer_bank::answer_bank_export,
+            commands::answer_bank::answer_bank_find_duplicates,
+            commands::answer_bank::answer_bank_merge,
+            commands::answer_bank::answer_bank_list_variables,
+            commands::answer_bank::answer_bank_set_variable,
+            commands::answer_bank::answer_bank_delete_variable,
+            commands::answer_bank::answer_bank_render_entry,
+            commands::answer_bank::answer_bank_unresolved_variables,
+            commands::answer_bank::answer_bank_list_tags,
+            commands::answer_bank::answer_bank_define_tag,
+            commands::answer_bank::answer_bank_rename_tag,
+            commands::answer_bank::answer_bank_delete_tag,
+            commands::answer_bank::answer_bank_map_control,
+            commands::answer_bank::answer_bank_unmap_control,
+            commands::answer_bank::answer_bank_list_entry_controls,
+            commands::answer_bank::answer_bank_list_controls,
+            commands::answer_bank::answer_bank_entries_for_control,
             // Matching commands
             commands::matching::get_matching_suggestions,
+            commands::matching::generate_import_suggestions,
+            commands::matching::list_import_suggestions,
+            commands::matching::accept_suggestion,
+            commands::matching::reject_suggestion,
+            commands::matching::auto_answer_import,
+            commands::matching::list_match_synonyms,
+            commands::matching::set_match_synonym,
+            commands::matching::delete_match_synonym,
             // Export commands
             commands::export::generate_export_pack,
+            commands::export::get_export_signing_key,
+            commands::export::rotate_export_signing_key,
+            commands::export::validate_export_pack,
+            commands::export::list_redaction_profiles,
+            commands::export::save_redaction_profile,
+            commands::export::delete_redaction_profile,
             // License commands
             commands::license::check_license_status,
             commands::license::install_license,
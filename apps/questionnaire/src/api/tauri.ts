@@ -2,12 +2,42 @@ import { invoke } from "@tauri-apps/api/core";
 import type {
   VaultDto,
   QuestionnaireImportDto,
+  RevisionDiffDto,
+  CarryOverReportDto,
   ColumnMapDto,
   AnswerBankEntryDto,
   AnswerBankCreateInputDto,
   AnswerBankUpdatePatchDto,
+  AnswerBankAliasDto,
+  AnswerBankRevisionDto,
+  AnswerBankRevisionDiffDto,
+  EntryStatus,
+  ReviewPolicyDto,
+  ReviewPolicyScope,
+  OwnerOverdueReviewsDto,
+  AnswerBankColumnMapDto,
+  AnswerBankImportProfileDto,
+  AnswerBankImportReportDto,
+  AnswerBankExportSummaryDto,
+  DuplicateGroupDto,
+  VaultVariableDto,
+  RenderedEntryDto,
+  UnresolvedVariablesDto,
+  AnswerBankTagDto,
+  TagUsageDto,
+  TagRenameReportDto,
+  ControlMappingDto,
+  ControlUsageDto,
   MatchSuggestionDto,
+  RankingModel,
+  QuestionSuggestionDto,
+  AutoAnswerReportDto,
+  MatchSynonymDto,
   LicenseStatusDto,
+  QuestionnaireQuestionDto,
+  QuestionStatus,
+  QuestionCommentDto,
+  ImportProgressDto,
 } from "@packages/types";
 
 // ============================================================================
@@ -46,6 +76,24 @@ export async function invokeImportQuestionnaire(filePath: string): Promise<Quest
   return invoke("import_questionnaire", { file_path: filePath });
 }
 
+export async function invokeImportQuestionnaireRevision(
+  parentImportId: string,
+  filePath: string
+): Promise<QuestionnaireImportDto> {
+  return invoke("import_questionnaire_revision", {
+    parent_import_id: parentImportId,
+    file_path: filePath,
+  });
+}
+
+export async function invokeDiffQuestionnaireRevision(importId: string): Promise<RevisionDiffDto> {
+  return invoke("diff_questionnaire_revision", { import_id: importId });
+}
+
+export async function invokeCarryOverRevision(importId: string): Promise<CarryOverReportDto> {
+  return invoke("carry_over_revision", { import_id: importId });
+}
+
 export async function invokeGetColumnProfiles(importId: string): Promise<ColumnProfileDto[]> {
   return invoke("get_column_profiles", { import_id: importId });
 }
@@ -60,6 +108,69 @@ export async function invokeSaveColumnMapping(
   });
 }
 
+export async function invokeSetImportLanguage(
+  importId: string,
+  language: string
+): Promise<QuestionnaireImportDto> {
+  return invoke("set_import_language", { import_id: importId, language });
+}
+
+// ============================================================================
+// QUESTION WORKFLOW COMMANDS
+// ============================================================================
+
+export async function invokeListQuestions(importId: string): Promise<QuestionnaireQuestionDto[]> {
+  return invoke("list_questions", { import_id: importId });
+}
+
+export async function invokeListQuestionsForControl(
+  importId: string,
+  framework: string,
+  controlId?: string
+): Promise<QuestionnaireQuestionDto[]> {
+  return invoke("list_questions_for_control", {
+    import_id: importId,
+    framework,
+    control_id: controlId,
+  });
+}
+
+export async function invokeSetQuestionStatus(
+  questionId: string,
+  status: QuestionStatus
+): Promise<QuestionnaireQuestionDto> {
+  return invoke("set_question_status", { question_id: questionId, status });
+}
+
+export async function invokeSetQuestionAnswer(
+  questionId: string,
+  answer: { answer_bank_entry_id: string } | { answer_override: string }
+): Promise<QuestionnaireQuestionDto> {
+  return invoke("set_question_answer", { question_id: questionId, ...answer });
+}
+
+export async function invokeAssignQuestion(
+  questionId: string,
+  assignee: string | null
+): Promise<QuestionnaireQuestionDto> {
+  return invoke("assign_question", { question_id: questionId, assignee });
+}
+
+export async function invokeAddQuestionComment(
+  questionId: string,
+  body: string
+): Promise<QuestionCommentDto> {
+  return invoke("add_question_comment", { question_id: questionId, body });
+}
+
+export async function invokeListQuestionComments(questionId: string): Promise<QuestionCommentDto[]> {
+  return invoke("list_question_comments", { question_id: questionId });
+}
+
+export async function invokeGetImportProgress(importId: string): Promise<ImportProgressDto> {
+  return invoke("get_import_progress", { import_id: importId });
+}
+
 // ============================================================================
 // ANSWER BANK COMMANDS
 // ============================================================================
@@ -88,32 +199,387 @@ export async function invokeAnswerBankList(
   return invoke("answer_bank_list", { limit, offset });
 }
 
+export async function invokeAnswerBankSearch(
+  query: string,
+  limit: number,
+  offset: number
+): Promise<AnswerBankEntryDto[]> {
+  return invoke("answer_bank_search", { query, limit, offset });
+}
+
+export async function invokeAnswerBankListAliases(
+  entryId: string
+): Promise<AnswerBankAliasDto[]> {
+  return invoke("answer_bank_list_aliases", { entry_id: entryId });
+}
+
+export async function invokeAnswerBankAddAlias(
+  entryId: string,
+  aliasText: string
+): Promise<AnswerBankAliasDto> {
+  return invoke("answer_bank_add_alias", { entry_id: entryId, alias_text: aliasText });
+}
+
+export async function invokeAnswerBankDeleteAlias(aliasId: string): Promise<void> {
+  return invoke("answer_bank_delete_alias", { alias_id: aliasId });
+}
+
+export async function invokeAnswerBankListRevisions(
+  entryId: string
+): Promise<AnswerBankRevisionDto[]> {
+  return invoke("answer_bank_list_revisions", { entry_id: entryId });
+}
+
+/** The revision current at an audit position, e.g. an export pack's audit head. */
+export async function invokeAnswerBankRevisionAtAuditSeq(
+  entryId: string,
+  auditSeq: number
+): Promise<AnswerBankRevisionDto> {
+  return invoke("answer_bank_revision_at_audit_seq", { entry_id: entryId, audit_seq: auditSeq });
+}
+
+export async function invokeAnswerBankDiffRevisions(
+  entryId: string,
+  fromRevision: number,
+  toRevision: number
+): Promise<AnswerBankRevisionDiffDto> {
+  return invoke("answer_bank_diff_revisions", {
+    entry_id: entryId,
+    from_revision: fromRevision,
+    to_revision: toRevision,
+  });
+}
+
+export async function invokeAnswerBankRollback(
+  entryId: string,
+  revision: number
+): Promise<AnswerBankEntryDto> {
+  return invoke("answer_bank_rollback", { entry_id: entryId, revision });
+}
+
+export async function invokeAnswerBankSetStatus(
+  entryId: string,
+  status: EntryStatus
+): Promise<AnswerBankEntryDto> {
+  return invoke("answer_bank_set_status", { entry_id: entryId, status });
+}
+
+export async function invokeAnswerBankListReviewPolicies(): Promise<ReviewPolicyDto[]> {
+  return invoke("answer_bank_list_review_policies");
+}
+
+export async function invokeAnswerBankSetReviewPolicy(
+  scope: ReviewPolicyScope,
+  target: string | undefined,
+  intervalDays: number
+): Promise<ReviewPolicyDto> {
+  return invoke("answer_bank_set_review_policy", {
+    scope,
+    target,
+    interval_days: intervalDays,
+  });
+}
+
+export async function invokeAnswerBankDeleteReviewPolicy(
+  scope: ReviewPolicyScope,
+  target?: string
+): Promise<void> {
+  return invoke("answer_bank_delete_review_policy", { scope, target });
+}
+
+export async function invokeAnswerBankMarkReviewed(
+  entryId: string,
+  reviewedAt?: string
+): Promise<AnswerBankEntryDto> {
+  return invoke("answer_bank_mark_reviewed", { entry_id: entryId, reviewed_at: reviewedAt });
+}
+
+export async function invokeAnswerBankOverdueReviews(
+  asOf?: string
+): Promise<OwnerOverdueReviewsDto[]> {
+  return invoke("answer_bank_overdue_reviews", { as_of: asOf });
+}
+
+export async function invokeAnswerBankProfileImport(
+  sourcePath: string
+): Promise<AnswerBankImportProfileDto> {
+  return invoke("answer_bank_profile_import", { source_path: sourcePath });
+}
+
+export async function invokeAnswerBankBulkImport(
+  sourcePath: string,
+  map: AnswerBankColumnMapDto,
+  dryRun: boolean
+): Promise<AnswerBankImportReportDto> {
+  return invoke("answer_bank_bulk_import", { source_path: sourcePath, map, dry_run: dryRun });
+}
+
+export async function invokeAnswerBankExport(
+  outPath: string,
+  renderVariables?: boolean
+): Promise<AnswerBankExportSummaryDto> {
+  return invoke("answer_bank_export", { out_path: outPath, render_variables: renderVariables });
+}
+
+export async function invokeAnswerBankFindDuplicates(
+  threshold?: number
+): Promise<DuplicateGroupDto[]> {
+  return invoke("answer_bank_find_duplicates", { threshold });
+}
+
+export async function invokeAnswerBankMerge(
+  keepEntryId: string,
+  mergeEntryIds: string[]
+): Promise<AnswerBankEntryDto> {
+  return invoke("answer_bank_merge", {
+    keep_entry_id: keepEntryId,
+    merge_entry_ids: mergeEntryIds,
+  });
+}
+
+export async function invokeAnswerBankListVariables(): Promise<VaultVariableDto[]> {
+  return invoke("answer_bank_list_variables");
+}
+
+export async function invokeAnswerBankSetVariable(
+  name: string,
+  value: string
+): Promise<VaultVariableDto> {
+  return invoke("answer_bank_set_variable", { name, value });
+}
+
+export async function invokeAnswerBankDeleteVariable(name: string): Promise<void> {
+  return invoke("answer_bank_delete_variable", { name });
+}
+
+export async function invokeAnswerBankRenderEntry(entryId: string): Promise<RenderedEntryDto> {
+  return invoke("answer_bank_render_entry", { entry_id: entryId });
+}
+
+export async function invokeAnswerBankUnresolvedVariables(): Promise<UnresolvedVariablesDto[]> {
+  return invoke("answer_bank_unresolved_variables");
+}
+
+export async function invokeAnswerBankListTags(): Promise<TagUsageDto[]> {
+  return invoke("answer_bank_list_tags");
+}
+
+export async function invokeAnswerBankDefineTag(
+  tag: string,
+  description?: string
+): Promise<AnswerBankTagDto> {
+  return invoke("answer_bank_define_tag", { tag, description });
+}
+
+export async function invokeAnswerBankRenameTag(
+  from: string,
+  to: string
+): Promise<TagRenameReportDto> {
+  return invoke("answer_bank_rename_tag", { from, to });
+}
+
+export async function invokeAnswerBankDeleteTag(tag: string): Promise<void> {
+  return invoke("answer_bank_delete_tag", { tag });
+}
+
+export async function invokeAnswerBankMapControl(
+  entryId: string,
+  framework: string,
+  controlId: string
+): Promise<ControlMappingDto> {
+  return invoke("answer_bank_map_control", {
+    entry_id: entryId,
+    framework,
+    control_id: controlId,
+  });
+}
+
+export async function invokeAnswerBankUnmapControl(
+  entryId: string,
+  framework: string,
+  controlId: string
+): Promise<void> {
+  return invoke("answer_bank_unmap_control", {
+    entry_id: entryId,
+    framework,
+    control_id: controlId,
+  });
+}
+
+export async function invokeAnswerBankListEntryControls(
+  entryId: string
+): Promise<ControlMappingDto[]> {
+  return invoke("answer_bank_list_entry_controls", { entry_id: entryId });
+}
+
+export async function invokeAnswerBankListControls(): Promise<ControlUsageDto[]> {
+  return invoke("answer_bank_list_controls");
+}
+
+export async function invokeAnswerBankEntriesForControl(
+  framework: string,
+  controlId?: string
+): Promise<AnswerBankEntryDto[]> {
+  return invoke("answer_bank_entries_for_control", { framework, control_id: controlId });
+}
+
 // ============================================================================
 // MATCHING COMMANDS
 // ============================================================================
 
 export async function invokeGetMatchingSuggestions(
   question: string,
-  topN?: number
+  topN?: number,
+  model?: RankingModel,
+  locale?: string
 ): Promise<MatchSuggestionDto[]> {
   return invoke("get_matching_suggestions", {
     question,
     top_n: topN ?? 5,
+    model,
+    locale,
   });
 }
 
+export async function invokeGenerateImportSuggestions(
+  importId: string,
+  topN?: number
+): Promise<QuestionSuggestionDto[]> {
+  return invoke("generate_import_suggestions", {
+    import_id: importId,
+    top_n: topN ?? 5,
+  });
+}
+
+export async function invokeListImportSuggestions(
+  importId: string
+): Promise<QuestionSuggestionDto[]> {
+  return invoke("list_import_suggestions", { import_id: importId });
+}
+
+export async function invokeAcceptSuggestion(suggestionId: string): Promise<QuestionSuggestionDto> {
+  return invoke("accept_suggestion", { suggestion_id: suggestionId });
+}
+
+export async function invokeRejectSuggestion(suggestionId: string): Promise<QuestionSuggestionDto> {
+  return invoke("reject_suggestion", { suggestion_id: suggestionId });
+}
+
+export async function invokeAutoAnswerImport(
+  importId: string,
+  threshold: number,
+  topN?: number
+): Promise<AutoAnswerReportDto> {
+  return invoke("auto_answer_import", {
+    import_id: importId,
+    threshold,
+    top_n: topN ?? 5,
+  });
+}
+
+export async function invokeListMatchSynonyms(): Promise<MatchSynonymDto[]> {
+  return invoke("list_match_synonyms");
+}
+
+export async function invokeSetMatchSynonym(
+  term: string,
+  canonical: string
+): Promise<MatchSynonymDto> {
+  return invoke("set_match_synonym", { term, canonical });
+}
+
+export async function invokeDeleteMatchSynonym(term: string): Promise<void> {
+  return invoke("delete_match_synonym", { term });
+}
+
 // ============================================================================
 // EXPORT COMMANDS
 // ============================================================================
 
+export interface ExportSectionDto {
+  name: string; // "answer_bank", "audit_log", "column_maps", "evidence", "index", "questionnaires", "redactions"
+  version: number;
+  file_count: number;
+}
+
+/** Empty criteria select everything; set criteria combine with AND. */
+export interface ExportScopeDto {
+  import_ids?: string[];
+  answer_bank_tags?: string[];
+  evidence_tags?: string[];
+  controls?: { framework: string; control_id: string | null }[];
+  evidence_added_from_seq?: number | null; // audit seq of EvidenceAdded, inclusive
+  evidence_added_to_seq?: number | null;
+  full_audit_log?: boolean; // scoped packs leave out the vault-wide audit log unless set
+}
+
 export interface ExportPackDto {
   zip_path: string;
   manifest_version: number;
   file_count: number;
+  sections: ExportSectionDto[];
+  signer_public_key_hex: string;
+  audit_head_seq: number | null;
+  audit_head_hash: string | null;
+  scope: Required<ExportScopeDto>;
+  redaction_profile: string | null;
+}
+
+export interface RedactionRuleDto {
+  kind: "keyword" | "regex"; // keywords match whole words, case-insensitively
+  pattern: string;
+  replacement: string;
+}
+
+export interface RedactionProfileDto {
+  name: string;
+  rules: RedactionRuleDto[];
+  excluded_evidence_tags: string[];
+  remove_notes: boolean;
+  remove_owners: boolean;
+}
+
+export async function invokeGenerateExportPack(
+  outputPath: string,
+  scope?: ExportScopeDto,
+  redactionProfile?: string
+): Promise<ExportPackDto> {
+  return invoke("generate_export_pack", {
+    output_path: outputPath,
+    scope: scope ?? null,
+    redaction_profile: redactionProfile ?? null,
+  });
+}
+
+export async function invokeListRedactionProfiles(): Promise<RedactionProfileDto[]> {
+  return invoke("list_redaction_profiles");
+}
+
+export async function invokeSaveRedactionProfile(
+  profile: RedactionProfileDto
+): Promise<RedactionProfileDto> {
+  return invoke("save_redaction_profile", { profile });
+}
+
+export async function invokeDeleteRedactionProfile(name: string): Promise<void> {
+  return invoke("delete_redaction_profile", { name });
+}
+
+export async function invokeGetExportSigningKey(): Promise<string | null> {
+  return invoke("get_export_signing_key");
+}
+
+/** Replaces the vault's signing key; resolves to the new public key. */
+export async function invokeRotateExportSigningKey(): Promise<string> {
+  return invoke("rotate_export_signing_key");
 }
 
-export async function invokeGenerateExportPack(outputPath: string): Promise<ExportPackDto> {
-  return invoke("generate_export_pack", { output_path: outputPath });
+/** Resolves to the signer's public key if the pack is signed by a trusted key. */
+export async function invokeValidateExportPack(
+  zipPath: string,
+  trustedKeys: string[]
+): Promise<string> {
+  return invoke("validate_export_pack", { zip_path: zipPath, trusted_keys: trustedKeys });
 }
 
 // ============================================================================
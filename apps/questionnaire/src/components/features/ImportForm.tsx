@@ -19,7 +19,7 @@ export default function ImportForm({ onComplete }: ImportFormProps) {
         filters: [
           {
             name: "Questionnaire Files",
-            extensions: ["xlsx", "csv"],
+            extensions: ["xlsx", "csv", "ods", "docx"],
           },
         ],
       });
@@ -50,7 +50,7 @@ export default function ImportForm({ onComplete }: ImportFormProps) {
         <div>
           <h2 className="text-lg font-semibold mb-2">Select Questionnaire File</h2>
           <p className="text-sm text-muted-foreground mb-4">
-            Choose an Excel (.xlsx) or CSV (.csv) file containing your questionnaire data.
+            Choose an Excel (.xlsx), CSV (.csv), LibreOffice (.ods) or Word (.docx) file containing your questionnaire data.
           </p>
         </div>
 
@@ -0,0 +1,348 @@
+//! Question aliases for answer bank entries.
+//!
+//! An alias is a question wording that was answered with the entry. Matching
+//! treats aliases as extra question text, and an alias identical to the
+//! incoming question scores 1.0. Aliases are learned when a reviewer accepts
+//! an entry for a questionnaire question (not by auto-answer) and can be added
+//! or removed by hand.
+
+use super::{ab_get_entry, fts_words, load_vault_id, AnswerBankEntry};
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::ids::Ulid;
+use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
+use crate::storage::db::{escape_db_text, unescape_db_text, SqliteDb};
+use crate::storage::AuditEventBatch;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerBankAlias {
+    pub alias_id: String,
+    pub entry_id: String,
+    pub alias_text: String,
+    /// Questionnaire question the alias was learned from; `None` if added by hand.
+    pub source_question_id: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+pub fn ab_list_aliases(db: &SqliteDb, entry_id: &str) -> CoreResult<Vec<AnswerBankAlias>> {
+    ab_get_entry(db, entry_id)?;
+    query_aliases(
+        db,
+        &format!(
+            "SELECT {} FROM answer_bank_alias WHERE entry_id={} ORDER BY normalized_text ASC, alias_id ASC;",
+            ALIAS_COLUMNS,
+            db.q(entry_id)
+        ),
+    )
+}
+
+/// Add a question wording to an entry by hand.
+pub fn ab_add_alias(
+    db: &SqliteDb,
+    entry_id: &str,
+    alias_text: &str,
+    actor: &str,
+) -> CoreResult<AnswerBankAlias> {
+    validator::validate_chain(db)?;
+
+    let entry = ab_get_entry(db, entry_id)?;
+    let (text, normalized) = alias_key(alias_text)?;
+    if alias_exists(db, entry_id, &normalized)? {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            "alias already exists for this entry",
+        ));
+    }
+
+    let mut events = AuditEventBatch::new(db, &entry.vault_id)?;
+    let (alias_id, insert_sql) = insert_alias_sql(db, entry_id, &text, &normalized, None, actor)?;
+    events.push(db, actor, "AnswerBankAliasAdded", {
+        let mut o = CanonicalJson::object();
+        o.insert("alias_id", CanonicalJson::String(alias_id.clone()));
+        o.insert("entry_id", CanonicalJson::String(entry_id.to_string()));
+        o
+    })?;
+    db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", insert_sql, events.sql()))?;
+
+    get_alias(db, &alias_id)
+}
+
+pub fn ab_delete_alias(db: &SqliteDb, alias_id: &str, actor: &str) -> CoreResult<()> {
+    validator::validate_chain(db)?;
+
+    let alias = get_alias(db, alias_id)?;
+    let vault_id = load_vault_id(db)?;
+    let delete_sql = format!(
+        "DELETE FROM answer_bank_alias_fts WHERE rowid=(SELECT rowid FROM answer_bank_alias WHERE alias_id={});\nDELETE FROM answer_bank_alias WHERE alias_id={};",
+        db.q(alias_id),
+        db.q(alias_id)
+    );
+    let event_sql =
+        crate::storage::build_event_insert_sql(db, &vault_id, actor, "AnswerBankAliasDeleted", {
+            let mut o = CanonicalJson::object();
+            o.insert("alias_id", CanonicalJson::String(alias.alias_id.clone()));
+            o.insert("entry_id", CanonicalJson::String(alias.entry_id.clone()));
+            o
+        })?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", delete_sql, event_sql))
+}
+
+/// SQL remembering `question_text` as an alias of `entry_id`, or an empty
+/// string when the entry already knows that wording (as an alias or as its
+/// canonical question).
+pub(crate) fn learn_alias_sql(
+    db: &SqliteDb,
+    entry_id: &str,
+    question_text: &str,
+    question_id: &str,
+    actor: &str,
+    events: &mut AuditEventBatch,
+) -> CoreResult<String> {
+    let (text, normalized) = match alias_key(question_text) {
+        Ok(k) => k,
+        Err(_) => return Ok(String::new()),
+    };
+    let entry = ab_get_entry(db, entry_id)?;
+    if fts_words(&entry.question_canonical).join(" ") == normalized
+        || alias_exists(db, entry_id, &normalized)?
+    {
+        return Ok(String::new());
+    }
+
+    let (alias_id, sql) =
+        insert_alias_sql(db, entry_id, &text, &normalized, Some(question_id), actor)?;
+    events.push(db, actor, "AnswerBankAliasAdded", {
+        let mut o = CanonicalJson::object();
+        o.insert("alias_id", CanonicalJson::String(alias_id));
+        o.insert("entry_id", CanonicalJson::String(entry_id.to_string()));
+        o.insert(
+            "source_question_id",
+            CanonicalJson::String(question_id.to_string()),
+        );
+        o
+    })?;
+    Ok(sql)
+}
+
+/// SQL moving the aliases of `merged` entries to `keep` and remembering each
+/// merged entry's question as an alias of `keep`. Wordings `keep` already
+/// knows are dropped. Returns the SQL and the ids of moved aliases.
+pub(crate) fn merge_aliases_sql(
+    db: &SqliteDb,
+    keep: &AnswerBankEntry,
+    merged: &[AnswerBankEntry],
+    actor: &str,
+    events: &mut AuditEventBatch,
+) -> CoreResult<(String, Vec<String>)> {
+    let mut known: HashSet<String> = query_aliases(
+        db,
+        &format!(
+            "SELECT {} FROM answer_bank_alias WHERE entry_id={};",
+            ALIAS_COLUMNS,
+            db.q(&keep.entry_id)
+        ),
+    )?
+    .into_iter()
+    .filter_map(|a| alias_key(&a.alias_text).ok().map(|(_, n)| n))
+    .collect();
+    known.insert(fts_words(&keep.question_canonical).join(" "));
+
+    let mut sql = String::new();
+    let mut moved = Vec::new();
+    for entry in merged {
+        let aliases = query_aliases(
+            db,
+            &format!(
+                "SELECT {} FROM answer_bank_alias WHERE entry_id={} ORDER BY normalized_text ASC, alias_id ASC;",
+                ALIAS_COLUMNS,
+                db.q(&entry.entry_id)
+            ),
+        )?;
+        for alias in aliases {
+            let normalized = alias_key(&alias.alias_text)
+                .map(|(_, n)| n)
+                .unwrap_or_default();
+            if known.insert(normalized) {
+                sql.push_str(&format!(
+                    "UPDATE answer_bank_alias SET entry_id={} WHERE alias_id={};\n",
+                    db.q(&keep.entry_id),
+                    db.q(&alias.alias_id)
+                ));
+                moved.push(alias.alias_id);
+            } else {
+                sql.push_str(&format!(
+                    "DELETE FROM answer_bank_alias_fts WHERE rowid=(SELECT rowid FROM answer_bank_alias WHERE alias_id={});\nDELETE FROM answer_bank_alias WHERE alias_id={};\n",
+                    db.q(&alias.alias_id),
+                    db.q(&alias.alias_id)
+                ));
+            }
+        }
+
+        let Ok((text, normalized)) = alias_key(&entry.question_canonical) else {
+            continue;
+        };
+        if !known.insert(normalized.clone()) {
+            continue;
+        }
+        let (alias_id, insert_sql) =
+            insert_alias_sql(db, &keep.entry_id, &text, &normalized, None, actor)?;
+        sql.push_str(&insert_sql);
+        events.push(db, actor, "AnswerBankAliasAdded", {
+            let mut o = CanonicalJson::object();
+            o.insert("alias_id", CanonicalJson::String(alias_id));
+            o.insert("entry_id", CanonicalJson::String(keep.entry_id.clone()));
+            o.insert(
+                "merged_entry_id",
+                CanonicalJson::String(entry.entry_id.clone()),
+            );
+            o
+        })?;
+    }
+    Ok((sql, moved))
+}
+
+/// SQL removing every alias of an entry (used when the entry is deleted).
+pub(crate) fn delete_entry_aliases_sql(db: &SqliteDb, entry_id: &str) -> String {
+    format!(
+        "DELETE FROM answer_bank_alias_fts WHERE rowid IN (SELECT rowid FROM answer_bank_alias WHERE entry_id={});\nDELETE FROM answer_bank_alias WHERE entry_id={};",
+        db.q(entry_id),
+        db.q(entry_id)
+    )
+}
+
+/// Alias texts per entry for the given entries.
+pub(crate) fn aliases_for_entries(
+    db: &SqliteDb,
+    entry_ids: &[String],
+) -> CoreResult<HashMap<String, Vec<String>>> {
+    let mut out: HashMap<String, Vec<String>> = HashMap::new();
+    if entry_ids.is_empty() {
+        return Ok(out);
+    }
+    let in_list: Vec<String> = entry_ids.iter().map(|id| db.q(id)).collect();
+    for r in db.query_rows_tsv(&format!(
+        "SELECT entry_id, alias_text FROM answer_bank_alias WHERE entry_id IN ({}) ORDER BY entry_id ASC, normalized_text ASC;",
+        in_list.join(", ")
+    ))? {
+        if r.len() >= 2 {
+            out.entry(r[0].clone())
+                .or_default()
+                .push(unescape_db_text(&r[1]));
+        }
+    }
+    Ok(out)
+}
+
+/// Ids of matchable entries whose aliases match any of the FTS `match_expr`,
+/// best first.
+pub(crate) fn alias_candidate_ids(
+    db: &SqliteDb,
+    match_expr: &str,
+    limit: i64,
+) -> CoreResult<Vec<String>> {
+    let mut ids: Vec<String> = Vec::new();
+    for r in db.query_rows_tsv(&format!(
+        "SELECT answer_bank_alias.entry_id FROM answer_bank_alias_fts JOIN answer_bank_alias ON answer_bank_alias.rowid=answer_bank_alias_fts.rowid JOIN answer_bank ON answer_bank.entry_id=answer_bank_alias.entry_id WHERE answer_bank_alias_fts MATCH {} AND {} ORDER BY bm25(answer_bank_alias_fts) ASC, answer_bank_alias.entry_id ASC LIMIT {};",
+        db.q(match_expr),
+        super::status::MATCHABLE_SQL,
+        limit
+    ))? {
+        if let Some(id) = r.into_iter().next() {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    Ok(ids)
+}
+
+const ALIAS_COLUMNS: &str =
+    "alias_id, entry_id, alias_text, IFNULL(source_question_id,''), created_by, created_at";
+
+fn get_alias(db: &SqliteDb, alias_id: &str) -> CoreResult<AnswerBankAlias> {
+    query_aliases(
+        db,
+        &format!(
+            "SELECT {} FROM answer_bank_alias WHERE alias_id={} LIMIT 1;",
+            ALIAS_COLUMNS,
+            db.q(alias_id)
+        ),
+    )?
+    .into_iter()
+    .next()
+    .ok_or_else(|| CoreError::new(CoreErrorCode::NotFound, "answer bank alias not found"))
+}
+
+fn query_aliases(db: &SqliteDb, sql: &str) -> CoreResult<Vec<AnswerBankAlias>> {
+    let mut out = Vec::new();
+    for r in db.query_rows_tsv(sql)? {
+        if r.len() < 6 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected answer_bank_alias row",
+            ));
+        }
+        out.push(AnswerBankAlias {
+            alias_id: r[0].clone(),
+            entry_id: r[1].clone(),
+            alias_text: unescape_db_text(&r[2]),
+            source_question_id: Some(r[3].clone()).filter(|s| !s.is_empty()),
+            created_by: unescape_db_text(&r[4]),
+            created_at: r[5].clone(),
+        });
+    }
+    Ok(out)
+}
+
+/// Trimmed single-line text and its normalized key.
+fn alias_key(alias_text: &str) -> CoreResult<(String, String)> {
+    let text = alias_text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let normalized = fts_words(&text).join(" ");
+    if normalized.is_empty() {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "alias_text is required",
+        ));
+    }
+    Ok((text, normalized))
+}
+
+fn alias_exists(db: &SqliteDb, entry_id: &str, normalized: &str) -> CoreResult<bool> {
+    Ok(db
+        .query_optional_string(&format!(
+            "SELECT alias_id FROM answer_bank_alias WHERE entry_id={} AND normalized_text={} LIMIT 1;",
+            db.q(entry_id),
+            db.q(normalized)
+        ))?
+        .is_some())
+}
+
+fn insert_alias_sql(
+    db: &SqliteDb,
+    entry_id: &str,
+    text: &str,
+    normalized: &str,
+    source_question_id: Option<&str>,
+    actor: &str,
+) -> CoreResult<(String, String)> {
+    let alias_id = Ulid::new()?.to_string();
+    let sql = format!(
+        "INSERT INTO answer_bank_alias (alias_id, entry_id, alias_text, normalized_text, source_question_id, created_by, created_at) VALUES ({}, {}, {}, {}, {}, {}, {});\nINSERT INTO answer_bank_alias_fts (rowid, alias_text) VALUES ((SELECT rowid FROM answer_bank_alias WHERE alias_id={}), {});\n",
+        db.q(&alias_id),
+        db.q(entry_id),
+        db.q(&escape_db_text(text)),
+        db.q(normalized),
+        match source_question_id {
+            Some(id) => db.q(id),
+            None => "NULL".to_string(),
+        },
+        db.q(&escape_db_text(actor)),
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        db.q(&alias_id),
+        db.q(text),
+    );
+    Ok((alias_id, sql))
+}
@@ -0,0 +1,605 @@
+//! Spreadsheet import and export of the answer bank.
+//!
+//! Files are read with the questionnaire importer and profiled into the same
+//! [`ColumnProfile`]s, then mapped to entry fields with an
+//! [`AnswerBankColumnMap`]. Each data row becomes a create, or an update when
+//! it names an existing `entry_id`; rows that would change nothing, repeat
+//! content already in the bank or earlier in the file (same `content_hash`),
+//! or cannot be applied are reported and skipped. A dry run returns the same
+//! report without writing. Otherwise every write and one
+//! `AnswerBankBulkImported` summary event commit in a single transaction.
+//!
+//! Exports use [`EXPORT_HEADERS`] as the header row, so an exported file maps
+//! with [`ab_guess_column_map`] and imports back as unchanged rows. List
+//! fields (tags, evidence links) are `;`-separated in both directions.
+//! Translations have no columns; updates from a file keep them. A
+//! rendered export resolves `{{variable}}` placeholders in the answers for
+//! sharing outside the vault; it no longer round-trips the templates.
+
+use super::{
+    compute_changed_fields, create_entry_sql, load_vault_id, new_entry, patched_entry,
+    update_entry_sql, AnswerBankCreateInput, AnswerBankEntry, AnswerBankUpdatePatch,
+    TemplateVariables, ENTRY_COLUMNS,
+};
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::questionnaire::ColumnProfile;
+use crate::storage::db::SqliteDb;
+use crate::storage::AuditEventBatch;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+/// Header row of exported files.
+pub const EXPORT_HEADERS: [&str; 12] = [
+    "entry_id",
+    "question",
+    "answer_short",
+    "answer_long",
+    "notes",
+    "owner",
+    "tags",
+    "evidence_links",
+    "source",
+    "status",
+    "last_reviewed_at",
+    "content_hash",
+];
+
+const LIST_SEPARATOR: char = ';';
+const DEFAULT_SOURCE: &str = "import";
+
+/// Spreadsheet columns (`ColumnProfile::col_ref`) holding each entry field.
+///
+/// Blank `answer_short`, `owner` and `source` cells keep the current value
+/// on update; on create they default to the long answer, the importing
+/// actor and `import`. Blank notes, tags or evidence cells clear the field.
+/// Unmapped fields are left alone on update.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnswerBankColumnMap {
+    pub question: String,
+    pub answer_long: String,
+    pub answer_short: Option<String>,
+    pub entry_id: Option<String>,
+    pub notes: Option<String>,
+    pub owner: Option<String>,
+    pub tags: Option<String>,
+    pub evidence_links: Option<String>,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkRowAction {
+    Create,
+    Update,
+    /// Names an entry whose content already matches the row.
+    Unchanged,
+    /// Same content as an existing entry or an earlier row.
+    Duplicate,
+    /// Cannot be applied; see the row message.
+    Conflict,
+}
+
+impl BulkRowAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BulkRowAction::Create => "create",
+            BulkRowAction::Update => "update",
+            BulkRowAction::Unchanged => "unchanged",
+            BulkRowAction::Duplicate => "duplicate",
+            BulkRowAction::Conflict => "conflict",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkImportRow {
+    /// 1-based sheet row; the header is row 1. A CSV record spanning lines
+    /// counts once.
+    pub row_number: i64,
+    pub action: BulkRowAction,
+    /// Entry written, matched or duplicated. Unset for dry-run creates.
+    pub entry_id: Option<String>,
+    pub question: String,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerBankImportReport {
+    pub source_filename: String,
+    pub source_sha256: String,
+    pub dry_run: bool,
+    pub created: i64,
+    pub updated: i64,
+    pub unchanged: i64,
+    pub duplicates: i64,
+    pub conflicts: i64,
+    pub rows: Vec<BulkImportRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerBankExportSummary {
+    pub format: String, // 'csv' | 'xlsx'
+    pub entry_count: i64,
+    pub sha256: String,
+    /// Placeholders left in a rendered export, sorted and distinct.
+    pub unresolved_variables: Vec<String>,
+}
+
+/// Profile the columns of a CSV or XLSX file for mapping.
+pub fn ab_profile_import_file(source_path: &Path) -> CoreResult<Vec<ColumnProfile>> {
+    Ok(read_import_file(source_path)?.2)
+}
+
+/// Map columns whose labels match [`EXPORT_HEADERS`] (case-insensitive;
+/// `answer` is accepted for `answer_long`). `None` without question and
+/// answer columns.
+pub fn ab_guess_column_map(cols: &[ColumnProfile]) -> Option<AnswerBankColumnMap> {
+    let find = |labels: &[&str]| {
+        cols.iter()
+            .find(|c| {
+                labels
+                    .iter()
+                    .any(|l| c.label.trim().eq_ignore_ascii_case(l))
+            })
+            .map(|c| c.col_ref.clone())
+    };
+    Some(AnswerBankColumnMap {
+        question: find(&["question"])?,
+        answer_long: find(&["answer_long", "answer"])?,
+        answer_short: find(&["answer_short"]),
+        entry_id: find(&["entry_id"]),
+        notes: find(&["notes"]),
+        owner: find(&["owner"]),
+        tags: find(&["tags"]),
+        evidence_links: find(&["evidence_links"]),
+        source: find(&["source"]),
+    })
+}
+
+/// Import the rows of a CSV or XLSX file; see the module docs.
+pub fn ab_bulk_import(
+    db: &SqliteDb,
+    source_path: &Path,
+    map: &AnswerBankColumnMap,
+    dry_run: bool,
+    actor: &str,
+) -> CoreResult<AnswerBankImportReport> {
+    validator::validate_chain(db)?;
+
+    let (_, rows, cols) = read_import_file(source_path)?;
+    let columns = resolve_columns(map, &cols)?;
+    let vault_id = load_vault_id(db)?;
+
+    let existing = all_entries(db)?;
+    let by_id: HashMap<&str, &AnswerBankEntry> =
+        existing.iter().map(|e| (e.entry_id.as_str(), e)).collect();
+    let mut content_owners: HashMap<String, ContentOwner> = existing
+        .iter()
+        .map(|e| {
+            (
+                e.content_hash.clone(),
+                ContentOwner::Entry(e.entry_id.clone()),
+            )
+        })
+        .collect();
+    let mut seen_ids = HashSet::new();
+
+    let mut events = AuditEventBatch::new(db, &vault_id)?;
+    let mut sql = String::new();
+    let mut report_rows = Vec::new();
+
+    for (i, row) in rows.iter().enumerate().skip(1) {
+        let cells = RowCells {
+            row,
+            columns: &columns,
+        };
+        if row.iter().all(|c| c.trim().is_empty()) {
+            continue;
+        }
+        let row_number = i as i64 + 1;
+        let mut out = BulkImportRow {
+            row_number,
+            action: BulkRowAction::Conflict,
+            entry_id: None,
+            question: cells.get(Field::Question).unwrap_or_default(),
+            message: None,
+        };
+
+        let entry_id = cells.get(Field::EntryId);
+        if let Some(id) = entry_id {
+            out.entry_id = Some(id.clone());
+            let Some(before) = by_id.get(id.as_str()).copied() else {
+                out.message = Some("unknown entry_id".to_string());
+                report_rows.push(out);
+                continue;
+            };
+            if !seen_ids.insert(id.clone()) {
+                out.message = Some("entry_id appears more than once".to_string());
+                report_rows.push(out);
+                continue;
+            }
+            let after = match patched_entry(before, cells.update_patch(), actor) {
+                Ok(after) => after,
+                Err(e) => {
+                    out.message = Some(row_error_message(e)?);
+                    report_rows.push(out);
+                    continue;
+                }
+            };
+            if compute_changed_fields(before, &after).is_empty() {
+                out.action = BulkRowAction::Unchanged;
+                report_rows.push(out);
+                continue;
+            }
+            if let Some(owner) = content_owners.get(&after.content_hash) {
+                if !owner.is_entry(&id) {
+                    out.message = Some(owner.describe("same content as"));
+                    report_rows.push(out);
+                    continue;
+                }
+            }
+            if content_owners
+                .get(&before.content_hash)
+                .is_some_and(|o| o.is_entry(&id))
+            {
+                content_owners.remove(&before.content_hash);
+            }
+            content_owners.insert(after.content_hash.clone(), ContentOwner::Entry(id.clone()));
+            if !dry_run {
+                sql.push_str(&update_entry_sql(
+                    db,
+                    before,
+                    &after,
+                    actor,
+                    None,
+                    &mut events,
+                )?);
+            }
+            out.action = BulkRowAction::Update;
+            report_rows.push(out);
+            continue;
+        }
+
+        let entry = match new_entry(&vault_id, cells.create_input(actor), actor) {
+            Ok(entry) => entry,
+            Err(e) => {
+                out.message = Some(row_error_message(e)?);
+                report_rows.push(out);
+                continue;
+            }
+        };
+        if let Some(owner) = content_owners.get(&entry.content_hash) {
+            out.action = BulkRowAction::Duplicate;
+            out.entry_id = owner.entry_id(dry_run);
+            out.message = Some(owner.describe("same content as"));
+            report_rows.push(out);
+            continue;
+        }
+        content_owners.insert(
+            entry.content_hash.clone(),
+            ContentOwner::Row(row_number, entry.entry_id.clone()),
+        );
+        if !dry_run {
+            sql.push_str(&create_entry_sql(db, &entry, actor, &mut events)?);
+            out.entry_id = Some(entry.entry_id.clone());
+        }
+        out.action = BulkRowAction::Create;
+        report_rows.push(out);
+    }
+
+    let count = |a: BulkRowAction| report_rows.iter().filter(|r| r.action == a).count() as i64;
+    let report = AnswerBankImportReport {
+        source_filename: source_path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "answer_bank".to_string()),
+        source_sha256: crate::audit::hasher::sha256_hex_file(source_path)?,
+        dry_run,
+        created: count(BulkRowAction::Create),
+        updated: count(BulkRowAction::Update),
+        unchanged: count(BulkRowAction::Unchanged),
+        duplicates: count(BulkRowAction::Duplicate),
+        conflicts: count(BulkRowAction::Conflict),
+        rows: report_rows,
+    };
+
+    if !dry_run {
+        events.push(db, actor, "AnswerBankBulkImported", {
+            let mut o = CanonicalJson::object();
+            o.insert(
+                "source_filename",
+                CanonicalJson::String(report.source_filename.clone()),
+            );
+            o.insert(
+                "source_sha256",
+                CanonicalJson::String(report.source_sha256.clone()),
+            );
+            o.insert("created", CanonicalJson::Number(report.created));
+            o.insert("updated", CanonicalJson::Number(report.updated));
+            o.insert("unchanged", CanonicalJson::Number(report.unchanged));
+            o.insert("duplicates", CanonicalJson::Number(report.duplicates));
+            o.insert("conflicts", CanonicalJson::Number(report.conflicts));
+            o
+        })?;
+        db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", sql, events.sql()))?;
+    }
+
+    Ok(report)
+}
+
+/// Write every entry to `out_path` as CSV or XLSX (by extension), ordered by
+/// question.
+pub fn ab_export_entries(
+    db: &SqliteDb,
+    out_path: &Path,
+    render_variables: bool,
+) -> CoreResult<AnswerBankExportSummary> {
+    let entries = all_entries(db)?;
+    let variables = if render_variables {
+        Some(TemplateVariables::load(db)?)
+    } else {
+        None
+    };
+    let unresolved = write_entries(out_path, &entries, variables.as_ref())?;
+
+    Ok(AnswerBankExportSummary {
+        format: file_format(out_path),
+        entry_count: entries.len() as i64,
+        sha256: crate::audit::hasher::sha256_hex_file(out_path)?,
+        unresolved_variables: unresolved,
+    })
+}
+
+/// Write `entries` in [`EXPORT_HEADERS`] layout, rendering answers when
+/// `variables` is given. Returns the placeholders left unresolved.
+pub(crate) fn write_entries(
+    out_path: &Path,
+    entries: &[AnswerBankEntry],
+    variables: Option<&TemplateVariables>,
+) -> CoreResult<Vec<String>> {
+    let mut unresolved = BTreeSet::new();
+    let mut rows: Vec<Vec<String>> = vec![EXPORT_HEADERS.iter().map(|h| h.to_string()).collect()];
+    for e in entries {
+        let (answer_short, answer_long) = match variables {
+            Some(v) => {
+                let rendered = v.render_entry(e);
+                unresolved.extend(rendered.unresolved);
+                (rendered.answer_short, rendered.answer_long)
+            }
+            None => (e.answer_short.clone(), e.answer_long.clone()),
+        };
+        rows.push(vec![
+            e.entry_id.clone(),
+            e.question_canonical.clone(),
+            answer_short,
+            answer_long,
+            e.notes.clone().unwrap_or_default(),
+            e.owner.clone(),
+            join_list(&e.tags),
+            join_list(&e.evidence_links),
+            e.source.clone(),
+            e.status.as_str().to_string(),
+            e.last_reviewed_at.clone().unwrap_or_default(),
+            e.content_hash.clone(),
+        ]);
+    }
+
+    crate::questionnaire::write_source(out_path, "Answer Bank", &rows)?;
+    Ok(unresolved.into_iter().collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Field {
+    Question,
+    AnswerLong,
+    AnswerShort,
+    EntryId,
+    Notes,
+    Owner,
+    Tags,
+    EvidenceLinks,
+    Source,
+}
+
+impl Field {
+    fn name(&self) -> &'static str {
+        match self {
+            Field::Question => "question",
+            Field::AnswerLong => "answer_long",
+            Field::AnswerShort => "answer_short",
+            Field::EntryId => "entry_id",
+            Field::Notes => "notes",
+            Field::Owner => "owner",
+            Field::Tags => "tags",
+            Field::EvidenceLinks => "evidence_links",
+            Field::Source => "source",
+        }
+    }
+}
+
+/// Who already has a given content hash.
+enum ContentOwner {
+    Entry(String),
+    /// An earlier row of this import, with the id its create will get.
+    Row(i64, String),
+}
+
+impl ContentOwner {
+    fn is_entry(&self, entry_id: &str) -> bool {
+        matches!(self, ContentOwner::Entry(id) if id == entry_id)
+    }
+
+    fn entry_id(&self, dry_run: bool) -> Option<String> {
+        match self {
+            ContentOwner::Entry(id) => Some(id.clone()),
+            ContentOwner::Row(_, id) if !dry_run => Some(id.clone()),
+            ContentOwner::Row(..) => None,
+        }
+    }
+
+    fn describe(&self, prefix: &str) -> String {
+        match self {
+            ContentOwner::Entry(id) => format!("{prefix} entry {id}"),
+            ContentOwner::Row(n, _) => format!("{prefix} row {n}"),
+        }
+    }
+}
+
+struct RowCells<'a> {
+    row: &'a [String],
+    columns: &'a HashMap<Field, usize>,
+}
+
+impl RowCells<'_> {
+    fn mapped(&self, field: Field) -> bool {
+        self.columns.contains_key(&field)
+    }
+
+    /// Trimmed cell of `field`; `None` when unmapped or blank.
+    fn get(&self, field: Field) -> Option<String> {
+        let idx = *self.columns.get(&field)?;
+        let v = self.row.get(idx).map(|s| s.trim()).unwrap_or("");
+        if v.is_empty() {
+            None
+        } else {
+            Some(v.to_string())
+        }
+    }
+
+    fn list(&self, field: Field) -> Vec<String> {
+        self.get(field)
+            .map(|v| v.split(LIST_SEPARATOR).map(|s| s.to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    fn create_input(&self, actor: &str) -> AnswerBankCreateInput {
+        let answer_long = self.get(Field::AnswerLong).unwrap_or_default();
+        AnswerBankCreateInput {
+            question_canonical: self.get(Field::Question).unwrap_or_default(),
+            answer_short: self
+                .get(Field::AnswerShort)
+                .unwrap_or_else(|| answer_long.clone()),
+            answer_long,
+            notes: self.get(Field::Notes),
+            evidence_links: self.list(Field::EvidenceLinks),
+            owner: self.get(Field::Owner).unwrap_or_else(|| actor.to_string()),
+            last_reviewed_at: None,
+            tags: self.list(Field::Tags),
+            source: self
+                .get(Field::Source)
+                .unwrap_or_else(|| DEFAULT_SOURCE.to_string()),
+            translations: Vec::new(),
+        }
+    }
+
+    fn update_patch(&self) -> AnswerBankUpdatePatch {
+        AnswerBankUpdatePatch {
+            question_canonical: Some(self.get(Field::Question).unwrap_or_default()),
+            answer_long: Some(self.get(Field::AnswerLong).unwrap_or_default()),
+            answer_short: self.get(Field::AnswerShort),
+            notes: self.mapped(Field::Notes).then(|| self.get(Field::Notes)),
+            evidence_links: self
+                .mapped(Field::EvidenceLinks)
+                .then(|| self.list(Field::EvidenceLinks)),
+            owner: self.get(Field::Owner),
+            last_reviewed_at: None,
+            tags: self.mapped(Field::Tags).then(|| self.list(Field::Tags)),
+            source: self.get(Field::Source),
+            translations: None,
+        }
+    }
+}
+
+fn read_import_file(
+    source_path: &Path,
+) -> CoreResult<(String, Vec<Vec<String>>, Vec<ColumnProfile>)> {
+    if !source_path.exists() {
+        return Err(CoreError::new(
+            CoreErrorCode::NotFound,
+            "answer bank import file not found",
+        ));
+    }
+    if !matches!(file_format(source_path).as_str(), "csv" | "xlsx") {
+        return Err(CoreError::new(
+            CoreErrorCode::UnsupportedFormat,
+            "unsupported answer bank import format (expected .csv or .xlsx)",
+        ));
+    }
+    crate::questionnaire::read_source(source_path)
+}
+
+/// Column index of each mapped field; every column must exist and be mapped
+/// once.
+fn resolve_columns(
+    map: &AnswerBankColumnMap,
+    cols: &[ColumnProfile],
+) -> CoreResult<HashMap<Field, usize>> {
+    let mapped = [
+        (Field::Question, Some(&map.question)),
+        (Field::AnswerLong, Some(&map.answer_long)),
+        (Field::AnswerShort, map.answer_short.as_ref()),
+        (Field::EntryId, map.entry_id.as_ref()),
+        (Field::Notes, map.notes.as_ref()),
+        (Field::Owner, map.owner.as_ref()),
+        (Field::Tags, map.tags.as_ref()),
+        (Field::EvidenceLinks, map.evidence_links.as_ref()),
+        (Field::Source, map.source.as_ref()),
+    ];
+
+    let mut out = HashMap::new();
+    let mut used: HashMap<&str, Field> = HashMap::new();
+    for (field, col_ref) in mapped {
+        let Some(col_ref) = col_ref else {
+            continue;
+        };
+        let col = cols.iter().find(|c| c.col_ref == *col_ref).ok_or_else(|| {
+            CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!("unknown {} column: {}", field.name(), col_ref),
+            )
+        })?;
+        if let Some(other) = used.insert(col_ref.as_str(), field) {
+            return Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!(
+                    "{} and {} columns must be different",
+                    other.name(),
+                    field.name()
+                ),
+            ));
+        }
+        out.insert(field, col.ordinal as usize);
+    }
+    Ok(out)
+}
+
+/// Validation failures become row conflicts; anything else aborts.
+fn row_error_message(e: CoreError) -> CoreResult<String> {
+    if e.code == CoreErrorCode::ValidationError {
+        Ok(e.message)
+    } else {
+        Err(e)
+    }
+}
+
+pub(crate) fn all_entries(db: &SqliteDb) -> CoreResult<Vec<AnswerBankEntry>> {
+    db.query_rows_tsv(&format!(
+        "SELECT {} FROM answer_bank ORDER BY question_canonical ASC, entry_id ASC;",
+        ENTRY_COLUMNS
+    ))?
+    .iter()
+    .map(|r| super::entry_from_row(r))
+    .collect()
+}
+
+fn join_list(items: &[String]) -> String {
+    items.join(&format!("{} ", LIST_SEPARATOR))
+}
+
+fn file_format(path: &Path) -> String {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
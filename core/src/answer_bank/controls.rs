@@ -0,0 +1,263 @@
+//! Framework control mappings.
+//!
+//! An entry can be mapped to any number of controls, each a framework name and
+//! a control identifier within it (`SOC 2` / `CC6.1`, `ISO 27001` / `A.8.5`).
+//! Both are compared case-insensitively and stored as first written. Mapping
+//! and unmapping append `AnswerBankControlMapped` / `AnswerBankControlUnmapped`
+//! events. Mappings are not part of an entry's content, so they do not create
+//! revisions or affect approval.
+
+use super::{ab_get_entry, load_vault_id, AnswerBankEntry, ENTRY_COLUMNS};
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
+use crate::storage::db::{escape_db_text, unescape_db_text, SqliteDb};
+
+const MAX_LEN: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlMapping {
+    pub entry_id: String,
+    pub framework: String,
+    pub control_id: String,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+/// A mapped control and how many entries are mapped to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlUsage {
+    pub framework: String,
+    pub control_id: String,
+    pub entry_count: i64,
+}
+
+pub fn ab_map_control(
+    db: &SqliteDb,
+    entry_id: &str,
+    framework: &str,
+    control_id: &str,
+    actor: &str,
+) -> CoreResult<ControlMapping> {
+    validator::validate_chain(db)?;
+
+    let entry = ab_get_entry(db, entry_id)?;
+    let framework = normalize_name("framework", framework)?;
+    let control_id = normalize_name("control_id", control_id)?;
+    if get_mapping(db, entry_id, &framework, &control_id)?.is_some() {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            "entry is already mapped to this control",
+        ));
+    }
+
+    let insert_sql = format!(
+        "INSERT INTO answer_bank_control (entry_id, framework, control_id, created_by, created_at) VALUES ({}, {}, {}, {}, {});",
+        db.q(entry_id),
+        db.q(&framework),
+        db.q(&control_id),
+        db.q(&escape_db_text(actor)),
+        db.q(DETERMINISTIC_TIMESTAMP_UTC)
+    );
+    let event_sql = crate::storage::build_event_insert_sql(
+        db,
+        &entry.vault_id,
+        actor,
+        "AnswerBankControlMapped",
+        control_payload(entry_id, &framework, &control_id),
+    )?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", insert_sql, event_sql))?;
+
+    get_mapping(db, entry_id, &framework, &control_id)?.ok_or_else(|| {
+        CoreError::new(
+            CoreErrorCode::CorruptVault,
+            "control mapping was not stored",
+        )
+    })
+}
+
+pub fn ab_unmap_control(
+    db: &SqliteDb,
+    entry_id: &str,
+    framework: &str,
+    control_id: &str,
+    actor: &str,
+) -> CoreResult<()> {
+    validator::validate_chain(db)?;
+
+    let framework = normalize_name("framework", framework)?;
+    let control_id = normalize_name("control_id", control_id)?;
+    let mapping = get_mapping(db, entry_id, &framework, &control_id)?
+        .ok_or_else(|| CoreError::new(CoreErrorCode::NotFound, "control mapping not found"))?;
+
+    let vault_id = load_vault_id(db)?;
+    let delete_sql = format!(
+        "DELETE FROM answer_bank_control WHERE entry_id={} AND framework={} AND control_id={};",
+        db.q(entry_id),
+        db.q(&framework),
+        db.q(&control_id)
+    );
+    let event_sql = crate::storage::build_event_insert_sql(
+        db,
+        &vault_id,
+        actor,
+        "AnswerBankControlUnmapped",
+        control_payload(entry_id, &mapping.framework, &mapping.control_id),
+    )?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", delete_sql, event_sql))
+}
+
+/// Controls an entry is mapped to, by framework and control.
+pub fn ab_list_entry_controls(db: &SqliteDb, entry_id: &str) -> CoreResult<Vec<ControlMapping>> {
+    db.query_rows_tsv(&format!(
+        "SELECT entry_id, framework, control_id, created_by, created_at FROM answer_bank_control WHERE entry_id={} ORDER BY framework ASC, control_id ASC;",
+        db.q(entry_id)
+    ))?
+    .iter()
+    .map(|r| mapping_from_row(r))
+    .collect()
+}
+
+/// Every mapped control with its entry count, by framework and control.
+pub fn ab_list_controls(db: &SqliteDb) -> CoreResult<Vec<ControlUsage>> {
+    let mut out = Vec::new();
+    for r in db.query_rows_tsv(
+        "SELECT MIN(framework), MIN(control_id), COUNT(*) FROM answer_bank_control GROUP BY framework, control_id ORDER BY framework ASC, control_id ASC;",
+    )? {
+        if r.len() < 3 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected answer_bank_control row",
+            ));
+        }
+        out.push(ControlUsage {
+            framework: r[0].clone(),
+            control_id: r[1].clone(),
+            entry_count: r[2].parse().unwrap_or(0),
+        });
+    }
+    Ok(out)
+}
+
+/// Entries mapped to a control, or to any control of `framework` when
+/// `control_id` is `None`, by question.
+pub fn ab_entries_for_control(
+    db: &SqliteDb,
+    framework: &str,
+    control_id: Option<&str>,
+) -> CoreResult<Vec<AnswerBankEntry>> {
+    let filter = control_filter_sql(db, framework, control_id)?;
+    db.query_rows_tsv(&format!(
+        "SELECT {} FROM answer_bank WHERE entry_id IN (SELECT entry_id FROM answer_bank_control WHERE {}) ORDER BY question_canonical ASC, entry_id ASC;",
+        ENTRY_COLUMNS, filter
+    ))?
+    .iter()
+    .map(|r| super::entry_from_row(r))
+    .collect()
+}
+
+/// Every mapping, by entry, framework and control.
+pub(crate) fn all_control_mappings(db: &SqliteDb) -> CoreResult<Vec<ControlMapping>> {
+    db.query_rows_tsv(
+        "SELECT entry_id, framework, control_id, created_by, created_at FROM answer_bank_control ORDER BY entry_id ASC, framework ASC, control_id ASC;",
+    )?
+    .iter()
+    .map(|r| mapping_from_row(r))
+    .collect()
+}
+
+/// `WHERE` condition on `answer_bank_control` selecting a control, or every
+/// control of a framework.
+pub(crate) fn control_filter_sql(
+    db: &SqliteDb,
+    framework: &str,
+    control_id: Option<&str>,
+) -> CoreResult<String> {
+    let framework = normalize_name("framework", framework)?;
+    Ok(match control_id {
+        Some(c) => format!(
+            "framework={} AND control_id={}",
+            db.q(&framework),
+            db.q(&normalize_name("control_id", c)?)
+        ),
+        None => format!("framework={}", db.q(&framework)),
+    })
+}
+
+pub(crate) fn delete_entry_controls_sql(db: &SqliteDb, entry_id: &str) -> String {
+    format!(
+        "DELETE FROM answer_bank_control WHERE entry_id={};",
+        db.q(entry_id)
+    )
+}
+
+/// SQL giving `keep` every control mapping of the `merged` entries it lacks.
+pub(crate) fn merge_controls_sql(
+    db: &SqliteDb,
+    keep: &AnswerBankEntry,
+    merged: &[AnswerBankEntry],
+    actor: &str,
+) -> String {
+    let ids: Vec<String> = merged.iter().map(|e| db.q(&e.entry_id)).collect();
+    format!(
+        "INSERT OR IGNORE INTO answer_bank_control (entry_id, framework, control_id, created_by, created_at) SELECT {}, framework, control_id, {}, {} FROM answer_bank_control WHERE entry_id IN ({});\n",
+        db.q(&keep.entry_id),
+        db.q(&escape_db_text(actor)),
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        ids.join(", ")
+    )
+}
+
+fn get_mapping(
+    db: &SqliteDb,
+    entry_id: &str,
+    framework: &str,
+    control_id: &str,
+) -> CoreResult<Option<ControlMapping>> {
+    db.query_rows_tsv(&format!(
+        "SELECT entry_id, framework, control_id, created_by, created_at FROM answer_bank_control WHERE entry_id={} AND framework={} AND control_id={} LIMIT 1;",
+        db.q(entry_id),
+        db.q(framework),
+        db.q(control_id)
+    ))?
+    .first()
+    .map(|r| mapping_from_row(r))
+    .transpose()
+}
+
+fn mapping_from_row(r: &[String]) -> CoreResult<ControlMapping> {
+    if r.len() < 5 {
+        return Err(CoreError::new(
+            CoreErrorCode::CorruptVault,
+            "unexpected answer_bank_control row",
+        ));
+    }
+    Ok(ControlMapping {
+        entry_id: r[0].clone(),
+        framework: r[1].clone(),
+        control_id: r[2].clone(),
+        created_by: unescape_db_text(&r[3]),
+        created_at: r[4].clone(),
+    })
+}
+
+fn control_payload(entry_id: &str, framework: &str, control_id: &str) -> CanonicalJson {
+    let mut o = CanonicalJson::object();
+    o.insert("entry_id", CanonicalJson::String(entry_id.to_string()));
+    o.insert("framework", CanonicalJson::String(framework.to_string()));
+    o.insert("control_id", CanonicalJson::String(control_id.to_string()));
+    o
+}
+
+/// Trim and collapse inner whitespace.
+pub(crate) fn normalize_name(field: &str, s: &str) -> CoreResult<String> {
+    let out = s.split_whitespace().collect::<Vec<_>>().join(" ");
+    if out.is_empty() || out.chars().count() > MAX_LEN {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            format!("{field} must be 1-{MAX_LEN} characters"),
+        ));
+    }
+    Ok(out)
+}
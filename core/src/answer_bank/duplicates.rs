@@ -0,0 +1,264 @@
+//! Near-duplicate answer bank entries.
+//!
+//! Questions are normalized with the vault's matching pipeline (stop words,
+//! stemming, synonyms) and compared by Jaccard overlap of their terms. Entries
+//! linked by at least `threshold` similarity, directly or through other
+//! entries, form one candidate group. Retired entries are left out.
+//!
+//! Merging keeps one entry: it gains the union of the tags, evidence links,
+//! control mappings and aliases of the others, each merged question is kept
+//! as an alias, and the others are retired. Everything is written in one
+//! transaction ending with an `AnswerBankEntriesMerged` event.
+
+use super::status::set_status_sql;
+use super::{
+    ab_get_entry, alias, controls, load_vault_id, patched_entry, update_entry_sql, AnswerBankEntry,
+    AnswerBankUpdatePatch, EntryStatus, ENTRY_COLUMNS,
+};
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::storage::db::SqliteDb;
+use crate::storage::AuditEventBatch;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Question similarity at which entries are reported as duplicates by default.
+pub const DEFAULT_DUPLICATE_THRESHOLD: f64 = 0.6;
+
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    /// Suggested entry to keep first (approved, then most evidence links,
+    /// then oldest), the rest by entry_id.
+    pub entries: Vec<AnswerBankEntry>,
+    /// Highest question similarity between two members, 0.0 to 1.0.
+    pub similarity: f64,
+}
+
+/// Candidate duplicate groups, most similar first.
+pub fn ab_find_duplicates(db: &SqliteDb, threshold: f64) -> CoreResult<Vec<DuplicateGroup>> {
+    if !(threshold > 0.0 && threshold <= 1.0) {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "threshold must be greater than 0 and at most 1",
+        ));
+    }
+
+    let normalizer = crate::questionnaire::synonyms::load_normalizer(db)?;
+    let entries: Vec<AnswerBankEntry> = db
+        .query_rows_tsv(&format!(
+            "SELECT {} FROM answer_bank WHERE {} ORDER BY entry_id ASC;",
+            ENTRY_COLUMNS,
+            super::status::MATCHABLE_SQL
+        ))?
+        .iter()
+        .map(|r| super::entry_from_row(r))
+        .collect::<CoreResult<_>>()?;
+    let terms: Vec<BTreeSet<String>> = entries
+        .iter()
+        .map(|e| {
+            normalizer
+                .tokens(&e.question_canonical)
+                .into_iter()
+                .collect()
+        })
+        .collect();
+
+    // Only entries sharing a term can reach the threshold.
+    let mut postings: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, t) in terms.iter().enumerate() {
+        for term in t {
+            postings.entry(term.as_str()).or_default().push(i);
+        }
+    }
+
+    let mut parent: Vec<usize> = (0..entries.len()).collect();
+    let mut best = vec![0.0f64; entries.len()];
+    let mut compared = HashSet::new();
+    for (i, t) in terms.iter().enumerate() {
+        for term in t {
+            for &j in &postings[term.as_str()] {
+                if j <= i || !compared.insert((i, j)) {
+                    continue;
+                }
+                let similarity = jaccard(t, &terms[j]);
+                if similarity < threshold {
+                    continue;
+                }
+                let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                let top = best[a].max(best[b]).max(similarity);
+                let root = a.min(b);
+                parent[a.max(b)] = root;
+                best[root] = top;
+            }
+        }
+    }
+
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..entries.len() {
+        let root = find(&mut parent, i);
+        members.entry(root).or_default().push(i);
+    }
+    let mut groups: Vec<DuplicateGroup> = members
+        .into_iter()
+        .filter(|(_, m)| m.len() > 1)
+        .map(|(root, m)| {
+            let mut group: Vec<AnswerBankEntry> = m.iter().map(|&i| entries[i].clone()).collect();
+            group.sort_by(|a, b| a.entry_id.cmp(&b.entry_id));
+            let keep = group
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, e)| {
+                    (
+                        e.status != EntryStatus::Approved,
+                        std::cmp::Reverse(e.evidence_links.len()),
+                    )
+                })
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            let first = group.remove(keep);
+            group.insert(0, first);
+            DuplicateGroup {
+                entries: group,
+                similarity: best[root],
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then_with(|| a.entries[0].entry_id.cmp(&b.entries[0].entry_id))
+    });
+    Ok(groups)
+}
+
+/// Merge `merge_entry_ids` into `keep_entry_id` and retire them.
+///
+/// Adding tags or evidence changes the kept entry, so an approved keeper goes
+/// back to draft like any other edit.
+pub fn ab_merge_entries(
+    db: &SqliteDb,
+    keep_entry_id: &str,
+    merge_entry_ids: &[String],
+    actor: &str,
+) -> CoreResult<AnswerBankEntry> {
+    validator::validate_chain(db)?;
+
+    if merge_entry_ids.is_empty() {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "merge_entry_ids is required",
+        ));
+    }
+    let mut seen = HashSet::new();
+    for id in merge_entry_ids {
+        if id == keep_entry_id || !seen.insert(id.as_str()) {
+            return Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                "merge_entry_ids must be distinct and must not include the kept entry",
+            ));
+        }
+    }
+
+    let keep = ab_get_entry(db, keep_entry_id)?;
+    let merged: Vec<AnswerBankEntry> = merge_entry_ids
+        .iter()
+        .map(|id| ab_get_entry(db, id))
+        .collect::<CoreResult<_>>()?;
+    if let Some(e) = std::iter::once(&keep)
+        .chain(&merged)
+        .find(|e| e.status == EntryStatus::Retired)
+    {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            format!("answer bank entry {} is retired", e.entry_id),
+        ));
+    }
+
+    let mut events = AuditEventBatch::new(db, &load_vault_id(db)?)?;
+    let mut sql = String::new();
+
+    let mut tags = keep.tags.clone();
+    let mut evidence_links = keep.evidence_links.clone();
+    for e in &merged {
+        tags.extend(e.tags.iter().cloned());
+        evidence_links.extend(e.evidence_links.iter().cloned());
+    }
+    let after = patched_entry(
+        &keep,
+        AnswerBankUpdatePatch {
+            tags: Some(tags),
+            evidence_links: Some(evidence_links),
+            ..Default::default()
+        },
+        actor,
+    )?;
+    if after.tags != keep.tags || after.evidence_links != keep.evidence_links {
+        sql.push_str(&update_entry_sql(
+            db,
+            &keep,
+            &after,
+            actor,
+            None,
+            &mut events,
+        )?);
+    }
+
+    let (alias_sql, moved_alias_ids) =
+        alias::merge_aliases_sql(db, &keep, &merged, actor, &mut events)?;
+    sql.push_str(&alias_sql);
+    sql.push_str(&controls::merge_controls_sql(db, &keep, &merged, actor));
+
+    for e in &merged {
+        sql.push_str(&set_status_sql(
+            db,
+            e,
+            EntryStatus::Retired,
+            actor,
+            &mut events,
+        )?);
+    }
+
+    events.push(db, actor, "AnswerBankEntriesMerged", {
+        let mut o = CanonicalJson::object();
+        o.insert("entry_id", CanonicalJson::String(keep.entry_id.clone()));
+        o.insert(
+            "merged_entry_ids",
+            CanonicalJson::Array(
+                merge_entry_ids
+                    .iter()
+                    .cloned()
+                    .map(CanonicalJson::String)
+                    .collect(),
+            ),
+        );
+        o.insert(
+            "moved_alias_ids",
+            CanonicalJson::Array(
+                moved_alias_ids
+                    .into_iter()
+                    .map(CanonicalJson::String)
+                    .collect(),
+            ),
+        );
+        o
+    })?;
+
+    db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", sql, events.sql()))?;
+    ab_get_entry(db, keep_entry_id)
+}
+
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
@@ -0,0 +1,148 @@
+//! Per-locale variants of answer bank entries.
+//!
+//! An entry's own question and answers are written in [`DEFAULT_LOCALE`];
+//! `translations` add the same question and answers in other locales. They
+//! are part of the entry's content: edits, revisions, rollbacks and the
+//! content hash cover them, and the full-text index holds every locale so
+//! candidates are found whatever language a question is asked in. Matching a
+//! questionnaire in some locale uses that locale's variant of each entry and
+//! falls back to the default text for entries without one.
+
+use super::{normalize_text_required, AnswerBankEntry};
+use crate::audit::canonical::CanonicalJson;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+
+/// Locale of an entry's own question and answers, and of imports without
+/// another language.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Question and answers in one locale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalizedText {
+    /// Lowercase language tag, e.g. `de` or `fr-ca`.
+    pub locale: String,
+    pub question_canonical: String,
+    pub answer_short: String,
+    pub answer_long: String,
+}
+
+impl AnswerBankEntry {
+    /// Text of the entry in `locale`, or its default text when it has no
+    /// variant for it.
+    pub fn text_in(&self, locale: &str) -> LocalizedText {
+        self.translations
+            .iter()
+            .find(|t| t.locale == locale)
+            .cloned()
+            .unwrap_or_else(|| LocalizedText {
+                locale: DEFAULT_LOCALE.to_string(),
+                question_canonical: self.question_canonical.clone(),
+                answer_short: self.answer_short.clone(),
+                answer_long: self.answer_long.clone(),
+            })
+    }
+
+    /// The entry with its question and answers replaced by their `locale`
+    /// variant, for matching and display.
+    pub fn localized(&self, locale: &str) -> AnswerBankEntry {
+        let text = self.text_in(locale);
+        AnswerBankEntry {
+            question_canonical: text.question_canonical,
+            answer_short: text.answer_short,
+            answer_long: text.answer_long,
+            ..self.clone()
+        }
+    }
+}
+
+/// Lowercase a language tag (`de`, `pt-BR`, `zh_Hant`) and check its shape:
+/// a 2-3 letter language, then optional 2-8 character subtags.
+pub fn normalize_locale(locale: &str) -> CoreResult<String> {
+    let out = locale.trim().to_ascii_lowercase().replace('_', "-");
+    let mut parts = out.split('-');
+    let language_ok = parts
+        .next()
+        .is_some_and(|p| (2..=3).contains(&p.len()) && p.chars().all(|c| c.is_ascii_lowercase()));
+    let subtags_ok =
+        parts.all(|p| (2..=8).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric()));
+    if language_ok && subtags_ok {
+        Ok(out)
+    } else {
+        Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            format!("invalid locale: {}", locale.trim()),
+        ))
+    }
+}
+
+/// Normalize translations as stored: text normalized like the entry's own,
+/// sorted by locale, one per locale and none for the default locale.
+pub(crate) fn normalize_translations(
+    translations: &[LocalizedText],
+) -> CoreResult<Vec<LocalizedText>> {
+    let mut out = Vec::with_capacity(translations.len());
+    for t in translations {
+        let locale = normalize_locale(&t.locale)?;
+        if locale == DEFAULT_LOCALE {
+            return Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!("the entry's own text is the {DEFAULT_LOCALE} variant"),
+            ));
+        }
+        out.push(LocalizedText {
+            locale,
+            question_canonical: normalize_text_required(
+                "translation question_canonical",
+                &t.question_canonical,
+            )?,
+            answer_short: normalize_text_required("translation answer_short", &t.answer_short)?,
+            answer_long: normalize_text_required("translation answer_long", &t.answer_long)?,
+        });
+    }
+    out.sort_by(|a, b| a.locale.cmp(&b.locale));
+    if let Some(w) = out.windows(2).find(|w| w[0].locale == w[1].locale) {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            format!("duplicate translation for locale {}", w[0].locale),
+        ));
+    }
+    Ok(out)
+}
+
+pub(crate) fn translations_json(translations: &[LocalizedText]) -> String {
+    CanonicalJson::Array(
+        translations
+            .iter()
+            .map(|t| {
+                let mut o = CanonicalJson::object();
+                o.insert("locale", CanonicalJson::String(t.locale.clone()));
+                o.insert(
+                    "question_canonical",
+                    CanonicalJson::String(t.question_canonical.clone()),
+                );
+                o.insert(
+                    "answer_short",
+                    CanonicalJson::String(t.answer_short.clone()),
+                );
+                o.insert("answer_long", CanonicalJson::String(t.answer_long.clone()));
+                o
+            })
+            .collect(),
+    )
+    .to_string()
+}
+
+pub(crate) fn parse_translations_json(s: &str) -> CoreResult<Vec<LocalizedText>> {
+    let v = crate::util::json::JsonValue::parse(s)?;
+    let mut out = Vec::new();
+    for t in v.as_array()? {
+        let o = t.as_object()?;
+        out.push(LocalizedText {
+            locale: o.get_string("locale")?,
+            question_canonical: o.get_string("question_canonical")?,
+            answer_short: o.get_string("answer_short")?,
+            answer_long: o.get_string("answer_long")?,
+        });
+    }
+    Ok(out)
+}
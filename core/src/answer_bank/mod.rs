@@ -3,13 +3,78 @@
 //! Stores canonical Q/A entries for questionnaire matching and export.
 //! All writes append audit events and use deterministic canonicalization rules
 //! for tags and content hashing.
+//! Question and answer text is mirrored into the `answer_bank_fts` FTS5 index
+//! in the same transaction as each write; search and match candidate
+//! retrieval run against it.
+//! Each write also stores the entry's full content as a numbered revision;
+//! see [`revision`]. Review intervals and staleness live in `review`, the
+//! draft → approved workflow in `status`. Spreadsheet import and export live
+//! in `bulk`, near-duplicate grouping and merging in `duplicates`, and
+//! `{{variable}}` placeholders in answers in `template`, per-locale variants
+//! in `locale`. The managed tag taxonomy lives in `taxonomy` and framework
+//! control mappings in `controls`.
 
 use crate::audit::canonical::CanonicalJson;
 use crate::audit::validator;
 use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
 use crate::domain::ids::Ulid;
 use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
-use crate::storage::db::SqliteDb;
+use crate::storage::db::{escape_db_text, unescape_db_text, SqliteDb};
+use crate::storage::AuditEventBatch;
+use std::collections::HashMap;
+
+mod alias;
+mod bulk;
+mod controls;
+mod duplicates;
+mod locale;
+mod review;
+pub mod revision;
+mod status;
+mod taxonomy;
+mod template;
+
+pub use alias::{ab_add_alias, ab_delete_alias, ab_list_aliases, AnswerBankAlias};
+pub(crate) use alias::{aliases_for_entries, learn_alias_sql};
+pub use bulk::{
+    ab_bulk_import, ab_export_entries, ab_guess_column_map, ab_profile_import_file,
+    AnswerBankColumnMap, AnswerBankExportSummary, AnswerBankImportReport, BulkImportRow,
+    BulkRowAction, EXPORT_HEADERS,
+};
+pub(crate) use bulk::{all_entries, write_entries};
+pub use controls::{
+    ab_entries_for_control, ab_list_controls, ab_list_entry_controls, ab_map_control,
+    ab_unmap_control, ControlMapping, ControlUsage,
+};
+pub(crate) use controls::{
+    all_control_mappings, control_filter_sql, normalize_name as normalize_control_name,
+};
+pub use duplicates::{
+    ab_find_duplicates, ab_merge_entries, DuplicateGroup, DEFAULT_DUPLICATE_THRESHOLD,
+};
+pub use locale::{normalize_locale, LocalizedText, DEFAULT_LOCALE};
+pub(crate) use review::stale_entry_ids;
+pub use review::{
+    ab_delete_review_policy, ab_list_review_policies, ab_mark_reviewed, ab_overdue_reviews,
+    ab_set_review_policy, OverdueReview, OwnerOverdueReviews, ReviewPolicy, ReviewPolicyScope,
+};
+pub use revision::{
+    ab_diff_revisions, ab_get_revision, ab_list_revisions, ab_revision_at_audit_seq,
+    ab_rollback_entry, AnswerBankRevision, AnswerBankRevisionDiff, RevisionFieldChange,
+    RevisionKind,
+};
+pub(crate) use status::approved_entry_ids;
+pub use status::{ab_set_entry_status, EntryStatus};
+pub use taxonomy::{
+    ab_define_tag, ab_delete_tag, ab_list_tags, ab_rename_tag, normalize_tag, AnswerBankTag,
+    TagRenameReport, TagUsage, TAG_SEPARATOR,
+};
+pub(crate) use taxonomy::{is_descendant, stored_tag};
+pub use template::{
+    ab_delete_variable, ab_list_variables, ab_render_entry, ab_set_variable,
+    ab_unresolved_variables, RenderedEntry, RenderedText, TemplateVariables, UnresolvedVariables,
+    VaultVariable,
+};
 
 #[derive(Debug, Clone)]
 pub struct AnswerBankEntry {
@@ -27,6 +92,12 @@ pub struct AnswerBankEntry {
     pub content_hash: String,
     pub created_at: String,
     pub updated_at: String,
+    pub status: EntryStatus,
+    /// Actor of the last create, update or rollback; `None` for entries from
+    /// before the approval workflow.
+    pub last_edited_by: Option<String>,
+    /// Variants in other locales, sorted by locale.
+    pub translations: Vec<LocalizedText>,
 }
 
 #[derive(Debug, Clone)]
@@ -40,6 +111,7 @@ pub struct AnswerBankCreateInput {
     pub last_reviewed_at: Option<String>,
     pub tags: Vec<String>,
     pub source: String, // 'manual' | 'import' | 'match' (free string, validated non-empty)
+    pub translations: Vec<LocalizedText>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -53,6 +125,8 @@ pub struct AnswerBankUpdatePatch {
     pub last_reviewed_at: Option<Option<String>>,
     pub tags: Option<Vec<String>>,
     pub source: Option<String>,
+    /// Replaces every translation.
+    pub translations: Option<Vec<LocalizedText>>,
 }
 
 #[derive(Debug, Clone)]
@@ -69,7 +143,21 @@ pub fn ab_create_entry(
     validator::validate_chain(db)?;
 
     let vault_id = load_vault_id(db)?;
+    let entry = new_entry(&vault_id, input, actor)?;
 
+    let mut events = AuditEventBatch::new(db, &vault_id)?;
+    let sql = create_entry_sql(db, &entry, actor, &mut events)?;
+    db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", sql, events.sql()))?;
+
+    ab_get_entry(db, &entry.entry_id)
+}
+
+/// Normalize `input` into a new draft entry with a fresh id.
+fn new_entry(
+    vault_id: &str,
+    input: AnswerBankCreateInput,
+    actor: &str,
+) -> CoreResult<AnswerBankEntry> {
     let question_canonical =
         normalize_text_required("question_canonical", &input.question_canonical)?;
     let answer_short = normalize_text_required("answer_short", &input.answer_short)?;
@@ -81,19 +169,9 @@ pub fn ab_create_entry(
     let owner = normalize_text_required("owner", &input.owner)?;
     let source = normalize_text_required("source", &input.source)?;
 
-    let tags = normalize_tags(&input.tags);
+    let tags = normalize_tags(&input.tags)?;
     let evidence_links = normalize_ids(&input.evidence_links);
-
-    let tags_json =
-        CanonicalJson::Array(tags.iter().cloned().map(CanonicalJson::String).collect()).to_string();
-    let evidence_json = CanonicalJson::Array(
-        evidence_links
-            .iter()
-            .cloned()
-            .map(CanonicalJson::String)
-            .collect(),
-    )
-    .to_string();
+    let translations = locale::normalize_translations(&input.translations)?;
 
     let content_hash = compute_content_hash(
         &question_canonical,
@@ -102,55 +180,113 @@ pub fn ab_create_entry(
         notes.as_deref(),
         &tags,
         &source,
+        &translations,
     )?;
 
-    let entry_id = Ulid::new()?.to_string();
-    let created_at = DETERMINISTIC_TIMESTAMP_UTC.to_string();
-    let updated_at = DETERMINISTIC_TIMESTAMP_UTC.to_string();
+    Ok(AnswerBankEntry {
+        entry_id: Ulid::new()?.to_string(),
+        vault_id: vault_id.to_string(),
+        question_canonical,
+        answer_short,
+        answer_long,
+        notes,
+        evidence_links,
+        owner,
+        last_reviewed_at: input.last_reviewed_at,
+        tags,
+        source,
+        content_hash,
+        created_at: DETERMINISTIC_TIMESTAMP_UTC.to_string(),
+        updated_at: DETERMINISTIC_TIMESTAMP_UTC.to_string(),
+        status: EntryStatus::Draft,
+        last_edited_by: Some(actor.to_string()),
+        translations,
+    })
+}
+
+/// SQL inserting `entry` (from [`new_entry`]) with its FTS row and first
+/// revision; the created event is pushed onto `events`.
+fn create_entry_sql(
+    db: &SqliteDb,
+    entry: &AnswerBankEntry,
+    actor: &str,
+    events: &mut AuditEventBatch,
+) -> CoreResult<String> {
+    let tags_json = CanonicalJson::Array(
+        entry
+            .tags
+            .iter()
+            .cloned()
+            .map(CanonicalJson::String)
+            .collect(),
+    )
+    .to_string();
+    let evidence_json = CanonicalJson::Array(
+        entry
+            .evidence_links
+            .iter()
+            .cloned()
+            .map(CanonicalJson::String)
+            .collect(),
+    )
+    .to_string();
 
     // NOTE: SqliteDb uses a simple TSV-oriented reader that is not safe for
     // embedded tabs/newlines. Store user text with lightweight escaping and
     // unescape on read.
     let insert_sql = format!(
-        "INSERT INTO answer_bank (entry_id, vault_id, question_canonical, answer_short, answer_long, evidence_links_json, owner, last_reviewed_at, tags_json, notes, source, content_hash, created_at, updated_at) VALUES ({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {});",
-        db.q(&entry_id),
-        db.q(&vault_id),
-        db.q(&escape_db_text(&question_canonical)),
-        db.q(&escape_db_text(&answer_short)),
-        db.q(&escape_db_text(&answer_long)),
+        "INSERT INTO answer_bank (entry_id, vault_id, question_canonical, answer_short, answer_long, evidence_links_json, owner, last_reviewed_at, tags_json, notes, source, content_hash, created_at, updated_at, status, last_edited_by, translations_json) VALUES ({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {});",
+        db.q(&entry.entry_id),
+        db.q(&entry.vault_id),
+        db.q(&escape_db_text(&entry.question_canonical)),
+        db.q(&escape_db_text(&entry.answer_short)),
+        db.q(&escape_db_text(&entry.answer_long)),
         db.q(&evidence_json),
-        db.q(&escape_db_text(&owner)),
-        match &input.last_reviewed_at {
+        db.q(&escape_db_text(&entry.owner)),
+        match &entry.last_reviewed_at {
             Some(s) => db.q(s),
             None => "NULL".to_string(),
         },
         db.q(&tags_json),
-        match &notes {
+        match &entry.notes {
             Some(s) => db.q(&escape_db_text(s)),
             None => "NULL".to_string(),
         },
-        db.q(&escape_db_text(&source)),
-        db.q(&content_hash),
-        db.q(&created_at),
-        db.q(&updated_at),
+        db.q(&escape_db_text(&entry.source)),
+        db.q(&entry.content_hash),
+        db.q(&entry.created_at),
+        db.q(&entry.updated_at),
+        db.q(entry.status.as_str()),
+        db.q(actor),
+        db.q(&locale::translations_json(&entry.translations)),
     );
 
-    let event_sql =
-        crate::storage::build_event_insert_sql(db, &vault_id, actor, "AnswerBankEntryCreated", {
-            let mut o = CanonicalJson::object();
-            o.insert("entry_id", CanonicalJson::String(entry_id.clone()));
-            o.insert("content_hash", CanonicalJson::String(content_hash.clone()));
-            o
-        })?;
-
-    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", insert_sql, event_sql))?;
-
-    ab_get_entry(db, &entry_id)
+    events.push(db, actor, "AnswerBankEntryCreated", {
+        let mut o = CanonicalJson::object();
+        o.insert("entry_id", CanonicalJson::String(entry.entry_id.clone()));
+        o.insert(
+            "content_hash",
+            CanonicalJson::String(entry.content_hash.clone()),
+        );
+        o.insert("revision", CanonicalJson::Number(1));
+        o.insert(
+            "status",
+            CanonicalJson::String(entry.status.as_str().to_string()),
+        );
+        o
+    })?;
+
+    let fts_sql = fts_upsert_sql(db, entry);
+    let revision_sql =
+        revision::insert_revision_sql(db, &entry.entry_id, 1, RevisionKind::Created, None, actor);
+
+    Ok(format!("{}\n{}\n{}\n", insert_sql, fts_sql, revision_sql))
 }
 
 pub fn ab_get_entry(db: &SqliteDb, entry_id: &str) -> CoreResult<AnswerBankEntry> {
     let rows = db.query_rows_tsv(&format!(
-        "SELECT entry_id, vault_id, question_canonical, answer_short, answer_long, IFNULL(notes,''), evidence_links_json, owner, IFNULL(last_reviewed_at,''), tags_json, source, content_hash, created_at, updated_at FROM answer_bank WHERE entry_id={} LIMIT 1;",
+        "SELECT {} FROM answer_bank WHERE entry_id={} LIMIT 1;",
+        ENTRY_COLUMNS,
         db.q(entry_id)
     ))?;
     if rows.is_empty() {
@@ -159,8 +295,13 @@ pub fn ab_get_entry(db: &SqliteDb, entry_id: &str) -> CoreResult<AnswerBankEntry
             "answer bank entry not found",
         ));
     }
-    let r = &rows[0];
-    if r.len() < 14 {
+    entry_from_row(&rows[0])
+}
+
+const ENTRY_COLUMNS: &str = "entry_id, vault_id, question_canonical, answer_short, answer_long, IFNULL(notes,''), evidence_links_json, owner, IFNULL(last_reviewed_at,''), tags_json, source, content_hash, created_at, updated_at, status, IFNULL(last_edited_by,''), translations_json";
+
+fn entry_from_row(r: &[String]) -> CoreResult<AnswerBankEntry> {
+    if r.len() < 17 {
         return Err(CoreError::new(
             CoreErrorCode::CorruptVault,
             "unexpected answer_bank row",
@@ -193,6 +334,14 @@ pub fn ab_get_entry(db: &SqliteDb, entry_id: &str) -> CoreResult<AnswerBankEntry
         content_hash: r[11].clone(),
         created_at: r[12].clone(),
         updated_at: r[13].clone(),
+        status: EntryStatus::parse(&r[14])
+            .map_err(|e| CoreError::new(CoreErrorCode::CorruptVault, e.message))?,
+        last_edited_by: if r[15].is_empty() {
+            None
+        } else {
+            Some(r[15].clone())
+        },
+        translations: locale::parse_translations_json(&r[16])?,
     })
 }
 
@@ -201,11 +350,37 @@ pub fn ab_update_entry(
     entry_id: &str,
     patch: AnswerBankUpdatePatch,
     actor: &str,
+) -> CoreResult<AnswerBankEntry> {
+    update_entry(db, entry_id, patch, actor, None)
+}
+
+/// Apply `patch`, recording the result as a new revision. `rolled_back_to`
+/// is set when the patch restores an earlier revision.
+fn update_entry(
+    db: &SqliteDb,
+    entry_id: &str,
+    patch: AnswerBankUpdatePatch,
+    actor: &str,
+    rolled_back_to: Option<i64>,
 ) -> CoreResult<AnswerBankEntry> {
     validator::validate_chain(db)?;
 
     let before = ab_get_entry(db, entry_id)?;
+    let after = patched_entry(&before, patch, actor)?;
 
+    let mut events = AuditEventBatch::new(db, &before.vault_id)?;
+    let sql = update_entry_sql(db, &before, &after, actor, rolled_back_to, &mut events)?;
+    db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", sql, events.sql()))?;
+
+    ab_get_entry(db, entry_id)
+}
+
+/// `before` with `patch` applied and normalized, as it would be stored.
+fn patched_entry(
+    before: &AnswerBankEntry,
+    patch: AnswerBankUpdatePatch,
+    actor: &str,
+) -> CoreResult<AnswerBankEntry> {
     let question_canonical = patch
         .question_canonical
         .map(|s| normalize_text_required("question_canonical", &s))
@@ -243,7 +418,7 @@ pub fn ab_update_entry(
         None => before.last_reviewed_at.clone(),
     };
     let tags = match patch.tags {
-        Some(v) => normalize_tags(&v),
+        Some(v) => normalize_tags(&v)?,
         None => before.tags.clone(),
     };
     let source = patch
@@ -251,17 +426,10 @@ pub fn ab_update_entry(
         .map(|s| normalize_text_required("source", &s))
         .transpose()?
         .unwrap_or_else(|| before.source.clone());
-
-    let tags_json =
-        CanonicalJson::Array(tags.iter().cloned().map(CanonicalJson::String).collect()).to_string();
-    let evidence_json = CanonicalJson::Array(
-        evidence_links
-            .iter()
-            .cloned()
-            .map(CanonicalJson::String)
-            .collect(),
-    )
-    .to_string();
+    let translations = match patch.translations {
+        Some(v) => locale::normalize_translations(&v)?,
+        None => before.translations.clone(),
+    };
 
     let content_hash = compute_content_hash(
         &question_canonical,
@@ -270,75 +438,151 @@ pub fn ab_update_entry(
         notes.as_deref(),
         &tags,
         &source,
+        &translations,
     )?;
 
-    let updated_at = DETERMINISTIC_TIMESTAMP_UTC.to_string();
+    Ok(AnswerBankEntry {
+        entry_id: before.entry_id.clone(),
+        vault_id: before.vault_id.clone(),
+        question_canonical,
+        answer_short,
+        answer_long,
+        notes,
+        evidence_links,
+        owner,
+        last_reviewed_at,
+        tags,
+        source,
+        content_hash,
+        created_at: before.created_at.clone(),
+        updated_at: DETERMINISTIC_TIMESTAMP_UTC.to_string(),
+        status: before.status,
+        last_edited_by: Some(actor.to_string()),
+        translations,
+    })
+}
+
+/// SQL writing `after` (from [`patched_entry`]) over `before`, with its FTS
+/// row and a new revision; events are pushed onto `events`.
+fn update_entry_sql(
+    db: &SqliteDb,
+    before: &AnswerBankEntry,
+    after: &AnswerBankEntry,
+    actor: &str,
+    rolled_back_to: Option<i64>,
+    events: &mut AuditEventBatch,
+) -> CoreResult<String> {
+    let entry_id = before.entry_id.as_str();
+    let revision = revision::next_revision(db, entry_id)?;
+
+    let tags_json = CanonicalJson::Array(
+        after
+            .tags
+            .iter()
+            .cloned()
+            .map(CanonicalJson::String)
+            .collect(),
+    )
+    .to_string();
+    let evidence_json = CanonicalJson::Array(
+        after
+            .evidence_links
+            .iter()
+            .cloned()
+            .map(CanonicalJson::String)
+            .collect(),
+    )
+    .to_string();
 
     let update_sql = format!(
-        "UPDATE answer_bank SET question_canonical={}, answer_short={}, answer_long={}, evidence_links_json={}, owner={}, last_reviewed_at={}, tags_json={}, notes={}, source={}, content_hash={}, updated_at={} WHERE entry_id={};",
-        db.q(&escape_db_text(&question_canonical)),
-        db.q(&escape_db_text(&answer_short)),
-        db.q(&escape_db_text(&answer_long)),
+        "UPDATE answer_bank SET question_canonical={}, answer_short={}, answer_long={}, evidence_links_json={}, owner={}, last_reviewed_at={}, tags_json={}, notes={}, source={}, content_hash={}, updated_at={}, last_edited_by={}, translations_json={} WHERE entry_id={};",
+        db.q(&escape_db_text(&after.question_canonical)),
+        db.q(&escape_db_text(&after.answer_short)),
+        db.q(&escape_db_text(&after.answer_long)),
         db.q(&evidence_json),
-        db.q(&escape_db_text(&owner)),
-        match &last_reviewed_at {
+        db.q(&escape_db_text(&after.owner)),
+        match &after.last_reviewed_at {
             Some(s) => db.q(s),
             None => "NULL".to_string(),
         },
         db.q(&tags_json),
-        match &notes {
+        match &after.notes {
             Some(s) => db.q(&escape_db_text(s)),
             None => "NULL".to_string(),
         },
-        db.q(&escape_db_text(&source)),
-        db.q(&content_hash),
-        db.q(&updated_at),
+        db.q(&escape_db_text(&after.source)),
+        db.q(&after.content_hash),
+        db.q(&after.updated_at),
+        db.q(actor),
+        db.q(&locale::translations_json(&after.translations)),
         db.q(entry_id),
     );
 
-    let after_for_diff = AnswerBankEntry {
-        entry_id: before.entry_id.clone(),
-        vault_id: before.vault_id.clone(),
-        question_canonical: question_canonical.clone(),
-        answer_short: answer_short.clone(),
-        answer_long: answer_long.clone(),
-        notes: notes.clone(),
-        evidence_links: evidence_links.clone(),
-        owner: owner.clone(),
-        last_reviewed_at: last_reviewed_at.clone(),
-        tags: tags.clone(),
-        source: source.clone(),
-        content_hash: content_hash.clone(),
-        created_at: before.created_at.clone(),
-        updated_at: updated_at.clone(),
-    };
-    let changed_fields = compute_changed_fields(&before, &after_for_diff);
-
-    let event_sql = crate::storage::build_event_insert_sql(
-        db,
-        &before.vault_id,
-        actor,
-        "AnswerBankEntryUpdated",
-        {
+    let changed_fields = compute_changed_fields(before, after);
+    // Changed approved content has to be approved again.
+    let unapprove = before.status == EntryStatus::Approved && !changed_fields.is_empty();
+
+    events.push(db, actor, "AnswerBankEntryUpdated", {
+        let mut o = CanonicalJson::object();
+        o.insert("entry_id", CanonicalJson::String(entry_id.to_string()));
+        o.insert(
+            "content_hash",
+            CanonicalJson::String(after.content_hash.clone()),
+        );
+        o.insert(
+            "changed_fields",
+            CanonicalJson::Array(
+                changed_fields
+                    .into_iter()
+                    .map(CanonicalJson::String)
+                    .collect(),
+            ),
+        );
+        o.insert("revision", CanonicalJson::Number(revision));
+        if let Some(n) = rolled_back_to {
+            o.insert("rolled_back_to", CanonicalJson::Number(n));
+        }
+        o
+    })?;
+    let mut status_sql = String::new();
+    if unapprove {
+        status_sql = format!(
+            "UPDATE answer_bank SET status={} WHERE entry_id={};",
+            db.q(EntryStatus::Draft.as_str()),
+            db.q(entry_id)
+        );
+        events.push(db, actor, "AnswerBankEntryStatusChanged", {
             let mut o = CanonicalJson::object();
             o.insert("entry_id", CanonicalJson::String(entry_id.to_string()));
-            o.insert("content_hash", CanonicalJson::String(content_hash.clone()));
             o.insert(
-                "changed_fields",
-                CanonicalJson::Array(
-                    changed_fields
-                        .into_iter()
-                        .map(CanonicalJson::String)
-                        .collect(),
-                ),
+                "content_hash",
+                CanonicalJson::String(after.content_hash.clone()),
+            );
+            o.insert(
+                "from",
+                CanonicalJson::String(EntryStatus::Approved.as_str().to_string()),
+            );
+            o.insert(
+                "to",
+                CanonicalJson::String(EntryStatus::Draft.as_str().to_string()),
             );
             o
-        },
-    )?;
+        })?;
+    }
 
-    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", update_sql, event_sql))?;
+    let fts_sql = fts_upsert_sql(db, after);
+    let kind = if rolled_back_to.is_some() {
+        RevisionKind::RolledBack
+    } else {
+        RevisionKind::Updated
+    };
+    let revision_sql =
+        revision::insert_revision_sql(db, entry_id, revision, kind, rolled_back_to, actor);
 
-    ab_get_entry(db, entry_id)
+    Ok(format!(
+        "{}\n{}\n{}\n{}\n",
+        update_sql, status_sql, fts_sql, revision_sql
+    ))
 }
 
 pub fn ab_delete_entry(db: &SqliteDb, entry_id: &str, actor: &str) -> CoreResult<()> {
@@ -346,7 +590,31 @@ pub fn ab_delete_entry(db: &SqliteDb, entry_id: &str, actor: &str) -> CoreResult
 
     let before = ab_get_entry(db, entry_id)?;
 
-    let delete_sql = format!("DELETE FROM answer_bank WHERE entry_id={};", db.q(entry_id));
+    // Entries chosen as a questionnaire answer stay until the answer changes;
+    // unreviewed and rejected suggestions for them are simply dropped.
+    let in_use = db
+        .query_optional_string(&format!(
+            "SELECT question_id FROM questionnaire_question WHERE answer_bank_entry_id={} UNION ALL SELECT question_id FROM match_suggestion WHERE answer_bank_entry_id={} AND accepted=1 LIMIT 1;",
+            db.q(entry_id),
+            db.q(entry_id)
+        ))?
+        .is_some();
+    if in_use {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            "answer bank entry is the answer to a questionnaire question",
+        ));
+    }
+
+    let delete_sql = format!(
+        "DELETE FROM match_suggestion WHERE answer_bank_entry_id={};\n{}\n{}\n{}\n{}\nDELETE FROM answer_bank WHERE entry_id={};",
+        db.q(entry_id),
+        alias::delete_entry_aliases_sql(db, entry_id),
+        controls::delete_entry_controls_sql(db, entry_id),
+        review::delete_entry_policy_sql(db, entry_id),
+        fts_delete_sql(db, entry_id),
+        db.q(entry_id)
+    );
     let event_sql = crate::storage::build_event_insert_sql(
         db,
         &before.vault_id,
@@ -385,35 +653,164 @@ pub fn ab_list_entries(db: &SqliteDb, params: ListParams) -> CoreResult<Vec<Answ
     Ok(out)
 }
 
+/// Full-text search over question and answers, best match first.
+///
+/// Every word of `query` must appear (as a word or word prefix, after Porter
+/// stemming). Results are ranked by the FTS5 BM25 score with the canonical
+/// question weighted highest; equal scores are ordered by entry_id.
 pub fn ab_search_entries(
     db: &SqliteDb,
     query: &str,
     params: ListParams,
 ) -> CoreResult<Vec<AnswerBankEntry>> {
     validate_list_params(&params)?;
-    let vault_id = load_vault_id(db)?;
     let q = normalize_text_optional(query);
     if q.is_empty() {
         return ab_list_entries(db, params);
     }
-    // Basic LIKE search. Deterministic ordering, no ranking yet.
-    let like = format!("%{}%", q.replace('%', "\\%").replace('_', "\\_"));
-    let rows = db.query_rows_tsv(&format!(
-        "SELECT entry_id FROM answer_bank WHERE vault_id={} AND (question_canonical LIKE {} ESCAPE '\\' OR answer_short LIKE {} ESCAPE '\\' OR answer_long LIKE {} ESCAPE '\\') ORDER BY question_canonical ASC, entry_id ASC LIMIT {} OFFSET {};",
-        db.q(&vault_id),
-        db.q(&like),
-        db.q(&like),
-        db.q(&like),
-        params.limit,
-        params.offset
-    ))?;
-    let mut out = Vec::new();
-    for r in rows {
-        if let Some(id) = r.first() {
-            out.push(ab_get_entry(db, id)?);
+    let terms: Vec<String> = fts_words(&q).into_iter().map(|w| fts_term(&w)).collect();
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    fts_ranked_entries(db, &terms.join(" AND "), params)
+}
+
+/// Candidate entries for matching: entries whose text or aliases contain any
+/// of `terms` (words or phrases), at most `limit` of them. Alias hits come
+/// first, then the best full-text matches on the entries themselves. Retired
+/// entries are never candidates.
+pub fn ab_match_candidates(
+    db: &SqliteDb,
+    terms: &[String],
+    limit: i64,
+) -> CoreResult<Vec<AnswerBankEntry>> {
+    let terms: Vec<String> = terms
+        .iter()
+        .map(|t| fts_words(t).join(" "))
+        .filter(|t| !t.is_empty())
+        .map(|t| fts_term(&t))
+        .collect();
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let match_expr = terms.join(" OR ");
+
+    let mut ids = alias::alias_candidate_ids(db, &match_expr, limit)?;
+    for id in fts_ranked_ids(db, &match_expr, ListParams { limit, offset: 0 }, true)? {
+        if !ids.contains(&id) {
+            ids.push(id);
         }
     }
-    Ok(out)
+    ids.truncate(limit.max(0) as usize);
+    entries_in_order(db, &ids)
+}
+
+pub fn ab_count_entries(db: &SqliteDb) -> CoreResult<i64> {
+    let vault_id = load_vault_id(db)?;
+    let n = db.query_optional_string(&format!(
+        "SELECT COUNT(*) FROM answer_bank WHERE vault_id={};",
+        db.q(&vault_id)
+    ))?;
+    Ok(n.and_then(|s| s.parse().ok()).unwrap_or(0))
+}
+
+/// Column weights for `bm25()`: entry_id (unindexed), question, short, long.
+const FTS_BM25_WEIGHTS: &str = "0.0, 3.0, 1.0, 0.5";
+
+fn fts_ranked_entries(
+    db: &SqliteDb,
+    match_expr: &str,
+    params: ListParams,
+) -> CoreResult<Vec<AnswerBankEntry>> {
+    let ids = fts_ranked_ids(db, match_expr, params, false)?;
+    entries_in_order(db, &ids)
+}
+
+/// `matchable_only` leaves out retired entries.
+fn fts_ranked_ids(
+    db: &SqliteDb,
+    match_expr: &str,
+    params: ListParams,
+    matchable_only: bool,
+) -> CoreResult<Vec<String>> {
+    validate_list_params(&params)?;
+    let vault_id = load_vault_id(db)?;
+    Ok(db
+        .query_rows_tsv(&format!(
+            "SELECT answer_bank.entry_id FROM answer_bank_fts JOIN answer_bank ON answer_bank.rowid=answer_bank_fts.rowid WHERE answer_bank_fts MATCH {} AND answer_bank.vault_id={}{} ORDER BY bm25(answer_bank_fts, {}) ASC, answer_bank.entry_id ASC LIMIT {} OFFSET {};",
+            db.q(match_expr),
+            db.q(&vault_id),
+            if matchable_only {
+                format!(" AND {}", status::MATCHABLE_SQL)
+            } else {
+                String::new()
+            },
+            FTS_BM25_WEIGHTS,
+            params.limit,
+            params.offset
+        ))?
+        .into_iter()
+        .filter_map(|r| r.into_iter().next())
+        .collect())
+}
+
+/// Load entries by id, keeping the order of `ids` (unknown ids are skipped).
+fn entries_in_order(db: &SqliteDb, ids: &[String]) -> CoreResult<Vec<AnswerBankEntry>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let in_list: Vec<String> = ids.iter().map(|id| db.q(id)).collect();
+    let mut by_id: HashMap<String, AnswerBankEntry> = HashMap::new();
+    for r in db.query_rows_tsv(&format!(
+        "SELECT {} FROM answer_bank WHERE entry_id IN ({});",
+        ENTRY_COLUMNS,
+        in_list.join(", ")
+    ))? {
+        let e = entry_from_row(&r)?;
+        by_id.insert(e.entry_id.clone(), e);
+    }
+    Ok(ids.iter().filter_map(|id| by_id.remove(id)).collect())
+}
+
+/// Words as the FTS tokenizer sees them (letters and digits).
+fn fts_words(s: &str) -> Vec<String> {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// Quoted FTS5 phrase with prefix matching; words carry no FTS syntax.
+fn fts_term(words: &str) -> String {
+    format!("\"{}\"*", words)
+}
+
+/// Replace the FTS row for an entry. Text is indexed unescaped; each column
+/// holds the default text followed by its translations.
+fn fts_upsert_sql(db: &SqliteDb, entry: &AnswerBankEntry) -> String {
+    let column = |default: &str, variant: fn(&LocalizedText) -> &str| {
+        std::iter::once(default)
+            .chain(entry.translations.iter().map(variant))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let entry_id = entry.entry_id.as_str();
+    format!(
+        "{}\nINSERT INTO answer_bank_fts (rowid, entry_id, question_canonical, answer_short, answer_long) VALUES ((SELECT rowid FROM answer_bank WHERE entry_id={}), {}, {}, {}, {});",
+        fts_delete_sql(db, entry_id),
+        db.q(entry_id),
+        db.q(entry_id),
+        db.q(&column(&entry.question_canonical, |t| &t.question_canonical)),
+        db.q(&column(&entry.answer_short, |t| &t.answer_short)),
+        db.q(&column(&entry.answer_long, |t| &t.answer_long)),
+    )
+}
+
+fn fts_delete_sql(db: &SqliteDb, entry_id: &str) -> String {
+    format!(
+        "DELETE FROM answer_bank_fts WHERE rowid=(SELECT rowid FROM answer_bank WHERE entry_id={});",
+        db.q(entry_id)
+    )
 }
 
 pub fn ab_link_evidence(
@@ -454,50 +851,15 @@ fn normalize_text_optional(s: &str) -> String {
     trimmed.replace("\r\n", "\n").replace('\r', "\n")
 }
 
-fn escape_db_text(s: &str) -> String {
-    // Keep it reversible and deterministic. Order matters.
-    s.replace('\\', "\\\\")
-        .replace('\t', "\\t")
-        .replace('\n', "\\n")
-}
-
-fn unescape_db_text(s: &str) -> String {
-    // Reverse of escape_db_text.
-    let mut out = String::new();
-    let mut chars = s.chars().peekable();
-    while let Some(c) = chars.next() {
-        if c != '\\' {
-            out.push(c);
-            continue;
-        }
-        match chars.peek().copied() {
-            Some('n') => {
-                let _ = chars.next();
-                out.push('\n');
-            }
-            Some('t') => {
-                let _ = chars.next();
-                out.push('\t');
-            }
-            Some('\\') => {
-                let _ = chars.next();
-                out.push('\\');
-            }
-            _ => out.push('\\'),
-        }
-    }
-    out
-}
-
-fn normalize_tags(tags: &[String]) -> Vec<String> {
-    let mut out: Vec<String> = tags
+fn normalize_tags(tags: &[String]) -> CoreResult<Vec<String>> {
+    let mut out = tags
         .iter()
-        .map(|t| t.trim().to_string())
-        .filter(|t| !t.is_empty())
-        .collect();
+        .filter(|t| !t.trim().is_empty())
+        .map(|t| normalize_tag(t))
+        .collect::<CoreResult<Vec<_>>>()?;
     out.sort();
     out.dedup();
-    out
+    Ok(out)
 }
 
 fn normalize_ids(ids: &[String]) -> Vec<String> {
@@ -511,13 +873,14 @@ fn normalize_ids(ids: &[String]) -> Vec<String> {
     out
 }
 
-fn compute_content_hash(
+pub(crate) fn compute_content_hash(
     question_canonical: &str,
     answer_short: &str,
     answer_long: &str,
     notes: Option<&str>,
     tags: &[String],
     source: &str,
+    translations: &[LocalizedText],
 ) -> CoreResult<String> {
     let mut s = String::new();
     s.push_str(question_canonical);
@@ -538,10 +901,23 @@ fn compute_content_hash(
     }
     s.push('\n');
     s.push_str(source);
+    // Appended only when present, so hashes of single-locale entries are
+    // unchanged. Translations are sorted by locale.
+    for t in translations {
+        for part in [
+            &t.locale,
+            &t.question_canonical,
+            &t.answer_short,
+            &t.answer_long,
+        ] {
+            s.push('\n');
+            s.push_str(part);
+        }
+    }
     crate::audit::hasher::sha256_hex_bytes(s.as_bytes())
 }
 
-fn parse_string_array_json(s: &str) -> CoreResult<Vec<String>> {
+pub(crate) fn parse_string_array_json(s: &str) -> CoreResult<Vec<String>> {
     let v = crate::util::json::JsonValue::parse(s)?;
     let arr = v.as_array()?;
     let mut out = Vec::new();
@@ -588,6 +964,9 @@ fn compute_changed_fields(before: &AnswerBankEntry, after: &AnswerBankEntry) ->
     if before.source != after.source {
         fields.push("source".to_string());
     }
+    if before.translations != after.translations {
+        fields.push("translations".to_string());
+    }
     if before.content_hash != after.content_hash {
         fields.push("content_hash".to_string());
     }
@@ -0,0 +1,447 @@
+//! Answer review cycles.
+//!
+//! A review policy sets how many days an answer stays fresh after
+//! `last_reviewed_at`. Policies apply to a single entry, to a tag and its
+//! descendants, or to the whole vault; an entry's own policy wins over its
+//! tags (the shortest interval of any tag or ancestor tag applies), which win
+//! over the vault default. Entries without any
+//! policy never go stale; entries under a policy that were never reviewed are
+//! overdue. Staleness is judged against a caller-supplied date so results are
+//! reproducible; matching uses today's date and down-ranks stale answers.
+
+use super::revision::{self, RevisionKind};
+use super::{
+    ab_get_entry, is_descendant, load_vault_id, normalize_tag, parse_string_array_json, stored_tag,
+    AnswerBankEntry,
+};
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::time::{
+    format_date_days, parse_date_days, today_utc_date, DETERMINISTIC_TIMESTAMP_UTC,
+};
+use crate::storage::db::{escape_db_text, unescape_db_text, SqliteDb};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReviewPolicyScope {
+    Default,
+    Tag,
+    Entry,
+}
+
+impl ReviewPolicyScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewPolicyScope::Default => "default",
+            ReviewPolicyScope::Tag => "tag",
+            ReviewPolicyScope::Entry => "entry",
+        }
+    }
+
+    pub fn parse(s: &str) -> CoreResult<Self> {
+        match s {
+            "default" => Ok(ReviewPolicyScope::Default),
+            "tag" => Ok(ReviewPolicyScope::Tag),
+            "entry" => Ok(ReviewPolicyScope::Entry),
+            other => Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!("unknown review policy scope: {other}"),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewPolicy {
+    pub scope: ReviewPolicyScope,
+    /// Tag or entry_id; empty for the vault default.
+    pub target: String,
+    pub interval_days: i64,
+    pub updated_by: String,
+    pub updated_at: String,
+}
+
+/// An entry past its review date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverdueReview {
+    pub entry_id: String,
+    pub question_canonical: String,
+    pub last_reviewed_at: Option<String>,
+    pub interval_days: i64,
+    /// `None` when the entry was never reviewed.
+    pub due_on: Option<String>,
+    pub days_overdue: Option<i64>,
+}
+
+/// Overdue entries of one owner, most overdue (never reviewed) first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnerOverdueReviews {
+    pub owner: String,
+    pub entries: Vec<OverdueReview>,
+}
+
+pub fn ab_list_review_policies(db: &SqliteDb) -> CoreResult<Vec<ReviewPolicy>> {
+    let mut out = Vec::new();
+    for r in db.query_rows_tsv(
+        "SELECT scope, target, interval_days, updated_by, updated_at FROM answer_bank_review_policy ORDER BY scope ASC, target ASC;",
+    )? {
+        if r.len() < 5 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected answer_bank_review_policy row",
+            ));
+        }
+        out.push(ReviewPolicy {
+            scope: ReviewPolicyScope::parse(&r[0])
+                .map_err(|e| CoreError::new(CoreErrorCode::CorruptVault, e.message))?,
+            target: unescape_db_text(&r[1]),
+            interval_days: r[2].parse().map_err(|_| {
+                CoreError::new(CoreErrorCode::CorruptVault, "invalid review interval")
+            })?,
+            updated_by: r[3].clone(),
+            updated_at: r[4].clone(),
+        });
+    }
+    // Default first, then tags, then entries.
+    out.sort_by(|a, b| a.scope.cmp(&b.scope).then_with(|| a.target.cmp(&b.target)));
+    Ok(out)
+}
+
+/// Create or replace the review interval for the vault (`target` ignored),
+/// a tag, or an entry.
+pub fn ab_set_review_policy(
+    db: &SqliteDb,
+    scope: ReviewPolicyScope,
+    target: &str,
+    interval_days: i64,
+    actor: &str,
+) -> CoreResult<ReviewPolicy> {
+    validator::validate_chain(db)?;
+
+    if interval_days <= 0 {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "interval_days must be > 0",
+        ));
+    }
+    let target = policy_target(db, scope, target)?;
+    let vault_id = load_vault_id(db)?;
+    let updated_at = DETERMINISTIC_TIMESTAMP_UTC.to_string();
+
+    let upsert_sql = format!(
+        "INSERT INTO answer_bank_review_policy (scope, target, interval_days, updated_by, updated_at) VALUES ({}, {}, {}, {}, {}) ON CONFLICT(scope, target) DO UPDATE SET interval_days=excluded.interval_days, updated_by=excluded.updated_by, updated_at=excluded.updated_at;",
+        db.q(scope.as_str()),
+        db.q(&escape_db_text(&target)),
+        interval_days,
+        db.q(actor),
+        db.q(&updated_at)
+    );
+    let event_sql = crate::storage::build_event_insert_sql(
+        db,
+        &vault_id,
+        actor,
+        "AnswerBankReviewPolicySet",
+        {
+            let mut o = CanonicalJson::object();
+            o.insert("scope", CanonicalJson::String(scope.as_str().to_string()));
+            o.insert("target", CanonicalJson::String(target.clone()));
+            o.insert("interval_days", CanonicalJson::Number(interval_days));
+            o
+        },
+    )?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", upsert_sql, event_sql))?;
+
+    Ok(ReviewPolicy {
+        scope,
+        target,
+        interval_days,
+        updated_by: actor.to_string(),
+        updated_at,
+    })
+}
+
+pub fn ab_delete_review_policy(
+    db: &SqliteDb,
+    scope: ReviewPolicyScope,
+    target: &str,
+    actor: &str,
+) -> CoreResult<()> {
+    validator::validate_chain(db)?;
+
+    let target = match scope {
+        ReviewPolicyScope::Default => String::new(),
+        ReviewPolicyScope::Tag => normalize_tag(target)?,
+        ReviewPolicyScope::Entry => target.trim().to_string(),
+    };
+    let where_sql = format!(
+        "scope={} AND target={}",
+        db.q(scope.as_str()),
+        db.q(&escape_db_text(&target))
+    );
+    if db
+        .query_optional_string(&format!(
+            "SELECT scope FROM answer_bank_review_policy WHERE {} LIMIT 1;",
+            where_sql
+        ))?
+        .is_none()
+    {
+        return Err(CoreError::new(
+            CoreErrorCode::NotFound,
+            "review policy not found",
+        ));
+    }
+
+    let vault_id = load_vault_id(db)?;
+    let event_sql = crate::storage::build_event_insert_sql(
+        db,
+        &vault_id,
+        actor,
+        "AnswerBankReviewPolicyDeleted",
+        {
+            let mut o = CanonicalJson::object();
+            o.insert("scope", CanonicalJson::String(scope.as_str().to_string()));
+            o.insert("target", CanonicalJson::String(target.clone()));
+            o
+        },
+    )?;
+    db.exec_batch(&format!(
+        "BEGIN;\nDELETE FROM answer_bank_review_policy WHERE {};\n{}\nCOMMIT;",
+        where_sql, event_sql
+    ))
+}
+
+/// Record that the entry's answer was reviewed on `reviewed_at`
+/// (`YYYY-MM-DD` or a UTC timestamp, not after today). Adds a `reviewed`
+/// revision.
+pub fn ab_mark_reviewed(
+    db: &SqliteDb,
+    entry_id: &str,
+    reviewed_at: &str,
+    actor: &str,
+) -> CoreResult<AnswerBankEntry> {
+    validator::validate_chain(db)?;
+
+    let entry = ab_get_entry(db, entry_id)?;
+    let reviewed_at = reviewed_at.trim().to_string();
+    if parse_date_days(&reviewed_at)? > parse_date_days(&today_utc_date())? {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            format!("reviewed_at is in the future: {reviewed_at}"),
+        ));
+    }
+    let revision = revision::next_revision(db, entry_id)?;
+
+    let update_sql = format!(
+        "UPDATE answer_bank SET last_reviewed_at={}, updated_at={} WHERE entry_id={};",
+        db.q(&reviewed_at),
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        db.q(entry_id)
+    );
+    let revision_sql =
+        revision::insert_revision_sql(db, entry_id, revision, RevisionKind::Reviewed, None, actor);
+    let event_sql = crate::storage::build_event_insert_sql(
+        db,
+        &entry.vault_id,
+        actor,
+        "AnswerBankEntryReviewed",
+        {
+            let mut o = CanonicalJson::object();
+            o.insert("entry_id", CanonicalJson::String(entry_id.to_string()));
+            o.insert(
+                "content_hash",
+                CanonicalJson::String(entry.content_hash.clone()),
+            );
+            o.insert("reviewed_at", CanonicalJson::String(reviewed_at.clone()));
+            o.insert("revision", CanonicalJson::Number(revision));
+            o
+        },
+    )?;
+    db.exec_batch(&format!(
+        "BEGIN;\n{}\n{}\n{}\nCOMMIT;",
+        update_sql, revision_sql, event_sql
+    ))?;
+
+    ab_get_entry(db, entry_id)
+}
+
+/// Entries overdue for review on `as_of` (`YYYY-MM-DD`), grouped by owner in
+/// owner order.
+pub fn ab_overdue_reviews(db: &SqliteDb, as_of: &str) -> CoreResult<Vec<OwnerOverdueReviews>> {
+    let today = parse_date_days(as_of)?;
+    let policies = PolicySet::load(db)?;
+    let vault_id = load_vault_id(db)?;
+
+    let mut by_owner: BTreeMap<String, Vec<OverdueReview>> = BTreeMap::new();
+    for r in db.query_rows_tsv(&format!(
+        "SELECT entry_id, owner, question_canonical, tags_json, IFNULL(last_reviewed_at,'') FROM answer_bank WHERE vault_id={};",
+        db.q(&vault_id)
+    ))? {
+        let row = ReviewRow::from_row(&r)?;
+        if let Some(due) = policies.due(&row, today) {
+            if due.overdue {
+                by_owner
+                    .entry(unescape_db_text(&r[1]))
+                    .or_default()
+                    .push(OverdueReview {
+                        entry_id: row.entry_id,
+                        question_canonical: unescape_db_text(&r[2]),
+                        last_reviewed_at: row.last_reviewed_at,
+                        interval_days: due.interval_days,
+                        due_on: due.due_day.map(format_date_days),
+                        days_overdue: due.due_day.map(|d| today - d),
+                    });
+            }
+        }
+    }
+
+    Ok(by_owner
+        .into_iter()
+        .map(|(owner, mut entries)| {
+            // Never reviewed first, then most days overdue.
+            entries.sort_by_key(|e| (e.days_overdue.map_or(i64::MIN, |d| -d), e.entry_id.clone()));
+            OwnerOverdueReviews { owner, entries }
+        })
+        .collect())
+}
+
+/// Ids among `entry_ids` that are overdue for review on `as_of`.
+pub(crate) fn stale_entry_ids(
+    db: &SqliteDb,
+    entry_ids: &[String],
+    as_of: &str,
+) -> CoreResult<HashSet<String>> {
+    let mut out = HashSet::new();
+    let policies = PolicySet::load(db)?;
+    if entry_ids.is_empty() || policies.is_empty() {
+        return Ok(out);
+    }
+    let today = parse_date_days(as_of)?;
+    let in_list: Vec<String> = entry_ids.iter().map(|id| db.q(id)).collect();
+    for r in db.query_rows_tsv(&format!(
+        "SELECT entry_id, owner, question_canonical, tags_json, IFNULL(last_reviewed_at,'') FROM answer_bank WHERE entry_id IN ({});",
+        in_list.join(", ")
+    ))? {
+        let row = ReviewRow::from_row(&r)?;
+        if policies.due(&row, today).is_some_and(|d| d.overdue) {
+            out.insert(row.entry_id);
+        }
+    }
+    Ok(out)
+}
+
+/// SQL removing an entry's own review policy (used when the entry is deleted).
+pub(crate) fn delete_entry_policy_sql(db: &SqliteDb, entry_id: &str) -> String {
+    format!(
+        "DELETE FROM answer_bank_review_policy WHERE scope='entry' AND target={};",
+        db.q(&escape_db_text(entry_id))
+    )
+}
+
+fn policy_target(db: &SqliteDb, scope: ReviewPolicyScope, target: &str) -> CoreResult<String> {
+    let target = target.trim();
+    match scope {
+        ReviewPolicyScope::Default => Ok(String::new()),
+        _ if target.is_empty() => Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "review policy target is required",
+        )),
+        ReviewPolicyScope::Entry => Ok(ab_get_entry(db, target)?.entry_id),
+        ReviewPolicyScope::Tag => normalize_tag(target),
+    }
+}
+
+struct ReviewRow {
+    entry_id: String,
+    tags: Vec<String>,
+    last_reviewed_at: Option<String>,
+}
+
+impl ReviewRow {
+    /// From `entry_id, owner, question_canonical, tags_json, last_reviewed_at`.
+    fn from_row(r: &[String]) -> CoreResult<Self> {
+        if r.len() < 5 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected answer_bank row",
+            ));
+        }
+        Ok(Self {
+            entry_id: r[0].clone(),
+            tags: parse_string_array_json(&r[3])?
+                .iter()
+                .map(|t| stored_tag(t))
+                .collect(),
+            last_reviewed_at: if r[4].trim().is_empty() {
+                None
+            } else {
+                Some(r[4].clone())
+            },
+        })
+    }
+}
+
+struct Due {
+    interval_days: i64,
+    due_day: Option<i64>,
+    overdue: bool,
+}
+
+struct PolicySet {
+    default: Option<i64>,
+    tags: HashMap<String, i64>,
+    entries: HashMap<String, i64>,
+}
+
+impl PolicySet {
+    fn load(db: &SqliteDb) -> CoreResult<Self> {
+        let mut set = Self {
+            default: None,
+            tags: HashMap::new(),
+            entries: HashMap::new(),
+        };
+        for p in ab_list_review_policies(db)? {
+            match p.scope {
+                ReviewPolicyScope::Default => set.default = Some(p.interval_days),
+                ReviewPolicyScope::Tag => {
+                    set.tags.insert(p.target, p.interval_days);
+                }
+                ReviewPolicyScope::Entry => {
+                    set.entries.insert(p.target, p.interval_days);
+                }
+            }
+        }
+        Ok(set)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.default.is_none() && self.tags.is_empty() && self.entries.is_empty()
+    }
+
+    fn interval(&self, row: &ReviewRow) -> Option<i64> {
+        self.entries.get(&row.entry_id).copied().or_else(|| {
+            self.tags
+                .iter()
+                .filter(|(p, _)| row.tags.iter().any(|t| t == *p || is_descendant(t, p)))
+                .map(|(_, days)| *days)
+                .min()
+                .or(self.default)
+        })
+    }
+
+    /// Review state on day `today`, or `None` when no policy applies. A
+    /// review date that does not parse counts as never reviewed.
+    fn due(&self, row: &ReviewRow, today: i64) -> Option<Due> {
+        let interval_days = self.interval(row)?;
+        let due_day = row
+            .last_reviewed_at
+            .as_deref()
+            .and_then(|s| parse_date_days(s).ok())
+            .map(|d| d + interval_days);
+        Some(Due {
+            interval_days,
+            due_day,
+            overdue: due_day.is_none_or(|d| today > d),
+        })
+    }
+}
@@ -0,0 +1,406 @@
+//! Answer bank revision history.
+//!
+//! Every create, update, rollback and review stores the entry's full content
+//! as a new revision in the same transaction as the write, so the wording
+//! given at any point can be read back ("what did we tell customer X last
+//! March?"), two versions compared, and an earlier version restored. A
+//! rollback is itself an audited update that adds a revision; history is never
+//! rewritten. Revisions are kept when their entry is deleted. Translations are
+//! part of the content; diffs show their fields per locale, e.g.
+//! `answer_long[de]`.
+//!
+//! The vault stores fixed timestamps, so a point in time is a position in the
+//! audit log: each revision is placed by the `seq` of the event that recorded
+//! it, and [`ab_revision_at_audit_seq`] answers "what did the entry say when
+//! the pack with this audit head was exported?".
+
+use super::locale::parse_translations_json;
+use super::{
+    ab_get_entry, parse_string_array_json, update_entry, AnswerBankEntry, AnswerBankUpdatePatch,
+    LocalizedText,
+};
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::storage::db::{unescape_db_text, SqliteDb};
+use crate::util::json::JsonValue;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionKind {
+    Created,
+    Updated,
+    RolledBack,
+    /// Marked reviewed; only `last_reviewed_at` changed.
+    Reviewed,
+}
+
+impl RevisionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RevisionKind::Created => "created",
+            RevisionKind::Updated => "updated",
+            RevisionKind::RolledBack => "rolled_back",
+            RevisionKind::Reviewed => "reviewed",
+        }
+    }
+
+    pub fn parse(s: &str) -> CoreResult<Self> {
+        match s {
+            "created" => Ok(RevisionKind::Created),
+            "updated" => Ok(RevisionKind::Updated),
+            "rolled_back" => Ok(RevisionKind::RolledBack),
+            "reviewed" => Ok(RevisionKind::Reviewed),
+            other => Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!("unknown revision kind: {other}"),
+            )),
+        }
+    }
+}
+
+/// An entry as it stood after one write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerBankRevision {
+    pub entry_id: String,
+    /// 1 for the create, then one more per update, rollback or review.
+    pub revision: i64,
+    pub kind: RevisionKind,
+    /// Revision whose content a rollback restored.
+    pub rolled_back_to: Option<i64>,
+    pub question_canonical: String,
+    pub answer_short: String,
+    pub answer_long: String,
+    pub notes: Option<String>,
+    pub evidence_links: Vec<String>,
+    pub owner: String,
+    pub last_reviewed_at: Option<String>,
+    pub tags: Vec<String>,
+    pub source: String,
+    pub content_hash: String,
+    pub created_by: String,
+    /// The vault's fixed write timestamp; not a point in time, see `audit_seq`.
+    pub created_at: String,
+    pub translations: Vec<LocalizedText>,
+    /// Seq of the audit event that recorded this revision.
+    pub audit_seq: Option<i64>,
+}
+
+/// One field that differs between two revisions. List fields (tags,
+/// evidence links) are shown comma-separated; `None` means unset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevisionFieldChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerBankRevisionDiff {
+    pub entry_id: String,
+    pub from_revision: i64,
+    pub to_revision: i64,
+    /// Changed fields in field-name order; empty when the content is equal.
+    pub changes: Vec<RevisionFieldChange>,
+}
+
+/// All revisions of an entry, oldest first. Works for deleted entries too.
+pub fn ab_list_revisions(db: &SqliteDb, entry_id: &str) -> CoreResult<Vec<AnswerBankRevision>> {
+    let out = query_revisions(
+        db,
+        entry_id,
+        &format!(
+            "SELECT {} FROM answer_bank_revision WHERE entry_id={} ORDER BY revision ASC;",
+            REVISION_COLUMNS,
+            db.q(entry_id)
+        ),
+    )?;
+    if out.is_empty() {
+        return Err(CoreError::new(
+            CoreErrorCode::NotFound,
+            "answer bank entry not found",
+        ));
+    }
+    Ok(out)
+}
+
+pub fn ab_get_revision(
+    db: &SqliteDb,
+    entry_id: &str,
+    revision: i64,
+) -> CoreResult<AnswerBankRevision> {
+    query_revisions(
+        db,
+        entry_id,
+        &format!(
+            "SELECT {} FROM answer_bank_revision WHERE entry_id={} AND revision={} LIMIT 1;",
+            REVISION_COLUMNS,
+            db.q(entry_id),
+            revision
+        ),
+    )?
+    .into_iter()
+    .next()
+    .ok_or_else(|| CoreError::new(CoreErrorCode::NotFound, "answer bank revision not found"))
+}
+
+/// The revision that was current once audit event `audit_seq` was written:
+/// the latest one recorded at or before it. Pass an export pack's
+/// `audit_head.seq` to see the entry as that pack had it.
+pub fn ab_revision_at_audit_seq(
+    db: &SqliteDb,
+    entry_id: &str,
+    audit_seq: i64,
+) -> CoreResult<AnswerBankRevision> {
+    if audit_seq < 1 {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "audit_seq must be positive",
+        ));
+    }
+    ab_list_revisions(db, entry_id)?
+        .into_iter()
+        .rev()
+        .find(|r| r.audit_seq.is_some_and(|seq| seq <= audit_seq))
+        .ok_or_else(|| {
+            CoreError::new(
+                CoreErrorCode::NotFound,
+                "answer bank entry has no revision at that audit position",
+            )
+        })
+}
+
+/// Field-by-field differences going from `from_revision` to `to_revision`.
+pub fn ab_diff_revisions(
+    db: &SqliteDb,
+    entry_id: &str,
+    from_revision: i64,
+    to_revision: i64,
+) -> CoreResult<AnswerBankRevisionDiff> {
+    let from = ab_get_revision(db, entry_id, from_revision)?;
+    let to = ab_get_revision(db, entry_id, to_revision)?;
+    let mut from = field_values(&from);
+    let mut to = field_values(&to);
+    let fields: Vec<String> = from.keys().chain(to.keys()).cloned().collect();
+    let mut changes: Vec<RevisionFieldChange> = Vec::new();
+    for field in fields {
+        let before = from.remove(&field).flatten();
+        let after = to.remove(&field).flatten();
+        if before != after {
+            changes.push(RevisionFieldChange {
+                field,
+                before,
+                after,
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.field.cmp(&b.field));
+    Ok(AnswerBankRevisionDiff {
+        entry_id: entry_id.to_string(),
+        from_revision,
+        to_revision,
+        changes,
+    })
+}
+
+/// Restore the content of `revision` as a new, audited update.
+///
+/// Everything but `last_reviewed_at` is restored: bringing back old text is
+/// an edit, not a review.
+pub fn ab_rollback_entry(
+    db: &SqliteDb,
+    entry_id: &str,
+    revision: i64,
+    actor: &str,
+) -> CoreResult<AnswerBankEntry> {
+    let current = ab_get_entry(db, entry_id)?;
+    let target = ab_get_revision(db, entry_id, revision)?;
+    if same_content(&current, &target) {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            "answer bank entry already matches that revision",
+        ));
+    }
+
+    let patch = AnswerBankUpdatePatch {
+        question_canonical: Some(target.question_canonical),
+        answer_short: Some(target.answer_short),
+        answer_long: Some(target.answer_long),
+        notes: Some(target.notes),
+        evidence_links: Some(target.evidence_links),
+        owner: Some(target.owner),
+        last_reviewed_at: None,
+        tags: Some(target.tags),
+        source: Some(target.source),
+        translations: Some(target.translations),
+    };
+    update_entry(db, entry_id, patch, actor, Some(revision))
+}
+
+/// Number the next write of `entry_id` will get.
+pub(crate) fn next_revision(db: &SqliteDb, entry_id: &str) -> CoreResult<i64> {
+    let n = db.query_optional_string(&format!(
+        "SELECT IFNULL(MAX(revision),0) FROM answer_bank_revision WHERE entry_id={};",
+        db.q(entry_id)
+    ))?;
+    Ok(n.and_then(|s| s.parse::<i64>().ok()).unwrap_or(0) + 1)
+}
+
+/// SQL copying the entry's current row into a revision. Must run after the
+/// write in the same transaction.
+pub(crate) fn insert_revision_sql(
+    db: &SqliteDb,
+    entry_id: &str,
+    revision: i64,
+    kind: RevisionKind,
+    rolled_back_to: Option<i64>,
+    actor: &str,
+) -> String {
+    format!(
+        "INSERT INTO answer_bank_revision (entry_id, revision, kind, rolled_back_to, question_canonical, answer_short, answer_long, notes, evidence_links_json, owner, last_reviewed_at, tags_json, source, content_hash, created_by, created_at, translations_json) SELECT entry_id, {}, {}, {}, question_canonical, answer_short, answer_long, notes, evidence_links_json, owner, last_reviewed_at, tags_json, source, content_hash, {}, updated_at, translations_json FROM answer_bank WHERE entry_id={};",
+        revision,
+        db.q(kind.as_str()),
+        match rolled_back_to {
+            Some(n) => n.to_string(),
+            None => "NULL".to_string(),
+        },
+        db.q(actor),
+        db.q(entry_id)
+    )
+}
+
+const REVISION_COLUMNS: &str = "entry_id, revision, kind, IFNULL(rolled_back_to,''), question_canonical, answer_short, answer_long, IFNULL(notes,''), evidence_links_json, owner, IFNULL(last_reviewed_at,''), tags_json, source, content_hash, created_by, created_at, translations_json";
+
+fn query_revisions(
+    db: &SqliteDb,
+    entry_id: &str,
+    sql: &str,
+) -> CoreResult<Vec<AnswerBankRevision>> {
+    let mut out = db
+        .query_rows_tsv(sql)?
+        .iter()
+        .map(|r| revision_from_row(r))
+        .collect::<CoreResult<Vec<_>>>()?;
+    if !out.is_empty() {
+        let seqs = revision_audit_seqs(db, entry_id)?;
+        for r in &mut out {
+            r.audit_seq = seqs.get(&r.revision).copied();
+        }
+    }
+    Ok(out)
+}
+
+/// Seq of the event that recorded each revision of `entry_id`. Writes put the
+/// `entry_id` and `revision` in their event; entries created before revisions
+/// existed got revision 1 from migration 0015, recorded by their create event.
+fn revision_audit_seqs(db: &SqliteDb, entry_id: &str) -> CoreResult<BTreeMap<i64, i64>> {
+    let mut out = BTreeMap::new();
+    for r in db.query_rows_tsv(&format!(
+        "SELECT seq, event_type, payload_json FROM audit_event WHERE payload_json LIKE {} ORDER BY seq ASC;",
+        db.q(&format!("%{}%", entry_id))
+    ))? {
+        if r.len() != 3 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected audit row shape",
+            ));
+        }
+        let payload = JsonValue::parse(&r[2])?;
+        let payload = payload.as_object()?;
+        if !matches!(payload.get("entry_id"), Some(JsonValue::String(id)) if id == entry_id) {
+            continue;
+        }
+        let revision = match payload.get("revision") {
+            Some(JsonValue::Number(n)) => *n,
+            None if r[1] == "AnswerBankEntryCreated" => 1,
+            _ => continue,
+        };
+        let seq = r[0]
+            .parse::<i64>()
+            .map_err(|_| CoreError::new(CoreErrorCode::CorruptVault, "invalid audit seq"))?;
+        out.entry(revision).or_insert(seq);
+    }
+    Ok(out)
+}
+
+fn revision_from_row(r: &[String]) -> CoreResult<AnswerBankRevision> {
+    if r.len() < 17 {
+        return Err(CoreError::new(
+            CoreErrorCode::CorruptVault,
+            "unexpected answer_bank_revision row",
+        ));
+    }
+    let corrupt = |_| CoreError::new(CoreErrorCode::CorruptVault, "invalid revision number");
+    let optional = |s: &str| {
+        if s.trim().is_empty() {
+            None
+        } else {
+            Some(s.to_string())
+        }
+    };
+
+    Ok(AnswerBankRevision {
+        entry_id: r[0].clone(),
+        revision: r[1].parse().map_err(corrupt)?,
+        kind: RevisionKind::parse(&r[2])
+            .map_err(|e| CoreError::new(CoreErrorCode::CorruptVault, e.message))?,
+        rolled_back_to: optional(&r[3])
+            .map(|s| s.parse().map_err(corrupt))
+            .transpose()?,
+        question_canonical: unescape_db_text(&r[4]),
+        answer_short: unescape_db_text(&r[5]),
+        answer_long: unescape_db_text(&r[6]),
+        notes: optional(&r[7]).map(|s| unescape_db_text(&s)),
+        evidence_links: parse_string_array_json(&r[8])?,
+        owner: unescape_db_text(&r[9]),
+        last_reviewed_at: optional(&r[10]),
+        tags: parse_string_array_json(&r[11])?,
+        source: unescape_db_text(&r[12]),
+        content_hash: r[13].clone(),
+        created_by: r[14].clone(),
+        created_at: r[15].clone(),
+        translations: parse_translations_json(&r[16])?,
+        audit_seq: None,
+    })
+}
+
+/// Compared fields by name; translated text as `field[locale]`.
+fn field_values(r: &AnswerBankRevision) -> BTreeMap<String, Option<String>> {
+    let mut out: BTreeMap<String, Option<String>> = [
+        ("answer_long", Some(r.answer_long.clone())),
+        ("answer_short", Some(r.answer_short.clone())),
+        ("content_hash", Some(r.content_hash.clone())),
+        ("evidence_links", list_value(&r.evidence_links)),
+        ("last_reviewed_at", r.last_reviewed_at.clone()),
+        ("notes", r.notes.clone()),
+        ("owner", Some(r.owner.clone())),
+        ("question_canonical", Some(r.question_canonical.clone())),
+        ("source", Some(r.source.clone())),
+        ("tags", list_value(&r.tags)),
+    ]
+    .into_iter()
+    .map(|(field, value)| (field.to_string(), value))
+    .collect();
+    for t in &r.translations {
+        for (field, value) in [
+            ("answer_long", &t.answer_long),
+            ("answer_short", &t.answer_short),
+            ("question_canonical", &t.question_canonical),
+        ] {
+            out.insert(format!("{field}[{}]", t.locale), Some(value.clone()));
+        }
+    }
+    out
+}
+
+fn list_value(items: &[String]) -> Option<String> {
+    if items.is_empty() {
+        None
+    } else {
+        Some(items.join(", "))
+    }
+}
+
+fn same_content(entry: &AnswerBankEntry, r: &AnswerBankRevision) -> bool {
+    entry.content_hash == r.content_hash
+        && entry.evidence_links == r.evidence_links
+        && entry.owner == r.owner
+}
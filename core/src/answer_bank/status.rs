@@ -0,0 +1,149 @@
+//! Answer bank approval workflow.
+//!
+//! Entries move between draft, in review, approved and retired. Only approved
+//! entries are used to auto-answer questionnaires, and retired entries are not
+//! matched at all. Approval requires an actor other than the entry's last
+//! editor, and editing an approved entry sends it back to draft. Every
+//! transition appends an `AnswerBankEntryStatusChanged` event.
+
+use super::{ab_get_entry, AnswerBankEntry};
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
+use crate::storage::db::SqliteDb;
+use crate::storage::AuditEventBatch;
+use std::collections::HashSet;
+
+/// SQL condition on `answer_bank` rows that may be offered by matching.
+pub(crate) const MATCHABLE_SQL: &str = "answer_bank.status<>'retired'";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryStatus {
+    Draft,
+    InReview,
+    Approved,
+    Retired,
+}
+
+impl EntryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryStatus::Draft => "draft",
+            EntryStatus::InReview => "in_review",
+            EntryStatus::Approved => "approved",
+            EntryStatus::Retired => "retired",
+        }
+    }
+
+    pub fn parse(s: &str) -> CoreResult<Self> {
+        match s {
+            "draft" => Ok(EntryStatus::Draft),
+            "in_review" => Ok(EntryStatus::InReview),
+            "approved" => Ok(EntryStatus::Approved),
+            "retired" => Ok(EntryStatus::Retired),
+            other => Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!("unknown answer bank entry status: {other}"),
+            )),
+        }
+    }
+
+    fn can_transition_to(&self, to: EntryStatus) -> bool {
+        use EntryStatus::*;
+        matches!(
+            (self, to),
+            (Draft, InReview | Approved | Retired)
+                | (InReview, Draft | Approved | Retired)
+                | (Approved, Draft | Retired)
+                | (Retired, Draft)
+        )
+    }
+}
+
+/// Move an entry to `status`. Setting the current status is a no-op.
+pub fn ab_set_entry_status(
+    db: &SqliteDb,
+    entry_id: &str,
+    status: EntryStatus,
+    actor: &str,
+) -> CoreResult<AnswerBankEntry> {
+    validator::validate_chain(db)?;
+
+    let before = ab_get_entry(db, entry_id)?;
+    if before.status == status {
+        return Ok(before);
+    }
+    if !before.status.can_transition_to(status) {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            format!(
+                "cannot move answer bank entry from {} to {}",
+                before.status.as_str(),
+                status.as_str()
+            ),
+        ));
+    }
+    if status == EntryStatus::Approved && before.last_edited_by.as_deref() == Some(actor) {
+        return Err(CoreError::new(
+            CoreErrorCode::PermissionDenied,
+            "answer bank entry must be approved by someone other than its last editor",
+        ));
+    }
+
+    let mut events = AuditEventBatch::new(db, &before.vault_id)?;
+    let sql = set_status_sql(db, &before, status, actor, &mut events)?;
+    db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", sql, events.sql()))?;
+    ab_get_entry(db, entry_id)
+}
+
+/// SQL moving `entry` to `status`, pushing the transition event onto
+/// `events`. Callers check the transition first.
+pub(crate) fn set_status_sql(
+    db: &SqliteDb,
+    entry: &AnswerBankEntry,
+    status: EntryStatus,
+    actor: &str,
+    events: &mut AuditEventBatch,
+) -> CoreResult<String> {
+    events.push(db, actor, "AnswerBankEntryStatusChanged", {
+        let mut o = CanonicalJson::object();
+        o.insert("entry_id", CanonicalJson::String(entry.entry_id.clone()));
+        o.insert(
+            "content_hash",
+            CanonicalJson::String(entry.content_hash.clone()),
+        );
+        o.insert(
+            "from",
+            CanonicalJson::String(entry.status.as_str().to_string()),
+        );
+        o.insert("to", CanonicalJson::String(status.as_str().to_string()));
+        o
+    })?;
+    Ok(format!(
+        "UPDATE answer_bank SET status={}, updated_at={} WHERE entry_id={};\n",
+        db.q(status.as_str()),
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        db.q(&entry.entry_id)
+    ))
+}
+
+/// Ids among `entry_ids` that are approved.
+pub(crate) fn approved_entry_ids(
+    db: &SqliteDb,
+    entry_ids: &[String],
+) -> CoreResult<HashSet<String>> {
+    if entry_ids.is_empty() {
+        return Ok(HashSet::new());
+    }
+    let in_list: Vec<String> = entry_ids.iter().map(|id| db.q(id)).collect();
+    Ok(db
+        .query_rows_tsv(&format!(
+            "SELECT entry_id FROM answer_bank WHERE status={} AND entry_id IN ({});",
+            db.q(EntryStatus::Approved.as_str()),
+            in_list.join(", ")
+        ))?
+        .into_iter()
+        .filter_map(|r| r.into_iter().next())
+        .collect())
+}
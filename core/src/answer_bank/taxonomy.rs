@@ -0,0 +1,558 @@
+//! Managed tag taxonomy.
+//!
+//! Tags are `/`-separated paths: `security/encryption` is a child of
+//! `security`. The taxonomy lists the tags the vault defines (with optional
+//! descriptions); entries still carry their tags as plain strings, so a tag
+//! in use but never defined shows up as undefined rather than failing writes.
+//!
+//! Renaming a tag renames its descendants too, everywhere: in the taxonomy,
+//! on every entry and evidence item that uses them and in tag review
+//! policies. Each changed entry gets a new revision and an
+//! `AnswerBankEntryUpdated` event; a rename does not change what an entry
+//! says, so approved entries stay approved and `last_edited_by` is kept. One
+//! `AnswerBankTagRenamed` event, listing the changed entries and evidence,
+//! closes the transaction.
+
+use super::{
+    load_vault_id, parse_string_array_json, patched_entry, revision, AnswerBankEntry,
+    AnswerBankUpdatePatch, RevisionKind, ENTRY_COLUMNS,
+};
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
+use crate::storage::db::{escape_db_text, unescape_db_text, SqliteDb};
+use crate::storage::AuditEventBatch;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Separator between the levels of a tag path.
+pub const TAG_SEPARATOR: char = '/';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerBankTag {
+    pub tag: String,
+    pub description: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+/// A tag in the taxonomy or in use, with how many entries carry it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagUsage {
+    pub tag: String,
+    /// Parent path; `None` for top-level tags.
+    pub parent: Option<String>,
+    pub description: Option<String>,
+    /// Listed in the taxonomy (otherwise only found on entries).
+    pub defined: bool,
+    /// Entries tagged with exactly this tag.
+    pub entry_count: i64,
+    /// Entries tagged with this tag or any descendant, each counted once.
+    pub total_count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagRenameReport {
+    pub from: String,
+    pub to: String,
+    /// Old names of the renamed tags (`from` and its descendants).
+    pub renamed_tags: Vec<String>,
+    /// Entries whose tags changed.
+    pub entry_ids: Vec<String>,
+    /// Evidence items whose tags changed.
+    pub evidence_ids: Vec<String>,
+}
+
+/// Trim and lowercase each level of a tag path; empty levels are rejected.
+/// Every tag write (entries, the taxonomy, review policies, evidence) goes
+/// through here, so `Security/Access` and `security/access` are one tag.
+pub fn normalize_tag(tag: &str) -> CoreResult<String> {
+    let levels: Vec<&str> = tag.split(TAG_SEPARATOR).map(str::trim).collect();
+    if levels
+        .iter()
+        .any(|l| l.is_empty() || l.chars().any(char::is_control))
+    {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            format!("invalid tag: {}", tag.trim()),
+        ));
+    }
+    Ok(levels
+        .iter()
+        .map(|l| l.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// A tag read back from storage, normalized for comparison. Tags written
+/// before normalization was enforced may differ in case or spacing; ones
+/// that do not normalize at all are compared as stored.
+pub(crate) fn stored_tag(tag: &str) -> String {
+    normalize_tag(tag).unwrap_or_else(|_| tag.to_string())
+}
+
+/// The taxonomy merged with the tags in use, in tag order. Ancestors of any
+/// listed tag are listed too.
+pub fn ab_list_tags(db: &SqliteDb) -> CoreResult<Vec<TagUsage>> {
+    let mut tags: BTreeMap<String, TagUsage> = BTreeMap::new();
+    for t in list_defined(db)? {
+        tags.insert(t.tag.clone(), usage(&t.tag, t.description, true));
+    }
+
+    let vault_id = load_vault_id(db)?;
+    for r in db.query_rows_tsv(&format!(
+        "SELECT tags_json FROM answer_bank WHERE vault_id={};",
+        db.q(&vault_id)
+    ))? {
+        let entry_tags = parse_string_array_json(r.first().map(String::as_str).unwrap_or("[]"))?;
+        let mut with_ancestors = BTreeSet::new();
+        for tag in entry_tags.iter().map(|t| stored_tag(t)) {
+            tags.entry(tag.clone())
+                .or_insert_with(|| usage(&tag, None, false))
+                .entry_count += 1;
+            with_ancestors.extend(ancestors(&tag));
+            with_ancestors.insert(tag);
+        }
+        for tag in with_ancestors {
+            tags.entry(tag.clone())
+                .or_insert_with(|| usage(&tag, None, false))
+                .total_count += 1;
+        }
+    }
+
+    let missing: Vec<String> = tags
+        .keys()
+        .flat_map(|t| ancestors(t))
+        .filter(|a| !tags.contains_key(a))
+        .collect();
+    for tag in missing {
+        tags.insert(tag.clone(), usage(&tag, None, false));
+    }
+    Ok(tags.into_values().collect())
+}
+
+/// Add a tag (and any missing ancestors) to the taxonomy, or change the
+/// description of a defined one.
+pub fn ab_define_tag(
+    db: &SqliteDb,
+    tag: &str,
+    description: Option<&str>,
+    actor: &str,
+) -> CoreResult<AnswerBankTag> {
+    validator::validate_chain(db)?;
+
+    let tag = normalize_tag(tag)?;
+    let description = description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    let previous = get_defined(db, &tag)?;
+    if let Some(p) = previous.as_ref().filter(|p| p.description == description) {
+        return Ok(p.clone());
+    }
+
+    let defined: BTreeSet<String> = list_defined(db)?.into_iter().map(|t| t.tag).collect();
+    let new_ancestors: Vec<String> = ancestors(&tag)
+        .into_iter()
+        .filter(|a| !defined.contains(a))
+        .collect();
+
+    let mut sql = String::new();
+    for a in &new_ancestors {
+        sql.push_str(&insert_tag_sql(db, a, None, actor));
+    }
+    if previous.is_some() {
+        sql.push_str(&format!(
+            "UPDATE answer_bank_tag SET description={} WHERE tag={};\n",
+            optional_text(db, description.as_deref()),
+            db.q(&tag)
+        ));
+    } else {
+        sql.push_str(&insert_tag_sql(db, &tag, description.as_deref(), actor));
+    }
+
+    let vault_id = load_vault_id(db)?;
+    let event_sql =
+        crate::storage::build_event_insert_sql(db, &vault_id, actor, "AnswerBankTagDefined", {
+            let mut o = CanonicalJson::object();
+            o.insert("tag", CanonicalJson::String(tag.clone()));
+            if let Some(d) = &description {
+                o.insert("description", CanonicalJson::String(d.clone()));
+            }
+            if let Some(d) = previous.as_ref().and_then(|p| p.description.clone()) {
+                o.insert("previous_description", CanonicalJson::String(d));
+            }
+            if !new_ancestors.is_empty() {
+                o.insert(
+                    "defined_ancestors",
+                    CanonicalJson::Array(
+                        new_ancestors
+                            .iter()
+                            .cloned()
+                            .map(CanonicalJson::String)
+                            .collect(),
+                    ),
+                );
+            }
+            o
+        })?;
+    db.exec_batch(&format!("BEGIN;\n{}{}\nCOMMIT;", sql, event_sql))?;
+
+    get_defined(db, &tag)?
+        .ok_or_else(|| CoreError::new(CoreErrorCode::CorruptVault, "tag was not stored"))
+}
+
+/// Remove a tag from the taxonomy. Tags still on entries, or with defined
+/// child tags, cannot be removed.
+pub fn ab_delete_tag(db: &SqliteDb, tag: &str, actor: &str) -> CoreResult<()> {
+    validator::validate_chain(db)?;
+
+    let tag = normalize_tag(tag)?;
+    if get_defined(db, &tag)?.is_none() {
+        return Err(CoreError::new(CoreErrorCode::NotFound, "tag not found"));
+    }
+    let in_use = ab_list_tags(db)?
+        .into_iter()
+        .find(|t| t.tag == tag)
+        .is_some_and(|t| t.total_count > 0);
+    if in_use {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            "tag is used by answer bank entries",
+        ));
+    }
+    if list_defined(db)?
+        .iter()
+        .any(|t| is_descendant(&t.tag, &tag))
+    {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            "tag has child tags",
+        ));
+    }
+
+    let vault_id = load_vault_id(db)?;
+    let delete_sql = format!("DELETE FROM answer_bank_tag WHERE tag={};", db.q(&tag));
+    let event_sql =
+        crate::storage::build_event_insert_sql(db, &vault_id, actor, "AnswerBankTagDeleted", {
+            let mut o = CanonicalJson::object();
+            o.insert("tag", CanonicalJson::String(tag.clone()));
+            o
+        })?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", delete_sql, event_sql))
+}
+
+/// Rename `from` and its descendants to `to`, merging into `to` if it
+/// already exists.
+pub fn ab_rename_tag(
+    db: &SqliteDb,
+    from: &str,
+    to: &str,
+    actor: &str,
+) -> CoreResult<TagRenameReport> {
+    validator::validate_chain(db)?;
+
+    let from = normalize_tag(from)?;
+    let to = normalize_tag(to)?;
+    if from == to {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "new tag name is the same as the old one",
+        ));
+    }
+    if is_descendant(&to, &from) {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "a tag cannot be moved under itself",
+        ));
+    }
+
+    let vault_id = load_vault_id(db)?;
+    let evidence = evidence_tags(db, &vault_id)?;
+    let renamed_tags: Vec<String> = ab_list_tags(db)?
+        .into_iter()
+        .map(|t| t.tag)
+        .chain(evidence.iter().flat_map(|(_, tags)| tags.iter().cloned()))
+        .filter(|t| renamed(t, &from, &to).is_some())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    if renamed_tags.is_empty() {
+        return Err(CoreError::new(CoreErrorCode::NotFound, "tag not found"));
+    }
+
+    let mut events = AuditEventBatch::new(db, &vault_id)?;
+    let mut sql = String::new();
+
+    let defined: BTreeSet<String> = list_defined(db)?.into_iter().map(|t| t.tag).collect();
+    for a in ancestors(&to) {
+        if !defined.contains(&a) && renamed(&a, &from, &to).is_none() {
+            sql.push_str(&insert_tag_sql(db, &a, None, actor));
+        }
+    }
+    for old in &renamed_tags {
+        let new = renamed(old, &from, &to).unwrap_or_default();
+        sql.push_str(&format!(
+            "INSERT OR IGNORE INTO answer_bank_tag (tag, description, created_by, created_at) SELECT {}, description, created_by, created_at FROM answer_bank_tag WHERE tag={};\nDELETE FROM answer_bank_tag WHERE tag={};\n",
+            db.q(&new),
+            db.q(old),
+            db.q(old)
+        ));
+        // A policy already on the new name wins.
+        sql.push_str(&format!(
+            "UPDATE OR IGNORE answer_bank_review_policy SET target={} WHERE scope='tag' AND target={};\nDELETE FROM answer_bank_review_policy WHERE scope='tag' AND target={};\n",
+            db.q(&escape_db_text(&new)),
+            db.q(&escape_db_text(old)),
+            db.q(&escape_db_text(old))
+        ));
+    }
+
+    let mut entry_ids = Vec::new();
+    for r in db.query_rows_tsv(&format!(
+        "SELECT {} FROM answer_bank WHERE vault_id={} ORDER BY entry_id ASC;",
+        ENTRY_COLUMNS,
+        db.q(&vault_id)
+    ))? {
+        let before = super::entry_from_row(&r)?;
+        let tags: Vec<String> = before.tags.iter().map(|t| stored_tag(t)).collect();
+        if !tags.iter().any(|t| renamed(t, &from, &to).is_some()) {
+            continue;
+        }
+        let tags = tags
+            .into_iter()
+            .map(|t| renamed(&t, &from, &to).unwrap_or(t))
+            .collect();
+        sql.push_str(&retag_entry_sql(db, &before, tags, actor, &mut events)?);
+        entry_ids.push(before.entry_id);
+    }
+
+    let mut evidence_ids = Vec::new();
+    for (evidence_id, tags) in evidence {
+        if !tags.iter().any(|t| renamed(t, &from, &to).is_some()) {
+            continue;
+        }
+        let tags: BTreeSet<String> = tags
+            .into_iter()
+            .map(|t| renamed(&t, &from, &to).unwrap_or(t))
+            .collect();
+        let tags_json = CanonicalJson::Array(tags.into_iter().map(CanonicalJson::String).collect());
+        sql.push_str(&format!(
+            "UPDATE evidence_item SET tags_json={} WHERE evidence_id={};\n",
+            db.q(&tags_json.encode()),
+            db.q(&evidence_id)
+        ));
+        evidence_ids.push(evidence_id);
+    }
+
+    events.push(db, actor, "AnswerBankTagRenamed", {
+        let mut o = CanonicalJson::object();
+        o.insert("from", CanonicalJson::String(from.clone()));
+        o.insert("to", CanonicalJson::String(to.clone()));
+        o.insert(
+            "renamed_tags",
+            CanonicalJson::Array(
+                renamed_tags
+                    .iter()
+                    .cloned()
+                    .map(CanonicalJson::String)
+                    .collect(),
+            ),
+        );
+        o.insert(
+            "entry_ids",
+            CanonicalJson::Array(
+                entry_ids
+                    .iter()
+                    .cloned()
+                    .map(CanonicalJson::String)
+                    .collect(),
+            ),
+        );
+        o.insert(
+            "evidence_ids",
+            CanonicalJson::Array(
+                evidence_ids
+                    .iter()
+                    .cloned()
+                    .map(CanonicalJson::String)
+                    .collect(),
+            ),
+        );
+        o
+    })?;
+    db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", sql, events.sql()))?;
+
+    Ok(TagRenameReport {
+        from,
+        to,
+        renamed_tags,
+        entry_ids,
+        evidence_ids,
+    })
+}
+
+/// Tags of the vault's live evidence items, normalized, by evidence id.
+fn evidence_tags(db: &SqliteDb, vault_id: &str) -> CoreResult<Vec<(String, Vec<String>)>> {
+    db.query_rows_tsv(&format!(
+        "SELECT evidence_id, tags_json FROM evidence_item WHERE vault_id={} AND deleted_at IS NULL ORDER BY evidence_id ASC;",
+        db.q(vault_id)
+    ))?
+    .into_iter()
+    .map(|r| {
+        let tags = parse_string_array_json(r.get(1).map(String::as_str).unwrap_or("[]"))?;
+        Ok((
+            r[0].clone(),
+            tags.iter().map(|t| stored_tag(t)).collect(),
+        ))
+    })
+    .collect()
+}
+
+/// SQL replacing an entry's tags without unapproving it, with a revision;
+/// the update event is pushed onto `events`.
+fn retag_entry_sql(
+    db: &SqliteDb,
+    before: &AnswerBankEntry,
+    tags: Vec<String>,
+    actor: &str,
+    events: &mut AuditEventBatch,
+) -> CoreResult<String> {
+    let after = patched_entry(
+        before,
+        AnswerBankUpdatePatch {
+            tags: Some(tags),
+            ..Default::default()
+        },
+        actor,
+    )?;
+    let entry_id = before.entry_id.as_str();
+    let revision = revision::next_revision(db, entry_id)?;
+    let tags_json = CanonicalJson::Array(
+        after
+            .tags
+            .iter()
+            .cloned()
+            .map(CanonicalJson::String)
+            .collect(),
+    )
+    .to_string();
+
+    let update_sql = format!(
+        "UPDATE answer_bank SET tags_json={}, content_hash={}, updated_at={} WHERE entry_id={};",
+        db.q(&tags_json),
+        db.q(&after.content_hash),
+        db.q(&after.updated_at),
+        db.q(entry_id)
+    );
+    let revision_sql =
+        revision::insert_revision_sql(db, entry_id, revision, RevisionKind::Updated, None, actor);
+    events.push(db, actor, "AnswerBankEntryUpdated", {
+        let mut o = CanonicalJson::object();
+        o.insert("entry_id", CanonicalJson::String(entry_id.to_string()));
+        o.insert(
+            "content_hash",
+            CanonicalJson::String(after.content_hash.clone()),
+        );
+        o.insert(
+            "changed_fields",
+            CanonicalJson::Array(
+                super::compute_changed_fields(before, &after)
+                    .into_iter()
+                    .map(CanonicalJson::String)
+                    .collect(),
+            ),
+        );
+        o.insert("revision", CanonicalJson::Number(revision));
+        o
+    })?;
+    Ok(format!("{}\n{}\n", update_sql, revision_sql))
+}
+
+fn list_defined(db: &SqliteDb) -> CoreResult<Vec<AnswerBankTag>> {
+    db.query_rows_tsv(
+        "SELECT tag, IFNULL(description,''), created_by, created_at FROM answer_bank_tag ORDER BY tag ASC;",
+    )?
+    .iter()
+    .map(|r| tag_from_row(r))
+    .collect()
+}
+
+fn get_defined(db: &SqliteDb, tag: &str) -> CoreResult<Option<AnswerBankTag>> {
+    db.query_rows_tsv(&format!(
+        "SELECT tag, IFNULL(description,''), created_by, created_at FROM answer_bank_tag WHERE tag={} LIMIT 1;",
+        db.q(tag)
+    ))?
+    .first()
+    .map(|r| tag_from_row(r))
+    .transpose()
+}
+
+fn tag_from_row(r: &[String]) -> CoreResult<AnswerBankTag> {
+    if r.len() < 4 {
+        return Err(CoreError::new(
+            CoreErrorCode::CorruptVault,
+            "unexpected answer_bank_tag row",
+        ));
+    }
+    Ok(AnswerBankTag {
+        tag: r[0].clone(),
+        description: if r[1].is_empty() {
+            None
+        } else {
+            Some(unescape_db_text(&r[1]))
+        },
+        created_by: unescape_db_text(&r[2]),
+        created_at: r[3].clone(),
+    })
+}
+
+fn insert_tag_sql(db: &SqliteDb, tag: &str, description: Option<&str>, actor: &str) -> String {
+    format!(
+        "INSERT INTO answer_bank_tag (tag, description, created_by, created_at) VALUES ({}, {}, {}, {});\n",
+        db.q(tag),
+        optional_text(db, description),
+        db.q(&escape_db_text(actor)),
+        db.q(DETERMINISTIC_TIMESTAMP_UTC)
+    )
+}
+
+fn optional_text(db: &SqliteDb, s: Option<&str>) -> String {
+    match s {
+        Some(s) => db.q(&escape_db_text(s)),
+        None => "NULL".to_string(),
+    }
+}
+
+fn usage(tag: &str, description: Option<String>, defined: bool) -> TagUsage {
+    TagUsage {
+        tag: tag.to_string(),
+        parent: tag
+            .rsplit_once(TAG_SEPARATOR)
+            .map(|(parent, _)| parent.to_string()),
+        description,
+        defined,
+        entry_count: 0,
+        total_count: 0,
+    }
+}
+
+/// `a/b/c` -> `a`, `a/b`
+fn ancestors(tag: &str) -> Vec<String> {
+    tag.match_indices(TAG_SEPARATOR)
+        .map(|(i, _)| tag[..i].to_string())
+        .collect()
+}
+
+pub(crate) fn is_descendant(tag: &str, of: &str) -> bool {
+    tag.len() > of.len() && tag.starts_with(of) && tag[of.len()..].starts_with(TAG_SEPARATOR)
+}
+
+/// New name of `tag` when `from` is renamed to `to`, if it is affected.
+fn renamed(tag: &str, from: &str, to: &str) -> Option<String> {
+    if tag == from {
+        Some(to.to_string())
+    } else if is_descendant(tag, from) {
+        Some(format!("{}{}", to, &tag[from.len()..]))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,276 @@
+//! Answer templating with vault variables.
+//!
+//! Answers may contain `{{name}}` placeholders (spaces inside the braces are
+//! allowed). They are stored as written and resolved from the vault's
+//! variables whenever an answer is shown: in match suggestions, rendered
+//! entries and rendered exports. Placeholders without a variable are left in
+//! the text and reported as unresolved. Setting or deleting a variable appends
+//! a `VaultVariableSet` / `VaultVariableDeleted` event with the old value.
+
+use super::{ab_get_entry, load_vault_id, AnswerBankEntry, ENTRY_COLUMNS};
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
+use crate::storage::db::{escape_db_text, unescape_db_text, SqliteDb};
+use std::collections::{BTreeSet, HashMap};
+
+const MAX_NAME_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultVariable {
+    pub name: String,
+    pub value: String,
+    pub updated_by: String,
+    pub updated_at: String,
+}
+
+/// Text with placeholders resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedText {
+    pub text: String,
+    /// Placeholder names without a variable, sorted and distinct.
+    pub unresolved: Vec<String>,
+}
+
+/// An entry's answers with placeholders resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedEntry {
+    pub entry_id: String,
+    pub answer_short: String,
+    pub answer_long: String,
+    /// Unresolved names across both answers, sorted and distinct.
+    pub unresolved: Vec<String>,
+}
+
+/// An entry whose answers use variables the vault does not define.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedVariables {
+    pub entry_id: String,
+    pub question_canonical: String,
+    pub names: Vec<String>,
+}
+
+/// Variable values by name, for rendering many answers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateVariables {
+    values: HashMap<String, String>,
+}
+
+impl TemplateVariables {
+    pub fn load(db: &SqliteDb) -> CoreResult<Self> {
+        Ok(Self {
+            values: ab_list_variables(db)?
+                .into_iter()
+                .map(|v| (v.name, v.value))
+                .collect(),
+        })
+    }
+
+    pub fn insert(&mut self, name: &str, value: &str) {
+        self.values.insert(name.to_string(), value.to_string());
+    }
+
+    /// Replace every `{{name}}` that has a value.
+    pub fn render(&self, text: &str) -> RenderedText {
+        let mut out = String::with_capacity(text.len());
+        let mut unresolved = BTreeSet::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let name = after
+                .find("}}")
+                .map(|end| (after[..end].trim(), end))
+                .filter(|(name, _)| valid_name(name));
+            match name {
+                Some((name, end)) => {
+                    match self.values.get(name) {
+                        Some(v) => out.push_str(v),
+                        None => {
+                            out.push_str(&rest[start..start + 2 + end + 2]);
+                            unresolved.insert(name.to_string());
+                        }
+                    }
+                    rest = &after[end + 2..];
+                }
+                None => {
+                    out.push_str("{{");
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+        RenderedText {
+            text: out,
+            unresolved: unresolved.into_iter().collect(),
+        }
+    }
+
+    pub fn render_entry(&self, entry: &AnswerBankEntry) -> RenderedEntry {
+        let short = self.render(&entry.answer_short);
+        let long = self.render(&entry.answer_long);
+        let unresolved: BTreeSet<String> = short
+            .unresolved
+            .into_iter()
+            .chain(long.unresolved)
+            .collect();
+        RenderedEntry {
+            entry_id: entry.entry_id.clone(),
+            answer_short: short.text,
+            answer_long: long.text,
+            unresolved: unresolved.into_iter().collect(),
+        }
+    }
+}
+
+pub fn ab_list_variables(db: &SqliteDb) -> CoreResult<Vec<VaultVariable>> {
+    let mut out = Vec::new();
+    for r in db.query_rows_tsv(
+        "SELECT name, value, updated_by, updated_at FROM vault_variable ORDER BY name ASC;",
+    )? {
+        if r.len() < 4 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected vault_variable row",
+            ));
+        }
+        out.push(VaultVariable {
+            name: r[0].clone(),
+            value: unescape_db_text(&r[1]),
+            updated_by: unescape_db_text(&r[2]),
+            updated_at: r[3].clone(),
+        });
+    }
+    Ok(out)
+}
+
+/// Create or change a variable.
+pub fn ab_set_variable(
+    db: &SqliteDb,
+    name: &str,
+    value: &str,
+    actor: &str,
+) -> CoreResult<VaultVariable> {
+    validator::validate_chain(db)?;
+
+    let name = name.trim();
+    if !valid_name(name) {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "variable name must start with a lowercase letter and contain only lowercase letters, digits and '_'",
+        ));
+    }
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "value is required",
+        ));
+    }
+    let previous = get_variable(db, name)?;
+    if let Some(unchanged) = previous.as_ref().filter(|p| p.value == value) {
+        return Ok(unchanged.clone());
+    }
+
+    let vault_id = load_vault_id(db)?;
+    let updated_at = DETERMINISTIC_TIMESTAMP_UTC.to_string();
+    let upsert_sql = format!(
+        "INSERT INTO vault_variable (name, value, updated_by, updated_at) VALUES ({}, {}, {}, {}) ON CONFLICT(name) DO UPDATE SET value=excluded.value, updated_by=excluded.updated_by, updated_at=excluded.updated_at;",
+        db.q(name),
+        db.q(&escape_db_text(value)),
+        db.q(&escape_db_text(actor)),
+        db.q(&updated_at)
+    );
+    let event_sql =
+        crate::storage::build_event_insert_sql(db, &vault_id, actor, "VaultVariableSet", {
+            let mut o = CanonicalJson::object();
+            o.insert("name", CanonicalJson::String(name.to_string()));
+            o.insert("value", CanonicalJson::String(value.to_string()));
+            if let Some(p) = &previous {
+                o.insert("previous_value", CanonicalJson::String(p.value.clone()));
+            }
+            o
+        })?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", upsert_sql, event_sql))?;
+
+    Ok(VaultVariable {
+        name: name.to_string(),
+        value: value.to_string(),
+        updated_by: actor.to_string(),
+        updated_at,
+    })
+}
+
+pub fn ab_delete_variable(db: &SqliteDb, name: &str, actor: &str) -> CoreResult<()> {
+    validator::validate_chain(db)?;
+
+    let name = name.trim();
+    let previous = get_variable(db, name)?
+        .ok_or_else(|| CoreError::new(CoreErrorCode::NotFound, "vault variable not found"))?;
+    let vault_id = load_vault_id(db)?;
+    let delete_sql = format!("DELETE FROM vault_variable WHERE name={};", db.q(name));
+    let event_sql =
+        crate::storage::build_event_insert_sql(db, &vault_id, actor, "VaultVariableDeleted", {
+            let mut o = CanonicalJson::object();
+            o.insert("name", CanonicalJson::String(name.to_string()));
+            o.insert("previous_value", CanonicalJson::String(previous.value));
+            o
+        })?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", delete_sql, event_sql))
+}
+
+/// An entry's answers rendered with the current variables.
+pub fn ab_render_entry(db: &SqliteDb, entry_id: &str) -> CoreResult<RenderedEntry> {
+    let entry = ab_get_entry(db, entry_id)?;
+    Ok(TemplateVariables::load(db)?.render_entry(&entry))
+}
+
+/// Entries (retired ones included) whose answers, in any locale, use
+/// undefined variables, by question.
+pub fn ab_unresolved_variables(db: &SqliteDb) -> CoreResult<Vec<UnresolvedVariables>> {
+    let variables = TemplateVariables::load(db)?;
+    let mut out = Vec::new();
+    for r in db.query_rows_tsv(&format!(
+        "SELECT {} FROM answer_bank WHERE answer_short LIKE '%{{{{%' OR answer_long LIKE '%{{{{%' OR translations_json LIKE '%{{{{%' ORDER BY question_canonical ASC, entry_id ASC;",
+        ENTRY_COLUMNS
+    ))? {
+        let entry = super::entry_from_row(&r)?;
+        let names: BTreeSet<String> = std::iter::once(entry.clone())
+            .chain(entry.translations.iter().map(|t| entry.localized(&t.locale)))
+            .flat_map(|e| variables.render_entry(&e).unresolved)
+            .collect();
+        if !names.is_empty() {
+            out.push(UnresolvedVariables {
+                entry_id: entry.entry_id,
+                question_canonical: entry.question_canonical,
+                names: names.into_iter().collect(),
+            });
+        }
+    }
+    Ok(out)
+}
+
+fn get_variable(db: &SqliteDb, name: &str) -> CoreResult<Option<VaultVariable>> {
+    Ok(db
+        .query_rows_tsv(&format!(
+            "SELECT value, updated_by, updated_at FROM vault_variable WHERE name={} LIMIT 1;",
+            db.q(name)
+        ))?
+        .into_iter()
+        .find(|r| r.len() >= 3)
+        .map(|r| VaultVariable {
+            name: name.to_string(),
+            value: unescape_db_text(&r[0]),
+            updated_by: unescape_db_text(&r[1]),
+            updated_at: r[2].clone(),
+        }))
+}
+
+fn valid_name(name: &str) -> bool {
+    name.len() <= MAX_NAME_LEN
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
@@ -2,16 +2,23 @@ use crate::audit::hasher;
 use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
 use crate::storage::db::SqliteDb;
 
-const GENESIS_PREV_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+pub(crate) const GENESIS_PREV_HASH: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Events hashed over their payload JSON (written before migration 0023).
+pub const HASH_FORMAT_PAYLOAD: i64 = 1;
+/// Events hashed over the SHA-256 of their payload JSON, so the hash can be
+/// checked without the payload.
+pub const HASH_FORMAT_PAYLOAD_SHA256: i64 = 2;
 
 pub fn validate_chain(db: &SqliteDb) -> CoreResult<()> {
     let rows = db.query_rows_tsv(
-        "SELECT seq, event_id, vault_id, occurred_at, actor, event_type, payload_json, prev_hash, hash FROM audit_event ORDER BY seq ASC;",
+        "SELECT seq, event_id, vault_id, occurred_at, actor, event_type, payload_json, prev_hash, hash, hash_format FROM audit_event ORDER BY seq ASC;",
     )?;
 
     let mut prev = GENESIS_PREV_HASH.to_string();
     for row in rows {
-        if row.len() != 9 {
+        if row.len() != 10 {
             return Err(CoreError::new(
                 CoreErrorCode::CorruptVault,
                 "unexpected audit row shape",
@@ -26,6 +33,7 @@ pub fn validate_chain(db: &SqliteDb) -> CoreResult<()> {
         let payload_json = &row[6];
         let prev_hash = &row[7];
         let hash = &row[8];
+        let hash_format: i64 = row[9].parse().unwrap_or(0);
 
         if prev_hash != &prev {
             return Err(CoreError::new(
@@ -34,15 +42,32 @@ pub fn validate_chain(db: &SqliteDb) -> CoreResult<()> {
             ));
         }
 
-        let canonical = canonical_event_string(
-            event_id,
-            vault_id,
-            occurred_at,
-            actor,
-            event_type,
-            payload_json,
-            prev_hash,
-        );
+        let canonical = match hash_format {
+            HASH_FORMAT_PAYLOAD => canonical_event_string(
+                event_id,
+                vault_id,
+                occurred_at,
+                actor,
+                event_type,
+                payload_json,
+                prev_hash,
+            ),
+            HASH_FORMAT_PAYLOAD_SHA256 => canonical_event_string_v2(
+                event_id,
+                vault_id,
+                occurred_at,
+                actor,
+                event_type,
+                &hasher::sha256_hex_bytes(payload_json.as_bytes())?,
+                prev_hash,
+            ),
+            _ => {
+                return Err(CoreError::new(
+                    CoreErrorCode::CorruptVault,
+                    format!("unknown hash_format at seq {}", seq),
+                ))
+            }
+        };
         let computed = hasher::sha256_hex_bytes(canonical.as_bytes())?;
         if &computed != hash {
             return Err(CoreError::new(
@@ -73,3 +98,20 @@ pub fn canonical_event_string(
         event_id, vault_id, occurred_at, actor, event_type, payload_json, prev_hash
     )
 }
+
+/// The string a format 2 event is hashed over: the payload's SHA-256 takes
+/// the place of the payload JSON.
+pub fn canonical_event_string_v2(
+    event_id: &str,
+    vault_id: &str,
+    occurred_at: &str,
+    actor: &str,
+    event_type: &str,
+    payload_sha256: &str,
+    prev_hash: &str,
+) -> String {
+    format!(
+        "event_id={}\nvault_id={}\noccurred_at={}\nactor={}\nevent_type={}\npayload_sha256={}\nprev_hash={}\n",
+        event_id, vault_id, occurred_at, actor, event_type, payload_sha256, prev_hash
+    )
+}
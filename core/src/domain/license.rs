@@ -132,7 +132,7 @@ pub fn verify_license_with_pubkey(license: &LicenseFile, pubkey: [u8; 32]) -> Co
     Ok(())
 }
 
-fn decode_hex_32(s: &str) -> Result<[u8; 32], String> {
+pub(crate) fn decode_hex_32(s: &str) -> Result<[u8; 32], String> {
     let v = decode_hex(s)?;
     if v.len() != 32 {
         return Err(format!("expected 32 bytes hex, got {}", v.len()));
@@ -142,7 +142,7 @@ fn decode_hex_32(s: &str) -> Result<[u8; 32], String> {
     Ok(out)
 }
 
-fn decode_hex_64(s: &str) -> Result<[u8; 64], String> {
+pub(crate) fn decode_hex_64(s: &str) -> Result<[u8; 64], String> {
     let v = decode_hex(s)?;
     if v.len() != 64 {
         return Err(format!("expected 64 bytes hex, got {}", v.len()));
@@ -1,4 +1,106 @@
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+
 /// Phase 1 determinism favors stable timestamps.
 ///
 /// We use a fixed UTC timestamp string for export pack generation.
 pub const DETERMINISTIC_TIMESTAMP_UTC: &str = "2000-01-01T00:00:00Z";
+
+/// Today's UTC date as `YYYY-MM-DD`.
+///
+/// Only for date-based policies (e.g. answer review due dates), never for
+/// stored or exported timestamps.
+pub fn today_utc_date() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    format_date_days(secs.div_euclid(86_400))
+}
+
+/// Days since 1970-01-01 for a `YYYY-MM-DD` date or a timestamp that starts
+/// with one (e.g. `2024-03-01T12:00:00Z`).
+pub fn parse_date_days(s: &str) -> CoreResult<i64> {
+    let invalid = || {
+        CoreError::new(
+            CoreErrorCode::ValidationError,
+            format!("invalid date (expected YYYY-MM-DD): {s}"),
+        )
+    };
+    let s = s.trim();
+    let date = s.get(..10).ok_or_else(invalid)?;
+    if s.len() > 10 && !s[10..].starts_with('T') {
+        return Err(invalid());
+    }
+    let b = date.as_bytes();
+    if b[4] != b'-' || b[7] != b'-' {
+        return Err(invalid());
+    }
+    let num = |r: std::ops::Range<usize>| -> CoreResult<i64> {
+        let part = &date[r];
+        if !part.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        part.parse().map_err(|_| invalid())
+    };
+    let (y, m, d) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    if !(1..=12).contains(&m) || d < 1 || d > days_in_month(y, m) {
+        return Err(invalid());
+    }
+    Ok(days_from_civil(y, m, d))
+}
+
+/// `YYYY-MM-DD` for a day count from [`parse_date_days`].
+pub fn format_date_days(days: i64) -> String {
+    // Inverse of days_from_civil (H. Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = if m > 2 { m - 3 } else { m + 9 };
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn days_in_month(y: i64, m: i64) -> i64 {
+    match m {
+        2 if (y % 4 == 0 && y % 100 != 0) || y % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_days_round_trip() {
+        assert_eq!(parse_date_days("1970-01-01").unwrap(), 0);
+        assert_eq!(parse_date_days("2000-01-01T00:00:00Z").unwrap(), 10_957);
+        assert_eq!(
+            parse_date_days("2024-03-01").unwrap() - parse_date_days("2024-02-28").unwrap(),
+            2
+        );
+        for s in ["1999-12-31", "2024-02-29", "2100-03-01"] {
+            assert_eq!(format_date_days(parse_date_days(s).unwrap()), s);
+        }
+        for bad in ["", "2024-3-1", "2023-02-29", "2024-13-01", "2024-01-01 x"] {
+            assert!(parse_date_days(bad).is_err(), "{bad}");
+        }
+    }
+}
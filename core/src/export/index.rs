@@ -1,5 +1,31 @@
 use crate::domain::errors::CoreResult;
+use crate::export::manifest::{AuditHead, ManifestFile};
 use crate::storage::EvidenceItem;
+use std::collections::HashMap;
+
+/// A questionnaire as listed in `index.html`.
+#[derive(Debug, Clone)]
+pub struct IndexQuestionnaire {
+    pub import_id: String,
+    pub source_filename: String,
+    pub language: String,
+    /// Path of the questionnaire's JSON file in the pack.
+    pub path: String,
+    pub questions: Vec<IndexQuestion>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexQuestion {
+    pub row_number: i64,
+    pub question_text: String,
+    pub status: String,
+    /// Final answer text, if answered.
+    pub answer: Option<String>,
+    /// Answered with an answer bank entry outside the pack's scope.
+    pub answer_withheld: bool,
+    /// Evidence linked from the answer bank entry used.
+    pub evidence_ids: Vec<String>,
+}
 
 pub fn render_index_md(evidence: &[EvidenceItem]) -> CoreResult<String> {
     let mut out = String::new();
@@ -19,3 +45,161 @@ pub fn render_index_md(evidence: &[EvidenceItem]) -> CoreResult<String> {
 
     Ok(out)
 }
+
+/// Static HTML index of the pack, readable in a browser straight from the
+/// unpacked zip: no scripts, no external assets, links relative to the pack
+/// root. `files` are the manifest entries of everything but the indexes
+/// themselves.
+pub fn render_index_html(
+    questionnaires: &[IndexQuestionnaire],
+    evidence: &[EvidenceItem],
+    files: &[ManifestFile],
+    audit_head: &AuditHead,
+) -> CoreResult<String> {
+    let mut evidence: Vec<&EvidenceItem> = evidence.iter().collect();
+    evidence.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    let by_id: HashMap<&str, &EvidenceItem> = evidence
+        .iter()
+        .map(|e| (e.evidence_id.as_str(), *e))
+        .collect();
+    let mut files: Vec<&ManifestFile> = files.iter().collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>Export Index</title>\n<style>\n");
+    out.push_str(STYLE);
+    out.push_str("</style>\n</head>\n<body>\n<h1>Export Index</h1>\n");
+    out.push_str(&format!(
+        "<p>Generated from audit event {} (<code>{}</code>). File hashes are SHA-256; <code>manifest.json</code> is signed in <code>manifest.sig</code>.</p>\n",
+        audit_head.seq,
+        escape(&audit_head.hash)
+    ));
+
+    out.push_str("<nav>\n<ul>\n");
+    for q in questionnaires {
+        out.push_str(&format!(
+            "<li><a href=\"#q-{}\">{}</a></li>\n",
+            escape(&q.import_id),
+            escape(&q.source_filename)
+        ));
+    }
+    out.push_str("<li><a href=\"#evidence\">Evidence</a></li>\n");
+    out.push_str("<li><a href=\"#files\">Files</a></li>\n</ul>\n</nav>\n");
+
+    for q in questionnaires {
+        out.push_str(&format!(
+            "<section id=\"q-{}\">\n<h2>{}</h2>\n",
+            escape(&q.import_id),
+            escape(&q.source_filename)
+        ));
+        out.push_str(&format!(
+            "<p>Import <code>{}</code>, language <code>{}</code>, <a href=\"{}\">{}</a></p>\n",
+            escape(&q.import_id),
+            escape(&q.language),
+            href(&q.path),
+            escape(&q.path)
+        ));
+        out.push_str("<table>\n<tr><th>Row</th><th>Question</th><th>Status</th><th>Answer</th><th>Evidence</th></tr>\n");
+        for question in &q.questions {
+            let mut links = Vec::new();
+            for id in &question.evidence_ids {
+                links.push(match by_id.get(id.as_str()) {
+                    Some(e) => format!(
+                        "<a href=\"{}\">{}</a>",
+                        href(&e.relative_path),
+                        escape(&e.filename)
+                    ),
+                    None => format!(
+                        "<span class=\"missing\">{} (not in this pack)</span>",
+                        escape(id)
+                    ),
+                });
+            }
+            out.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                question.row_number,
+                escape(&question.question_text),
+                escape(&question.status),
+                if question.answer_withheld {
+                    "<span class=\"missing\">withheld (outside this pack's scope)</span>"
+                        .to_string()
+                } else {
+                    question.answer.as_deref().map(escape).unwrap_or_default()
+                },
+                links.join("<br>")
+            ));
+        }
+        out.push_str("</table>\n</section>\n");
+    }
+
+    out.push_str("<section id=\"evidence\">\n<h2>Evidence</h2>\n<table>\n");
+    out.push_str("<tr><th>File</th><th>Type</th><th>Size</th><th>SHA-256</th><th>Source</th><th>Tags</th><th>Added</th><th>Notes</th></tr>\n");
+    for e in &evidence {
+        out.push_str(&format!(
+            "<tr id=\"e-{}\"><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape(&e.evidence_id),
+            href(&e.relative_path),
+            escape(&e.filename),
+            escape(&e.content_type),
+            e.byte_size,
+            escape(&e.sha256),
+            escape(&e.source),
+            escape(&e.tags.join(", ")),
+            escape(&e.created_at),
+            e.notes.as_deref().map(escape).unwrap_or_default()
+        ));
+    }
+    out.push_str("</table>\n</section>\n");
+
+    out.push_str("<section id=\"files\">\n<h2>Files</h2>\n<table>\n");
+    out.push_str("<tr><th>Path</th><th>Size</th><th>SHA-256</th></tr>\n");
+    for f in files {
+        out.push_str(&format!(
+            "<tr><td><a href=\"{}\">{}</a></td><td>{}</td><td><code>{}</code></td></tr>\n",
+            href(&f.path),
+            escape(&f.path),
+            f.size,
+            escape(&f.sha256)
+        ));
+    }
+    out.push_str("</table>\n</section>\n</body>\n</html>\n");
+
+    Ok(out)
+}
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.5em; text-align: left; vertical-align: top; }
+th { background: #f0f0f0; }
+code { font-size: 0.85em; word-break: break-all; }
+.missing { color: #999; }
+";
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Percent-encode a pack path for use as a relative link.
+fn href(path: &str) -> String {
+    let mut out = String::new();
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~/".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
+    }
+    out
+}
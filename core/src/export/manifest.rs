@@ -1,5 +1,6 @@
 use crate::audit::canonical::CanonicalJson;
 use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::export::scope::ExportScope;
 use crate::util::json::JsonValue;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -9,10 +10,44 @@ pub struct ManifestFile {
     pub size: i64,
 }
 
+/// A named group of pack files with its own format version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestSection {
+    pub name: String,
+    pub version: i64,
+    /// Paths of the section's files, each also listed in `files`.
+    pub files: Vec<String>,
+}
+
+/// Last audit event when the pack was generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditHead {
+    pub seq: i64,
+    pub hash: String,
+}
+
+/// Redaction profile a pack was generated with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestRedaction {
+    pub profile: String,
+    /// SHA-256 of the profile's canonical JSON.
+    pub profile_sha256: String,
+    /// Parts of the pack the profile's text rules do not reach.
+    pub unredacted: Vec<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExportManifest {
     pub version: i64,
     pub files: Vec<ManifestFile>,
+    /// Empty in version 1 manifests, which predate sections.
+    pub sections: Vec<ManifestSection>,
+    /// Set from version 3 on.
+    pub audit_head: Option<AuditHead>,
+    /// What the pack was limited to; set from version 4 on.
+    pub scope: Option<ExportScope>,
+    /// Set from version 5 on when the pack is redacted.
+    pub redaction: Option<ManifestRedaction>,
 }
 
 impl ExportManifest {
@@ -20,6 +55,37 @@ impl ExportManifest {
         let mut root = CanonicalJson::object();
         root.insert("version", CanonicalJson::Number(self.version));
 
+        if let Some(head) = &self.audit_head {
+            let mut o = CanonicalJson::object();
+            o.insert("seq", CanonicalJson::Number(head.seq));
+            o.insert("hash", CanonicalJson::String(head.hash.clone()));
+            root.insert("audit_head", o);
+        }
+
+        if let Some(r) = &self.redaction {
+            let mut o = CanonicalJson::object();
+            o.insert("profile", CanonicalJson::String(r.profile.clone()));
+            o.insert(
+                "profile_sha256",
+                CanonicalJson::String(r.profile_sha256.clone()),
+            );
+            o.insert(
+                "unredacted",
+                CanonicalJson::Array(
+                    r.unredacted
+                        .iter()
+                        .cloned()
+                        .map(CanonicalJson::String)
+                        .collect(),
+                ),
+            );
+            root.insert("redaction", o);
+        }
+
+        if let Some(scope) = &self.scope {
+            root.insert("scope", scope.to_canonical_json());
+        }
+
         let mut files = Vec::new();
         for f in &self.files {
             let mut o = CanonicalJson::object();
@@ -29,6 +95,27 @@ impl ExportManifest {
             files.push(o);
         }
         root.insert("files", CanonicalJson::Array(files));
+
+        if !self.sections.is_empty() {
+            let mut sections = Vec::new();
+            for sec in &self.sections {
+                let mut o = CanonicalJson::object();
+                o.insert("name", CanonicalJson::String(sec.name.clone()));
+                o.insert("version", CanonicalJson::Number(sec.version));
+                o.insert(
+                    "files",
+                    CanonicalJson::Array(
+                        sec.files
+                            .iter()
+                            .cloned()
+                            .map(CanonicalJson::String)
+                            .collect(),
+                    ),
+                );
+                sections.push(o);
+            }
+            root.insert("sections", CanonicalJson::Array(sections));
+        }
         root.encode()
     }
 
@@ -53,6 +140,64 @@ impl ExportManifest {
             });
         }
 
-        Ok(ExportManifest { version, files })
+        let mut sections = Vec::new();
+        if let Some(sections_v) = obj.get("sections") {
+            for item in sections_v.as_array()? {
+                let o = item.as_object()?;
+                sections.push(ManifestSection {
+                    name: o.get_string("name")?,
+                    version: o.get_i64("version")?,
+                    files: o
+                        .get_array("files")?
+                        .iter()
+                        .map(|f| f.as_string())
+                        .collect::<CoreResult<_>>()?,
+                });
+            }
+        }
+
+        let audit_head = match obj.get("audit_head") {
+            Some(v) => {
+                let o = v.as_object()?;
+                Some(AuditHead {
+                    seq: o.get_i64("seq")?,
+                    hash: o.get_string("hash")?,
+                })
+            }
+            None => None,
+        };
+
+        let scope = match obj.get("scope") {
+            Some(v) => Some(ExportScope::from_json_value(v)?),
+            None => None,
+        };
+
+        let redaction = match obj.get("redaction") {
+            Some(v) => {
+                let o = v.as_object()?;
+                Some(ManifestRedaction {
+                    profile: o.get_string("profile")?,
+                    profile_sha256: o.get_string("profile_sha256")?,
+                    unredacted: match o.get("unredacted") {
+                        Some(v) => v
+                            .as_array()?
+                            .iter()
+                            .map(|s| s.as_string())
+                            .collect::<CoreResult<_>>()?,
+                        None => Vec::new(),
+                    },
+                })
+            }
+            None => None,
+        };
+
+        Ok(ExportManifest {
+            version,
+            files,
+            sections,
+            audit_head,
+            scope,
+            redaction,
+        })
     }
 }
@@ -2,3 +2,7 @@ pub mod determinism;
 pub mod index;
 pub mod manifest;
 pub mod pack;
+pub mod redaction;
+pub mod scope;
+pub mod sections;
+pub mod signing;
@@ -1,65 +1,157 @@
 use crate::audit::hasher;
 use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
-use crate::export::index;
-use crate::export::manifest::{ExportManifest, ManifestFile};
+use crate::export::manifest::{AuditHead, ExportManifest, ManifestFile};
+use crate::export::redaction::PackRedaction;
+use crate::export::scope::ExportScope;
+use crate::export::signing::{self, ManifestSignature};
+use crate::export::{index, sections};
 use crate::storage::db::SqliteDb;
-use crate::storage::{vault_db_path, EvidenceItem};
+use crate::storage::{vault_db_path, AuditEventBatch, EvidenceItem};
 use crate::util::{fs, zip};
+use ed25519_dalek::SigningKey;
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 
+const INDEX_MD: &str = "index.md";
+const INDEX_HTML: &str = "index.html";
+
+/// Version 2 added sections, version 3 the audit head and `manifest.sig`,
+/// version 4 the scope, version 5 the redaction profile.
+pub const MANIFEST_VERSION: i64 = 5;
+
 pub struct ExportPack {
     pub zip_path: PathBuf,
     pub manifest: ExportManifest,
+    pub signature: ManifestSignature,
 }
 
-pub fn generate_pack(vault_root: &Path, out_zip: &Path) -> CoreResult<ExportPack> {
+/// Write a signed pack of what `scope` selects (see [`ExportScope`]) to
+/// `out_zip`, redacted by the named profile if one is given (see
+/// [`crate::export::redaction`]).
+pub fn generate_pack(
+    vault_root: &Path,
+    out_zip: &Path,
+    scope: &ExportScope,
+    redaction_profile: Option<&str>,
+    actor: &str,
+) -> CoreResult<ExportPack> {
+    let scope = scope.normalized()?;
     let db = SqliteDb::new(&vault_db_path(vault_root));
     db.migrate()?;
 
     crate::audit::validator::validate_chain(&db)?;
+    let mut redaction = redaction_profile
+        .map(|name| PackRedaction::load(&db, name))
+        .transpose()?;
 
-    let evidence = load_evidence(&db)?;
+    // A new key and the redaction profile are only recorded once the pack is
+    // written, so a failed export leaves no trace in the audit log.
+    let (signing_key, new_key) = match signing::load_signing_key(&db)? {
+        Some(key) => (key, false),
+        None => (signing::generate_signing_key()?, true),
+    };
+    let audit_head = load_audit_head(&db)?;
+
+    let mut content = scope.select(&db, load_evidence(&db)?)?;
+    // Redaction can rename evidence in the pack; files are copied from where
+    // the vault keeps them.
+    let vault_paths: HashMap<String, String> = content
+        .evidence
+        .iter()
+        .map(|e| (e.evidence_id.clone(), e.relative_path.clone()))
+        .collect();
+    let mut bank_entries = None;
+    if let Some(r) = redaction.as_mut() {
+        content.evidence = r.filter_evidence(content.evidence)?;
+        bank_entries = Some(r.redact_entries(&content.entries)?);
+    }
+    let evidence = &content.evidence;
 
     let staging = make_temp_dir("cs_export_staging")?;
 
     // Copy evidence files into staging under their relative paths.
-    for e in &evidence {
-        let src = vault_root.join(&e.relative_path);
+    for e in evidence {
+        let src = vault_root.join(&vault_paths[&e.evidence_id]);
         let dst = staging.join(&e.relative_path);
         fs::atomic_copy_to(&src, &dst)?;
     }
 
-    // Write index.md
-    let index_md = index::render_index_md(&evidence)?;
-    fs::write_string(&staging.join("index.md"), &index_md)?;
+    // Questionnaires render from the original entries and redact the result.
+    let questionnaires = sections::write_questionnaires(
+        &db,
+        &staging,
+        &content.imports,
+        &content.entries,
+        redaction.as_mut(),
+    )?;
+    let mut pack_sections = vec![
+        sections::section(
+            sections::EVIDENCE,
+            evidence
+                .iter()
+                .map(|e| e.relative_path.clone())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        ),
+        sections::write_answer_bank(
+            &db,
+            &staging,
+            bank_entries.as_deref().unwrap_or(&content.entries),
+        )?,
+        questionnaires.questionnaires,
+        questionnaires.column_maps,
+    ];
+    if scope.includes_audit_log() {
+        pack_sections.push(sections::write_audit_log(
+            &db,
+            &staging,
+            redaction.as_mut(),
+        )?);
+    }
+    if let Some(r) = &redaction {
+        pack_sections.push(r.write_report(&staging)?);
+    }
 
     // Build manifest entries (excluding manifest.json itself).
     let mut files = Vec::new();
+    for path in pack_sections.iter().flat_map(|s| &s.files) {
+        files.push(manifest_file(&staging, path)?);
+    }
 
-    // index
-    let idx_path = staging.join("index.md");
-    let idx_meta = std::fs::metadata(&idx_path)?;
-    files.push(ManifestFile {
-        path: "index.md".to_string(),
-        sha256: hasher::sha256_hex_file(&idx_path)?,
-        size: idx_meta.len() as i64,
-    });
-
-    for e in &evidence {
-        let p = staging.join(&e.relative_path);
-        let meta = std::fs::metadata(&p)?;
-        files.push(ManifestFile {
-            path: e.relative_path.clone(),
-            sha256: hasher::sha256_hex_file(&p)?,
-            size: meta.len() as i64,
-        });
+    // The indexes come last: index.html lists every other file's hash.
+    let index_md = index::render_index_md(evidence)?;
+    fs::write_string(&staging.join(INDEX_MD), &index_md)?;
+    let index_html =
+        index::render_index_html(&questionnaires.index, evidence, &files, &audit_head)?;
+    fs::write_string(&staging.join(INDEX_HTML), &index_html)?;
+    for path in [INDEX_HTML, INDEX_MD] {
+        files.push(manifest_file(&staging, path)?);
     }
+    pack_sections.push(sections::section(
+        sections::INDEX,
+        vec![INDEX_HTML.to_string(), INDEX_MD.to_string()],
+    ));
+    pack_sections.sort_by(|a, b| a.name.cmp(&b.name));
 
     files.sort_by(|a, b| a.path.cmp(&b.path));
 
-    let manifest = ExportManifest { version: 1, files };
+    let manifest = ExportManifest {
+        version: MANIFEST_VERSION,
+        files,
+        sections: pack_sections,
+        audit_head: Some(audit_head),
+        scope: Some(scope),
+        redaction: redaction.as_ref().map(PackRedaction::manifest_entry),
+    };
 
-    fs::write_string(&staging.join("manifest.json"), &manifest.to_json_string())?;
+    let manifest_json = manifest.to_json_string();
+    let signature = signing::sign_manifest(&signing_key, &manifest_json);
+    fs::write_string(&staging.join("manifest.json"), &manifest_json)?;
+    fs::write_string(
+        &staging.join(signing::SIGNATURE_FILE),
+        &signature.to_json_string(),
+    )?;
 
     zip::touch_tree_deterministic(&staging)?;
 
@@ -73,23 +165,55 @@ pub fn generate_pack(vault_root: &Path, out_zip: &Path) -> CoreResult<ExportPack
     }
 
     zip::zip_dir_deterministic(&staging, out_zip)?;
+    drop(staging);
 
-    // best-effort cleanup
-    let _ = std::fs::remove_dir_all(&staging);
+    if let Err(e) = record_export(&db, &signing_key, new_key, redaction.as_ref(), actor) {
+        let _ = std::fs::remove_file(out_zip);
+        return Err(e);
+    }
 
     Ok(ExportPack {
         zip_path: out_zip.to_path_buf(),
         manifest,
+        signature,
     })
 }
 
-pub fn validate_pack(zip_path: &Path) -> CoreResult<()> {
+/// Check a pack: its manifest signature against `trusted_keys` (hex encoded
+/// Ed25519 public keys), every file against the manifest, then the sections.
+/// Returns the verified signature.
+pub fn validate_pack(zip_path: &Path, trusted_keys: &[String]) -> CoreResult<ManifestSignature> {
     let out_dir = make_temp_dir("cs_export_validate")?;
     zip::unzip_to_dir(zip_path, &out_dir)?;
 
     let manifest_path = out_dir.join("manifest.json");
     let manifest_str = fs::read_to_string(&manifest_path)?;
+    let signature_path = out_dir.join(signing::SIGNATURE_FILE);
+    if !signature_path.exists() {
+        return Err(CoreError::new(
+            CoreErrorCode::PermissionDenied,
+            "pack is not signed",
+        ));
+    }
+    let signature = ManifestSignature::from_json_str(&fs::read_to_string(&signature_path)?)?;
+    signing::verify_manifest(&signature, &manifest_str, trusted_keys)?;
     let manifest = ExportManifest::from_json_str(&manifest_str)?;
+    if manifest.audit_head.is_none() {
+        return Err(CoreError::new(
+            CoreErrorCode::CorruptVault,
+            "manifest missing audit_head",
+        ));
+    }
+
+    for path in zip::list_files_sorted(&out_dir)? {
+        let known = path == "manifest.json" || path == signing::SIGNATURE_FILE;
+        if !known && !manifest.files.iter().any(|f| f.path == path) {
+            return Err(CoreError::new(
+                CoreErrorCode::HashMismatch,
+                format!("file {} is not in the manifest", path),
+            ));
+        }
+    }
 
     for f in &manifest.files {
         let p = out_dir.join(&f.path);
@@ -108,8 +232,65 @@ pub fn validate_pack(zip_path: &Path) -> CoreResult<()> {
         }
     }
 
-    let _ = std::fs::remove_dir_all(&out_dir);
-    Ok(())
+    sections::validate_sections(&out_dir, &manifest)?;
+    Ok(signature)
+}
+
+/// Record what a written pack used: a newly created signing key and the
+/// redaction profile.
+fn record_export(
+    db: &SqliteDb,
+    signing_key: &SigningKey,
+    new_key: bool,
+    redaction: Option<&PackRedaction>,
+    actor: &str,
+) -> CoreResult<()> {
+    if !new_key && redaction.is_none() {
+        return Ok(());
+    }
+    crate::audit::validator::validate_chain(db)?;
+    let vault_id = db
+        .query_optional_string("SELECT vault_id FROM vault LIMIT 1;")?
+        .ok_or_else(|| CoreError::new(CoreErrorCode::CorruptVault, "missing vault row"))?;
+    let mut events = AuditEventBatch::new(db, &vault_id)?;
+    let mut sql = String::new();
+    if new_key {
+        sql.push_str(&signing::store_new_key_sql(
+            db,
+            signing_key,
+            actor,
+            &mut events,
+        )?);
+        sql.push('\n');
+    }
+    if let Some(r) = redaction {
+        r.record_use(db, actor, &mut events)?;
+    }
+    db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", sql, events.sql()))
+}
+
+fn manifest_file(staging: &Path, path: &str) -> CoreResult<ManifestFile> {
+    let p = staging.join(path);
+    let meta = std::fs::metadata(&p)?;
+    Ok(ManifestFile {
+        path: path.to_string(),
+        sha256: hasher::sha256_hex_file(&p)?,
+        size: meta.len() as i64,
+    })
+}
+
+fn load_audit_head(db: &SqliteDb) -> CoreResult<AuditHead> {
+    let rows = db.query_rows_tsv("SELECT seq, hash FROM audit_event ORDER BY seq DESC LIMIT 1;")?;
+    match rows.first() {
+        Some(r) if r.len() == 2 => Ok(AuditHead {
+            seq: r[0].parse().unwrap_or(0),
+            hash: r[1].clone(),
+        }),
+        _ => Err(CoreError::new(
+            CoreErrorCode::CorruptVault,
+            "audit log is empty",
+        )),
+    }
 }
 
 fn load_evidence(db: &SqliteDb) -> CoreResult<Vec<EvidenceItem>> {
@@ -134,7 +315,7 @@ fn load_evidence(db: &SqliteDb) -> CoreResult<Vec<EvidenceItem>> {
             byte_size: r[5].parse().unwrap_or(0),
             sha256: r[6].clone(),
             source: r[7].clone(),
-            tags: vec![],
+            tags: crate::answer_bank::parse_string_array_json(&r[8])?,
             created_at: r[9].clone(),
             notes: if r[10].is_empty() {
                 None
@@ -146,15 +327,37 @@ fn load_evidence(db: &SqliteDb) -> CoreResult<Vec<EvidenceItem>> {
     Ok(out)
 }
 
-fn make_temp_dir(prefix: &str) -> CoreResult<PathBuf> {
+/// A scratch directory, removed (best effort) when dropped, so staging and
+/// validation leave nothing behind on any return path.
+struct TempDir(PathBuf);
+
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn make_temp_dir(prefix: &str) -> CoreResult<TempDir> {
+    use std::sync::atomic::{AtomicU64, Ordering};
     use std::time::{SystemTime, UNIX_EPOCH};
 
+    // Calls within the same millisecond must not share (and remove) a dir.
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
     let ts = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map_err(|e| CoreError::new(CoreErrorCode::InternalError, e.to_string()))?
         .as_millis();
 
-    let dir = std::env::temp_dir().join(format!("{}_{}_{}", prefix, std::process::id(), ts));
+    let dir = std::env::temp_dir().join(format!("{}_{}_{}_{}", prefix, std::process::id(), ts, n));
     std::fs::create_dir_all(&dir)?;
-    Ok(dir)
+    Ok(TempDir(dir))
 }
@@ -0,0 +1,437 @@
+//! Redaction profiles in the vault and how packs apply them.
+//!
+//! A pack generated with a profile (see [`RedactionProfile`]) differs from an
+//! unredacted one as follows:
+//!
+//! - evidence with an excluded tag is left out;
+//! - text rules run on answer bank entries (questions, answers in every
+//!   locale and source), on questionnaire questions, answers (after variables
+//!   are rendered) and source file names, and on evidence file names, sources
+//!   and paths in the pack; redacted entries carry the content hash of what
+//!   the pack contains;
+//! - notes (entries and evidence) and owners (entry owners and question
+//!   assignees, including `owner` and `assignee` fields of audit event
+//!   payloads) are removed when the profile says so;
+//! - text rules also run on the string values of audit event payloads, which
+//!   are re-encoded as canonical JSON; keys, numbers and the JSON syntax are
+//!   left alone. A redacted event is marked `payload_redacted` and keeps its
+//!   stored hash and the SHA-256 of its original payload, which the hash
+//!   covers. Events written before hashes covered the payload's SHA-256 (hash
+//!   format 1) can then only be checked by their links to their neighbours.
+//!
+//! Text rules do not reach what [`UNREDACTED`] lists: the actors of audit
+//! events, which their hashes cover, the contents of evidence files, which
+//! are copied as they are, tags and column maps.
+//!
+//! Every redaction is listed, without the removed text, in
+//! `redactions/redaction_report.json`, which also carries [`UNREDACTED`].
+//! The manifest and an `ExportRedacted` audit event, appended once the pack
+//! is written, record the profile's name and the SHA-256 of its canonical
+//! JSON rather than its rules, which may themselves name what is hidden.
+
+use crate::answer_bank::{self, AnswerBankEntry};
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::{hasher, validator};
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
+use crate::export::manifest::{ManifestRedaction, ManifestSection};
+use crate::export::sections;
+use crate::questionnaire::QuestionnaireImport;
+use crate::storage::db::{escape_db_text, unescape_db_text, SqliteDb};
+use crate::storage::{AuditEventBatch, EvidenceItem};
+use crate::util::fs;
+use crate::util::json::JsonValue;
+use crate::util::redact::{RedactionProfile, Redactor, RuleHit};
+use std::path::Path;
+
+pub const REDACTION_REPORT_JSON: &str = "redactions/redaction_report.json";
+
+/// Parts of a pack text rules do not reach, as recorded in the manifest and
+/// the report.
+pub const UNREDACTED: &[&str] = &["audit_actors", "column_maps", "evidence_contents", "tags"];
+
+/// Audit payload fields naming people, removed with owners.
+const OWNER_FIELDS: &[&str] = &["assignee", "owner"];
+
+/// Save a profile, replacing any profile with the same name.
+pub fn redaction_save_profile(
+    db: &SqliteDb,
+    profile: &RedactionProfile,
+    actor: &str,
+) -> CoreResult<RedactionProfile> {
+    validator::validate_chain(db)?;
+    let profile = profile.normalized()?;
+    let vault_id = load_vault_id(db)?;
+    let profile_json = profile.to_canonical_json().encode();
+
+    let upsert_sql = format!(
+        "INSERT INTO redaction_profile (name, profile_json, created_by, created_at, updated_by, updated_at) VALUES ({name}, {json}, {actor}, {at}, {actor}, {at}) ON CONFLICT(name) DO UPDATE SET profile_json=excluded.profile_json, updated_by=excluded.updated_by, updated_at=excluded.updated_at;",
+        name = db.q(&escape_db_text(&profile.name)),
+        json = db.q(&profile_json),
+        actor = db.q(&escape_db_text(actor)),
+        at = db.q(DETERMINISTIC_TIMESTAMP_UTC),
+    );
+    let event_sql =
+        crate::storage::build_event_insert_sql(db, &vault_id, actor, "RedactionProfileSaved", {
+            let mut o = CanonicalJson::object();
+            o.insert("name", CanonicalJson::String(profile.name.clone()));
+            o.insert(
+                "profile_sha256",
+                CanonicalJson::String(profile_sha256(&profile)?),
+            );
+            o
+        })?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", upsert_sql, event_sql))?;
+    Ok(profile)
+}
+
+pub fn redaction_delete_profile(db: &SqliteDb, name: &str, actor: &str) -> CoreResult<()> {
+    validator::validate_chain(db)?;
+    let profile = redaction_get_profile(db, name)?;
+    let vault_id = load_vault_id(db)?;
+    let delete_sql = format!(
+        "DELETE FROM redaction_profile WHERE name={};",
+        db.q(&escape_db_text(&profile.name))
+    );
+    let event_sql =
+        crate::storage::build_event_insert_sql(db, &vault_id, actor, "RedactionProfileDeleted", {
+            let mut o = CanonicalJson::object();
+            o.insert("name", CanonicalJson::String(profile.name.clone()));
+            o
+        })?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", delete_sql, event_sql))
+}
+
+pub fn redaction_get_profile(db: &SqliteDb, name: &str) -> CoreResult<RedactionProfile> {
+    let json = db
+        .query_optional_string(&format!(
+            "SELECT profile_json FROM redaction_profile WHERE name={};",
+            db.q(&escape_db_text(name.trim()))
+        ))?
+        .ok_or_else(|| CoreError::new(CoreErrorCode::NotFound, "redaction profile not found"))?;
+    RedactionProfile::from_json_str(&json)
+}
+
+/// Every profile, by name.
+pub fn redaction_list_profiles(db: &SqliteDb) -> CoreResult<Vec<RedactionProfile>> {
+    db.query_rows_tsv("SELECT name, profile_json FROM redaction_profile ORDER BY name ASC;")?
+        .iter()
+        .map(|r| {
+            if r.len() != 2 {
+                return Err(CoreError::new(
+                    CoreErrorCode::CorruptVault,
+                    "unexpected redaction profile row",
+                ));
+            }
+            let profile = RedactionProfile::from_json_str(&r[1])?;
+            if profile.name != unescape_db_text(&r[0]) {
+                return Err(CoreError::new(
+                    CoreErrorCode::CorruptVault,
+                    "redaction profile name mismatch",
+                ));
+            }
+            Ok(profile)
+        })
+        .collect()
+}
+
+/// SHA-256 of the profile's canonical JSON, as recorded in manifests.
+pub fn profile_sha256(profile: &RedactionProfile) -> CoreResult<String> {
+    hasher::sha256_hex_bytes(profile.to_canonical_json().encode().as_bytes())
+}
+
+/// A profile being applied to one pack, collecting its report.
+pub(crate) struct PackRedaction {
+    profile: RedactionProfile,
+    redactor: Redactor,
+    profile_sha256: String,
+    report: Vec<CanonicalJson>,
+}
+
+impl PackRedaction {
+    pub(crate) fn load(db: &SqliteDb, name: &str) -> CoreResult<Self> {
+        let profile = redaction_get_profile(db, name)?;
+        Ok(PackRedaction {
+            redactor: Redactor::new(&profile)?,
+            profile_sha256: profile_sha256(&profile)?,
+            profile,
+            report: Vec::new(),
+        })
+    }
+
+    pub(crate) fn manifest_entry(&self) -> ManifestRedaction {
+        ManifestRedaction {
+            profile: self.profile.name.clone(),
+            profile_sha256: self.profile_sha256.clone(),
+            unredacted: UNREDACTED.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Push the `ExportRedacted` event recording this profile's use onto
+    /// `events`, once the pack is written.
+    pub(crate) fn record_use(
+        &self,
+        db: &SqliteDb,
+        actor: &str,
+        events: &mut AuditEventBatch,
+    ) -> CoreResult<()> {
+        events.push(db, actor, "ExportRedacted", {
+            let mut o = CanonicalJson::object();
+            o.insert("profile", CanonicalJson::String(self.profile.name.clone()));
+            o.insert(
+                "profile_sha256",
+                CanonicalJson::String(self.profile_sha256.clone()),
+            );
+            o
+        })
+    }
+
+    /// Evidence without excluded items, with text rules applied to file
+    /// names, sources and pack paths.
+    pub(crate) fn filter_evidence(
+        &mut self,
+        evidence: Vec<EvidenceItem>,
+    ) -> CoreResult<Vec<EvidenceItem>> {
+        let excluded = self.profile.excluded_evidence_tags.clone();
+        let mut kept = Vec::new();
+        for mut e in evidence {
+            if e.tags.iter().any(|t| {
+                excluded
+                    .iter()
+                    .any(|x| t == x || answer_bank::is_descendant(t, x))
+            }) {
+                self.report
+                    .push(item("evidence", &e.evidence_id, None, "excluded"));
+                continue;
+            }
+            if self.profile.remove_notes && e.notes.take().is_some() {
+                self.report
+                    .push(item("evidence", &e.evidence_id, Some("notes"), "removed"));
+            }
+            let id = e.evidence_id.clone();
+            e.filename = self.text("evidence", &id, "filename", &e.filename)?;
+            e.source = self.text("evidence", &id, "source", &e.source)?;
+            e.relative_path = self.pack_path(&id, &e.relative_path)?;
+            kept.push(e);
+        }
+        Ok(kept)
+    }
+
+    /// `relative_path` with text rules applied to the file name after the
+    /// content hash prefix (`evidence/ab/<sha256>_<name>`). The result stays
+    /// one file in the same directory.
+    fn pack_path(&mut self, evidence_id: &str, relative_path: &str) -> CoreResult<String> {
+        let (dir, file) = relative_path
+            .rsplit_once('/')
+            .unwrap_or(("", relative_path));
+        let (prefix, name) = match file.split_once('_') {
+            Some((sha, name)) => (format!("{}_", sha), name),
+            None => (String::new(), file),
+        };
+        let redacted = self.text("evidence", evidence_id, "relative_path", name)?;
+        if redacted == name {
+            return Ok(relative_path.to_string());
+        }
+        let mut redacted = redacted.replace(['/', '\\'], "_");
+        if prefix.is_empty() && (redacted.is_empty() || redacted == "." || redacted == "..") {
+            redacted = "_".to_string();
+        }
+        Ok(if dir.is_empty() {
+            format!("{}{}", prefix, redacted)
+        } else {
+            format!("{}/{}{}", dir, prefix, redacted)
+        })
+    }
+
+    /// Redacted copies of `entries` for the answer bank section.
+    pub(crate) fn redact_entries(
+        &mut self,
+        entries: &[AnswerBankEntry],
+    ) -> CoreResult<Vec<AnswerBankEntry>> {
+        let mut out = Vec::new();
+        for entry in entries {
+            let mut e = entry.clone();
+            let id = entry.entry_id.clone();
+            let reported = self.report.len();
+            e.question_canonical = self.text(
+                "answer_bank_entry",
+                &id,
+                "question_canonical",
+                &e.question_canonical,
+            )?;
+            e.answer_short =
+                self.text("answer_bank_entry", &id, "answer_short", &e.answer_short)?;
+            e.answer_long = self.text("answer_bank_entry", &id, "answer_long", &e.answer_long)?;
+            e.source = self.text("answer_bank_entry", &id, "source", &e.source)?;
+            for t in &mut e.translations {
+                let question = format!("translations.{}.question_canonical", t.locale);
+                let short = format!("translations.{}.answer_short", t.locale);
+                let long = format!("translations.{}.answer_long", t.locale);
+                t.question_canonical =
+                    self.text("answer_bank_entry", &id, &question, &t.question_canonical)?;
+                t.answer_short = self.text("answer_bank_entry", &id, &short, &t.answer_short)?;
+                t.answer_long = self.text("answer_bank_entry", &id, &long, &t.answer_long)?;
+            }
+            if self.profile.remove_notes && e.notes.take().is_some() {
+                self.report
+                    .push(item("answer_bank_entry", &id, Some("notes"), "removed"));
+            }
+            if self.profile.remove_owners && !e.owner.is_empty() {
+                e.owner.clear();
+                self.report
+                    .push(item("answer_bank_entry", &id, Some("owner"), "removed"));
+            }
+            if self.report.len() > reported {
+                e.content_hash = answer_bank::compute_content_hash(
+                    &e.question_canonical,
+                    &e.answer_short,
+                    &e.answer_long,
+                    e.notes.as_deref(),
+                    &e.tags,
+                    &e.source,
+                    &e.translations,
+                )?;
+            }
+            out.push(e);
+        }
+        Ok(out)
+    }
+
+    pub(crate) fn redact_answer(&mut self, question_id: &str, answer: &str) -> CoreResult<String> {
+        self.text("question", question_id, "answer", answer)
+    }
+
+    pub(crate) fn redact_question(
+        &mut self,
+        question_id: &str,
+        question_text: &str,
+    ) -> CoreResult<String> {
+        self.text("question", question_id, "question_text", question_text)
+    }
+
+    /// The import with text rules applied to its source file name.
+    pub(crate) fn redact_import(
+        &mut self,
+        imp: &QuestionnaireImport,
+    ) -> CoreResult<QuestionnaireImport> {
+        let mut imp = imp.clone();
+        imp.source_filename = self.text(
+            "questionnaire",
+            &imp.import_id,
+            "source_filename",
+            &imp.source_filename,
+        )?;
+        Ok(imp)
+    }
+
+    /// `None` when assignees are removed.
+    pub(crate) fn assignee(&mut self, question_id: &str, assignee: String) -> Option<String> {
+        if self.profile.remove_owners {
+            self.report
+                .push(item("question", question_id, Some("assignee"), "removed"));
+            None
+        } else {
+            Some(assignee)
+        }
+    }
+
+    /// The payload with text rules applied to its string values and owner
+    /// fields removed, and whether it changed.
+    pub(crate) fn redact_payload(
+        &mut self,
+        event_id: &str,
+        payload: &str,
+    ) -> CoreResult<(String, bool)> {
+        let reported = self.report.len();
+        let redacted = self.json(event_id, "payload", &JsonValue::parse(payload)?)?;
+        if self.report.len() == reported {
+            return Ok((payload.to_string(), false));
+        }
+        Ok((redacted.encode(), true))
+    }
+
+    /// `value` as canonical JSON with the payload redactions applied; `path`
+    /// names the field in the report, e.g. `payload.value`.
+    fn json(&mut self, event_id: &str, path: &str, value: &JsonValue) -> CoreResult<CanonicalJson> {
+        Ok(match value {
+            JsonValue::Null => CanonicalJson::Null,
+            JsonValue::Bool(b) => CanonicalJson::Bool(*b),
+            JsonValue::Number(n) => CanonicalJson::Number(*n),
+            JsonValue::String(text) => {
+                CanonicalJson::String(self.text("audit_event", event_id, path, text)?)
+            }
+            JsonValue::Array(items) => CanonicalJson::Array(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| self.json(event_id, &format!("{}.{}", path, i), v))
+                    .collect::<CoreResult<_>>()?,
+            ),
+            JsonValue::Object(fields) => {
+                let mut o = CanonicalJson::object();
+                for (key, v) in fields {
+                    let field = format!("{}.{}", path, key);
+                    if self.profile.remove_owners && OWNER_FIELDS.contains(&key.as_str()) {
+                        self.report
+                            .push(item("audit_event", event_id, Some(&field), "removed"));
+                        continue;
+                    }
+                    o.insert(key, self.json(event_id, &field, v)?);
+                }
+                o
+            }
+        })
+    }
+
+    /// Write the report and return the `redactions` section.
+    pub(crate) fn write_report(&self, staging: &Path) -> CoreResult<ManifestSection> {
+        let mut root = CanonicalJson::object();
+        root.insert("profile", CanonicalJson::String(self.profile.name.clone()));
+        root.insert(
+            "profile_sha256",
+            CanonicalJson::String(self.profile_sha256.clone()),
+        );
+        root.insert("redactions", CanonicalJson::Array(self.report.clone()));
+        root.insert(
+            "unredacted",
+            CanonicalJson::Array(
+                UNREDACTED
+                    .iter()
+                    .map(|s| CanonicalJson::String(s.to_string()))
+                    .collect(),
+            ),
+        );
+        fs::write_string(&staging.join(REDACTION_REPORT_JSON), &root.encode())?;
+        Ok(sections::section(
+            sections::REDACTIONS,
+            vec![REDACTION_REPORT_JSON.to_string()],
+        ))
+    }
+
+    fn text(&mut self, target: &str, id: &str, field: &str, text: &str) -> CoreResult<String> {
+        let (out, hits) = self.redactor.redact(text)?;
+        for RuleHit { rule, matches } in hits {
+            let mut o = item(target, id, Some(field), "replaced");
+            o.insert("rule", CanonicalJson::Number(rule as i64));
+            o.insert("matches", CanonicalJson::Number(matches as i64));
+            self.report.push(o);
+        }
+        Ok(out)
+    }
+}
+
+fn item(target: &str, id: &str, field: Option<&str>, action: &str) -> CanonicalJson {
+    let mut o = CanonicalJson::object();
+    o.insert("target", CanonicalJson::String(target.to_string()));
+    o.insert("id", CanonicalJson::String(id.to_string()));
+    if let Some(f) = field {
+        o.insert("field", CanonicalJson::String(f.to_string()));
+    }
+    o.insert("action", CanonicalJson::String(action.to_string()));
+    o
+}
+
+fn load_vault_id(db: &SqliteDb) -> CoreResult<String> {
+    db.query_optional_string("SELECT vault_id FROM vault LIMIT 1;")?
+        .ok_or_else(|| CoreError::new(CoreErrorCode::CorruptVault, "missing vault row"))
+}
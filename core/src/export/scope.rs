@@ -0,0 +1,325 @@
+//! Export scopes.
+//!
+//! A scope narrows a pack to part of the vault, e.g. one customer's
+//! questionnaire and the evidence its answers link to. Every criterion is
+//! optional and they combine with AND; the empty scope exports everything.
+//!
+//! - `import_ids`: only these questionnaires (and their column maps), and
+//!   only the answer bank entries their answers use.
+//! - `answer_bank_tags`: only entries with one of these tags or a descendant.
+//! - `controls`: only entries mapped to one of these controls, or to any
+//!   control of a framework when `control_id` is `None`.
+//! - `evidence_tags`: only evidence with one of these tags or a descendant.
+//! - `evidence_added_from_seq` / `evidence_added_to_seq`: only evidence added
+//!   in this range of the audit log (both ends inclusive). The vault stores
+//!   fixed timestamps, so the time range is given as positions in the audit
+//!   log: the `seq` of each item's `EvidenceAdded` event, as in a previous
+//!   pack's `audit_head.seq`.
+//! - `full_audit_log`: include the audit log in a scoped pack.
+//!
+//! Once an answer bank criterion is set, evidence is limited to what the
+//! selected entries link to, and questions answered with an entry outside the
+//! scope are exported without their answer, marked `answer_withheld`.
+//!
+//! The audit log can only be exported whole (its hash chain is checked from
+//! the start) and covers the entire vault, other customers included. An
+//! unscoped pack carries it; a scoped pack leaves it out unless
+//! `full_audit_log` is set. Either way the manifest binds the audit head.
+//!
+//! The normalized scope is recorded in the manifest.
+
+use crate::answer_bank::{self, AnswerBankEntry};
+use crate::audit::canonical::CanonicalJson;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::export::sections::{optional_i64, optional_string, string_array};
+use crate::questionnaire::workflow::{self, QuestionAnswer};
+use crate::questionnaire::{self, QuestionnaireImport};
+use crate::storage::db::SqliteDb;
+use crate::storage::EvidenceItem;
+use crate::util::json::JsonValue;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportScope {
+    pub import_ids: Vec<String>,
+    pub answer_bank_tags: Vec<String>,
+    pub evidence_tags: Vec<String>,
+    pub controls: Vec<ScopeControl>,
+    /// Lowest audit `seq` of an `EvidenceAdded` event in scope.
+    pub evidence_added_from_seq: Option<i64>,
+    /// Highest audit `seq` of an `EvidenceAdded` event in scope.
+    pub evidence_added_to_seq: Option<i64>,
+    /// Export the vault's whole audit log even though the scope narrows the
+    /// pack. Has no effect on an unscoped pack, which always carries it.
+    pub full_audit_log: bool,
+}
+
+/// A control, or every control of `framework` when `control_id` is `None`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ScopeControl {
+    pub framework: String,
+    pub control_id: Option<String>,
+}
+
+/// What a scope selects, in export order.
+pub(crate) struct ScopedContent {
+    pub imports: Vec<QuestionnaireImport>,
+    pub entries: Vec<AnswerBankEntry>,
+    pub evidence: Vec<EvidenceItem>,
+}
+
+impl ExportScope {
+    pub fn is_empty(&self) -> bool {
+        *self == ExportScope::default()
+    }
+
+    /// Whether any criterion narrows the pack.
+    pub fn narrows(&self) -> bool {
+        !ExportScope {
+            full_audit_log: false,
+            ..self.clone()
+        }
+        .is_empty()
+    }
+
+    /// Whether a pack of this scope carries the audit log.
+    pub fn includes_audit_log(&self) -> bool {
+        self.full_audit_log || !self.narrows()
+    }
+
+    /// Trimmed, sorted and deduplicated criteria.
+    pub fn normalized(&self) -> CoreResult<ExportScope> {
+        let mut import_ids: Vec<String> = self
+            .import_ids
+            .iter()
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .collect();
+        import_ids.sort();
+        import_ids.dedup();
+
+        let mut controls = self
+            .controls
+            .iter()
+            .map(|c| {
+                Ok(ScopeControl {
+                    framework: answer_bank::normalize_control_name("framework", &c.framework)?,
+                    control_id: c
+                        .control_id
+                        .as_deref()
+                        .map(|id| answer_bank::normalize_control_name("control_id", id))
+                        .transpose()?,
+                })
+            })
+            .collect::<CoreResult<Vec<_>>>()?;
+        controls.sort();
+        controls.dedup();
+
+        let (from, to) = (self.evidence_added_from_seq, self.evidence_added_to_seq);
+        if from.is_some_and(|seq| seq < 1) || to.is_some_and(|seq| seq < 1) {
+            return Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                "evidence audit seq range must be positive",
+            ));
+        }
+        if let (Some(from), Some(to)) = (from, to) {
+            if from > to {
+                return Err(CoreError::new(
+                    CoreErrorCode::ValidationError,
+                    format!(
+                        "evidence_added_from_seq {} is after evidence_added_to_seq {}",
+                        from, to
+                    ),
+                ));
+            }
+        }
+
+        Ok(ExportScope {
+            import_ids,
+            answer_bank_tags: normalize_tags(&self.answer_bank_tags)?,
+            evidence_tags: normalize_tags(&self.evidence_tags)?,
+            controls,
+            evidence_added_from_seq: from,
+            evidence_added_to_seq: to,
+            full_audit_log: self.full_audit_log,
+        })
+    }
+
+    pub fn to_canonical_json(&self) -> CanonicalJson {
+        let strings = |v: &[String]| {
+            CanonicalJson::Array(v.iter().cloned().map(CanonicalJson::String).collect())
+        };
+        let mut o = CanonicalJson::object();
+        o.insert("import_ids", strings(&self.import_ids));
+        o.insert("answer_bank_tags", strings(&self.answer_bank_tags));
+        o.insert("evidence_tags", strings(&self.evidence_tags));
+        o.insert(
+            "controls",
+            CanonicalJson::Array(
+                self.controls
+                    .iter()
+                    .map(|c| {
+                        let mut c_o = CanonicalJson::object();
+                        c_o.insert("framework", CanonicalJson::String(c.framework.clone()));
+                        if let Some(id) = &c.control_id {
+                            c_o.insert("control_id", CanonicalJson::String(id.clone()));
+                        }
+                        c_o
+                    })
+                    .collect(),
+            ),
+        );
+        if let Some(seq) = self.evidence_added_from_seq {
+            o.insert("evidence_added_from_seq", CanonicalJson::Number(seq));
+        }
+        if let Some(seq) = self.evidence_added_to_seq {
+            o.insert("evidence_added_to_seq", CanonicalJson::Number(seq));
+        }
+        if self.full_audit_log {
+            o.insert("full_audit_log", CanonicalJson::Bool(true));
+        }
+        o
+    }
+
+    pub fn from_json_value(v: &JsonValue) -> CoreResult<Self> {
+        let o = v.as_object()?;
+        let mut controls = Vec::new();
+        for c in o.get_array("controls")? {
+            let c = c.as_object()?;
+            controls.push(ScopeControl {
+                framework: c.get_string("framework")?,
+                control_id: optional_string(&c, "control_id")?,
+            });
+        }
+        Ok(ExportScope {
+            import_ids: string_array(&o, "import_ids")?,
+            answer_bank_tags: string_array(&o, "answer_bank_tags")?,
+            evidence_tags: string_array(&o, "evidence_tags")?,
+            controls,
+            evidence_added_from_seq: optional_i64(&o, "evidence_added_from_seq")?,
+            evidence_added_to_seq: optional_i64(&o, "evidence_added_to_seq")?,
+            full_audit_log: o.get("full_audit_log").is_some() && o.get_bool("full_audit_log")?,
+        })
+    }
+
+    /// Imports, entries and evidence in scope. Expects a normalized scope.
+    pub(crate) fn select(
+        &self,
+        db: &SqliteDb,
+        evidence: Vec<EvidenceItem>,
+    ) -> CoreResult<ScopedContent> {
+        let mut imports = questionnaire::list_imports(db)?;
+        if !self.import_ids.is_empty() {
+            if let Some(missing) = self
+                .import_ids
+                .iter()
+                .find(|id| !imports.iter().any(|imp| &imp.import_id == *id))
+            {
+                return Err(CoreError::new(
+                    CoreErrorCode::NotFound,
+                    format!("questionnaire import not found: {}", missing),
+                ));
+            }
+            imports.retain(|imp| self.import_ids.contains(&imp.import_id));
+        }
+
+        let mut entries = answer_bank::all_entries(db)?;
+        if !self.import_ids.is_empty() {
+            let mut used = BTreeSet::new();
+            for imp in &imports {
+                for q in workflow::list_questions(db, &imp.import_id)? {
+                    if let Some(QuestionAnswer::Entry(id)) = q.answer {
+                        used.insert(id);
+                    }
+                }
+            }
+            entries.retain(|e| used.contains(&e.entry_id));
+        }
+        if !self.answer_bank_tags.is_empty() {
+            entries.retain(|e| tags_match(&e.tags, &self.answer_bank_tags));
+        }
+        if !self.controls.is_empty() {
+            let mapped: BTreeSet<String> = answer_bank::all_control_mappings(db)?
+                .into_iter()
+                .filter(|m| {
+                    self.controls.iter().any(|c| {
+                        c.framework == m.framework
+                            && c.control_id.as_ref().is_none_or(|id| *id == m.control_id)
+                    })
+                })
+                .map(|m| m.entry_id)
+                .collect();
+            entries.retain(|e| mapped.contains(&e.entry_id));
+        }
+
+        let mut evidence = evidence;
+        if !self.import_ids.is_empty()
+            || !self.answer_bank_tags.is_empty()
+            || !self.controls.is_empty()
+        {
+            let linked: BTreeSet<&str> = entries
+                .iter()
+                .flat_map(|e| e.evidence_links.iter().map(String::as_str))
+                .collect();
+            evidence.retain(|e| linked.contains(e.evidence_id.as_str()));
+        }
+        if !self.evidence_tags.is_empty() {
+            evidence.retain(|e| tags_match(&e.tags, &self.evidence_tags));
+        }
+        if self.evidence_added_from_seq.is_some() || self.evidence_added_to_seq.is_some() {
+            let added = evidence_added_seqs(db)?;
+            evidence.retain(|e| {
+                added.get(&e.evidence_id).is_some_and(|seq| {
+                    self.evidence_added_from_seq.is_none_or(|from| *seq >= from)
+                        && self.evidence_added_to_seq.is_none_or(|to| *seq <= to)
+                })
+            });
+        }
+        Ok(ScopedContent {
+            imports,
+            entries,
+            evidence,
+        })
+    }
+}
+
+/// Seq of the `EvidenceAdded` event of each evidence item.
+fn evidence_added_seqs(db: &SqliteDb) -> CoreResult<BTreeMap<String, i64>> {
+    let mut out = BTreeMap::new();
+    for r in db.query_rows_tsv(
+        "SELECT seq, payload_json FROM audit_event WHERE event_type='EvidenceAdded' ORDER BY seq ASC;",
+    )? {
+        if r.len() != 2 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected audit row shape",
+            ));
+        }
+        let seq = r[0]
+            .parse::<i64>()
+            .map_err(|_| CoreError::new(CoreErrorCode::CorruptVault, "invalid audit seq"))?;
+        let payload = JsonValue::parse(&r[1])?;
+        out.entry(payload.as_object()?.get_string("evidence_id")?)
+            .or_insert(seq);
+    }
+    Ok(out)
+}
+
+fn normalize_tags(tags: &[String]) -> CoreResult<Vec<String>> {
+    let mut out = tags
+        .iter()
+        .map(|t| answer_bank::normalize_tag(t))
+        .collect::<CoreResult<Vec<_>>>()?;
+    out.sort();
+    out.dedup();
+    Ok(out)
+}
+
+/// Whether any of `tags` is one of `wanted` or a descendant of one.
+fn tags_match(tags: &[String], wanted: &[String]) -> bool {
+    tags.iter().map(|t| answer_bank::stored_tag(t)).any(|t| {
+        wanted
+            .iter()
+            .any(|w| t == *w || answer_bank::is_descendant(&t, w))
+    })
+}
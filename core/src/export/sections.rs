@@ -0,0 +1,713 @@
+//! Pack sections.
+//!
+//! Besides evidence files and its indexes, a pack carries the vault's answer
+//! bank, answered questionnaires, their column maps and the audit log. Each
+//! section is listed in `manifest.json` with its files and a format version,
+//! so readers can tell what a pack contains and how to parse it:
+//!
+//! - `answer_bank`: `answer_bank/answer_bank.json` (every entry in scope with
+//!   its translations and control mappings) and `answer_bank/answer_bank.csv` (the
+//!   bulk import layout).
+//! - `questionnaires`: `questionnaires/<import_id>.json` per import with
+//!   questions, each question with its status and final answer text. A
+//!   question answered with an entry outside the pack's scope is listed
+//!   without its answer and marked `answer_withheld`.
+//! - `column_maps`: `questionnaires/column_maps.json`, the column map of every
+//!   import in scope.
+//! - `audit_log`: `audit_log/audit_log.jsonl`, one event per line with the
+//!   stored payload and hashes. Scoped packs only carry it when the scope
+//!   asks for the full log (see [`crate::export::scope`]).
+//! - `redactions`: `redactions/redaction_report.json`, only in packs generated
+//!   with a redaction profile (see [`crate::export::redaction`]).
+//!
+//! [`validate_sections`] checks what hashes alone cannot: entry content
+//! hashes, that answers point at exported entries, that questionnaires are
+//! within the manifest's scope, and the audit hash chain up to the manifest's
+//! audit head.
+
+use crate::answer_bank::{self, AnswerBankEntry, LocalizedText, TemplateVariables};
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::{hasher, validator};
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::export::index::{IndexQuestion, IndexQuestionnaire};
+use crate::export::manifest::{AuditHead, ExportManifest, ManifestSection};
+use crate::export::redaction::{PackRedaction, REDACTION_REPORT_JSON};
+use crate::questionnaire::workflow::{self, QuestionAnswer};
+use crate::questionnaire::QuestionnaireImport;
+use crate::storage::db::SqliteDb;
+use crate::util::fs;
+use crate::util::json::{JsonObject, JsonValue};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+pub const EVIDENCE: &str = "evidence";
+/// `index.md` and `index.html`.
+pub const INDEX: &str = "index";
+pub const ANSWER_BANK: &str = "answer_bank";
+pub const QUESTIONNAIRES: &str = "questionnaires";
+pub const COLUMN_MAPS: &str = "column_maps";
+pub const AUDIT_LOG: &str = "audit_log";
+/// The redaction report of packs generated with a redaction profile.
+pub const REDACTIONS: &str = "redactions";
+
+const ANSWER_BANK_JSON: &str = "answer_bank/answer_bank.json";
+const ANSWER_BANK_CSV: &str = "answer_bank/answer_bank.csv";
+const COLUMN_MAPS_JSON: &str = "questionnaires/column_maps.json";
+const AUDIT_LOG_JSONL: &str = "audit_log/audit_log.jsonl";
+
+/// Format version written for each section; packs with newer versions are
+/// rejected by `validate_pack`.
+pub fn section_version(name: &str) -> Option<i64> {
+    match name {
+        EVIDENCE | ANSWER_BANK | QUESTIONNAIRES | COLUMN_MAPS | REDACTIONS => Some(1),
+        // Version 2 added index.html.
+        INDEX => Some(2),
+        // Version 2 added `payload_redacted` events and `hash_format`.
+        AUDIT_LOG => Some(2),
+        _ => None,
+    }
+}
+
+pub(crate) fn section(name: &str, files: Vec<String>) -> ManifestSection {
+    ManifestSection {
+        name: name.to_string(),
+        version: section_version(name).unwrap_or(1),
+        files,
+    }
+}
+
+pub(crate) fn write_answer_bank(
+    db: &SqliteDb,
+    staging: &Path,
+    entries: &[AnswerBankEntry],
+) -> CoreResult<ManifestSection> {
+    let mut controls: HashMap<String, Vec<CanonicalJson>> = HashMap::new();
+    for m in answer_bank::all_control_mappings(db)? {
+        let mut o = CanonicalJson::object();
+        o.insert("framework", CanonicalJson::String(m.framework));
+        o.insert("control_id", CanonicalJson::String(m.control_id));
+        controls.entry(m.entry_id).or_default().push(o);
+    }
+
+    let mut root = CanonicalJson::object();
+    root.insert(
+        "entries",
+        CanonicalJson::Array(
+            entries
+                .iter()
+                .map(|e| entry_json(e, controls.remove(&e.entry_id).unwrap_or_default()))
+                .collect(),
+        ),
+    );
+    fs::write_string(&staging.join(ANSWER_BANK_JSON), &root.encode())?;
+    answer_bank::write_entries(&staging.join(ANSWER_BANK_CSV), entries, None)?;
+
+    Ok(section(
+        ANSWER_BANK,
+        vec![ANSWER_BANK_CSV.to_string(), ANSWER_BANK_JSON.to_string()],
+    ))
+}
+
+/// The `questionnaires` and `column_maps` sections, with the written
+/// questionnaires as listed in `index.html`.
+pub(crate) struct QuestionnaireSections {
+    pub questionnaires: ManifestSection,
+    pub column_maps: ManifestSection,
+    pub index: Vec<IndexQuestionnaire>,
+}
+
+/// Questions answered with an entry not in `entries` are exported without
+/// their answer and marked `answer_withheld`. Answers are redacted after
+/// variables are rendered; question text and source file names are redacted
+/// too.
+pub(crate) fn write_questionnaires(
+    db: &SqliteDb,
+    staging: &Path,
+    imports: &[QuestionnaireImport],
+    entries: &[AnswerBankEntry],
+    mut redaction: Option<&mut PackRedaction>,
+) -> CoreResult<QuestionnaireSections> {
+    let entries: HashMap<&str, &AnswerBankEntry> =
+        entries.iter().map(|e| (e.entry_id.as_str(), e)).collect();
+    let variables = TemplateVariables::load(db)?;
+
+    let mut files = Vec::new();
+    let mut maps = Vec::new();
+    let mut index = Vec::new();
+    for imp in imports {
+        let redacted;
+        let imp = match redaction.as_deref_mut() {
+            Some(r) => {
+                redacted = r.redact_import(imp)?;
+                &redacted
+            }
+            None => imp,
+        };
+        maps.push(column_map_json(imp));
+
+        let questions = workflow::list_questions(db, &imp.import_id)?;
+        if questions.is_empty() {
+            continue;
+        }
+        let mut root = import_json(imp);
+        let mut items = Vec::new();
+        let mut listed = Vec::new();
+        for mut q in questions {
+            let withheld = matches!(
+                &q.answer,
+                Some(QuestionAnswer::Entry(id)) if !entries.contains_key(id.as_str())
+            );
+            if withheld {
+                q.answer = None;
+            }
+            if let Some(r) = redaction.as_deref_mut() {
+                q.question_text = r.redact_question(&q.question_id, &q.question_text)?;
+            }
+            let mut listed_q = IndexQuestion {
+                row_number: q.row_number,
+                question_text: q.question_text.clone(),
+                status: q.status.as_str().to_string(),
+                answer: None,
+                answer_withheld: withheld,
+                evidence_ids: Vec::new(),
+            };
+            let question_id = q.question_id.clone();
+            let mut redact = |text: String| -> CoreResult<String> {
+                match redaction.as_deref_mut() {
+                    Some(r) => r.redact_answer(&question_id, &text),
+                    None => Ok(text),
+                }
+            };
+            let answer = match q.answer {
+                Some(QuestionAnswer::Entry(id)) => {
+                    let e = entries[id.as_str()];
+                    let text = variables.render(&e.text_in(&imp.language).answer_long);
+                    listed_q.evidence_ids = e.evidence_links.clone();
+                    Some((redact(text.text)?, QuestionAnswer::Entry(id)))
+                }
+                Some(QuestionAnswer::Override(text)) => {
+                    let text = redact(text)?;
+                    Some((text.clone(), QuestionAnswer::Override(text)))
+                }
+                None => None,
+            };
+            let assignee = match (redaction.as_deref_mut(), q.assignee) {
+                (Some(r), Some(a)) => r.assignee(&question_id, a),
+                (_, a) => a,
+            };
+
+            let mut o = CanonicalJson::object();
+            o.insert("question_id", CanonicalJson::String(q.question_id));
+            o.insert("row_number", CanonicalJson::Number(q.row_number));
+            o.insert("question_text", CanonicalJson::String(q.question_text));
+            o.insert(
+                "status",
+                CanonicalJson::String(q.status.as_str().to_string()),
+            );
+            if let Some(a) = assignee {
+                o.insert("assignee", CanonicalJson::String(a));
+            }
+            if withheld {
+                o.insert("answer_withheld", CanonicalJson::Bool(true));
+            }
+            if let Some((text, answer)) = answer {
+                listed_q.answer = Some(text.clone());
+                o.insert("answer", CanonicalJson::String(text));
+                match answer {
+                    QuestionAnswer::Entry(id) => {
+                        o.insert("answer_bank_entry_id", CanonicalJson::String(id));
+                    }
+                    QuestionAnswer::Override(text) => {
+                        o.insert("answer_override", CanonicalJson::String(text));
+                    }
+                }
+            }
+            items.push(o);
+            listed.push(listed_q);
+        }
+        root.insert("questions", CanonicalJson::Array(items));
+
+        let path = format!("questionnaires/{}.json", imp.import_id);
+        fs::write_string(&staging.join(&path), &root.encode())?;
+        index.push(IndexQuestionnaire {
+            import_id: imp.import_id.clone(),
+            source_filename: imp.source_filename.clone(),
+            language: imp.language.clone(),
+            path: path.clone(),
+            questions: listed,
+        });
+        files.push(path);
+    }
+
+    let mut root = CanonicalJson::object();
+    root.insert("imports", CanonicalJson::Array(maps));
+    fs::write_string(&staging.join(COLUMN_MAPS_JSON), &root.encode())?;
+
+    files.sort();
+    Ok(QuestionnaireSections {
+        questionnaires: section(QUESTIONNAIRES, files),
+        column_maps: section(COLUMN_MAPS, vec![COLUMN_MAPS_JSON.to_string()]),
+        index,
+    })
+}
+
+/// Payloads changed by `redaction` are marked `payload_redacted`. Events of
+/// hash format 2 carry the SHA-256 of their stored payload, so their hash can
+/// be checked whether or not the payload was redacted.
+pub(crate) fn write_audit_log(
+    db: &SqliteDb,
+    staging: &Path,
+    mut redaction: Option<&mut PackRedaction>,
+) -> CoreResult<ManifestSection> {
+    let mut out = String::new();
+    for r in db.query_rows_tsv(
+        "SELECT seq, event_id, vault_id, occurred_at, actor, event_type, payload_json, prev_hash, hash, hash_format FROM audit_event ORDER BY seq ASC;",
+    )? {
+        if r.len() != 10 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected audit row shape",
+            ));
+        }
+        let mut o = CanonicalJson::object();
+        o.insert("seq", CanonicalJson::Number(r[0].parse().unwrap_or(0)));
+        for (i, key) in [
+            "event_id",
+            "vault_id",
+            "occurred_at",
+            "actor",
+            "event_type",
+            "payload",
+            "prev_hash",
+            "hash",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            o.insert(key, CanonicalJson::String(r[i + 1].clone()));
+        }
+        let hash_format: i64 = r[9].parse().unwrap_or(0);
+        o.insert("hash_format", CanonicalJson::Number(hash_format));
+        if hash_format == validator::HASH_FORMAT_PAYLOAD_SHA256 {
+            o.insert(
+                "payload_sha256",
+                CanonicalJson::String(hasher::sha256_hex_bytes(r[6].as_bytes())?),
+            );
+        }
+        if let Some(redaction) = redaction.as_deref_mut() {
+            let (payload, changed) = redaction.redact_payload(&r[1], &r[6])?;
+            if changed {
+                o.insert("payload", CanonicalJson::String(payload));
+                o.insert("payload_redacted", CanonicalJson::Bool(true));
+            }
+        }
+        out.push_str(&o.encode());
+        out.push('\n');
+    }
+    fs::write_string(&staging.join(AUDIT_LOG_JSONL), &out)?;
+    Ok(section(AUDIT_LOG, vec![AUDIT_LOG_JSONL.to_string()]))
+}
+
+/// Check a pack's sections; file hashes are checked separately. Packs without
+/// sections (manifest version 1) have nothing to check.
+pub(crate) fn validate_sections(dir: &Path, manifest: &ExportManifest) -> CoreResult<()> {
+    let listed: BTreeSet<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+    let mut covered = BTreeSet::new();
+    for sec in &manifest.sections {
+        match section_version(&sec.name) {
+            Some(v) if sec.version <= v => {}
+            _ => {
+                return Err(CoreError::new(
+                    CoreErrorCode::UnsupportedFormat,
+                    format!("unsupported section {} version {}", sec.name, sec.version),
+                ))
+            }
+        }
+        for f in &sec.files {
+            if !listed.contains(f.as_str()) || !covered.insert(f.as_str()) {
+                return Err(invalid(format!(
+                    "section {} file {} is not listed once in the manifest",
+                    sec.name, f
+                )));
+            }
+        }
+    }
+    if manifest.sections.is_empty() {
+        return Ok(());
+    }
+    if let Some(f) = listed.difference(&covered).next() {
+        return Err(invalid(format!("file {} is not in any section", f)));
+    }
+
+    let has = |name: &str| manifest.sections.iter().any(|s| s.name == name);
+    let entry_ids = if has(ANSWER_BANK) {
+        Some(validate_answer_bank(dir)?)
+    } else {
+        None
+    };
+    // Questionnaires must be among the scope's imports, when it names any.
+    let in_scope = |import_id: &str| -> CoreResult<()> {
+        match &manifest.scope {
+            Some(scope)
+                if !scope.import_ids.is_empty()
+                    && !scope.import_ids.iter().any(|id| id == import_id) =>
+            {
+                Err(invalid(format!(
+                    "questionnaire import {} is outside the pack's scope",
+                    import_id
+                )))
+            }
+            _ => Ok(()),
+        }
+    };
+    for sec in manifest
+        .sections
+        .iter()
+        .filter(|s| s.name == QUESTIONNAIRES)
+    {
+        for f in &sec.files {
+            in_scope(&validate_questionnaire(dir, f, entry_ids.as_ref())?)?;
+        }
+    }
+    if has(COLUMN_MAPS) {
+        let text = fs::read_to_string(&dir.join(COLUMN_MAPS_JSON))?;
+        for m in JsonValue::parse(&text)?.as_object()?.get_array("imports")? {
+            in_scope(&m.as_object()?.get_string("import_id")?)?;
+        }
+    }
+    // The report and the manifest name the same profile and list the same
+    // unredacted parts, or neither exists.
+    match (&manifest.redaction, has(REDACTIONS)) {
+        (Some(r), true) => {
+            let text = fs::read_to_string(&dir.join(REDACTION_REPORT_JSON))?;
+            let v = JsonValue::parse(&text)?;
+            let o = v.as_object()?;
+            let unredacted = match o.get("unredacted") {
+                Some(v) => v
+                    .as_array()?
+                    .iter()
+                    .map(|s| s.as_string())
+                    .collect::<CoreResult<Vec<_>>>()?,
+                None => Vec::new(),
+            };
+            if o.get_string("profile")? != r.profile
+                || o.get_string("profile_sha256")? != r.profile_sha256
+                || unredacted != r.unredacted
+            {
+                return Err(invalid(
+                    "redaction report does not match the manifest's profile".to_string(),
+                ));
+            }
+            o.get_array("redactions")?;
+        }
+        (None, false) => {}
+        _ => {
+            return Err(invalid(
+                "redaction report and manifest redaction must come together".to_string(),
+            ))
+        }
+    }
+    if has(AUDIT_LOG) {
+        if manifest
+            .scope
+            .as_ref()
+            .is_some_and(|s| !s.includes_audit_log())
+        {
+            return Err(invalid(
+                "audit log is in a pack whose scope leaves it out".to_string(),
+            ));
+        }
+        let head = validate_audit_log(dir, manifest.redaction.is_some())?;
+        if let Some(expected) = &manifest.audit_head {
+            if head.as_ref() != Some(expected) {
+                return Err(CoreError::new(
+                    CoreErrorCode::HashMismatch,
+                    "audit log does not end at the manifest's audit head",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Recompute every entry's content hash; returns the exported entry ids.
+fn validate_answer_bank(dir: &Path) -> CoreResult<BTreeSet<String>> {
+    let text = fs::read_to_string(&dir.join(ANSWER_BANK_JSON))?;
+    let mut ids = BTreeSet::new();
+    for e in JsonValue::parse(&text)?.as_object()?.get_array("entries")? {
+        let o = e.as_object()?;
+        let entry_id = o.get_string("entry_id")?;
+        let notes = optional_string(&o, "notes")?;
+        let tags = string_array(&o, "tags")?;
+        let mut translations = Vec::new();
+        for t in o.get_array("translations")? {
+            let t = t.as_object()?;
+            translations.push(LocalizedText {
+                locale: t.get_string("locale")?,
+                question_canonical: t.get_string("question_canonical")?,
+                answer_short: t.get_string("answer_short")?,
+                answer_long: t.get_string("answer_long")?,
+            });
+        }
+        let computed = answer_bank::compute_content_hash(
+            &o.get_string("question_canonical")?,
+            &o.get_string("answer_short")?,
+            &o.get_string("answer_long")?,
+            notes.as_deref(),
+            &tags,
+            &o.get_string("source")?,
+            &translations,
+        )?;
+        if computed != o.get_string("content_hash")? {
+            return Err(CoreError::new(
+                CoreErrorCode::HashMismatch,
+                format!("content hash mismatch for answer bank entry {}", entry_id),
+            ));
+        }
+        ids.insert(entry_id);
+    }
+    Ok(ids)
+}
+
+fn validate_questionnaire(
+    dir: &Path,
+    path: &str,
+    entry_ids: Option<&BTreeSet<String>>,
+) -> CoreResult<String> {
+    let text = fs::read_to_string(&dir.join(path))?;
+    let v = JsonValue::parse(&text)?;
+    let o = v.as_object()?;
+    let import_id = o.get_string("import_id")?;
+    for q in o.get_array("questions")? {
+        let q = q.as_object()?;
+        q.get_string("question_id")?;
+        let entry_id = optional_string(&q, "answer_bank_entry_id")?;
+        if q.get("answer_withheld").is_some()
+            && (!q.get_bool("answer_withheld")? || q.get("answer").is_some())
+        {
+            return Err(invalid(format!(
+                "{} has a withheld question that carries an answer",
+                path
+            )));
+        }
+        if let (Some(id), Some(ids)) = (entry_id, entry_ids) {
+            if !ids.contains(&id) {
+                return Err(invalid(format!(
+                    "{} answers with answer bank entry {} missing from the pack",
+                    path, id
+                )));
+            }
+        }
+    }
+    Ok(import_id)
+}
+
+/// Recompute the hash chain from the exported events; returns the last one.
+/// Format 2 events are hashed over `payload_sha256`, which must match the
+/// payload unless it is marked `payload_redacted`. Redacted format 1 events,
+/// written before the vault had format 2, keep their stored hash and are only
+/// checked by their links.
+fn validate_audit_log(dir: &Path, redacted: bool) -> CoreResult<Option<AuditHead>> {
+    let text = fs::read_to_string(&dir.join(AUDIT_LOG_JSONL))?;
+    let mut prev = validator::GENESIS_PREV_HASH.to_string();
+    let mut last_seq = 0;
+    for line in text.lines() {
+        let v = JsonValue::parse(line)?;
+        let o = v.as_object()?;
+        let seq = o.get_i64("seq")?;
+        let field = |k: &str| o.get_string(k);
+        if seq <= last_seq || field("prev_hash")? != prev {
+            return Err(CoreError::new(
+                CoreErrorCode::HashMismatch,
+                format!("audit log chain broken at seq {}", seq),
+            ));
+        }
+        let payload_redacted = o.get("payload_redacted").is_some();
+        if payload_redacted && (!redacted || !o.get_bool("payload_redacted")?) {
+            return Err(invalid(format!(
+                "audit event at seq {} has an unexpected payload_redacted mark",
+                seq
+            )));
+        }
+        let hash_format = match o.get("hash_format") {
+            Some(_) => o.get_i64("hash_format")?,
+            None => validator::HASH_FORMAT_PAYLOAD,
+        };
+        let computed = match hash_format {
+            validator::HASH_FORMAT_PAYLOAD if payload_redacted => field("hash")?,
+            validator::HASH_FORMAT_PAYLOAD => {
+                let canonical = validator::canonical_event_string(
+                    &field("event_id")?,
+                    &field("vault_id")?,
+                    &field("occurred_at")?,
+                    &field("actor")?,
+                    &field("event_type")?,
+                    &field("payload")?,
+                    &prev,
+                );
+                hasher::sha256_hex_bytes(canonical.as_bytes())?
+            }
+            validator::HASH_FORMAT_PAYLOAD_SHA256 => {
+                let payload_sha256 = field("payload_sha256")?;
+                if !payload_redacted
+                    && hasher::sha256_hex_bytes(field("payload")?.as_bytes())? != payload_sha256
+                {
+                    return Err(CoreError::new(
+                        CoreErrorCode::HashMismatch,
+                        format!("audit log payload hash mismatch at seq {}", seq),
+                    ));
+                }
+                let canonical = validator::canonical_event_string_v2(
+                    &field("event_id")?,
+                    &field("vault_id")?,
+                    &field("occurred_at")?,
+                    &field("actor")?,
+                    &field("event_type")?,
+                    &payload_sha256,
+                    &prev,
+                );
+                hasher::sha256_hex_bytes(canonical.as_bytes())?
+            }
+            _ => {
+                return Err(invalid(format!(
+                    "audit event at seq {} has unknown hash_format {}",
+                    seq, hash_format
+                )))
+            }
+        };
+        if computed != field("hash")? {
+            return Err(CoreError::new(
+                CoreErrorCode::HashMismatch,
+                format!("audit log hash mismatch at seq {}", seq),
+            ));
+        }
+        prev = computed;
+        last_seq = seq;
+    }
+    Ok((last_seq > 0).then_some(AuditHead {
+        seq: last_seq,
+        hash: prev,
+    }))
+}
+
+fn entry_json(e: &AnswerBankEntry, controls: Vec<CanonicalJson>) -> CanonicalJson {
+    let strings =
+        |v: &[String]| CanonicalJson::Array(v.iter().cloned().map(CanonicalJson::String).collect());
+    let mut o = CanonicalJson::object();
+    o.insert("entry_id", CanonicalJson::String(e.entry_id.clone()));
+    o.insert(
+        "question_canonical",
+        CanonicalJson::String(e.question_canonical.clone()),
+    );
+    o.insert(
+        "answer_short",
+        CanonicalJson::String(e.answer_short.clone()),
+    );
+    o.insert("answer_long", CanonicalJson::String(e.answer_long.clone()));
+    if let Some(n) = &e.notes {
+        o.insert("notes", CanonicalJson::String(n.clone()));
+    }
+    o.insert("evidence_links", strings(&e.evidence_links));
+    o.insert("owner", CanonicalJson::String(e.owner.clone()));
+    if let Some(d) = &e.last_reviewed_at {
+        o.insert("last_reviewed_at", CanonicalJson::String(d.clone()));
+    }
+    o.insert("tags", strings(&e.tags));
+    o.insert("source", CanonicalJson::String(e.source.clone()));
+    o.insert(
+        "status",
+        CanonicalJson::String(e.status.as_str().to_string()),
+    );
+    o.insert(
+        "content_hash",
+        CanonicalJson::String(e.content_hash.clone()),
+    );
+    o.insert("created_at", CanonicalJson::String(e.created_at.clone()));
+    o.insert("updated_at", CanonicalJson::String(e.updated_at.clone()));
+    o.insert(
+        "translations",
+        CanonicalJson::Array(
+            e.translations
+                .iter()
+                .map(|t| {
+                    let mut t_o = CanonicalJson::object();
+                    t_o.insert("locale", CanonicalJson::String(t.locale.clone()));
+                    t_o.insert(
+                        "question_canonical",
+                        CanonicalJson::String(t.question_canonical.clone()),
+                    );
+                    t_o.insert(
+                        "answer_short",
+                        CanonicalJson::String(t.answer_short.clone()),
+                    );
+                    t_o.insert("answer_long", CanonicalJson::String(t.answer_long.clone()));
+                    t_o
+                })
+                .collect(),
+        ),
+    );
+    o.insert("controls", CanonicalJson::Array(controls));
+    o
+}
+
+fn import_json(imp: &QuestionnaireImport) -> CanonicalJson {
+    let mut o = CanonicalJson::object();
+    o.insert("import_id", CanonicalJson::String(imp.import_id.clone()));
+    o.insert(
+        "source_filename",
+        CanonicalJson::String(imp.source_filename.clone()),
+    );
+    o.insert(
+        "source_sha256",
+        CanonicalJson::String(imp.source_sha256.clone()),
+    );
+    o.insert(
+        "imported_at",
+        CanonicalJson::String(imp.imported_at.clone()),
+    );
+    o.insert("format", CanonicalJson::String(imp.format.clone()));
+    o.insert("language", CanonicalJson::String(imp.language.clone()));
+    if let Some(p) = &imp.parent_import_id {
+        o.insert("parent_import_id", CanonicalJson::String(p.clone()));
+    }
+    o
+}
+
+fn column_map_json(imp: &QuestionnaireImport) -> CanonicalJson {
+    let mut o = CanonicalJson::object();
+    o.insert("import_id", CanonicalJson::String(imp.import_id.clone()));
+    o.insert(
+        "source_filename",
+        CanonicalJson::String(imp.source_filename.clone()),
+    );
+    o.insert(
+        "column_map",
+        imp.column_map
+            .as_ref()
+            .map(|m| m.to_canonical_json())
+            .unwrap_or(CanonicalJson::Null),
+    );
+    o
+}
+
+pub(crate) fn optional_string(o: &JsonObject<'_>, key: &str) -> CoreResult<Option<String>> {
+    match o.get(key) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(_) => o.get_string(key).map(Some),
+    }
+}
+
+pub(crate) fn optional_i64(o: &JsonObject<'_>, key: &str) -> CoreResult<Option<i64>> {
+    match o.get(key) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(_) => o.get_i64(key).map(Some),
+    }
+}
+
+pub(crate) fn string_array(o: &JsonObject<'_>, key: &str) -> CoreResult<Vec<String>> {
+    o.get_array(key)?.iter().map(|v| v.as_string()).collect()
+}
+
+fn invalid(message: String) -> CoreError {
+    CoreError::new(CoreErrorCode::CorruptVault, message)
+}
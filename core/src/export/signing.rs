@@ -0,0 +1,245 @@
+//! Export pack signatures.
+//!
+//! Each vault has an Ed25519 key, created on its first export and recorded
+//! with an `ExportSigningKeyCreated` event once that pack is written.
+//! `generate_pack` signs the exact bytes of `manifest.json` and stores the
+//! signature with the signer's public key in `manifest.sig`. Since the
+//! manifest lists every file's hash and the audit head the pack was
+//! generated from, the signature covers the whole pack.
+//!
+//! Trust model: the vault database only holds the public key. The secret
+//! key lives in [`SIGNING_KEY_FILE`] next to it, readable by its owner only
+//! (mode 0600 on Unix), so a copy of the database cannot sign packs. Anyone
+//! who can read that file can sign packs as the vault; if it may have leaked,
+//! [`rotate_signing_key`] replaces it (`ExportSigningKeyRotated`) and
+//! recipients must stop trusting the old public key.
+//!
+//! `validate_pack` only accepts packs signed by a key in the caller's trusted
+//! list; anyone can produce a well-formed signature with their own key.
+
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::license::{decode_hex_32, decode_hex_64};
+use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
+use crate::storage::db::{escape_db_text, SqliteDb};
+use crate::storage::{build_event_insert_sql, AuditEventBatch};
+use crate::util::fs;
+use crate::util::json::JsonValue;
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
+use std::io::Read;
+use std::path::PathBuf;
+
+pub const SIGNATURE_FILE: &str = "manifest.sig";
+pub const SIGNATURE_ALGORITHM: &str = "ed25519";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestSignature {
+    pub algorithm: String,
+    pub public_key_hex: String,
+    pub signature_hex: String,
+}
+
+impl ManifestSignature {
+    pub fn to_json_string(&self) -> String {
+        let mut root = CanonicalJson::object();
+        root.insert("algorithm", CanonicalJson::String(self.algorithm.clone()));
+        root.insert(
+            "public_key_hex",
+            CanonicalJson::String(self.public_key_hex.clone()),
+        );
+        root.insert(
+            "signature_hex",
+            CanonicalJson::String(self.signature_hex.clone()),
+        );
+        root.encode()
+    }
+
+    pub fn from_json_str(s: &str) -> CoreResult<Self> {
+        let v = JsonValue::parse(s)?;
+        let obj = v.as_object()?;
+        Ok(ManifestSignature {
+            algorithm: obj.get_string("algorithm")?,
+            public_key_hex: obj.get_string("public_key_hex")?,
+            signature_hex: obj.get_string("signature_hex")?,
+        })
+    }
+}
+
+/// Name of the file next to the vault database holding the secret key.
+pub const SIGNING_KEY_FILE: &str = "export_signing.key";
+
+/// Public key of the vault's signing key, hex encoded; `None` before the
+/// first export.
+pub fn vault_signing_public_key(db: &SqliteDb) -> CoreResult<Option<String>> {
+    db.query_optional_string("SELECT public_key_hex FROM export_signing_key LIMIT 1;")
+}
+
+/// Path of the secret key file for the vault database `db`.
+pub fn signing_key_path(db: &SqliteDb) -> PathBuf {
+    db.path().with_file_name(SIGNING_KEY_FILE)
+}
+
+/// The vault's signing key, if it has one. The key file must match the
+/// public key recorded in the database.
+pub(crate) fn load_signing_key(db: &SqliteDb) -> CoreResult<Option<SigningKey>> {
+    let Some(public_key_hex) = vault_signing_public_key(db)? else {
+        return Ok(None);
+    };
+    let path = signing_key_path(db);
+    if !path.exists() {
+        return Err(CoreError::new(
+            CoreErrorCode::NotFound,
+            format!(
+                "signing key file {} is missing; rotate the signing key",
+                path.display()
+            ),
+        ));
+    }
+    let secret_hex = fs::read_to_string(&path)?;
+    let secret = decode_hex_32(secret_hex.trim())
+        .map_err(|e| CoreError::new(CoreErrorCode::CorruptVault, e))?;
+    let key = SigningKey::from_bytes(&secret);
+    if encode_hex(&key.verifying_key().to_bytes()) != public_key_hex {
+        return Err(CoreError::new(
+            CoreErrorCode::CorruptVault,
+            "signing key file does not match the vault's public key; rotate the signing key",
+        ));
+    }
+    Ok(Some(key))
+}
+
+/// A fresh random signing key; nothing is stored until [`store_new_key_sql`].
+pub(crate) fn generate_signing_key() -> CoreResult<SigningKey> {
+    let mut secret = [0u8; 32];
+    std::fs::File::open("/dev/urandom")?.read_exact(&mut secret)?;
+    Ok(SigningKey::from_bytes(&secret))
+}
+
+/// Write `key` to the key file and return the SQL recording it as the
+/// vault's first key; the `ExportSigningKeyCreated` event is pushed onto
+/// `events`.
+pub(crate) fn store_new_key_sql(
+    db: &SqliteDb,
+    key: &SigningKey,
+    actor: &str,
+    events: &mut AuditEventBatch,
+) -> CoreResult<String> {
+    let public_key_hex = encode_hex(&key.verifying_key().to_bytes());
+    fs::write_private_string(&signing_key_path(db), &encode_hex(&key.to_bytes()))?;
+    events.push(db, actor, "ExportSigningKeyCreated", {
+        let mut o = CanonicalJson::object();
+        o.insert(
+            "public_key_hex",
+            CanonicalJson::String(public_key_hex.clone()),
+        );
+        o
+    })?;
+    Ok(insert_key_sql(db, &public_key_hex, actor))
+}
+
+/// Replace the vault's signing key with a new one and return its public key.
+/// Packs signed before stay valid for recipients that still trust the old
+/// key.
+pub fn rotate_signing_key(db: &SqliteDb, actor: &str) -> CoreResult<String> {
+    validator::validate_chain(db)?;
+    let previous = vault_signing_public_key(db)?
+        .ok_or_else(|| CoreError::new(CoreErrorCode::NotFound, "vault has no signing key yet"))?;
+
+    let key = generate_signing_key()?;
+    let public_key_hex = encode_hex(&key.verifying_key().to_bytes());
+    let vault_id = db
+        .query_optional_string("SELECT vault_id FROM vault LIMIT 1;")?
+        .ok_or_else(|| CoreError::new(CoreErrorCode::CorruptVault, "missing vault row"))?;
+    let event_sql = build_event_insert_sql(db, &vault_id, actor, "ExportSigningKeyRotated", {
+        let mut o = CanonicalJson::object();
+        o.insert("previous_public_key_hex", CanonicalJson::String(previous));
+        o.insert(
+            "public_key_hex",
+            CanonicalJson::String(public_key_hex.clone()),
+        );
+        o
+    })?;
+
+    // Write the new key first and put the old file back if the database
+    // write fails, so the file always matches the recorded public key.
+    let path = signing_key_path(db);
+    let old_file = fs::read_to_string(&path).ok();
+    fs::write_private_string(&path, &encode_hex(&key.to_bytes()))?;
+    let sql = format!(
+        "BEGIN;\nDELETE FROM export_signing_key;\n{}\n{}\nCOMMIT;",
+        insert_key_sql(db, &public_key_hex, actor),
+        event_sql
+    );
+    if let Err(e) = db.exec_batch(&sql) {
+        match old_file {
+            Some(old) => {
+                let _ = fs::write_private_string(&path, &old);
+            }
+            None => {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+        return Err(e);
+    }
+    Ok(public_key_hex)
+}
+
+fn insert_key_sql(db: &SqliteDb, public_key_hex: &str, actor: &str) -> String {
+    format!(
+        "INSERT INTO export_signing_key (public_key_hex, created_by, created_at) VALUES ({}, {}, {});",
+        db.q(public_key_hex),
+        db.q(&escape_db_text(actor)),
+        db.q(DETERMINISTIC_TIMESTAMP_UTC)
+    )
+}
+
+pub(crate) fn sign_manifest(key: &SigningKey, manifest_json: &str) -> ManifestSignature {
+    ManifestSignature {
+        algorithm: SIGNATURE_ALGORITHM.to_string(),
+        public_key_hex: encode_hex(&key.verifying_key().to_bytes()),
+        signature_hex: encode_hex(&key.sign(manifest_json.as_bytes()).to_bytes()),
+    }
+}
+
+/// Check that `signature` is a valid signature of `manifest_json` by one of
+/// `trusted_keys` (hex encoded public keys).
+pub(crate) fn verify_manifest(
+    signature: &ManifestSignature,
+    manifest_json: &str,
+    trusted_keys: &[String],
+) -> CoreResult<()> {
+    if signature.algorithm != SIGNATURE_ALGORITHM {
+        return Err(CoreError::new(
+            CoreErrorCode::UnsupportedFormat,
+            format!("unsupported signature algorithm {}", signature.algorithm),
+        ));
+    }
+    let signer = signature.public_key_hex.to_ascii_lowercase();
+    if !trusted_keys
+        .iter()
+        .any(|k| k.trim().to_ascii_lowercase() == signer)
+    {
+        return Err(CoreError::new(
+            CoreErrorCode::PermissionDenied,
+            format!("pack is signed by an untrusted key {}", signer),
+        ));
+    }
+
+    let public_key =
+        decode_hex_32(&signer).map_err(|e| CoreError::new(CoreErrorCode::HashMismatch, e))?;
+    let sig = decode_hex_64(&signature.signature_hex)
+        .map_err(|e| CoreError::new(CoreErrorCode::HashMismatch, e))?;
+    let verifying_key = VerifyingKey::from_bytes(&public_key)
+        .map_err(|e| CoreError::new(CoreErrorCode::HashMismatch, e.to_string()))?;
+    verifying_key
+        .verify_strict(
+            manifest_json.as_bytes(),
+            &ed25519_dalek::Signature::from_bytes(&sig),
+        )
+        .map_err(|_| CoreError::new(CoreErrorCode::HashMismatch, "invalid manifest signature"))
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -5,17 +5,45 @@ use std::path::Path;
 const SAMPLE_LIMIT: usize = 5;
 const ROW_LIMIT: usize = 50;
 
-pub(crate) fn profile_columns(path: &Path) -> CoreResult<Vec<ColumnProfile>> {
+/// Read a CSV file as rows of fields; `rows[0]` is the header. Quoted fields
+/// may span lines.
+pub(crate) fn read_rows(path: &Path) -> CoreResult<Vec<Vec<String>>> {
     let s = crate::util::fs::read_to_string(path)?;
-    let mut lines = s.lines();
-    let header_line = lines.next().ok_or_else(|| {
+    let mut rows = Vec::new();
+    let mut record = String::new();
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if record.is_empty() {
+            if i > 0 && line.trim().is_empty() {
+                rows.push(Vec::new());
+                continue;
+            }
+            record.push_str(line);
+        } else {
+            record.push('\n');
+            record.push_str(line);
+        }
+        // An odd number of quotes means a quoted field continues on the next line.
+        if record.matches('"').count() % 2 == 1 {
+            continue;
+        }
+        rows.push(parse_csv_line(&record)?);
+        record.clear();
+    }
+    if !record.is_empty() {
+        rows.push(parse_csv_line(&record)?);
+    }
+    Ok(rows)
+}
+
+pub(crate) fn profile_rows(rows: &[Vec<String>]) -> CoreResult<Vec<ColumnProfile>> {
+    let headers = rows.first().ok_or_else(|| {
         CoreError::new(
             CoreErrorCode::ImportFailed,
             "CSV file is empty (missing header)",
         )
     })?;
 
-    let headers = parse_csv_line(header_line.trim_end_matches('\r'))?;
     if headers.is_empty() {
         return Err(CoreError::new(
             CoreErrorCode::ImportFailed,
@@ -51,8 +79,7 @@ pub(crate) fn profile_columns(path: &Path) -> CoreResult<Vec<ColumnProfile>> {
         })
         .collect();
 
-    for line in lines.take(ROW_LIMIT) {
-        let fields = parse_csv_line(line.trim_end_matches('\r'))?;
+    for fields in rows.iter().skip(1).take(ROW_LIMIT) {
         for (col_i, c) in cols.iter_mut().enumerate() {
             let v = fields.get(col_i).map(|s| s.as_str()).unwrap_or("");
             let v = v.trim();
@@ -69,6 +96,26 @@ pub(crate) fn profile_columns(path: &Path) -> CoreResult<Vec<ColumnProfile>> {
     Ok(cols)
 }
 
+/// Write rows as CSV, quoting fields that need it. Newlines inside fields
+/// are kept within quotes, which `read_rows` reads back.
+pub(crate) fn write_rows(path: &Path, rows: &[Vec<String>]) -> CoreResult<()> {
+    let mut out = String::new();
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|f| quote_field(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    crate::util::fs::write_string(path, &out)
+}
+
+fn quote_field(f: &str) -> String {
+    if f.contains([',', '"', '\n', '\r']) || f.trim() != f {
+        format!("\"{}\"", f.replace('"', "\"\""))
+    } else {
+        f.to_string()
+    }
+}
+
 fn parse_csv_line(line: &str) -> CoreResult<Vec<String>> {
     // Minimal RFC4180-ish parser good enough for fixtures and offline-first imports.
     // Handles:
@@ -0,0 +1,191 @@
+use super::sheet;
+use super::xml::{attr_value, decode_xml_entities, XmlEvent, XmlReader};
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use std::path::Path;
+
+/// Read the question table of a DOCX file as rows of cell text.
+///
+/// Word questionnaires often carry a cover/metadata table before the actual
+/// questions, and split long question lists into one table per section with
+/// the header row repeated. We pick the first table with a header and at least
+/// one data row, then append the data rows of later tables that repeat the
+/// same header.
+pub(crate) fn read_rows(path: &Path) -> CoreResult<Vec<Vec<String>>> {
+    let tmp = sheet::unzip_to_temp(path, "cs_docx")?;
+    let document = tmp.join("word").join("document.xml");
+    let xml = if document.exists() {
+        crate::util::fs::read_to_string(&document)
+    } else {
+        Err(CoreError::new(
+            CoreErrorCode::ImportFailed,
+            "docx missing word/document.xml",
+        ))
+    };
+    let _ = std::fs::remove_dir_all(&tmp);
+
+    let tables = parse_tables(&xml?);
+    select_question_rows(tables)
+}
+
+fn select_question_rows(tables: Vec<Vec<Vec<String>>>) -> CoreResult<Vec<Vec<String>>> {
+    let mut tables = tables.into_iter().map(|mut t| {
+        sheet::trim_trailing_empty(&mut t);
+        t
+    });
+
+    let mut rows = tables
+        .by_ref()
+        .find(|t| t.len() >= 2 && t[0].iter().filter(|c| !c.trim().is_empty()).count() >= 2)
+        .ok_or_else(|| {
+            CoreError::new(
+                CoreErrorCode::ImportFailed,
+                "docx contained no question table (expected a header row and at least one data row)",
+            )
+        })?;
+
+    let header = normalized_row(&rows[0]);
+    for t in tables {
+        if t.first()
+            .map(|h| normalized_row(h) == header)
+            .unwrap_or(false)
+        {
+            rows.extend(t.into_iter().skip(1));
+        }
+    }
+
+    Ok(rows)
+}
+
+fn normalized_row(row: &[String]) -> Vec<String> {
+    row.iter().map(|c| c.trim().to_lowercase()).collect()
+}
+
+fn parse_tables(xml: &str) -> Vec<Vec<Vec<String>>> {
+    let mut tables: Vec<Vec<Vec<String>>> = Vec::new();
+    let mut depth = 0usize;
+
+    let mut table: Vec<Vec<String>> = Vec::new();
+    let mut row: Option<Vec<String>> = None;
+    let mut cell: Option<String> = None;
+    let mut span = 1usize;
+    let mut paragraphs = 0usize;
+    let mut in_text = false;
+    let mut in_run = false;
+
+    for ev in XmlReader::new(xml) {
+        match ev {
+            XmlEvent::Start {
+                name: "w:tbl",
+                self_closing: false,
+                ..
+            } => {
+                depth += 1;
+                if depth == 1 {
+                    table = Vec::new();
+                }
+            }
+            XmlEvent::End { name: "w:tbl" } => {
+                if depth == 1 {
+                    tables.push(std::mem::take(&mut table));
+                }
+                depth = depth.saturating_sub(1);
+            }
+            XmlEvent::Start {
+                name: "w:tr",
+                self_closing: false,
+                ..
+            } if depth == 1 => row = Some(Vec::new()),
+            XmlEvent::End { name: "w:tr" } if depth == 1 => {
+                if let Some(r) = row.take() {
+                    table.push(r);
+                }
+            }
+            XmlEvent::Start {
+                name: "w:tc",
+                self_closing,
+                ..
+            } if depth == 1 => {
+                if self_closing {
+                    if let Some(r) = row.as_mut() {
+                        r.push(String::new());
+                    }
+                } else {
+                    cell = Some(String::new());
+                    span = 1;
+                    paragraphs = 0;
+                }
+            }
+            XmlEvent::End { name: "w:tc" } if depth == 1 => {
+                if let (Some(r), Some(text)) = (row.as_mut(), cell.take()) {
+                    r.push(text);
+                    // Horizontally merged cells occupy several grid columns.
+                    for _ in 1..span {
+                        r.push(String::new());
+                    }
+                }
+            }
+            XmlEvent::Start {
+                name: "w:gridSpan",
+                tag,
+                ..
+            } if depth == 1 && cell.is_some() => {
+                span = attr_value(tag, "w:val")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .filter(|n| *n > 0)
+                    .unwrap_or(1);
+            }
+            XmlEvent::Start {
+                name: "w:p",
+                self_closing,
+                ..
+            } => {
+                if let Some(text) = cell.as_mut() {
+                    if paragraphs > 0 {
+                        text.push('\n');
+                    }
+                    if !self_closing {
+                        paragraphs += 1;
+                    }
+                }
+            }
+            XmlEvent::Start {
+                name: "w:r",
+                self_closing: false,
+                ..
+            } => in_run = true,
+            XmlEvent::End { name: "w:r" } => in_run = false,
+            XmlEvent::Start {
+                name: "w:t",
+                self_closing: false,
+                ..
+            } => in_text = true,
+            XmlEvent::End { name: "w:t" } => in_text = false,
+            XmlEvent::Start { name: "w:tab", .. } if in_run => {
+                if let Some(text) = cell.as_mut() {
+                    text.push('\t');
+                }
+            }
+            XmlEvent::Start {
+                name: "w:br" | "w:cr",
+                ..
+            } if in_run => {
+                if let Some(text) = cell.as_mut() {
+                    text.push('\n');
+                }
+            }
+            XmlEvent::Text(t) if in_text => {
+                if let Some(text) = cell.as_mut() {
+                    text.push_str(&decode_xml_entities(t));
+                }
+            }
+            XmlEvent::CData(t) if in_text => {
+                if let Some(text) = cell.as_mut() {
+                    text.push_str(t);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    tables
+}
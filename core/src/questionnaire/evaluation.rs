@@ -0,0 +1,393 @@
+//! Matching evaluation against labeled gold sets.
+//!
+//! A gold set pairs questions with the answer bank entries a reviewer would
+//! pick for them; an [`EvalFixture`] is the answer bank they refer to. Any
+//! `MatchingEngine` configuration can be run over both with [`evaluate`],
+//! which reports precision@k, recall@k and mean reciprocal rank. An
+//! [`EvalBaseline`] stores the metrics of known configurations so tests can
+//! fail when a matching change makes rankings worse (see
+//! `tests/matching_eval_tests.rs` and `tests/fixtures/matching/`).
+//! [`EvalFixture::legacy_engine`] ranks the way matching did before BM25, as
+//! the reference the other configurations should improve on.
+//!
+//! Fixtures are JSON. Metrics are stored in basis points, as canonical JSON
+//! has no floats.
+
+use super::matching::{MatchingEngine, RankingModel, Scorer};
+use super::normalize::Normalizer;
+use crate::answer_bank::{AnswerBankEntry, EntryStatus};
+use crate::audit::canonical::CanonicalJson;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::util::json::{JsonObject, JsonValue};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+/// In-memory answer bank for evaluation.
+///
+/// ```json
+/// {"synonyms": [["2fa", "mfa"]],
+///  "entries": [{"entry_id": "enc-rest", "question": "...", "answer_short": "...",
+///               "answer_long": "...", "aliases": ["..."]}]}
+/// ```
+/// `synonyms`, `answer_long` and `aliases` are optional.
+#[derive(Debug, Clone)]
+pub struct EvalFixture {
+    pub entries: Vec<AnswerBankEntry>,
+    /// Alias texts keyed by entry_id.
+    pub aliases: HashMap<String, Vec<String>>,
+    /// (term, canonical) pairs added to the normalizer.
+    pub synonyms: Vec<(String, String)>,
+}
+
+impl EvalFixture {
+    pub fn from_json_str(s: &str) -> CoreResult<Self> {
+        let v = JsonValue::parse(s)?;
+        let o = v.as_object()?;
+
+        let mut synonyms = Vec::new();
+        if let Some(list) = o.get("synonyms") {
+            for pair in list.as_array()? {
+                match pair.as_array()? {
+                    [term, canonical] => synonyms.push((term.as_string()?, canonical.as_string()?)),
+                    _ => return Err(invalid("synonyms must be [term, canonical] pairs")),
+                }
+            }
+        }
+
+        let mut entries = Vec::new();
+        let mut aliases = HashMap::new();
+        let mut seen = HashSet::new();
+        for e in o.get_array("entries")? {
+            let e = e.as_object()?;
+            let entry_id = e.get_string("entry_id")?;
+            if !seen.insert(entry_id.clone()) {
+                return Err(invalid(format!("duplicate entry_id: {entry_id}")));
+            }
+            let list = match e.get("aliases") {
+                Some(v) => v
+                    .as_array()?
+                    .iter()
+                    .map(|a| a.as_string())
+                    .collect::<CoreResult<Vec<String>>>()?,
+                None => Vec::new(),
+            };
+            if !list.is_empty() {
+                aliases.insert(entry_id.clone(), list);
+            }
+            entries.push(AnswerBankEntry {
+                entry_id,
+                vault_id: "eval".to_string(),
+                question_canonical: e.get_string("question")?,
+                answer_short: e.get_string("answer_short")?,
+                answer_long: optional_string(&e, "answer_long")?,
+                notes: None,
+                evidence_links: Vec::new(),
+                owner: "eval".to_string(),
+                last_reviewed_at: None,
+                tags: Vec::new(),
+                source: "import".to_string(),
+                content_hash: String::new(),
+                created_at: String::new(),
+                updated_at: String::new(),
+                status: EntryStatus::Approved,
+                last_edited_by: None,
+                translations: Vec::new(),
+            });
+        }
+
+        Ok(Self {
+            entries,
+            aliases,
+            synonyms,
+        })
+    }
+
+    /// Default pipeline plus the fixture's synonyms
+    pub fn normalizer(&self) -> Normalizer {
+        let mut normalizer = Normalizer::default();
+        for (term, canonical) in &self.synonyms {
+            normalizer.add_synonym(term, canonical);
+        }
+        normalizer
+    }
+
+    /// Engine over the whole fixture ranking with `scorer`
+    pub fn engine(&self, scorer: Arc<dyn Scorer>) -> MatchingEngine {
+        MatchingEngine::with_scorer(self.entries.clone(), self.normalizer(), scorer)
+            .with_aliases(&self.aliases)
+    }
+
+    /// Engine ranking the way matching did before BM25: Jaccard over plain
+    /// tokens, without stop words, stemming, synonyms or aliases.
+    pub fn legacy_engine(&self) -> MatchingEngine {
+        MatchingEngine::with_normalizer(
+            self.entries.clone(),
+            RankingModel::Jaccard,
+            Normalizer::tokenize_only(),
+        )
+    }
+}
+
+/// A question and the entries that correctly answer it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoldCase {
+    pub question: String,
+    pub expected: Vec<String>,
+}
+
+/// Parse a gold set: `[{"question": "...", "expected": ["entry_id", ...]}]`.
+pub fn parse_gold_set(s: &str) -> CoreResult<Vec<GoldCase>> {
+    let v = JsonValue::parse(s)?;
+    let mut out = Vec::new();
+    for c in v.as_array()? {
+        let c = c.as_object()?;
+        let expected = c
+            .get_array("expected")?
+            .iter()
+            .map(|id| id.as_string())
+            .collect::<CoreResult<Vec<String>>>()?;
+        if expected.is_empty() {
+            return Err(invalid("gold case has no expected entries"));
+        }
+        out.push(GoldCase {
+            question: c.get_string("question")?,
+            expected,
+        });
+    }
+    if out.is_empty() {
+        return Err(invalid("gold set is empty"));
+    }
+    Ok(out)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvalMetrics {
+    /// Mean share of the top k suggestions that are expected entries.
+    pub precision_at_k: f64,
+    /// Mean share of expected entries found in the top k.
+    pub recall_at_k: f64,
+    /// Mean of 1 / rank of the first expected entry (0 when none is ranked).
+    pub mrr: f64,
+}
+
+/// How one gold case ranked.
+#[derive(Debug, Clone)]
+pub struct EvalCaseResult {
+    pub question: String,
+    pub expected: Vec<String>,
+    /// Entry ids of the top k suggestions, best first.
+    pub top_k: Vec<String>,
+    /// 1-based rank of the first expected entry among all suggestions.
+    pub first_relevant_rank: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EvalReport {
+    pub k: usize,
+    pub metrics: EvalMetrics,
+    pub cases: Vec<EvalCaseResult>,
+}
+
+/// Run every gold case through `engine` and compute metrics at `k`.
+pub fn evaluate(engine: &MatchingEngine, gold: &[GoldCase], k: usize) -> CoreResult<EvalReport> {
+    if k == 0 {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "k must be > 0",
+        ));
+    }
+    if gold.is_empty() {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "gold set is empty",
+        ));
+    }
+
+    let mut cases = Vec::new();
+    let (mut precision, mut recall, mut rr) = (0.0, 0.0, 0.0);
+    for case in gold {
+        let ranked: Vec<String> = engine
+            .get_suggestions(&case.question, usize::MAX)?
+            .into_iter()
+            .map(|s| s.answer_bank_entry_id)
+            .collect();
+        let expected: HashSet<&String> = case.expected.iter().collect();
+        let top_k: Vec<String> = ranked.iter().take(k).cloned().collect();
+        let hits = top_k.iter().filter(|id| expected.contains(id)).count();
+        let first_relevant_rank = ranked
+            .iter()
+            .position(|id| expected.contains(id))
+            .map(|i| i + 1);
+
+        precision += hits as f64 / k as f64;
+        recall += hits as f64 / expected.len() as f64;
+        rr += first_relevant_rank.map_or(0.0, |r| 1.0 / r as f64);
+        cases.push(EvalCaseResult {
+            question: case.question.clone(),
+            expected: case.expected.clone(),
+            top_k,
+            first_relevant_rank,
+        });
+    }
+
+    let n = gold.len() as f64;
+    Ok(EvalReport {
+        k,
+        metrics: EvalMetrics {
+            precision_at_k: precision / n,
+            recall_at_k: recall / n,
+            mrr: rr / n,
+        },
+        cases,
+    })
+}
+
+/// Stored metrics per named engine configuration, all measured at one k.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalBaseline {
+    pub k: usize,
+    pub configs: BTreeMap<String, EvalMetrics>,
+}
+
+impl EvalBaseline {
+    pub fn to_canonical_json(&self) -> CanonicalJson {
+        let mut configs = CanonicalJson::object();
+        for (name, m) in &self.configs {
+            let mut o = CanonicalJson::object();
+            o.insert(
+                "precision_at_k_bp",
+                CanonicalJson::Number(bp(m.precision_at_k)),
+            );
+            o.insert("recall_at_k_bp", CanonicalJson::Number(bp(m.recall_at_k)));
+            o.insert("mrr_bp", CanonicalJson::Number(bp(m.mrr)));
+            configs.insert(name.clone(), o);
+        }
+        let mut o = CanonicalJson::object();
+        o.insert("k", CanonicalJson::Number(self.k as i64));
+        o.insert("configs", configs);
+        o
+    }
+
+    pub fn from_json_str(s: &str) -> CoreResult<Self> {
+        let v = JsonValue::parse(s)?;
+        let o = v.as_object()?;
+        let k = usize::try_from(o.get_i64("k")?).map_err(|_| invalid("k must be > 0"))?;
+        let mut configs = BTreeMap::new();
+        match o.get("configs") {
+            Some(JsonValue::Object(fields)) => {
+                for (name, m) in fields {
+                    let m = m.as_object()?;
+                    configs.insert(
+                        name.clone(),
+                        EvalMetrics {
+                            precision_at_k: from_bp(m.get_i64("precision_at_k_bp")?),
+                            recall_at_k: from_bp(m.get_i64("recall_at_k_bp")?),
+                            mrr: from_bp(m.get_i64("mrr_bp")?),
+                        },
+                    );
+                }
+            }
+            _ => return Err(invalid("expected object field configs")),
+        }
+        Ok(Self { k, configs })
+    }
+
+    /// Metrics of `config` that fell more than `tolerance` below the
+    /// baseline, as readable messages; empty when nothing regressed.
+    /// A configuration missing from the baseline is reported too.
+    pub fn regressions(&self, config: &str, report: &EvalReport, tolerance: f64) -> Vec<String> {
+        if report.k != self.k {
+            return vec![format!(
+                "{config}: evaluated at k={} but the baseline is at k={}",
+                report.k, self.k
+            )];
+        }
+        let Some(base) = self.configs.get(config) else {
+            return vec![format!("{config}: no baseline recorded")];
+        };
+        let got = report.metrics;
+        [
+            ("precision@k", base.precision_at_k, got.precision_at_k),
+            ("recall@k", base.recall_at_k, got.recall_at_k),
+            ("MRR", base.mrr, got.mrr),
+        ]
+        .into_iter()
+        // Compare at stored precision so an unchanged run never regresses.
+        .filter(|(_, base, got)| from_bp(bp(*got)) < base - tolerance)
+        .map(|(metric, base, got)| format!("{config}: {metric} dropped from {base:.4} to {got:.4}"))
+        .collect()
+    }
+}
+
+fn bp(x: f64) -> i64 {
+    (x * 10_000.0).round() as i64
+}
+
+fn from_bp(n: i64) -> f64 {
+    n as f64 / 10_000.0
+}
+
+fn optional_string(o: &JsonObject<'_>, key: &str) -> CoreResult<String> {
+    match o.get(key) {
+        Some(v) => v.as_string(),
+        None => Ok(String::new()),
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> CoreError {
+    CoreError::new(CoreErrorCode::ValidationError, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_and_regressions() {
+        let fixture = EvalFixture::from_json_str(
+            r#"{"entries": [
+                {"entry_id": "a", "question": "Is there a firewall?", "answer_short": "Yes"},
+                {"entry_id": "b", "question": "Is data encrypted?", "answer_short": "Yes"},
+                {"entry_id": "c", "question": "Are backups restored?", "answer_short": "Yes"}
+            ]}"#,
+        )
+        .unwrap();
+        let gold = parse_gold_set(
+            r#"[{"question": "firewall", "expected": ["a"]},
+                {"question": "restore", "expected": ["b"]}]"#,
+        )
+        .unwrap();
+
+        let report = evaluate(&fixture.engine(RankingModel::Bm25.scorer()), &gold, 2).unwrap();
+        // Case 1 ranks its only entry first; case 2 never finds it.
+        assert_eq!(report.cases[0].first_relevant_rank, Some(1));
+        assert_eq!(report.cases[1].first_relevant_rank, None);
+        assert_eq!(
+            report.metrics,
+            EvalMetrics {
+                precision_at_k: 0.25,
+                recall_at_k: 0.5,
+                mrr: 0.5,
+            }
+        );
+
+        let mut baseline = EvalBaseline {
+            k: 2,
+            configs: BTreeMap::from([("bm25".to_string(), report.metrics)]),
+        };
+        let json = baseline.to_canonical_json().encode();
+        assert_eq!(EvalBaseline::from_json_str(&json).unwrap(), baseline);
+        assert!(baseline.regressions("bm25", &report, 0.0).is_empty());
+        assert_eq!(baseline.regressions("jaccard", &report, 0.0).len(), 1);
+
+        baseline.configs.get_mut("bm25").unwrap().mrr = 0.6;
+        assert!(baseline.regressions("bm25", &report, 0.2).is_empty());
+        assert_eq!(
+            baseline.regressions("bm25", &report, 0.0),
+            vec!["bm25: MRR dropped from 0.6000 to 0.5000".to_string()]
+        );
+
+        assert!(parse_gold_set("[]").is_err());
+        assert!(evaluate(&fixture.engine(RankingModel::Bm25.scorer()), &gold, 0).is_err());
+    }
+}
@@ -0,0 +1,380 @@
+//! Structured match explanations.
+//!
+//! Besides the one-line summary shown in suggestion lists, every suggestion
+//! says which question terms matched, where they occur in the question and in
+//! each entry field (character offsets, end exclusive, for highlighting),
+//! whether a stem or a synonym produced the match, and how much each field
+//! contributed to the score. Explanations are stored with suggestions as
+//! canonical JSON, with scores in basis points.
+
+use super::normalize::{SourceToken, TermOrigin};
+use crate::audit::canonical::CanonicalJson;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::util::json::{JsonObject, JsonValue};
+
+/// Answer bank entry field, in scoring order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchField {
+    Question,
+    AnswerShort,
+    AnswerLong,
+    /// A previously accepted question wording.
+    Alias,
+}
+
+impl MatchField {
+    pub const ALL: [MatchField; 4] = [
+        MatchField::Question,
+        MatchField::AnswerShort,
+        MatchField::AnswerLong,
+        MatchField::Alias,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchField::Question => "question",
+            MatchField::AnswerShort => "answer_short",
+            MatchField::AnswerLong => "answer_long",
+            MatchField::Alias => "alias",
+        }
+    }
+
+    pub fn parse(s: &str) -> CoreResult<Self> {
+        match s {
+            "question" => Ok(MatchField::Question),
+            "answer_short" => Ok(MatchField::AnswerShort),
+            "answer_long" => Ok(MatchField::AnswerLong),
+            "alias" => Ok(MatchField::Alias),
+            other => Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!("unknown match field: {other}"),
+            )),
+        }
+    }
+}
+
+/// Where a term occurs in a text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextSpan {
+    /// Character offsets into the text, end exclusive.
+    pub start: usize,
+    pub end: usize,
+    /// The text as written, e.g. "encrypted" for the term "encrypt".
+    pub text: String,
+    pub origin: TermOrigin,
+}
+
+/// Where a term occurs in an entry field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSpan {
+    pub field: MatchField,
+    /// Which alias (in `ab_list_aliases` order) for `MatchField::Alias`.
+    pub alias_index: Option<usize>,
+    pub span: TextSpan,
+}
+
+/// A normalized question term found in the entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TermMatch {
+    pub term: String,
+    pub question_spans: Vec<TextSpan>,
+    pub field_spans: Vec<FieldSpan>,
+}
+
+/// Share of the score contributed by one field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldScore {
+    pub field: MatchField,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchExplanation {
+    /// One-line summary, e.g. "42% weighted term match: 'access', 'control'".
+    pub summary: String,
+    /// Matched terms in order of first appearance in the question.
+    pub terms: Vec<TermMatch>,
+    /// Fields with a non-zero contribution, in field order.
+    pub field_scores: Vec<FieldScore>,
+}
+
+impl MatchExplanation {
+    pub fn to_canonical_json(&self) -> CanonicalJson {
+        let mut o = CanonicalJson::object();
+        o.insert("summary", CanonicalJson::String(self.summary.clone()));
+        o.insert(
+            "terms",
+            CanonicalJson::Array(
+                self.terms
+                    .iter()
+                    .map(|t| {
+                        let mut m = CanonicalJson::object();
+                        m.insert("term", CanonicalJson::String(t.term.clone()));
+                        m.insert(
+                            "question_spans",
+                            CanonicalJson::Array(t.question_spans.iter().map(span_json).collect()),
+                        );
+                        m.insert(
+                            "field_spans",
+                            CanonicalJson::Array(
+                                t.field_spans
+                                    .iter()
+                                    .map(|f| {
+                                        let mut j = span_json(&f.span);
+                                        j.insert(
+                                            "field",
+                                            CanonicalJson::String(f.field.as_str().to_string()),
+                                        );
+                                        j.insert(
+                                            "alias_index",
+                                            match f.alias_index {
+                                                Some(i) => CanonicalJson::Number(i as i64),
+                                                None => CanonicalJson::Null,
+                                            },
+                                        );
+                                        j
+                                    })
+                                    .collect(),
+                            ),
+                        );
+                        m
+                    })
+                    .collect(),
+            ),
+        );
+        o.insert(
+            "field_scores",
+            CanonicalJson::Array(
+                self.field_scores
+                    .iter()
+                    .map(|f| {
+                        let mut m = CanonicalJson::object();
+                        m.insert("field", CanonicalJson::String(f.field.as_str().to_string()));
+                        // Canonical JSON has no floats; scores are kept in basis points.
+                        m.insert(
+                            "score_bp",
+                            CanonicalJson::Number((f.score * 10_000.0).round() as i64),
+                        );
+                        m
+                    })
+                    .collect(),
+            ),
+        );
+        o
+    }
+
+    pub fn from_json_str(s: &str) -> CoreResult<Self> {
+        let v = JsonValue::parse(s)?;
+        let o = v.as_object()?;
+
+        let mut terms = Vec::new();
+        for t in o.get_array("terms")? {
+            let t = t.as_object()?;
+            let mut question_spans = Vec::new();
+            for s in t.get_array("question_spans")? {
+                question_spans.push(span_from_json(&s.as_object()?)?);
+            }
+            let mut field_spans = Vec::new();
+            for f in t.get_array("field_spans")? {
+                let f = f.as_object()?;
+                field_spans.push(FieldSpan {
+                    field: stored(MatchField::parse(&f.get_string("field")?))?,
+                    alias_index: match f.get("alias_index") {
+                        Some(JsonValue::Number(n)) => Some(offset(*n)?),
+                        _ => None,
+                    },
+                    span: span_from_json(&f)?,
+                });
+            }
+            terms.push(TermMatch {
+                term: t.get_string("term")?,
+                question_spans,
+                field_spans,
+            });
+        }
+
+        let mut field_scores = Vec::new();
+        for f in o.get_array("field_scores")? {
+            let f = f.as_object()?;
+            field_scores.push(FieldScore {
+                field: stored(MatchField::parse(&f.get_string("field")?))?,
+                score: f.get_i64("score_bp")? as f64 / 10_000.0,
+            });
+        }
+
+        Ok(Self {
+            summary: o.get_string("summary")?,
+            terms,
+            field_scores,
+        })
+    }
+}
+
+/// Field scores from per-field contributions indexed like `MatchField::ALL`,
+/// keeping only fields that contributed.
+pub(crate) fn field_scores(per_field: [f64; 4]) -> Vec<FieldScore> {
+    MatchField::ALL
+        .iter()
+        .zip(per_field)
+        .filter(|(_, score)| *score > 0.0)
+        .map(|(field, score)| FieldScore {
+            field: *field,
+            score,
+        })
+        .collect()
+}
+
+/// Question terms found in an entry, with their spans in the question and in
+/// each field. `fields` holds the canonical question, short answer and long
+/// answer texts with their normalized tokens; `aliases` the alias texts with
+/// theirs.
+pub(crate) fn term_matches(
+    question: &str,
+    question_tokens: &[SourceToken],
+    fields: [(&str, &[SourceToken]); 3],
+    aliases: &[(&str, &[SourceToken])],
+) -> Vec<TermMatch> {
+    let mut out: Vec<TermMatch> = Vec::new();
+    for q in question_tokens {
+        if out.iter().any(|t| t.term == q.term) {
+            continue;
+        }
+
+        let mut field_spans = Vec::new();
+        let texts = fields
+            .iter()
+            .zip(MatchField::ALL)
+            .map(|((text, tokens), field)| (field, None, *text, *tokens))
+            .chain(
+                aliases
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (text, tokens))| (MatchField::Alias, Some(i), *text, *tokens)),
+            );
+        for (field, alias_index, text, tokens) in texts {
+            for t in tokens.iter().filter(|t| t.term == q.term) {
+                field_spans.push(FieldSpan {
+                    field,
+                    alias_index,
+                    span: text_span(text, t),
+                });
+            }
+        }
+        if field_spans.is_empty() {
+            continue;
+        }
+
+        out.push(TermMatch {
+            term: q.term.clone(),
+            question_spans: question_tokens
+                .iter()
+                .filter(|t| t.term == q.term)
+                .map(|t| text_span(question, t))
+                .collect(),
+            field_spans,
+        });
+    }
+    out
+}
+
+fn text_span(text: &str, token: &SourceToken) -> TextSpan {
+    TextSpan {
+        start: token.start,
+        end: token.end,
+        text: text
+            .chars()
+            .skip(token.start)
+            .take(token.end - token.start)
+            .collect(),
+        origin: token.origin,
+    }
+}
+
+fn span_json(span: &TextSpan) -> CanonicalJson {
+    let mut o = CanonicalJson::object();
+    o.insert("start", CanonicalJson::Number(span.start as i64));
+    o.insert("end", CanonicalJson::Number(span.end as i64));
+    o.insert("text", CanonicalJson::String(span.text.clone()));
+    o.insert(
+        "origin",
+        CanonicalJson::String(span.origin.as_str().to_string()),
+    );
+    o
+}
+
+fn span_from_json(o: &JsonObject<'_>) -> CoreResult<TextSpan> {
+    Ok(TextSpan {
+        start: offset(o.get_i64("start")?)?,
+        end: offset(o.get_i64("end")?)?,
+        text: o.get_string("text")?,
+        origin: stored(TermOrigin::parse(&o.get_string("origin")?))?,
+    })
+}
+
+fn offset(n: i64) -> CoreResult<usize> {
+    usize::try_from(n).map_err(|_| {
+        CoreError::new(
+            CoreErrorCode::CorruptVault,
+            "negative offset in match explanation",
+        )
+    })
+}
+
+/// Unknown enum values in stored JSON mean the vault is damaged, not that the
+/// caller passed bad input.
+fn stored<T>(r: CoreResult<T>) -> CoreResult<T> {
+    r.map_err(|e| CoreError::new(CoreErrorCode::CorruptVault, e.message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explanation_json_round_trips() {
+        let span = |start, end, text: &str, origin| TextSpan {
+            start,
+            end,
+            text: text.to_string(),
+            origin,
+        };
+        let explanation = MatchExplanation {
+            summary: "80% weighted term match: 'mfa'".to_string(),
+            terms: vec![TermMatch {
+                term: "mfa".to_string(),
+                question_spans: vec![span(
+                    3,
+                    30,
+                    "multi-factor authentication",
+                    TermOrigin::Synonym,
+                )],
+                field_spans: vec![
+                    FieldSpan {
+                        field: MatchField::Question,
+                        alias_index: None,
+                        span: span(12, 15, "MFA", TermOrigin::Exact),
+                    },
+                    FieldSpan {
+                        field: MatchField::Alias,
+                        alias_index: Some(1),
+                        span: span(0, 3, "2FA", TermOrigin::Synonym),
+                    },
+                ],
+            }],
+            field_scores: vec![
+                FieldScore {
+                    field: MatchField::Question,
+                    score: 0.6,
+                },
+                FieldScore {
+                    field: MatchField::Alias,
+                    score: 0.2,
+                },
+            ],
+        };
+
+        let json = explanation.to_canonical_json().encode();
+        assert_eq!(MatchExplanation::from_json_str(&json).unwrap(), explanation);
+        assert!(MatchExplanation::from_json_str("{\"summary\":\"x\"}").is_err());
+    }
+}
@@ -1,11 +1,275 @@
 //! Matching Algorithm (Phase 2.4)
 //!
-//! Token-overlap scoring for questionnaire answer suggestions.
-//! Deterministic normalization ensures reproducible scoring.
+//! Ranks answer bank entries for a questionnaire question. Scoring is done by
+//! a [`Scorer`]; the built-in ones are BM25F (the default), which weights
+//! terms by how rare they are in the answer bank and favors the canonical
+//! question field, the original Jaccard token overlap, kept so rankings can be
+//! compared, and the offline scorers in [`super::scorers`] (character n-grams,
+//! local word embeddings, weighted blends). Every suggestion records the name
+//! and version of the scorer that produced it.
+//! Aliases (question wordings previously accepted for an entry) count as an
+//! extra question field, and an alias identical to the question scores 1.0.
+//! Text goes through a [`Normalizer`] (stop words, stemming, vault synonyms)
+//! before scoring; deterministic normalization ensures reproducible scoring.
+//! Each suggestion carries a structured [`MatchExplanation`] (matched spans,
+//! per-field score breakdown) besides its one-line summary.
+//! Answers overdue for review (see `answer_bank::ab_overdue_reviews`) keep
+//! their place in the candidate set but are down-ranked and flagged.
+//! Suggested answers have their `{{variable}}` placeholders resolved (see
+//! `answer_bank::TemplateVariables`); scoring uses the stored text.
+//! `AnswerBankMatcher` matches in one locale: entries are scored and
+//! suggested in their variant for it, or in their default text without one,
+//! with the normalizer for that locale (see [`Normalizer::for_locale`]).
 
-use crate::answer_bank::AnswerBankEntry;
+use super::explain::{self, MatchExplanation};
+use super::normalize::{Normalizer, SourceToken};
+use super::scorers::{BlendedScorer, CharNgramScorer};
+use crate::answer_bank::{self, AnswerBankEntry, TemplateVariables};
 use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
-use std::collections::HashSet;
+use crate::domain::time::today_utc_date;
+use crate::storage::db::SqliteDb;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+/// BM25 term-frequency saturation.
+const BM25_K1: f64 = 1.2;
+/// BM25 length normalization strength.
+const BM25_B: f64 = 0.75;
+/// Field weights for canonical question, short answer, long answer and aliases.
+const FIELD_WEIGHTS: [f64; 4] = [3.0, 1.0, 0.5, 3.0];
+/// Characters treated as word separators, besides whitespace.
+const PUNCTUATION: &str = ".,!?;:'\"()—-[]{}";
+/// Score multiplier for answers overdue for review.
+const STALE_SCORE_FACTOR: f64 = 0.8;
+/// Weights of BM25 and character trigrams in the `blended` model.
+const BLEND_WEIGHTS: [f64; 2] = [0.7, 0.3];
+/// Full-text candidates reranked per question by `AnswerBankMatcher`.
+pub const CANDIDATE_LIMIT: i64 = 200;
+
+/// Built-in ranking models, selectable by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RankingModel {
+    /// BM25F over question, short answer, long answer and alias fields.
+    #[default]
+    Bm25,
+    /// Token-set overlap over all fields concatenated.
+    Jaccard,
+    /// Character trigram similarity to the canonical question and aliases.
+    CharNgram,
+    /// BM25 and character trigrams, weighted 0.7 / 0.3.
+    Blended,
+}
+
+impl RankingModel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RankingModel::Bm25 => "bm25",
+            RankingModel::Jaccard => "jaccard",
+            RankingModel::CharNgram => "char_ngram",
+            RankingModel::Blended => "blended",
+        }
+    }
+
+    pub fn parse(s: &str) -> CoreResult<Self> {
+        match s {
+            "bm25" => Ok(RankingModel::Bm25),
+            "jaccard" => Ok(RankingModel::Jaccard),
+            "char_ngram" => Ok(RankingModel::CharNgram),
+            "blended" => Ok(RankingModel::Blended),
+            other => Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!("unknown ranking model: {other}"),
+            )),
+        }
+    }
+
+    /// Scorer implementing this model
+    pub fn scorer(&self) -> Arc<dyn Scorer> {
+        match self {
+            RankingModel::Bm25 => Arc::new(Bm25Scorer),
+            RankingModel::Jaccard => Arc::new(JaccardScorer),
+            RankingModel::CharNgram => Arc::new(CharNgramScorer::default()),
+            RankingModel::Blended => Arc::new(BlendedScorer::builtin(
+                RankingModel::Blended.as_str(),
+                vec![
+                    (Arc::new(Bm25Scorer), BLEND_WEIGHTS[0]),
+                    (Arc::new(CharNgramScorer::default()), BLEND_WEIGHTS[1]),
+                ],
+            )),
+        }
+    }
+}
+
+/// Scores how well one answer bank entry answers a question.
+///
+/// Implementations must be deterministic and run offline (no network, no
+/// GPU). `name` and `version` are stored with every suggestion, so bump the
+/// version whenever the same inputs could score differently.
+pub trait Scorer: Send + Sync {
+    fn name(&self) -> &str;
+    fn version(&self) -> &str;
+    /// Score entry `doc` of the corpus for the query, in 0.0..=1.0
+    fn score(&self, corpus: &MatchCorpus, query: &MatchQuery, doc: usize) -> ScoredMatch;
+}
+
+/// A scorer's verdict on one entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredMatch {
+    pub score: f64,
+    /// One-line explanation (see `MatchExplanation::summary`).
+    pub summary: String,
+    /// How much each field contributed; should add up to `score`.
+    pub field_scores: Vec<explain::FieldScore>,
+}
+
+/// The question being matched, raw and normalized.
+#[derive(Debug, Clone)]
+pub struct MatchQuery {
+    pub text: String,
+    pub tokens: Vec<String>,
+}
+
+/// Answer bank entries prepared for scoring: normalized fields, aliases and
+/// the term statistics BM25 needs.
+pub struct MatchCorpus {
+    entries: Vec<AnswerBankEntry>,
+    /// Alias texts per entry, as accepted.
+    aliases: Vec<Vec<String>>,
+    /// Normalized tokens per entry, one list per weighted field.
+    fields: Vec<[Vec<String>; 4]>,
+    /// Normalized question, short and long answer tokens with their sources.
+    sources: Vec<[Vec<SourceToken>; 3]>,
+    /// Normalized tokens of each alias with their sources, per entry.
+    alias_sources: Vec<Vec<Vec<SourceToken>>>,
+    /// Normalized tokens of each alias, per entry.
+    alias_tokens: Vec<Vec<Vec<String>>>,
+    /// Average token count per field across the answer bank.
+    avg_field_len: [f64; 4],
+    /// Number of entries containing each token in any field.
+    doc_freq: HashMap<String, usize>,
+    /// Entry count used for IDF; larger than the bank when it is a candidate subset.
+    corpus_size: usize,
+}
+
+impl MatchCorpus {
+    fn build(
+        entries: Vec<AnswerBankEntry>,
+        normalizer: &Normalizer,
+        aliases: &HashMap<String, Vec<String>>,
+    ) -> Self {
+        let aliases: Vec<Vec<String>> = entries
+            .iter()
+            .map(|e| aliases.get(&e.entry_id).cloned().unwrap_or_default())
+            .collect();
+        let alias_sources: Vec<Vec<Vec<SourceToken>>> = aliases
+            .iter()
+            .map(|list| list.iter().map(|a| normalizer.source_tokens(a)).collect())
+            .collect();
+        let alias_tokens: Vec<Vec<Vec<String>>> = alias_sources
+            .iter()
+            .map(|list| list.iter().map(|a| terms(a)).collect())
+            .collect();
+        let sources: Vec<[Vec<SourceToken>; 3]> = entries
+            .iter()
+            .map(|e| {
+                [
+                    normalizer.source_tokens(&e.question_canonical),
+                    normalizer.source_tokens(&e.answer_short),
+                    normalizer.source_tokens(&e.answer_long),
+                ]
+            })
+            .collect();
+        let fields: Vec<[Vec<String>; 4]> = sources
+            .iter()
+            .zip(&alias_tokens)
+            .map(|(s, a)| [terms(&s[0]), terms(&s[1]), terms(&s[2]), a.concat()])
+            .collect();
+
+        let mut avg_field_len = [0.0; 4];
+        let mut doc_freq: HashMap<String, usize> = HashMap::new();
+        for doc in &fields {
+            for (f, tokens) in doc.iter().enumerate() {
+                avg_field_len[f] += tokens.len() as f64;
+            }
+            let unique: HashSet<&String> = doc.iter().flatten().collect();
+            for t in unique {
+                *doc_freq.entry(t.clone()).or_insert(0) += 1;
+            }
+        }
+        if !fields.is_empty() {
+            for avg in avg_field_len.iter_mut() {
+                *avg /= fields.len() as f64;
+            }
+        }
+
+        Self {
+            entries,
+            aliases,
+            corpus_size: fields.len(),
+            fields,
+            sources,
+            alias_sources,
+            alias_tokens,
+            avg_field_len,
+            doc_freq,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entry(&self, doc: usize) -> &AnswerBankEntry {
+        &self.entries[doc]
+    }
+
+    /// Accepted question wordings of an entry
+    pub fn aliases(&self, doc: usize) -> &[String] {
+        &self.aliases[doc]
+    }
+
+    /// Normalized question, short answer, long answer and alias tokens
+    pub fn fields(&self, doc: usize) -> &[Vec<String>; 4] {
+        &self.fields[doc]
+    }
+
+    /// Matched question terms with their spans in the question and entry
+    fn term_matches(
+        &self,
+        question: &str,
+        q_sources: &[SourceToken],
+        doc: usize,
+    ) -> Vec<explain::TermMatch> {
+        let e = &self.entries[doc];
+        let s = &self.sources[doc];
+        let aliases: Vec<(&str, &[SourceToken])> = self.aliases[doc]
+            .iter()
+            .zip(&self.alias_sources[doc])
+            .map(|(a, t)| (a.as_str(), t.as_slice()))
+            .collect();
+        explain::term_matches(
+            question,
+            q_sources,
+            [
+                (&e.question_canonical, &s[0]),
+                (&e.answer_short, &s[1]),
+                (&e.answer_long, &s[2]),
+            ],
+            &aliases,
+        )
+    }
+
+    /// Inverse document frequency (BM25 variant, always positive)
+    pub fn idf(&self, term: &str) -> f64 {
+        let n = self.corpus_size as f64;
+        let df = self.doc_freq.get(term).copied().unwrap_or(0) as f64;
+        (1.0 + (n - df + 0.5) / (df + 0.5)).ln()
+    }
+}
 
 /// A single match suggestion with score and explanation
 #[derive(Debug, Clone)]
@@ -14,21 +278,104 @@ pub struct MatchSuggestion {
     pub score: f64,
     pub normalized_question: String,
     pub normalized_answer: String,
+    /// One-line explanation; same as `explanation.summary`.
     pub confidence_explanation: String,
+    pub explanation: MatchExplanation,
+    pub scorer_name: String,
+    pub scorer_version: String,
+    /// The entry is overdue for review; its score was scaled down.
+    pub stale: bool,
+    /// Answers with vault variables resolved.
+    pub answer_short: String,
+    pub answer_long: String,
+    /// Placeholders in the answers without a vault variable.
+    pub unresolved_variables: Vec<String>,
 }
 
 /// Matching engine for questionnaire answer suggestions
 pub struct MatchingEngine {
-    answer_bank: Vec<AnswerBankEntry>,
+    corpus: MatchCorpus,
+    normalizer: Normalizer,
+    scorer: Arc<dyn Scorer>,
+    stale: HashSet<String>,
+    variables: TemplateVariables,
 }
 
 impl MatchingEngine {
-    /// Create a new matching engine with the given answer bank
+    /// Create a new matching engine with the given answer bank, ranking with BM25
     pub fn new(answer_bank: Vec<AnswerBankEntry>) -> Self {
-        Self { answer_bank }
+        Self::with_model(answer_bank, RankingModel::default())
     }
 
-    /// Normalize text for matching: lowercase, remove punctuation, split into tokens
+    /// Create a new matching engine that ranks with the given model
+    pub fn with_model(answer_bank: Vec<AnswerBankEntry>, model: RankingModel) -> Self {
+        Self::with_normalizer(answer_bank, model, Normalizer::default())
+    }
+
+    /// Create a new matching engine with an explicit normalization pipeline
+    /// (see `synonyms::load_normalizer` for the vault's configured one)
+    pub fn with_normalizer(
+        answer_bank: Vec<AnswerBankEntry>,
+        model: RankingModel,
+        normalizer: Normalizer,
+    ) -> Self {
+        Self::with_scorer(answer_bank, normalizer, model.scorer())
+    }
+
+    /// Create a new matching engine that ranks with any scorer
+    pub fn with_scorer(
+        answer_bank: Vec<AnswerBankEntry>,
+        normalizer: Normalizer,
+        scorer: Arc<dyn Scorer>,
+    ) -> Self {
+        Self {
+            corpus: MatchCorpus::build(answer_bank, &normalizer, &HashMap::new()),
+            normalizer,
+            scorer,
+            stale: HashSet::new(),
+            variables: TemplateVariables::default(),
+        }
+    }
+
+    /// Add question aliases, keyed by entry_id
+    pub fn with_aliases(mut self, aliases: &HashMap<String, Vec<String>>) -> Self {
+        let corpus_size = self.corpus.corpus_size;
+        let entries = std::mem::take(&mut self.corpus.entries);
+        self.corpus = MatchCorpus::build(entries, &self.normalizer, aliases);
+        self.with_corpus_size(corpus_size)
+    }
+
+    /// Down-rank and flag these entries (by entry_id) as overdue for review
+    pub fn with_stale(mut self, entry_ids: HashSet<String>) -> Self {
+        self.stale = entry_ids;
+        self
+    }
+
+    /// Resolve `{{name}}` placeholders in suggested answers
+    pub fn with_variables(mut self, variables: TemplateVariables) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    /// Compute IDF as if the bank held `n` entries.
+    ///
+    /// Used when the engine only sees full-text candidates: entries outside the
+    /// candidate set share no query term, so document frequencies of query
+    /// terms are unchanged and only the corpus size needs restoring.
+    pub fn with_corpus_size(mut self, n: usize) -> Self {
+        self.corpus.corpus_size = n.max(self.corpus.len());
+        self
+    }
+
+    /// Scorer this engine ranks with
+    pub fn scorer(&self) -> &dyn Scorer {
+        self.scorer.as_ref()
+    }
+
+    /// Tokenize text for matching: lowercase, remove punctuation, split into tokens
+    ///
+    /// This is the first stage of the [`Normalizer`] pipeline, without stop
+    /// words, stemming or synonyms.
     ///
     /// Normalization rules:
     /// 1. Convert to lowercase
@@ -41,7 +388,7 @@ impl MatchingEngine {
             .chars()
             .map(|c| {
                 // Remove punctuation
-                if ".,!?;:'\"()—-[]{}".contains(c) {
+                if PUNCTUATION.contains(c) {
                     ' '
                 } else {
                     c
@@ -54,6 +401,252 @@ impl MatchingEngine {
             .collect()
     }
 
+    /// Words of `text` as `normalize` returns them, each with its character
+    /// offsets in `text` (end exclusive).
+    pub fn word_spans(text: &str) -> Vec<(String, usize, usize)> {
+        let mut out = Vec::new();
+        let mut start = None;
+        let chars: Vec<char> = text.chars().collect();
+        for (i, c) in chars.iter().enumerate() {
+            let separator = c.is_whitespace() || PUNCTUATION.contains(*c);
+            match (separator, start) {
+                (false, None) => start = Some(i),
+                (true, Some(s)) => {
+                    out.push((chars[s..i].iter().collect::<String>().to_lowercase(), s, i));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            out.push((
+                chars[s..].iter().collect::<String>().to_lowercase(),
+                s,
+                chars.len(),
+            ));
+        }
+        out
+    }
+
+    /// Get top-N matching suggestions for a question
+    ///
+    /// Returns suggestions sorted by score (highest first, ties by entry_id),
+    /// up to `top_n` results. Only returns suggestions with score > 0.0
+    pub fn get_suggestions(
+        &self,
+        question: &str,
+        top_n: usize,
+    ) -> CoreResult<Vec<MatchSuggestion>> {
+        if question.trim().is_empty() {
+            return Err(CoreError {
+                code: CoreErrorCode::ValidationError,
+                message: "Question cannot be empty".to_string(),
+            });
+        }
+
+        if self.corpus.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let q_sources = self.normalizer.source_tokens(question);
+        let query = MatchQuery {
+            text: question.to_string(),
+            tokens: terms(&q_sources),
+        };
+
+        let mut suggestions: Vec<MatchSuggestion> = (0..self.corpus.len())
+            .map(|doc| {
+                let a_tokens: Vec<String> =
+                    self.corpus.fields(doc).iter().flatten().cloned().collect();
+
+                let entry_id = &self.corpus.entry(doc).entry_id;
+                let stale = self.stale.contains(entry_id);
+                let mut scored = if self.corpus.alias_tokens[doc].contains(&query.tokens) {
+                    ScoredMatch {
+                        score: 1.0,
+                        summary: "100% match: same wording as a previously accepted question"
+                            .to_string(),
+                        field_scores: explain::field_scores([0.0, 0.0, 0.0, 1.0]),
+                    }
+                } else {
+                    let s = self.scorer.score(&self.corpus, &query, doc);
+                    ScoredMatch {
+                        score: s.score.clamp(0.0, 1.0),
+                        ..s
+                    }
+                };
+
+                if stale {
+                    scored.score *= STALE_SCORE_FACTOR;
+                    for f in &mut scored.field_scores {
+                        f.score *= STALE_SCORE_FACTOR;
+                    }
+                    scored.summary.push_str(" (review overdue)");
+                }
+
+                let rendered = self.variables.render_entry(self.corpus.entry(doc));
+                MatchSuggestion {
+                    answer_bank_entry_id: entry_id.clone(),
+                    score: scored.score,
+                    normalized_question: query.tokens.join(" "),
+                    normalized_answer: a_tokens.join(" "),
+                    confidence_explanation: scored.summary.clone(),
+                    explanation: MatchExplanation {
+                        summary: scored.summary,
+                        terms: self.corpus.term_matches(question, &q_sources, doc),
+                        field_scores: scored.field_scores,
+                    },
+                    scorer_name: self.scorer.name().to_string(),
+                    scorer_version: self.scorer.version().to_string(),
+                    stale,
+                    answer_short: rendered.answer_short,
+                    answer_long: rendered.answer_long,
+                    unresolved_variables: rendered.unresolved,
+                }
+            })
+            .filter(|s| s.score > 0.0) // Only non-zero scores
+            .collect();
+
+        // Sort by score descending, then entry_id so equal scores are stable
+        suggestions.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.answer_bank_entry_id.cmp(&b.answer_bank_entry_id))
+        });
+
+        // Take top N
+        suggestions.truncate(top_n);
+
+        Ok(suggestions)
+    }
+}
+
+/// BM25F over the weighted fields, normalized by the question's own score.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bm25Scorer;
+
+impl Bm25Scorer {
+    /// Field-weighted, length-normalized term frequency (BM25F pseudo-tf),
+    /// kept per field; the pseudo-tf is the sum
+    fn weighted_tf(corpus: &MatchCorpus, term: &str, fields: [&[String]; 4]) -> [f64; 4] {
+        let mut tf = [0.0; 4];
+        for (f, tokens) in fields.iter().enumerate() {
+            let count = tokens.iter().filter(|t| t.as_str() == term).count();
+            if count == 0 {
+                continue;
+            }
+            let avg = corpus.avg_field_len[f];
+            let len_norm = if avg > 0.0 {
+                1.0 - BM25_B + BM25_B * tokens.len() as f64 / avg
+            } else {
+                1.0
+            };
+            tf[f] = FIELD_WEIGHTS[f] * count as f64 / len_norm;
+        }
+        tf
+    }
+
+    /// Per-term BM25F contributions for a document, keyed by term, each with
+    /// its split across fields (in proportion to the field's share of tf)
+    fn terms(
+        corpus: &MatchCorpus,
+        q_terms: &BTreeSet<&String>,
+        fields: [&[String]; 4],
+    ) -> Vec<(String, f64, [f64; 4])> {
+        q_terms
+            .iter()
+            .filter_map(|t| {
+                let field_tf = Self::weighted_tf(corpus, t, fields);
+                let tf: f64 = field_tf.iter().sum();
+                if tf == 0.0 {
+                    return None;
+                }
+                let contribution = corpus.idf(t) * tf / (BM25_K1 + tf);
+                Some((
+                    (*t).clone(),
+                    contribution,
+                    field_tf.map(|f| contribution * f / tf),
+                ))
+            })
+            .collect()
+    }
+
+    /// Explain a BM25 score by its strongest matching terms
+    fn explain(score: f64, terms: &[(String, f64, [f64; 4])]) -> String {
+        let mut ranked: Vec<&(String, f64, [f64; 4])> = terms.iter().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let shown: Vec<String> = ranked
+            .iter()
+            .take(5)
+            .map(|(t, _, _)| format!("'{}'", t))
+            .collect();
+
+        let percentage = (score * 100.0) as u32;
+        if shown.is_empty() {
+            format!("{}% match: no common tokens", percentage)
+        } else {
+            format!("{}% weighted term match: {}", percentage, shown.join(", "))
+        }
+    }
+}
+
+impl Scorer for Bm25Scorer {
+    fn name(&self) -> &str {
+        RankingModel::Bm25.as_str()
+    }
+
+    fn version(&self) -> &str {
+        "1"
+    }
+
+    /// The raw score is divided by the score the question would get against an
+    /// entry whose canonical question is the question itself, so an exact
+    /// question match scores 1.0 and thresholds keep their meaning.
+    fn score(&self, corpus: &MatchCorpus, query: &MatchQuery, doc: usize) -> ScoredMatch {
+        let q_tokens = &query.tokens;
+        let fields = corpus.fields(doc);
+        let q_terms: BTreeSet<&String> = q_tokens.iter().collect();
+        let terms = Self::terms(
+            corpus,
+            &q_terms,
+            [&fields[0], &fields[1], &fields[2], &fields[3]],
+        );
+        let raw: f64 = terms.iter().map(|(_, s, _)| s).sum();
+        let score = if terms.is_empty() {
+            0.0
+        } else {
+            let ideal: f64 = Self::terms(corpus, &q_terms, [q_tokens, &[], &[], &[]])
+                .iter()
+                .map(|(_, s, _)| s)
+                .sum();
+            if ideal > 0.0 {
+                (raw / ideal).min(1.0)
+            } else {
+                0.0
+            }
+        };
+
+        // Scale raw per-field contributions so they add up to the score.
+        let scale = if raw > 0.0 { score / raw } else { 0.0 };
+        let mut per_field = [0.0; 4];
+        for (_, _, split) in &terms {
+            for (f, part) in split.iter().enumerate() {
+                per_field[f] += part * scale;
+            }
+        }
+        ScoredMatch {
+            score,
+            summary: Self::explain(score, &terms),
+            field_scores: explain::field_scores(per_field),
+        }
+    }
+}
+
+/// Token-set overlap between the question and all entry fields.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JaccardScorer;
+
+impl JaccardScorer {
     /// Calculate token overlap score between question and answer
     ///
     /// Score formula: (intersection count) / (union count)
@@ -81,8 +674,9 @@ impl MatchingEngine {
         let q_set: HashSet<&String> = q_tokens.iter().collect();
         let a_set: HashSet<&String> = a_tokens.iter().collect();
 
-        let common_tokens: Vec<String> = q_set
-            .intersection(&a_set)
+        let common: BTreeSet<&&String> = q_set.intersection(&a_set).collect();
+        let common_tokens: Vec<String> = common
+            .into_iter()
             .take(5) // Show up to 5 common tokens
             .map(|s| format!("'{}'", s))
             .collect();
@@ -99,71 +693,137 @@ impl MatchingEngine {
             )
         }
     }
+}
 
-    /// Get top-N matching suggestions for a question
-    ///
-    /// Returns suggestions sorted by score (highest first), up to `top_n` results.
-    /// Only returns suggestions with score > 0.0
-    pub fn get_suggestions(
-        &self,
-        question: &str,
-        top_n: usize,
-    ) -> CoreResult<Vec<MatchSuggestion>> {
-        if question.trim().is_empty() {
-            return Err(CoreError {
-                code: CoreErrorCode::ValidationError,
-                message: "Question cannot be empty".to_string(),
-            });
-        }
+impl Scorer for JaccardScorer {
+    fn name(&self) -> &str {
+        RankingModel::Jaccard.as_str()
+    }
 
-        if self.answer_bank.is_empty() {
-            return Ok(Vec::new());
-        }
+    fn version(&self) -> &str {
+        "1"
+    }
 
-        let q_tokens = Self::normalize(question);
+    fn score(&self, corpus: &MatchCorpus, query: &MatchQuery, doc: usize) -> ScoredMatch {
+        let fields = corpus.fields(doc);
+        let a_tokens: Vec<String> = fields.iter().flatten().cloned().collect();
+        let score = Self::score_tokens(&query.tokens, &a_tokens);
 
-        let mut suggestions: Vec<MatchSuggestion> = self
-            .answer_bank
+        // Each common token's share of the score goes to the fields holding it.
+        let common: BTreeSet<&String> = query
+            .tokens
             .iter()
-            .map(|entry| {
-                // Combine question_canonical + answer_short + answer_long for matching
-                let combined_answer = format!(
-                    "{} {} {}",
-                    entry.question_canonical, entry.answer_short, entry.answer_long
-                );
-                let a_tokens = Self::normalize(&combined_answer);
+            .filter(|t| a_tokens.contains(t))
+            .collect();
+        let mut per_field = [0.0; 4];
+        for t in &common {
+            let holders: Vec<usize> = (0..4).filter(|f| fields[*f].contains(t)).collect();
+            for f in &holders {
+                per_field[*f] += score / common.len() as f64 / holders.len() as f64;
+            }
+        }
+        ScoredMatch {
+            score,
+            summary: Self::explain_confidence(score, &query.tokens, &a_tokens),
+            field_scores: explain::field_scores(per_field),
+        }
+    }
+}
+
+/// Matches questions against the vault answer bank: candidates come from the
+/// full-text indexes over entries and their aliases (see
+/// `answer_bank::ab_match_candidates`) and are reranked with `MatchingEngine`,
+/// so the whole bank is never loaded.
+pub struct AnswerBankMatcher {
+    scorer: Arc<dyn Scorer>,
+    normalizer: Normalizer,
+    corpus_size: usize,
+    review_date: String,
+    variables: TemplateVariables,
+    locale: String,
+}
 
-                let score = Self::score_tokens(&q_tokens, &a_tokens);
-                let confidence_explanation = Self::explain_confidence(score, &q_tokens, &a_tokens);
+impl AnswerBankMatcher {
+    /// Load the vault's normalization pipeline and answer bank size
+    pub fn load(db: &SqliteDb, model: RankingModel) -> CoreResult<Self> {
+        Self::with_scorer(db, model.scorer())
+    }
 
-                MatchSuggestion {
-                    answer_bank_entry_id: entry.entry_id.clone(),
-                    score,
-                    normalized_question: q_tokens.join(" "),
-                    normalized_answer: a_tokens.join(" "),
-                    confidence_explanation,
-                }
-            })
-            .filter(|s| s.score > 0.0) // Only non-zero scores
-            .collect();
+    /// Like [`AnswerBankMatcher::load`], reranking with any scorer
+    pub fn with_scorer(db: &SqliteDb, scorer: Arc<dyn Scorer>) -> CoreResult<Self> {
+        Ok(Self {
+            scorer,
+            normalizer: super::synonyms::load_normalizer(db)?,
+            corpus_size: answer_bank::ab_count_entries(db)?.max(0) as usize,
+            review_date: today_utc_date(),
+            variables: TemplateVariables::load(db)?,
+            locale: answer_bank::DEFAULT_LOCALE.to_string(),
+        })
+    }
 
-        // Sort by score descending
-        suggestions.sort_by(|a, b| {
-            b.score
-                .partial_cmp(&a.score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+    /// Judge review staleness as of `as_of` (`YYYY-MM-DD`) instead of today
+    pub fn with_review_date(mut self, as_of: &str) -> Self {
+        self.review_date = as_of.to_string();
+        self
+    }
 
-        // Take top N
-        suggestions.truncate(top_n);
+    /// Match in `locale` (see `answer_bank::normalize_locale`) instead of
+    /// the default locale
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.locale = locale.to_string();
+        self
+    }
 
-        Ok(suggestions)
+    /// Scorer suggestions are ranked with
+    pub fn scorer(&self) -> &dyn Scorer {
+        self.scorer.as_ref()
     }
+
+    /// Top-N suggestions for a question (same contract as
+    /// `MatchingEngine::get_suggestions`)
+    pub fn get_suggestions(
+        &self,
+        db: &SqliteDb,
+        question: &str,
+        top_n: usize,
+    ) -> CoreResult<Vec<MatchSuggestion>> {
+        if question.trim().is_empty() {
+            return Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                "Question cannot be empty",
+            ));
+        }
+        let normalizer = self.normalizer.clone().for_locale(&self.locale);
+        let candidates = answer_bank::ab_match_candidates(
+            db,
+            &normalizer.retrieval_terms(question),
+            CANDIDATE_LIMIT,
+        )?;
+        let candidates: Vec<AnswerBankEntry> = candidates
+            .iter()
+            .map(|e| e.localized(&self.locale))
+            .collect();
+        let ids: Vec<String> = candidates.iter().map(|e| e.entry_id.clone()).collect();
+        let aliases = answer_bank::aliases_for_entries(db, &ids)?;
+        let stale = answer_bank::stale_entry_ids(db, &ids, &self.review_date)?;
+        MatchingEngine::with_scorer(candidates, normalizer, self.scorer.clone())
+            .with_aliases(&aliases)
+            .with_stale(stale)
+            .with_variables(self.variables.clone())
+            .with_corpus_size(self.corpus_size)
+            .get_suggestions(question, top_n)
+    }
+}
+
+fn terms(tokens: &[SourceToken]) -> Vec<String> {
+    tokens.iter().map(|t| t.term.clone()).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answer_bank::EntryStatus;
+    use crate::questionnaire::explain::MatchField;
 
     #[test]
     fn test_normalize_removes_punctuation() {
@@ -181,7 +841,7 @@ mod tests {
     fn test_score_tokens_identical() {
         let tokens_a = vec!["access".to_string(), "control".to_string()];
         let tokens_b = vec!["access".to_string(), "control".to_string()];
-        let score = MatchingEngine::score_tokens(&tokens_a, &tokens_b);
+        let score = JaccardScorer::score_tokens(&tokens_a, &tokens_b);
         assert!((score - 1.0).abs() < 0.01);
     }
 
@@ -193,7 +853,7 @@ mod tests {
             "control".to_string(),
             "rbac".to_string(),
         ];
-        let score = MatchingEngine::score_tokens(&tokens_a, &tokens_b);
+        let score = JaccardScorer::score_tokens(&tokens_a, &tokens_b);
         // 2 common / 3 total = 0.67
         assert!((score - 0.6666).abs() < 0.01);
     }
@@ -202,10 +862,166 @@ mod tests {
     fn test_score_tokens_no_overlap() {
         let tokens_a = vec!["access".to_string(), "control".to_string()];
         let tokens_b = vec!["network".to_string(), "firewall".to_string()];
-        let score = MatchingEngine::score_tokens(&tokens_a, &tokens_b);
+        let score = JaccardScorer::score_tokens(&tokens_a, &tokens_b);
         assert_eq!(score, 0.0);
     }
 
+    fn entry(id: &str, question: &str, short: &str, long: &str) -> AnswerBankEntry {
+        AnswerBankEntry {
+            entry_id: id.to_string(),
+            vault_id: "v".to_string(),
+            question_canonical: question.to_string(),
+            answer_short: short.to_string(),
+            answer_long: long.to_string(),
+            notes: None,
+            evidence_links: vec![],
+            owner: "alice".to_string(),
+            last_reviewed_at: None,
+            tags: vec![],
+            source: "manual".to_string(),
+            content_hash: String::new(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            status: EntryStatus::Approved,
+            last_edited_by: None,
+            translations: vec![],
+        }
+    }
+
+    #[test]
+    fn test_bm25_favors_question_field_over_long_answer() {
+        let bank = vec![
+            entry(
+                "a",
+                "Do you have a security policy?",
+                "Yes",
+                "Our policy covers encryption of all customer data at rest and in transit, \
+                 with keys rotated yearly and stored in a managed key service.",
+            ),
+            entry("b", "Is data encrypted at rest?", "Yes", "AES-256"),
+            entry("c", "Do you perform background checks?", "Yes", "Yes"),
+            entry("d", "Do you run vulnerability scans?", "Yes", "Weekly"),
+        ];
+        let question = "Do you encrypt data at rest?";
+
+        let bm25 = MatchingEngine::new(bank.clone())
+            .get_suggestions(question, 2)
+            .unwrap();
+        assert_eq!(bm25[0].answer_bank_entry_id, "b");
+        assert!(bm25[0].score <= 1.0);
+
+        let exact = MatchingEngine::new(bank.clone())
+            .get_suggestions("Is data encrypted at rest?", 1)
+            .unwrap();
+        assert_eq!(exact[0].answer_bank_entry_id, "b");
+        assert!((exact[0].score - 1.0).abs() < 1e-9);
+
+        // Jaccard over plain tokens reproduces the original ranking.
+        let engine = MatchingEngine::with_normalizer(
+            bank,
+            RankingModel::Jaccard,
+            Normalizer::tokenize_only(),
+        );
+        assert_eq!(engine.scorer().name(), "jaccard");
+        let jaccard = engine.get_suggestions(question, 4).unwrap();
+        let ranked: Vec<(&str, f64)> = jaccard
+            .iter()
+            .map(|s| (s.answer_bank_entry_id.as_str(), s.score))
+            .collect();
+        let expected = [
+            ("b", 3.0 / 11.0),
+            ("c", 2.0 / 10.0),
+            ("d", 2.0 / 11.0),
+            ("a", 5.0 / 28.0),
+        ];
+        assert_eq!(ranked.len(), expected.len());
+        for ((id, score), (want_id, want_score)) in ranked.iter().zip(expected) {
+            assert_eq!(*id, want_id);
+            assert!((score - want_score).abs() < 1e-9, "{id}: {score}");
+        }
+    }
+
+    #[test]
+    fn test_explanation_lists_spans_and_field_breakdown() {
+        let bank = vec![
+            entry(
+                "a",
+                "Is customer data encrypted at rest?",
+                "Yes, AES-256",
+                "All stored data is encrypted.",
+            ),
+            entry("b", "Do you run background checks?", "Yes", "Yes"),
+        ];
+        let found = MatchingEngine::new(bank)
+            .get_suggestions("Do you encrypt data?", 1)
+            .unwrap();
+        let explanation = &found[0].explanation;
+        assert_eq!(explanation.summary, found[0].confidence_explanation);
+
+        let terms: Vec<&str> = explanation.terms.iter().map(|t| t.term.as_str()).collect();
+        assert_eq!(terms, vec!["encrypt", "data"]);
+        let encrypt = &explanation.terms[0];
+        assert_eq!(encrypt.question_spans[0].text, "encrypt");
+        assert_eq!(
+            encrypt.question_spans[0].origin,
+            super::super::normalize::TermOrigin::Exact
+        );
+        let in_question = &encrypt.field_spans[0];
+        assert_eq!(in_question.field, MatchField::Question);
+        assert_eq!((in_question.span.start, in_question.span.end), (17, 26));
+        assert_eq!(in_question.span.text, "encrypted");
+        assert_eq!(
+            in_question.span.origin,
+            super::super::normalize::TermOrigin::Stem
+        );
+        assert!(encrypt
+            .field_spans
+            .iter()
+            .any(|f| f.field == MatchField::AnswerLong));
+
+        let fields: Vec<MatchField> = explanation.field_scores.iter().map(|f| f.field).collect();
+        assert_eq!(fields, vec![MatchField::Question, MatchField::AnswerLong]);
+        let total: f64 = explanation.field_scores.iter().map(|f| f.score).sum();
+        assert!((total - found[0].score).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_equal_scores_break_ties_by_entry_id() {
+        let bank = vec![
+            entry("z", "Do you have a firewall?", "Yes", "Yes"),
+            entry("m", "Do you have a firewall?", "Yes", "Yes"),
+            entry("a", "Do you have a firewall?", "Yes", "Yes"),
+        ];
+        for model in [RankingModel::Bm25, RankingModel::Jaccard] {
+            let engine = MatchingEngine::with_model(bank.clone(), model);
+            let first = engine.get_suggestions("firewall", 3).unwrap();
+            let ids: Vec<&str> = first
+                .iter()
+                .map(|s| s.answer_bank_entry_id.as_str())
+                .collect();
+            assert_eq!(ids, vec!["a", "m", "z"]);
+            let again = engine.get_suggestions("firewall", 3).unwrap();
+            assert_eq!(
+                first.iter().map(|s| s.score).collect::<Vec<_>>(),
+                again.iter().map(|s| s.score).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_ranking_model_round_trips() {
+        for model in [
+            RankingModel::Bm25,
+            RankingModel::Jaccard,
+            RankingModel::CharNgram,
+            RankingModel::Blended,
+        ] {
+            assert_eq!(RankingModel::parse(model.as_str()).unwrap(), model);
+            assert_eq!(model.scorer().name(), model.as_str());
+        }
+        assert!(RankingModel::parse("cosine").is_err());
+    }
+
     #[test]
     fn test_empty_answer_bank_returns_empty() {
         let engine = MatchingEngine::new(Vec::new());
@@ -2,11 +2,36 @@
 //!
 //! Phase 2.1: import + column profiling (minimal, for mapping UX + persistence).
 //! Phase 2.2: persist column map per import and validate it before matching.
-//! Phase 2.4: matching algorithm for answer suggestions.
+//! Phase 2.4: matching algorithm for answer suggestions, with a
+//! [`normalize`] pipeline, a vault [`synonyms`] dictionary and pluggable
+//! offline [`scorers`]; suggestions carry structured [`explain`]ations, and
+//! rankings can be measured against gold sets with [`evaluation`].
+//! Setting a column map materializes one question per data row; see
+//! [`workflow`] for per-question status, answers and comments, and
+//! [`suggestions`] for stored match suggestions. A new file can be imported
+//! as a revision of an earlier import; see [`revision`].
+//! Each import has a language (default `en`, set with
+//! [`set_import_language`]); its questions are matched against that locale's
+//! variant of answer bank entries.
+//!
+//! Supported sources: CSV, XLSX (first sheet), ODS (first sheet) and DOCX
+//! (question table). Spreadsheet-like formats use column letters as `col_ref`.
 
 mod csv;
+mod docx;
+pub mod evaluation;
+pub mod explain;
 pub mod matching;
+pub mod normalize;
+mod ods;
+pub mod revision;
+pub mod scorers;
+mod sheet;
+pub mod suggestions;
+pub mod synonyms;
+pub mod workflow;
 mod xlsx;
+mod xml;
 
 use crate::audit::canonical::CanonicalJson;
 use crate::audit::validator;
@@ -23,9 +48,13 @@ pub struct QuestionnaireImport {
     pub source_filename: String,
     pub source_sha256: String,
     pub imported_at: String,
-    pub format: String, // 'csv' | 'xlsx'
+    pub format: String, // 'csv' | 'xlsx' | 'ods' | 'docx'
     pub status: String,
     pub column_map: Option<ColumnMap>,
+    /// Set when this import is a newer revision of an earlier import.
+    pub parent_import_id: Option<String>,
+    /// Locale the questions are written in (see `answer_bank::normalize_locale`).
+    pub language: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -71,7 +100,7 @@ impl ColumnMap {
 
 #[derive(Debug, Clone)]
 pub struct ColumnProfile {
-    pub col_ref: String, // CSV header name or XLSX column letter
+    pub col_ref: String, // CSV header name, or column letter for XLSX/ODS/DOCX
     pub ordinal: i64,
     pub label: String,
     pub non_empty_count: i64,
@@ -96,6 +125,15 @@ pub fn import_questionnaire(
     _vault_root: &Path,
     source_path: &Path,
     actor: &str,
+) -> CoreResult<QuestionnaireImport> {
+    import_source(db, source_path, actor, None)
+}
+
+fn import_source(
+    db: &SqliteDb,
+    source_path: &Path,
+    actor: &str,
+    parent: Option<&QuestionnaireImport>,
 ) -> CoreResult<QuestionnaireImport> {
     validator::validate_chain(db)?;
 
@@ -108,21 +146,7 @@ pub fn import_questionnaire(
 
     let vault_id = load_vault_id(db)?;
 
-    let ext = source_path
-        .extension()
-        .and_then(|s| s.to_str())
-        .unwrap_or("")
-        .to_ascii_lowercase();
-    let (format, cols) = if ext == "csv" {
-        ("csv".to_string(), csv::profile_columns(source_path)?)
-    } else if ext == "xlsx" {
-        ("xlsx".to_string(), xlsx::profile_columns(source_path)?)
-    } else {
-        return Err(CoreError::new(
-            CoreErrorCode::UnsupportedFormat,
-            "unsupported questionnaire format (expected .csv or .xlsx)",
-        ));
-    };
+    let (format, rows, cols) = read_source(source_path)?;
 
     let import_id = Ulid::new()?.to_string();
     let imported_at = DETERMINISTIC_TIMESTAMP_UTC.to_string();
@@ -132,9 +156,13 @@ pub fn import_questionnaire(
         .map(|s| s.to_string_lossy().to_string())
         .unwrap_or_else(|| "questionnaire".to_string());
     let source_sha256 = crate::audit::hasher::sha256_hex_file(source_path)?;
+    let parent_import_id = parent.map(|p| p.import_id.as_str());
+    let language = parent
+        .map(|p| p.language.clone())
+        .unwrap_or_else(|| crate::answer_bank::DEFAULT_LOCALE.to_string());
 
     let import_insert = format!(
-        "INSERT INTO questionnaire_import (import_id, vault_id, source_filename, source_sha256, imported_at, format, status, column_map_json) VALUES ({}, {}, {}, {}, {}, {}, {}, NULL);",
+        "INSERT INTO questionnaire_import (import_id, vault_id, source_filename, source_sha256, imported_at, format, status, column_map_json, parent_import_id, language) VALUES ({}, {}, {}, {}, {}, {}, {}, NULL, {}, {});",
         db.q(&import_id),
         db.q(&vault_id),
         db.q(&source_filename),
@@ -142,6 +170,11 @@ pub fn import_questionnaire(
         db.q(&imported_at),
         db.q(&format),
         db.q("imported"),
+        match parent_import_id {
+            Some(p) => db.q(p),
+            None => "NULL".to_string(),
+        },
+        db.q(&language),
     );
 
     let mut cols_sql = String::new();
@@ -165,6 +198,16 @@ pub fn import_questionnaire(
         ));
     }
 
+    let mut rows_sql = String::new();
+    for (row_number, cells) in data_rows(&cols, &rows) {
+        rows_sql.push_str(&format!(
+            "INSERT INTO questionnaire_import_row (import_id, row_number, cells_json) VALUES ({}, {}, {});\n",
+            db.q(&import_id),
+            row_number,
+            db.q(&cells.to_string()),
+        ));
+    }
+
     let event_sql =
         crate::storage::build_event_insert_sql(db, &vault_id, actor, "QuestionnaireImported", {
             let mut o = CanonicalJson::object();
@@ -178,12 +221,16 @@ pub fn import_questionnaire(
                 "source_sha256",
                 CanonicalJson::String(source_sha256.clone()),
             );
+            if let Some(p) = parent_import_id {
+                o.insert("parent_import_id", CanonicalJson::String(p.to_string()));
+            }
+            o.insert("language", CanonicalJson::String(language.clone()));
             o
         })?;
 
     let script = format!(
-        "BEGIN;\n{}\n{}\n{}\nCOMMIT;",
-        import_insert, cols_sql, event_sql
+        "BEGIN;\n{}\n{}\n{}\n{}\nCOMMIT;",
+        import_insert, cols_sql, rows_sql, event_sql
     );
     db.exec_batch(&script)?;
 
@@ -196,9 +243,63 @@ pub fn import_questionnaire(
         format,
         status: "imported".to_string(),
         column_map: None,
+        parent_import_id: parent_import_id.map(|p| p.to_string()),
+        language,
     })
 }
 
+/// Read a questionnaire-format file into rows and profile its columns.
+/// Returns the lower-cased extension as format; `rows[0]` is the header row.
+pub(crate) fn read_source(
+    source_path: &Path,
+) -> CoreResult<(String, Vec<Vec<String>>, Vec<ColumnProfile>)> {
+    let ext = source_path
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    let rows = match ext.as_str() {
+        "csv" => csv::read_rows(source_path)?,
+        "xlsx" => xlsx::read_rows(source_path)?,
+        "ods" => ods::read_rows(source_path)?,
+        "docx" => docx::read_rows(source_path)?,
+        _ => {
+            return Err(CoreError::new(
+                CoreErrorCode::UnsupportedFormat,
+                "unsupported questionnaire format (expected .csv, .xlsx, .ods or .docx)",
+            ))
+        }
+    };
+    let cols = if ext == "csv" {
+        csv::profile_rows(&rows)?
+    } else {
+        sheet::profile_rows(&rows, &ext)?
+    };
+    Ok((ext, rows, cols))
+}
+
+/// Write rows as CSV or a single-sheet XLSX, chosen by the extension of
+/// `out_path`. `read_source` reads the result back.
+pub(crate) fn write_source(
+    out_path: &Path,
+    sheet_name: &str,
+    rows: &[Vec<String>],
+) -> CoreResult<()> {
+    let ext = out_path
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "csv" => csv::write_rows(out_path, rows),
+        "xlsx" => xlsx::write_rows(out_path, sheet_name, rows),
+        _ => Err(CoreError::new(
+            CoreErrorCode::UnsupportedFormat,
+            "unsupported export format (expected .csv or .xlsx)",
+        )),
+    }
+}
+
 pub fn list_columns(db: &SqliteDb, import_id: &str) -> CoreResult<Vec<ColumnProfile>> {
     let rows = db.query_rows_tsv(&format!(
         "SELECT col_ref, ordinal, label, non_empty_count, sample_json FROM questionnaire_import_column WHERE import_id={} ORDER BY ordinal ASC;",
@@ -231,19 +332,36 @@ pub fn list_columns(db: &SqliteDb, import_id: &str) -> CoreResult<Vec<ColumnProf
     Ok(cols)
 }
 
+const IMPORT_COLUMNS: &str = "import_id, vault_id, source_filename, source_sha256, imported_at, format, status, IFNULL(column_map_json, ''), IFNULL(parent_import_id, ''), language";
+
 pub fn load_import(db: &SqliteDb, import_id: &str) -> CoreResult<QuestionnaireImport> {
     let rows = db.query_rows_tsv(&format!(
-        "SELECT import_id, vault_id, source_filename, source_sha256, imported_at, format, status, IFNULL(column_map_json, '') FROM questionnaire_import WHERE import_id={} LIMIT 1;",
+        "SELECT {} FROM questionnaire_import WHERE import_id={} LIMIT 1;",
+        IMPORT_COLUMNS,
         db.q(import_id)
     ))?;
-    if rows.is_empty() {
-        return Err(CoreError::new(
+    match rows.first() {
+        Some(r) => parse_import_row(r),
+        None => Err(CoreError::new(
             CoreErrorCode::NotFound,
             "questionnaire import not found",
-        ));
+        )),
     }
-    let r = &rows[0];
-    if r.len() < 8 {
+}
+
+/// Every import, oldest first.
+pub fn list_imports(db: &SqliteDb) -> CoreResult<Vec<QuestionnaireImport>> {
+    db.query_rows_tsv(&format!(
+        "SELECT {} FROM questionnaire_import ORDER BY imported_at ASC, import_id ASC;",
+        IMPORT_COLUMNS
+    ))?
+    .iter()
+    .map(|r| parse_import_row(r))
+    .collect()
+}
+
+fn parse_import_row(r: &[String]) -> CoreResult<QuestionnaireImport> {
+    if r.len() < 10 {
         return Err(CoreError::new(
             CoreErrorCode::CorruptVault,
             "unexpected questionnaire_import row",
@@ -263,9 +381,57 @@ pub fn load_import(db: &SqliteDb, import_id: &str) -> CoreResult<QuestionnaireIm
         format: r[5].clone(),
         status: r[6].clone(),
         column_map,
+        parent_import_id: if r[8].is_empty() {
+            None
+        } else {
+            Some(r[8].clone())
+        },
+        language: r[9].clone(),
     })
 }
 
+/// Set the language an import's questions are written in. Stored
+/// suggestions are kept; the next matching pass uses the new language.
+pub fn set_import_language(
+    db: &SqliteDb,
+    import_id: &str,
+    language: &str,
+    actor: &str,
+) -> CoreResult<QuestionnaireImport> {
+    validator::validate_chain(db)?;
+
+    let language = crate::answer_bank::normalize_locale(language)?;
+    let before = load_import(db, import_id)?;
+    if before.language == language {
+        return Ok(before);
+    }
+
+    let update_sql = format!(
+        "UPDATE questionnaire_import SET language={} WHERE import_id={};",
+        db.q(&language),
+        db.q(import_id),
+    );
+    let event_sql = crate::storage::build_event_insert_sql(
+        db,
+        &before.vault_id,
+        actor,
+        "QuestionnaireLanguageSet",
+        {
+            let mut o = CanonicalJson::object();
+            o.insert("import_id", CanonicalJson::String(import_id.to_string()));
+            o.insert("language", CanonicalJson::String(language.clone()));
+            o.insert(
+                "previous_language",
+                CanonicalJson::String(before.language.clone()),
+            );
+            o
+        },
+    )?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", update_sql, event_sql))?;
+
+    load_import(db, import_id)
+}
+
 pub fn set_column_map(
     db: &SqliteDb,
     import_id: &str,
@@ -282,12 +448,15 @@ pub fn set_column_map(
         ));
     }
 
-    let vault_id = db
-        .query_optional_string(&format!(
-            "SELECT vault_id FROM questionnaire_import WHERE import_id={} LIMIT 1;",
-            db.q(import_id)
-        ))?
-        .ok_or_else(|| CoreError::new(CoreErrorCode::NotFound, "questionnaire import not found"))?;
+    let before = load_import(db, import_id)?;
+    let vault_id = before.vault_id;
+    let questions_sql = workflow::materialize_questions_sql(
+        db,
+        &vault_id,
+        import_id,
+        before.column_map.as_ref(),
+        map,
+    )?;
 
     let map_json = map.to_canonical_json().to_string();
 
@@ -311,7 +480,10 @@ pub fn set_column_map(
         },
     )?;
 
-    let script = format!("BEGIN;\n{}\n{}\nCOMMIT;", update_sql, event_sql);
+    let script = format!(
+        "BEGIN;\n{}\n{}{}\nCOMMIT;",
+        update_sql, questions_sql, event_sql
+    );
     db.exec_batch(&script)?;
 
     load_import(db, import_id)
@@ -390,6 +562,27 @@ pub fn validate_column_map(
     Ok(ColumnMapValidation { ok, issues })
 }
 
+/// Non-empty data rows keyed by 1-based source row number (header is row 1),
+/// with cells keyed by `col_ref`.
+fn data_rows(cols: &[ColumnProfile], rows: &[Vec<String>]) -> Vec<(i64, CanonicalJson)> {
+    let mut out = Vec::new();
+    for (i, row) in rows.iter().enumerate().skip(1) {
+        let mut cells = CanonicalJson::object();
+        let mut any = false;
+        for (col, v) in cols.iter().zip(row.iter()) {
+            let v = v.trim();
+            if !v.is_empty() {
+                cells.insert(col.col_ref.clone(), CanonicalJson::String(v.to_string()));
+                any = true;
+            }
+        }
+        if any {
+            out.push((i as i64 + 1, cells));
+        }
+    }
+    out
+}
+
 fn load_vault_id(db: &SqliteDb) -> CoreResult<String> {
     db.query_optional_string("SELECT vault_id FROM vault LIMIT 1;")?
         .ok_or_else(|| CoreError::new(CoreErrorCode::CorruptVault, "missing vault row"))
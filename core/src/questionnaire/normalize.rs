@@ -0,0 +1,631 @@
+//! Text normalization pipeline for matching.
+//!
+//! `MatchingEngine::normalize` only tokenizes (lowercase, strip punctuation,
+//! split on whitespace). `Normalizer` runs on top of it:
+//!
+//! 1. Synonym/acronym phrases are rewritten to their canonical term, longest
+//!    phrase first ("multi factor authentication" -> "mfa").
+//! 2. Stop words are dropped, unless that would leave nothing.
+//! 3. Remaining tokens are reduced with the Porter stemmer
+//!    ("encrypted", "encryption" -> "encrypt").
+//!
+//! Stop words and the stemmer are English: for text in other locales
+//! ([`Normalizer::for_locale`]) only synonyms apply on top of tokenizing.
+//!
+//! Every step is deterministic so scores stay reproducible.
+//! [`Normalizer::source_tokens`] runs the same pipeline but keeps, for each
+//! term, the characters it came from and whether a stem or synonym produced
+//! it, which is what match explanations highlight.
+
+use super::matching::MatchingEngine;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use std::collections::{BTreeSet, HashMap};
+
+/// English function words that carry no matching signal.
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "above", "after", "again", "all", "also", "am", "an", "and", "any", "are", "as",
+    "at", "be", "been", "being", "both", "but", "by", "can", "could", "did", "do", "does", "doing",
+    "each", "for", "from", "had", "has", "have", "having", "he", "her", "here", "his", "how", "i",
+    "if", "in", "into", "is", "it", "its", "me", "more", "most", "my", "of", "on", "or", "other",
+    "our", "ours", "please", "she", "should", "so", "such", "than", "that", "the", "their", "them",
+    "then", "there", "these", "they", "this", "those", "to", "too", "us", "very", "was", "we",
+    "were", "what", "when", "where", "which", "while", "who", "whom", "why", "will", "with",
+    "would", "you", "your", "yours",
+];
+
+/// How a normalized term was derived from the words of the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TermOrigin {
+    /// The word itself, lowercased.
+    Exact,
+    /// The Porter stem of the word.
+    Stem,
+    /// A synonym or acronym phrase rewritten to its canonical term.
+    Synonym,
+}
+
+impl TermOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TermOrigin::Exact => "exact",
+            TermOrigin::Stem => "stem",
+            TermOrigin::Synonym => "synonym",
+        }
+    }
+
+    pub fn parse(s: &str) -> CoreResult<Self> {
+        match s {
+            "exact" => Ok(TermOrigin::Exact),
+            "stem" => Ok(TermOrigin::Stem),
+            "synonym" => Ok(TermOrigin::Synonym),
+            other => Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!("unknown term origin: {other}"),
+            )),
+        }
+    }
+}
+
+/// A normalized term and the text it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceToken {
+    pub term: String,
+    /// Character offsets of the source word (or synonym phrase), end exclusive.
+    pub start: usize,
+    pub end: usize,
+    pub origin: TermOrigin,
+}
+
+/// Phrase tokens and the canonical tokens they are rewritten to.
+type SynonymRule = (Vec<String>, Vec<String>);
+
+/// Configurable normalization pipeline used by `MatchingEngine`.
+#[derive(Debug, Clone)]
+pub struct Normalizer {
+    pub remove_stop_words: bool,
+    pub stem: bool,
+    /// Phrase (as tokens) -> canonical tokens, keyed by the phrase's first token.
+    synonyms: HashMap<String, Vec<SynonymRule>>,
+}
+
+impl Default for Normalizer {
+    /// Stop-word removal and stemming on, no synonyms.
+    fn default() -> Self {
+        Self {
+            remove_stop_words: true,
+            stem: true,
+            synonyms: HashMap::new(),
+        }
+    }
+}
+
+impl Normalizer {
+    /// Pipeline that only tokenizes, matching `MatchingEngine::normalize`.
+    pub fn tokenize_only() -> Self {
+        Self {
+            remove_stop_words: false,
+            stem: false,
+            synonyms: HashMap::new(),
+        }
+    }
+
+    /// This pipeline for text in `locale`: stop-word removal and stemming are
+    /// turned off unless the language is English.
+    pub fn for_locale(mut self, locale: &str) -> Self {
+        if locale.split('-').next() != Some("en") {
+            self.remove_stop_words = false;
+            self.stem = false;
+        }
+        self
+    }
+
+    /// Add a synonym: occurrences of `term` are rewritten to `canonical`.
+    /// Both sides are tokenized; empty sides are ignored.
+    pub fn add_synonym(&mut self, term: &str, canonical: &str) {
+        let from = MatchingEngine::normalize(term);
+        let to = MatchingEngine::normalize(canonical);
+        if from.is_empty() || to.is_empty() || from == to {
+            return;
+        }
+        let bucket = self.synonyms.entry(from[0].clone()).or_default();
+        bucket.retain(|(f, _)| *f != from);
+        bucket.push((from, to));
+        // Longest phrase first; ties in phrase order for determinism.
+        bucket.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+    }
+
+    /// Run the full pipeline over `text`.
+    pub fn tokens(&self, text: &str) -> Vec<String> {
+        self.source_tokens(text)
+            .into_iter()
+            .map(|t| t.term)
+            .collect()
+    }
+
+    /// Run the full pipeline over `text`, keeping where each term came from.
+    pub fn source_tokens(&self, text: &str) -> Vec<SourceToken> {
+        let words = MatchingEngine::word_spans(text)
+            .into_iter()
+            .map(|(term, start, end)| SourceToken {
+                term,
+                start,
+                end,
+                origin: TermOrigin::Exact,
+            })
+            .collect();
+        let expanded = self.apply_synonyms(words);
+
+        let mut kept: Vec<SourceToken> = if self.remove_stop_words {
+            let filtered: Vec<SourceToken> = expanded
+                .iter()
+                .filter(|t| !is_stop_word(&t.term))
+                .cloned()
+                .collect();
+            if filtered.is_empty() {
+                expanded
+            } else {
+                filtered
+            }
+        } else {
+            expanded
+        };
+
+        if self.stem {
+            for t in kept.iter_mut() {
+                let stem = porter_stem(&t.term);
+                if stem != t.term && t.origin == TermOrigin::Exact {
+                    t.origin = TermOrigin::Stem;
+                }
+                t.term = stem;
+            }
+        }
+        kept
+    }
+
+    /// Terms for full-text candidate retrieval: the text's own words (minus
+    /// stop words) plus every phrase sharing a canonical term with it, so
+    /// "2FA" also retrieves entries written as "multi-factor authentication".
+    /// Unstemmed; the index stems on its own. Sorted and deduplicated.
+    pub fn retrieval_terms(&self, text: &str) -> Vec<String> {
+        let raw = MatchingEngine::normalize(text);
+        let mut terms: BTreeSet<String> = raw
+            .iter()
+            .filter(|t| !(self.remove_stop_words && is_stop_word(t)))
+            .cloned()
+            .collect();
+        if terms.is_empty() {
+            terms.extend(raw.iter().cloned());
+        }
+
+        let expanded: Vec<String> = self
+            .apply_synonyms(
+                raw.iter()
+                    .map(|t| SourceToken {
+                        term: t.clone(),
+                        start: 0,
+                        end: 0,
+                        origin: TermOrigin::Exact,
+                    })
+                    .collect(),
+            )
+            .into_iter()
+            .map(|t| t.term)
+            .collect();
+        let contains = |phrase: &[String]| expanded.windows(phrase.len()).any(|w| w == phrase);
+        let rules: Vec<&SynonymRule> = self.synonyms.values().flatten().collect();
+        let hit: BTreeSet<&Vec<String>> = rules
+            .iter()
+            .filter(|(_, to)| contains(to))
+            .map(|(_, to)| to)
+            .collect();
+        for (from, to) in rules {
+            if hit.contains(to) {
+                terms.insert(from.join(" "));
+                terms.insert(to.join(" "));
+            }
+        }
+        terms.into_iter().collect()
+    }
+
+    fn apply_synonyms(&self, tokens: Vec<SourceToken>) -> Vec<SourceToken> {
+        if self.synonyms.is_empty() {
+            return tokens;
+        }
+        let starts_with = |at: usize, phrase: &[String]| {
+            tokens.len() - at >= phrase.len()
+                && tokens[at..at + phrase.len()]
+                    .iter()
+                    .zip(phrase)
+                    .all(|(t, p)| t.term == *p)
+        };
+        let mut out = Vec::with_capacity(tokens.len());
+        let mut i = 0;
+        while i < tokens.len() {
+            let hit = self
+                .synonyms
+                .get(&tokens[i].term)
+                .and_then(|bucket| bucket.iter().find(|(from, _)| starts_with(i, from)));
+            match hit {
+                Some((from, to)) => {
+                    let (start, end) = (tokens[i].start, tokens[i + from.len() - 1].end);
+                    out.extend(to.iter().map(|term| SourceToken {
+                        term: term.clone(),
+                        start,
+                        end,
+                        origin: TermOrigin::Synonym,
+                    }));
+                    i += from.len();
+                }
+                None => {
+                    out.push(tokens[i].clone());
+                    i += 1;
+                }
+            }
+        }
+        out
+    }
+}
+
+fn is_stop_word(token: &str) -> bool {
+    STOP_WORDS.binary_search(&token).is_ok()
+}
+
+/// Porter (1980) stemmer. Tokens that are short or not plain ASCII letters
+/// are returned unchanged.
+pub fn porter_stem(word: &str) -> String {
+    if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return word.to_string();
+    }
+    let mut s = Stemmer {
+        b: word.as_bytes().to_vec(),
+        k: word.len() - 1,
+        j: 0,
+    };
+    s.step1ab();
+    if s.k > 0 {
+        s.step1c();
+        s.step2();
+        s.step3();
+        s.step4();
+        s.step5();
+    }
+    s.b.truncate(s.k + 1);
+    String::from_utf8(s.b).unwrap_or_else(|_| word.to_string())
+}
+
+/// Working state for `porter_stem`: `b[..=k]` is the current word and
+/// `b[..=j]` the stem left after the last suffix test.
+struct Stemmer {
+    b: Vec<u8>,
+    k: usize,
+    j: usize,
+}
+
+impl Stemmer {
+    fn cons(&self, i: usize) -> bool {
+        match self.b[i] {
+            b'a' | b'e' | b'i' | b'o' | b'u' => false,
+            b'y' => i == 0 || !self.cons(i - 1),
+            _ => true,
+        }
+    }
+
+    /// Number of vowel-consonant sequences in `b[..=j]`.
+    fn m(&self) -> usize {
+        let mut n = 0;
+        let mut i = 0;
+        loop {
+            if i > self.j {
+                return n;
+            }
+            if !self.cons(i) {
+                break;
+            }
+            i += 1;
+        }
+        i += 1;
+        loop {
+            loop {
+                if i > self.j {
+                    return n;
+                }
+                if self.cons(i) {
+                    break;
+                }
+                i += 1;
+            }
+            i += 1;
+            n += 1;
+            loop {
+                if i > self.j {
+                    return n;
+                }
+                if !self.cons(i) {
+                    break;
+                }
+                i += 1;
+            }
+            i += 1;
+        }
+    }
+
+    fn vowel_in_stem(&self) -> bool {
+        (0..=self.j).any(|i| !self.cons(i))
+    }
+
+    fn double_cons(&self, i: usize) -> bool {
+        i >= 1 && self.b[i] == self.b[i - 1] && self.cons(i)
+    }
+
+    /// consonant-vowel-consonant ending at `i`, last not w, x or y.
+    fn cvc(&self, i: usize) -> bool {
+        if i < 2 || !self.cons(i) || self.cons(i - 1) || !self.cons(i - 2) {
+            return false;
+        }
+        !matches!(self.b[i], b'w' | b'x' | b'y')
+    }
+
+    fn ends(&mut self, suffix: &str) -> bool {
+        let s = suffix.as_bytes();
+        let len = self.k + 1;
+        if s.len() >= len || !self.b[..len].ends_with(s) {
+            return false;
+        }
+        self.j = self.k - s.len();
+        true
+    }
+
+    fn set_to(&mut self, s: &str) {
+        self.b.truncate(self.j + 1);
+        self.b.extend_from_slice(s.as_bytes());
+        self.k = self.j + s.len();
+    }
+
+    fn replace_if_measured(&mut self, s: &str) {
+        if self.m() > 0 {
+            self.set_to(s);
+        }
+    }
+
+    fn step1ab(&mut self) {
+        if self.b[self.k] == b's' {
+            if self.ends("sses") {
+                self.k -= 2;
+            } else if self.ends("ies") {
+                self.set_to("i");
+            } else if self.b[self.k - 1] != b's' {
+                self.k -= 1;
+            }
+        }
+        if self.ends("eed") {
+            if self.m() > 0 {
+                self.k -= 1;
+            }
+        } else if (self.ends("ed") || self.ends("ing")) && self.vowel_in_stem() {
+            self.k = self.j;
+            self.b.truncate(self.k + 1);
+            if self.ends("at") {
+                self.set_to("ate");
+            } else if self.ends("bl") {
+                self.set_to("ble");
+            } else if self.ends("iz") {
+                self.set_to("ize");
+            } else if self.double_cons(self.k) {
+                if !matches!(self.b[self.k], b'l' | b's' | b'z') {
+                    self.k -= 1;
+                }
+            } else {
+                self.j = self.k;
+                if self.m() == 1 && self.cvc(self.k) {
+                    self.set_to("e");
+                }
+            }
+        }
+    }
+
+    fn step1c(&mut self) {
+        if self.ends("y") && self.vowel_in_stem() {
+            self.b[self.k] = b'i';
+        }
+    }
+
+    fn step2(&mut self) {
+        const RULES: &[(&str, &str)] = &[
+            ("ational", "ate"),
+            ("tional", "tion"),
+            ("enci", "ence"),
+            ("anci", "ance"),
+            ("izer", "ize"),
+            ("bli", "ble"),
+            ("alli", "al"),
+            ("entli", "ent"),
+            ("eli", "e"),
+            ("ousli", "ous"),
+            ("ization", "ize"),
+            ("ation", "ate"),
+            ("ator", "ate"),
+            ("alism", "al"),
+            ("iveness", "ive"),
+            ("fulness", "ful"),
+            ("ousness", "ous"),
+            ("aliti", "al"),
+            ("iviti", "ive"),
+            ("biliti", "ble"),
+            ("logi", "log"),
+        ];
+        self.apply_rules(RULES);
+    }
+
+    fn step3(&mut self) {
+        const RULES: &[(&str, &str)] = &[
+            ("icate", "ic"),
+            ("ative", ""),
+            ("alize", "al"),
+            ("iciti", "ic"),
+            ("ical", "ic"),
+            ("ful", ""),
+            ("ness", ""),
+        ];
+        self.apply_rules(RULES);
+    }
+
+    /// First matching suffix wins, whether or not its condition holds.
+    fn apply_rules(&mut self, rules: &[(&str, &str)]) {
+        if self.k == 0 {
+            return;
+        }
+        for (suffix, replacement) in rules {
+            if self.ends(suffix) {
+                self.replace_if_measured(replacement);
+                return;
+            }
+        }
+    }
+
+    fn step4(&mut self) {
+        const SUFFIXES: &[&str] = &[
+            "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent", "ion",
+            "ou", "ism", "ate", "iti", "ous", "ive", "ize",
+        ];
+        if self.k == 0 {
+            return;
+        }
+        for suffix in SUFFIXES {
+            if self.ends(suffix) {
+                if *suffix == "ion" && !matches!(self.b[self.j], b's' | b't') {
+                    continue;
+                }
+                if self.m() > 1 {
+                    self.k = self.j;
+                }
+                return;
+            }
+        }
+    }
+
+    fn step5(&mut self) {
+        self.j = self.k;
+        if self.b[self.k] == b'e' && self.k > 0 {
+            self.j = self.k - 1;
+            let a = self.m();
+            if a > 1 || (a == 1 && !self.cvc(self.k - 1)) {
+                self.k -= 1;
+            }
+        }
+        self.j = self.k;
+        if self.b[self.k] == b'l' && self.double_cons(self.k) && self.m() > 1 {
+            self.k -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_words_sorted_for_binary_search() {
+        let mut sorted = STOP_WORDS.to_vec();
+        sorted.sort();
+        assert_eq!(sorted, STOP_WORDS);
+    }
+
+    #[test]
+    fn test_porter_stem_conflates_word_forms() {
+        for (word, stem) in [
+            ("encrypted", "encrypt"),
+            ("encryption", "encrypt"),
+            ("encrypt", "encrypt"),
+            ("caresses", "caress"),
+            ("ponies", "poni"),
+            ("hopping", "hop"),
+            ("relational", "relat"),
+            ("controls", "control"),
+            ("policies", "polici"),
+            ("policy", "polici"),
+            ("aes", "ae"),
+            ("2fa", "2fa"),
+        ] {
+            assert_eq!(porter_stem(word), stem, "{word}");
+        }
+    }
+
+    #[test]
+    fn test_pipeline_synonyms_stop_words_and_stems() {
+        let mut n = Normalizer::default();
+        n.add_synonym("multi-factor authentication", "mfa");
+        n.add_synonym("two factor", "mfa");
+        n.add_synonym("2FA", "mfa");
+
+        assert_eq!(n.tokens("Do you enforce MFA?"), vec!["enforc", "mfa"]);
+        assert_eq!(
+            n.tokens("Is multi-factor authentication enforced?"),
+            vec!["mfa", "enforc"]
+        );
+        assert_eq!(n.tokens("Two-factor required"), vec!["mfa", "requir"]);
+        assert_eq!(n.tokens("2FA"), vec!["mfa"]);
+        // Only stop words: keep them rather than return nothing.
+        assert_eq!(n.tokens("Is it?"), vec!["is", "it"]);
+
+        assert_eq!(
+            n.tokens("Is 2FA required?"),
+            n.tokens("Is two-factor required?")
+        );
+        assert_eq!(
+            n.retrieval_terms("Is 2FA required?"),
+            vec![
+                "2fa",
+                "mfa",
+                "multi factor authentication",
+                "required",
+                "two factor"
+            ]
+        );
+
+        let raw = Normalizer::tokenize_only();
+        assert_eq!(raw.tokens("Do you encrypt?"), vec!["do", "you", "encrypt"]);
+    }
+
+    #[test]
+    fn test_non_english_locales_are_not_stemmed() {
+        let mut n = Normalizer::default();
+        n.add_synonym("2FA", "mfa");
+
+        let de = n.clone().for_locale("de");
+        assert_eq!(
+            de.tokens("Werden die Daten verschlüsselt? 2FA"),
+            vec!["werden", "die", "daten", "verschlüsselt", "mfa"]
+        );
+        let fr = n.clone().for_locale("fr-ca");
+        assert_eq!(
+            fr.tokens("Les données sont chiffrées"),
+            vec!["les", "données", "sont", "chiffrées"]
+        );
+        let en = n.for_locale("en-gb");
+        assert_eq!(en.tokens("Is data encrypted?"), vec!["data", "encrypt"]);
+    }
+
+    #[test]
+    fn test_source_tokens_keep_spans_and_origin() {
+        let mut n = Normalizer::default();
+        n.add_synonym("multi-factor authentication", "mfa");
+
+        let tokens = n.source_tokens("Is multi-factor authentication enforced?");
+        let summary: Vec<(&str, usize, usize, TermOrigin)> = tokens
+            .iter()
+            .map(|t| (t.term.as_str(), t.start, t.end, t.origin))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("mfa", 3, 30, TermOrigin::Synonym),
+                ("enforc", 31, 39, TermOrigin::Stem),
+            ]
+        );
+
+        // Offsets count characters, not bytes.
+        let tokens = n.source_tokens("Café access");
+        assert_eq!((tokens[1].term.as_str(), tokens[1].start), ("access", 5));
+        assert_eq!(tokens[1].origin, TermOrigin::Exact);
+    }
+}
@@ -0,0 +1,270 @@
+use super::sheet;
+use super::xml::{attr_value, decode_xml_entities, XmlEvent, XmlReader};
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use std::path::Path;
+
+// LibreOffice pads sheets with huge repeated empty rows/cells
+// (e.g. `number-columns-repeated="1024"`); empty repeats are capped.
+const EMPTY_REPEAT_CAP: usize = 256;
+// Repeats of non-empty cells/rows are expanded as written, so the sheet as a
+// whole is bounded (the columns and rows limits are Excel's). The text limit
+// counts every expanded copy, so a few KB of content.xml cannot repeat one
+// long cell into gigabytes.
+const MAX_COLUMNS: usize = 16_384;
+const MAX_ROWS: usize = 1_048_576;
+const MAX_CELLS: usize = 5_000_000;
+const MAX_TEXT_BYTES: usize = 64 << 20;
+
+/// What the sheet has expanded to so far.
+#[derive(Default)]
+struct SheetSize {
+    cells: usize,
+    bytes: usize,
+}
+
+/// Read the first sheet of an ODS file as rows of cell text.
+pub(crate) fn read_rows(path: &Path) -> CoreResult<Vec<Vec<String>>> {
+    // ODS is a zip file; the sheets live in content.xml.
+    let tmp = sheet::unzip_to_temp(path, "cs_ods")?;
+    let content = tmp.join("content.xml");
+    let xml = if content.exists() {
+        crate::util::fs::read_to_string(&content)
+    } else {
+        Err(CoreError::new(
+            CoreErrorCode::ImportFailed,
+            "ods missing content.xml",
+        ))
+    };
+    let _ = std::fs::remove_dir_all(&tmp);
+
+    parse_first_sheet(&xml?)
+}
+
+fn parse_first_sheet(xml: &str) -> CoreResult<Vec<Vec<String>>> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut size = SheetSize::default();
+    let mut table_depth = 0usize;
+    let mut seen_table = false;
+
+    // Cells, repeat and text bytes of the open row.
+    let mut row: Option<(Vec<String>, usize, usize)> = None;
+    let mut cell: Option<(String, usize)> = None;
+    let mut paragraphs = 0usize;
+
+    for ev in XmlReader::new(xml) {
+        match ev {
+            XmlEvent::Start {
+                name: "table:table",
+                self_closing,
+                ..
+            } => {
+                if self_closing {
+                    continue;
+                }
+                if table_depth == 0 && seen_table {
+                    // Only the first sheet is imported.
+                    break;
+                }
+                table_depth += 1;
+                seen_table = true;
+            }
+            XmlEvent::End {
+                name: "table:table",
+            } => {
+                table_depth = table_depth.saturating_sub(1);
+            }
+            _ if table_depth != 1 => {
+                // Outside the first sheet, or inside a nested sub-table: only
+                // text in an open cell matters.
+                if let (Some((text, _)), XmlEvent::Text(t)) = (cell.as_mut(), ev) {
+                    text.push_str(&decode_xml_entities(t));
+                }
+            }
+            XmlEvent::Start {
+                name: "table:table-row",
+                tag,
+                self_closing,
+            } => {
+                let repeat = repeat_attr(tag, "table:number-rows-repeated");
+                if self_closing {
+                    push_rows(&mut rows, &mut size, Vec::new(), 0, repeat)?;
+                } else {
+                    row = Some((Vec::new(), repeat, 0));
+                }
+            }
+            XmlEvent::End {
+                name: "table:table-row",
+            } => {
+                if let Some((cells, repeat, bytes)) = row.take() {
+                    push_rows(&mut rows, &mut size, cells, bytes, repeat)?;
+                }
+            }
+            XmlEvent::Start {
+                name: "table:table-cell" | "table:covered-table-cell",
+                tag,
+                self_closing,
+            } => {
+                let repeat = repeat_attr(tag, "table:number-columns-repeated");
+                if self_closing {
+                    if let Some((cells, _, bytes)) = row.as_mut() {
+                        push_cells(cells, bytes, &size, String::new(), repeat)?;
+                    }
+                } else {
+                    cell = Some((String::new(), repeat));
+                    paragraphs = 0;
+                }
+            }
+            XmlEvent::End {
+                name: "table:table-cell" | "table:covered-table-cell",
+            } => {
+                if let (Some((cells, _, bytes)), Some((text, repeat))) = (row.as_mut(), cell.take())
+                {
+                    push_cells(cells, bytes, &size, text, repeat)?;
+                }
+            }
+            XmlEvent::Start {
+                name: "text:p" | "text:h",
+                ..
+            } => {
+                if let Some((text, _)) = cell.as_mut() {
+                    if paragraphs > 0 {
+                        text.push('\n');
+                    }
+                    paragraphs += 1;
+                }
+            }
+            XmlEvent::Start {
+                name: "text:s",
+                tag,
+                ..
+            } => {
+                if let Some((text, _)) = cell.as_mut() {
+                    let n = repeat_attr(tag, "text:c");
+                    if text.len().saturating_add(n) > MAX_TEXT_BYTES {
+                        return Err(too_large(format!(
+                            "more than {} bytes of text",
+                            MAX_TEXT_BYTES
+                        )));
+                    }
+                    text.push_str(&" ".repeat(n));
+                }
+            }
+            XmlEvent::Start {
+                name: "text:tab", ..
+            } => {
+                if let Some((text, _)) = cell.as_mut() {
+                    text.push('\t');
+                }
+            }
+            XmlEvent::Start {
+                name: "text:line-break",
+                ..
+            } => {
+                if let Some((text, _)) = cell.as_mut() {
+                    text.push('\n');
+                }
+            }
+            XmlEvent::Text(t) | XmlEvent::CData(t) => {
+                if let Some((text, _)) = cell.as_mut() {
+                    if matches!(ev, XmlEvent::CData(_)) {
+                        text.push_str(t);
+                    } else {
+                        text.push_str(&decode_xml_entities(t));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !seen_table {
+        return Err(CoreError::new(
+            CoreErrorCode::ImportFailed,
+            "ods contained no sheets",
+        ));
+    }
+
+    sheet::trim_trailing_empty(&mut rows);
+    Ok(rows)
+}
+
+fn repeat_attr(tag: &str, name: &str) -> usize {
+    attr_value(tag, name)
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(1)
+}
+
+/// Append `repeat` copies of a cell to a row whose cells hold `row_bytes` of
+/// text.
+fn push_cells(
+    cells: &mut Vec<String>,
+    row_bytes: &mut usize,
+    size: &SheetSize,
+    text: String,
+    repeat: usize,
+) -> CoreResult<()> {
+    let repeat = if text.trim().is_empty() {
+        repeat.min(EMPTY_REPEAT_CAP)
+    } else {
+        repeat
+    };
+    if cells.len().saturating_add(repeat) > MAX_COLUMNS {
+        return Err(too_large(format!("more than {} columns", MAX_COLUMNS)));
+    }
+    *row_bytes = row_bytes.saturating_add(text.len().saturating_mul(repeat));
+    if size.bytes.saturating_add(*row_bytes) > MAX_TEXT_BYTES {
+        return Err(too_large(format!(
+            "more than {} bytes of text",
+            MAX_TEXT_BYTES
+        )));
+    }
+    for _ in 0..repeat {
+        cells.push(text.clone());
+    }
+    Ok(())
+}
+
+fn push_rows(
+    rows: &mut Vec<Vec<String>>,
+    size: &mut SheetSize,
+    mut cells: Vec<String>,
+    bytes: usize,
+    repeat: usize,
+) -> CoreResult<()> {
+    while cells.last().map(|c| c.trim().is_empty()).unwrap_or(false) {
+        cells.pop();
+    }
+    let repeat = if cells.is_empty() {
+        repeat.min(EMPTY_REPEAT_CAP)
+    } else {
+        repeat
+    };
+    if rows.len().saturating_add(repeat) > MAX_ROWS {
+        return Err(too_large(format!("more than {} rows", MAX_ROWS)));
+    }
+    size.cells = size
+        .cells
+        .saturating_add(cells.len().saturating_mul(repeat));
+    if size.cells > MAX_CELLS {
+        return Err(too_large(format!("more than {} cells", MAX_CELLS)));
+    }
+    size.bytes = size.bytes.saturating_add(bytes.saturating_mul(repeat));
+    if size.bytes > MAX_TEXT_BYTES {
+        return Err(too_large(format!(
+            "more than {} bytes of text",
+            MAX_TEXT_BYTES
+        )));
+    }
+    for _ in 0..repeat {
+        rows.push(cells.clone());
+    }
+    Ok(())
+}
+
+fn too_large(what: String) -> CoreError {
+    CoreError::new(
+        CoreErrorCode::ValidationError,
+        format!("ods sheet has {}", what),
+    )
+}
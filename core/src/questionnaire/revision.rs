@@ -0,0 +1,384 @@
+//! Questionnaire revisions.
+//!
+//! Customers often send a v2 of a questionnaire with reworded, added and
+//! removed questions. A revision is imported with a link to its parent import;
+//! its questions are then diffed against the parent's by normalized question
+//! text (`MatchingEngine::normalize`) and row position, and work done on
+//! unchanged questions is carried over.
+
+use super::matching::MatchingEngine;
+use super::workflow::{self, QuestionAnswer, QuestionStatus, QuestionnaireQuestion};
+use super::{ColumnMap, QuestionnaireImport};
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
+use crate::storage::db::{escape_db_text, SqliteDb};
+use crate::storage::AuditEventBatch;
+use std::collections::HashSet;
+use std::path::Path;
+
+// Reworded questions must share at least this share of tokens (Jaccard) to be
+// paired as "changed" when they also moved to another row.
+const REWORD_MIN_SIMILARITY: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionChange {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+impl RevisionChange {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RevisionChange::Added => "added",
+            RevisionChange::Removed => "removed",
+            RevisionChange::Changed => "changed",
+            RevisionChange::Unchanged => "unchanged",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RevisionDiffItem {
+    pub change: RevisionChange,
+    /// Question in the revision (absent for removed questions).
+    pub question_id: Option<String>,
+    pub row_number: Option<i64>,
+    pub question_text: Option<String>,
+    /// Question in the parent import (absent for added questions).
+    pub parent_question_id: Option<String>,
+    pub parent_row_number: Option<i64>,
+    pub parent_question_text: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RevisionDiff {
+    pub import_id: String,
+    pub parent_import_id: String,
+    pub added: i64,
+    pub removed: i64,
+    pub changed: i64,
+    pub unchanged: i64,
+    /// Revision questions in row order, followed by removed parent questions.
+    pub items: Vec<RevisionDiffItem>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CarryOverReport {
+    pub import_id: String,
+    pub parent_import_id: String,
+    /// Unchanged questions that received the parent's status/answer/assignee.
+    pub carried_over: i64,
+}
+
+/// Import `source_path` as a new revision of `parent_import_id`.
+///
+/// When the parent's column map still fits (same mapped columns with the same
+/// header labels) it is applied to the revision and work on unchanged
+/// questions is carried over right away; otherwise the revision is left
+/// `imported` for the user to map, after which `carry_over_revision` can run.
+/// The revision is in the parent's language.
+pub fn import_revision(
+    db: &SqliteDb,
+    parent_import_id: &str,
+    source_path: &Path,
+    actor: &str,
+) -> CoreResult<QuestionnaireImport> {
+    let parent = super::load_import(db, parent_import_id)?;
+    let imp = super::import_source(db, source_path, actor, Some(&parent))?;
+
+    if let Some(map) = &parent.column_map {
+        if column_map_fits(db, &parent.import_id, &imp.import_id, map)? {
+            super::set_column_map(db, &imp.import_id, map, actor)?;
+            carry_over_revision(db, &imp.import_id, actor)?;
+        }
+    }
+
+    super::load_import(db, &imp.import_id)
+}
+
+/// Row-level diff between a revision and its parent import.
+pub fn diff_revision(db: &SqliteDb, import_id: &str) -> CoreResult<RevisionDiff> {
+    let (imp, parent_import_id) = load_revision(db, import_id)?;
+    let questions = workflow::list_questions(db, &imp.import_id)?;
+    let parent_questions = workflow::list_questions(db, &parent_import_id)?;
+
+    let pairs = pair_questions(&parent_questions, &questions);
+
+    let mut diff = RevisionDiff {
+        import_id: imp.import_id.clone(),
+        parent_import_id,
+        added: 0,
+        removed: 0,
+        changed: 0,
+        unchanged: 0,
+        items: Vec::new(),
+    };
+    for (i, q) in questions.iter().enumerate() {
+        let parent = pairs.child_to_parent[i].map(|(p, _)| &parent_questions[p]);
+        let change = match pairs.child_to_parent[i] {
+            None => RevisionChange::Added,
+            Some((_, true)) => RevisionChange::Unchanged,
+            Some((_, false)) => RevisionChange::Changed,
+        };
+        diff.items.push(RevisionDiffItem {
+            change,
+            question_id: Some(q.question_id.clone()),
+            row_number: Some(q.row_number),
+            question_text: Some(q.question_text.clone()),
+            parent_question_id: parent.map(|p| p.question_id.clone()),
+            parent_row_number: parent.map(|p| p.row_number),
+            parent_question_text: parent.map(|p| p.question_text.clone()),
+        });
+    }
+    for (p, pq) in parent_questions.iter().enumerate() {
+        if !pairs.parent_matched.contains(&p) {
+            diff.items.push(RevisionDiffItem {
+                change: RevisionChange::Removed,
+                question_id: None,
+                row_number: None,
+                question_text: None,
+                parent_question_id: Some(pq.question_id.clone()),
+                parent_row_number: Some(pq.row_number),
+                parent_question_text: Some(pq.question_text.clone()),
+            });
+        }
+    }
+    for item in &diff.items {
+        match item.change {
+            RevisionChange::Added => diff.added += 1,
+            RevisionChange::Removed => diff.removed += 1,
+            RevisionChange::Changed => diff.changed += 1,
+            RevisionChange::Unchanged => diff.unchanged += 1,
+        }
+    }
+    Ok(diff)
+}
+
+/// Copy status, answer and assignee from parent questions to the unchanged
+/// questions of a revision.
+///
+/// Only revision questions nobody has worked on yet are touched, so running
+/// this again is harmless. Suggested-but-unreviewed parent questions carry
+/// nothing but their assignee.
+pub fn carry_over_revision(
+    db: &SqliteDb,
+    import_id: &str,
+    actor: &str,
+) -> CoreResult<CarryOverReport> {
+    validator::validate_chain(db)?;
+
+    let (imp, parent_import_id) = load_revision(db, import_id)?;
+    let questions = workflow::list_questions(db, &imp.import_id)?;
+    let parent_questions = workflow::list_questions(db, &parent_import_id)?;
+    let pairs = pair_questions(&parent_questions, &questions);
+
+    let mut sql = String::new();
+    let mut events = AuditEventBatch::new(db, &imp.vault_id)?;
+    let mut carried_over = 0i64;
+
+    for (i, q) in questions.iter().enumerate() {
+        let Some((p, true)) = pairs.child_to_parent[i] else {
+            continue;
+        };
+        let parent = &parent_questions[p];
+        let untouched = matches!(
+            q.status,
+            QuestionStatus::Unanswered | QuestionStatus::Suggested
+        ) && q.answer.is_none()
+            && q.assignee.is_none();
+        let carries_status = matches!(
+            parent.status,
+            QuestionStatus::Drafted | QuestionStatus::Approved | QuestionStatus::NotApplicable
+        );
+        if !untouched || (!carries_status && parent.assignee.is_none()) {
+            continue;
+        }
+        let (status, answer) = if carries_status {
+            (parent.status, parent.answer.clone())
+        } else {
+            (q.status, None)
+        };
+
+        let (entry_sql, override_sql) = match &answer {
+            Some(QuestionAnswer::Entry(id)) => (db.q(id), "NULL".to_string()),
+            Some(QuestionAnswer::Override(text)) => {
+                ("NULL".to_string(), db.q(&escape_db_text(text)))
+            }
+            None => ("NULL".to_string(), "NULL".to_string()),
+        };
+        sql.push_str(&format!(
+            "UPDATE questionnaire_question SET status={}, assignee={}, answer_bank_entry_id={}, answer_override={}, updated_at={} WHERE question_id={};\n",
+            db.q(status.as_str()),
+            match &parent.assignee {
+                Some(a) => db.q(&escape_db_text(a)),
+                None => "NULL".to_string(),
+            },
+            entry_sql,
+            override_sql,
+            db.q(DETERMINISTIC_TIMESTAMP_UTC),
+            db.q(&q.question_id),
+        ));
+        if status != q.status {
+            // Pending suggestions are moot once an answer is carried over.
+            sql.push_str(&format!(
+                "DELETE FROM match_suggestion WHERE question_id={} AND accepted=0 AND rejected_at IS NULL;\n",
+                db.q(&q.question_id)
+            ));
+        }
+        events.push(db, actor, "QuestionCarriedOver", {
+            let mut o = CanonicalJson::object();
+            o.insert("question_id", CanonicalJson::String(q.question_id.clone()));
+            o.insert("import_id", CanonicalJson::String(imp.import_id.clone()));
+            o.insert(
+                "parent_question_id",
+                CanonicalJson::String(parent.question_id.clone()),
+            );
+            o.insert("from", CanonicalJson::String(q.status.as_str().to_string()));
+            o.insert("to", CanonicalJson::String(status.as_str().to_string()));
+            o
+        })?;
+        carried_over += 1;
+    }
+
+    if carried_over > 0 {
+        db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", sql, events.sql()))?;
+    }
+
+    Ok(CarryOverReport {
+        import_id: imp.import_id,
+        parent_import_id,
+        carried_over,
+    })
+}
+
+struct QuestionPairs {
+    /// For each revision question: matched parent index and whether the
+    /// normalized text is identical.
+    child_to_parent: Vec<Option<(usize, bool)>>,
+    parent_matched: HashSet<usize>,
+}
+
+/// Pair revision questions with parent questions.
+///
+/// 1. Identical normalized text, preferring the closest row (unchanged).
+/// 2. Similar normalized text (reworded), most similar first, then closest row.
+/// 3. Same row number (changed).
+///
+/// Anything left over is added (revision) or removed (parent).
+fn pair_questions(
+    parent: &[QuestionnaireQuestion],
+    child: &[QuestionnaireQuestion],
+) -> QuestionPairs {
+    let norm = |q: &QuestionnaireQuestion| MatchingEngine::normalize(&q.question_text);
+    let parent_tokens: Vec<Vec<String>> = parent.iter().map(norm).collect();
+    let child_tokens: Vec<Vec<String>> = child.iter().map(norm).collect();
+
+    let mut pairs = QuestionPairs {
+        child_to_parent: vec![None; child.len()],
+        parent_matched: HashSet::new(),
+    };
+
+    // Candidate pairs scored by (similarity, -row distance); greedy best-first
+    // with a deterministic tie-break on row numbers.
+    let mut candidates: Vec<(f64, i64, usize, usize)> = Vec::new();
+    for (c, ct) in child_tokens.iter().enumerate() {
+        for (p, pt) in parent_tokens.iter().enumerate() {
+            let sim = if ct == pt {
+                // Identical text outranks any reworded pairing.
+                2.0
+            } else {
+                jaccard(ct, pt)
+            };
+            let distance = (child[c].row_number - parent[p].row_number).abs();
+            if sim >= REWORD_MIN_SIMILARITY {
+                candidates.push((sim, distance, c, p));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.1.cmp(&b.1))
+            .then(a.2.cmp(&b.2))
+            .then(a.3.cmp(&b.3))
+    });
+    for (sim, _, c, p) in candidates {
+        if pairs.child_to_parent[c].is_some() || pairs.parent_matched.contains(&p) {
+            continue;
+        }
+        pairs.child_to_parent[c] = Some((p, sim > 1.0));
+        pairs.parent_matched.insert(p);
+    }
+
+    for (c, q) in child.iter().enumerate() {
+        if pairs.child_to_parent[c].is_some() {
+            continue;
+        }
+        if let Some(p) = parent
+            .iter()
+            .position(|pq| pq.row_number == q.row_number)
+            .filter(|p| !pairs.parent_matched.contains(p))
+        {
+            pairs.child_to_parent[c] = Some((p, false));
+            pairs.parent_matched.insert(p);
+        }
+    }
+
+    pairs
+}
+
+fn jaccard(a: &[String], b: &[String]) -> f64 {
+    let a: HashSet<&String> = a.iter().collect();
+    let b: HashSet<&String> = b.iter().collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+fn load_revision(db: &SqliteDb, import_id: &str) -> CoreResult<(QuestionnaireImport, String)> {
+    let imp = super::load_import(db, import_id)?;
+    let Some(parent_import_id) = imp.parent_import_id.clone() else {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "questionnaire import is not a revision",
+        ));
+    };
+    if imp.column_map.is_none() {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "questionnaire revision has no column map",
+        ));
+    }
+    Ok((imp, parent_import_id))
+}
+
+/// True when every column the parent map uses exists in the revision under the
+/// same header label.
+fn column_map_fits(
+    db: &SqliteDb,
+    parent_import_id: &str,
+    import_id: &str,
+    map: &ColumnMap,
+) -> CoreResult<bool> {
+    let parent_cols = super::list_columns(db, parent_import_id)?;
+    let cols = super::list_columns(db, import_id)?;
+    let label = |cols: &[super::ColumnProfile], col_ref: &str| {
+        cols.iter()
+            .find(|c| c.col_ref == col_ref)
+            .map(|c| c.label.trim().to_lowercase())
+    };
+    let mut refs = vec![map.question.as_str(), map.answer.as_str()];
+    if let Some(n) = &map.notes {
+        refs.push(n.as_str());
+    }
+    Ok(refs.into_iter().all(|r| {
+        let l = label(&cols, r);
+        l.is_some() && l == label(&parent_cols, r)
+    }))
+}
@@ -0,0 +1,490 @@
+//! Offline scorers beyond token matching.
+//!
+//! All of these run on the CPU from data already in the vault or in a local
+//! file; nothing is fetched over the network. They plug into
+//! `MatchingEngine::with_scorer` / `AnswerBankMatcher::with_scorer`.
+//!
+//! - [`CharNgramScorer`]: cosine similarity of character n-gram counts, which
+//!   tolerates typos and word-form differences that stemming misses.
+//! - [`EmbeddingScorer`]: cosine similarity of averaged word vectors loaded
+//!   from a text file in the common `word v1 v2 ...` format (GloVe, fastText
+//!   `.vec`). Its version includes the file's hash.
+//! - [`BlendedScorer`]: weighted mean of other scorers.
+
+use super::explain::{self, MatchField};
+use super::matching::{MatchCorpus, MatchQuery, MatchingEngine, ScoredMatch, Scorer};
+use crate::audit::hasher;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Cosine similarity of character n-grams between the question and the
+/// entry's canonical question or any of its aliases (the best one counts).
+#[derive(Debug, Clone)]
+pub struct CharNgramScorer {
+    n: usize,
+    version: String,
+}
+
+impl CharNgramScorer {
+    /// Scorer over n-grams of `n` characters (at least 2)
+    pub fn new(n: usize) -> CoreResult<Self> {
+        if n < 2 {
+            return Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                "n-gram size must be at least 2",
+            ));
+        }
+        Ok(Self {
+            n,
+            version: format!("1+n{n}"),
+        })
+    }
+
+    /// N-gram counts of each word padded with spaces; sorted so sums are
+    /// computed in the same order every run.
+    fn grams(&self, text: &str) -> BTreeMap<String, f64> {
+        let mut out = BTreeMap::new();
+        for word in MatchingEngine::normalize(text) {
+            let padded: Vec<char> = format!(" {word} ").chars().collect();
+            if padded.len() < self.n {
+                continue;
+            }
+            for w in padded.windows(self.n) {
+                *out.entry(w.iter().collect()).or_insert(0.0) += 1.0;
+            }
+        }
+        out
+    }
+}
+
+impl Default for CharNgramScorer {
+    /// Trigrams
+    fn default() -> Self {
+        Self {
+            n: 3,
+            version: "1+n3".to_string(),
+        }
+    }
+}
+
+impl Scorer for CharNgramScorer {
+    fn name(&self) -> &str {
+        "char_ngram"
+    }
+
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    fn score(&self, corpus: &MatchCorpus, query: &MatchQuery, doc: usize) -> ScoredMatch {
+        let q = self.grams(&query.text);
+        let entry = corpus.entry(doc);
+        let (score, against) = best_of(
+            std::iter::once((entry.question_canonical.as_str(), None))
+                .chain(corpus.aliases(doc).iter().map(|a| (a.as_str(), Some(a)))),
+            |text| sparse_cosine(&q, &self.grams(text)),
+        );
+        similarity_match(score, "character n-gram", against)
+    }
+}
+
+/// Cosine similarity of mean word vectors between the question and the
+/// entry's canonical question or any of its aliases.
+///
+/// Words are looked up after lowercasing and stripping punctuation; words
+/// missing from the model are ignored.
+pub struct EmbeddingScorer {
+    vectors: HashMap<String, Vec<f64>>,
+    dimensions: usize,
+    version: String,
+}
+
+impl EmbeddingScorer {
+    /// Load word vectors from a local text file. A leading `count dimensions`
+    /// header line (fastText) is skipped.
+    pub fn load(path: &Path) -> CoreResult<Self> {
+        let bytes = std::fs::read(path).map_err(|e| {
+            CoreError::new(
+                CoreErrorCode::IoError,
+                format!("failed to read embedding model: {e}"),
+            )
+        })?;
+        let text = String::from_utf8(bytes).map_err(|_| {
+            CoreError::new(
+                CoreErrorCode::ValidationError,
+                "embedding model is not UTF-8 text",
+            )
+        })?;
+        Self::parse(&text)
+    }
+
+    /// Parse word vectors from `word v1 v2 ...` lines
+    pub fn parse(text: &str) -> CoreResult<Self> {
+        let invalid = |line: usize, what: &str| {
+            CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!("embedding model line {line}: {what}"),
+            )
+        };
+
+        let mut vectors = HashMap::new();
+        let mut dimensions = 0;
+        for (i, line) in text.lines().enumerate() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.is_empty() {
+                continue;
+            }
+            if i == 0 && parts.len() == 2 && parts.iter().all(|p| p.parse::<usize>().is_ok()) {
+                continue;
+            }
+            if parts.len() < 2 {
+                return Err(invalid(i + 1, "expected a word followed by numbers"));
+            }
+            let vector = parts[1..]
+                .iter()
+                .map(|v| v.parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| invalid(i + 1, "vector values must be numbers"))?;
+            if dimensions == 0 {
+                dimensions = vector.len();
+            } else if vector.len() != dimensions {
+                return Err(invalid(i + 1, "vector length differs from earlier lines"));
+            }
+            vectors.insert(parts[0].to_lowercase(), vector);
+        }
+        if vectors.is_empty() {
+            return Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                "embedding model has no vectors",
+            ));
+        }
+
+        let hash = hasher::sha256_hex_bytes(text.as_bytes())?;
+        Ok(Self {
+            vectors,
+            dimensions,
+            version: format!("1+sha256:{}", &hash[..16]),
+        })
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Mean vector of the known words, or `None` if none are known
+    fn embed(&self, text: &str) -> Option<Vec<f64>> {
+        let mut sum = vec![0.0; self.dimensions];
+        let mut known = 0;
+        for word in MatchingEngine::normalize(text) {
+            if let Some(v) = self.vectors.get(&word) {
+                for (s, x) in sum.iter_mut().zip(v) {
+                    *s += x;
+                }
+                known += 1;
+            }
+        }
+        if known == 0 {
+            return None;
+        }
+        Some(sum.into_iter().map(|s| s / known as f64).collect())
+    }
+}
+
+impl Scorer for EmbeddingScorer {
+    fn name(&self) -> &str {
+        "embedding"
+    }
+
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    fn score(&self, corpus: &MatchCorpus, query: &MatchQuery, doc: usize) -> ScoredMatch {
+        let Some(q) = self.embed(&query.text) else {
+            return ScoredMatch {
+                score: 0.0,
+                summary: "0% match: no question words in the embedding model".to_string(),
+                field_scores: Vec::new(),
+            };
+        };
+        let entry = corpus.entry(doc);
+        let (score, against) = best_of(
+            std::iter::once((entry.question_canonical.as_str(), None))
+                .chain(corpus.aliases(doc).iter().map(|a| (a.as_str(), Some(a)))),
+            |text| {
+                self.embed(text)
+                    .map(|d| dense_cosine(&q, &d))
+                    .unwrap_or(0.0)
+            },
+        );
+        similarity_match(score, "semantic", against)
+    }
+}
+
+/// Weighted mean of other scorers' scores.
+pub struct BlendedScorer {
+    name: String,
+    version: String,
+    parts: Vec<(Arc<dyn Scorer>, f64)>,
+}
+
+impl BlendedScorer {
+    /// Blend scorers with positive weights. The version lists each part's
+    /// name, version and weight, so changing any of them changes it.
+    pub fn new(parts: Vec<(Arc<dyn Scorer>, f64)>) -> CoreResult<Self> {
+        if parts.is_empty() {
+            return Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                "a blend needs at least one scorer",
+            ));
+        }
+        if parts.iter().any(|(_, w)| !(w.is_finite() && *w > 0.0)) {
+            return Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                "blend weights must be positive",
+            ));
+        }
+        Ok(Self::builtin("blended", parts))
+    }
+
+    /// Unchecked constructor for the built-in blends
+    pub(crate) fn builtin(name: &str, parts: Vec<(Arc<dyn Scorer>, f64)>) -> Self {
+        let version = parts
+            .iter()
+            .map(|(s, w)| format!("{}@{}*{}", s.name(), s.version(), w))
+            .collect::<Vec<_>>()
+            .join("+");
+        Self {
+            name: name.to_string(),
+            version,
+            parts,
+        }
+    }
+}
+
+impl Scorer for BlendedScorer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Explained by each part's percentage and the summary of the part
+    /// contributing most; field scores are the parts' weighted the same way.
+    fn score(&self, corpus: &MatchCorpus, query: &MatchQuery, doc: usize) -> ScoredMatch {
+        let total_weight: f64 = self.parts.iter().map(|(_, w)| w).sum();
+        let mut score = 0.0;
+        let mut per_field = [0.0; 4];
+        let mut shown = Vec::new();
+        let mut strongest: Option<(f64, String)> = None;
+        for (scorer, weight) in &self.parts {
+            let part = scorer.score(corpus, query, doc);
+            let part_score = part.score.clamp(0.0, 1.0);
+            let contribution = part_score * weight / total_weight;
+            score += contribution;
+            for fs in &part.field_scores {
+                per_field[fs.field as usize] += fs.score * weight / total_weight;
+            }
+            shown.push(format!(
+                "{} {}%",
+                scorer.name(),
+                (part_score * 100.0) as u32
+            ));
+            if strongest.as_ref().is_none_or(|(c, _)| contribution > *c) {
+                strongest = Some((contribution, part.summary));
+            }
+        }
+
+        let percentage = (score * 100.0) as u32;
+        let summary = match strongest {
+            Some((c, e)) if c > 0.0 => {
+                format!("{}% blended ({}): {}", percentage, shown.join(", "), e)
+            }
+            _ => format!("{}% match: no scorer found similarities", percentage),
+        };
+        ScoredMatch {
+            score,
+            summary,
+            field_scores: explain::field_scores(per_field),
+        }
+    }
+}
+
+/// Best score over the texts an entry can be compared with, and the alias it
+/// came from (`None` for the canonical question).
+fn best_of<'a>(
+    texts: impl Iterator<Item = (&'a str, Option<&'a String>)>,
+    score: impl Fn(&str) -> f64,
+) -> (f64, Option<&'a String>) {
+    let mut best = (0.0, None);
+    for (text, alias) in texts {
+        let s = score(text).clamp(0.0, 1.0);
+        if s > best.0 {
+            best = (s, alias);
+        }
+    }
+    best
+}
+
+/// Whole-text similarity: all of the score comes from the canonical question
+/// or from the alias it was measured against.
+fn similarity_match(score: f64, kind: &str, against: Option<&String>) -> ScoredMatch {
+    let mut per_field = [0.0; 4];
+    let field = match against {
+        None => MatchField::Question,
+        Some(_) => MatchField::Alias,
+    };
+    per_field[field as usize] = score;
+    ScoredMatch {
+        score,
+        summary: explain_similarity(score, kind, against),
+        field_scores: explain::field_scores(per_field),
+    }
+}
+
+fn explain_similarity(score: f64, kind: &str, against: Option<&String>) -> String {
+    let percentage = (score * 100.0) as u32;
+    if score == 0.0 {
+        return format!("{}% match: no {} similarity", percentage, kind);
+    }
+    match against {
+        None => format!(
+            "{}% {} similarity to the canonical question",
+            percentage, kind
+        ),
+        Some(alias) => format!(
+            "{}% {} similarity to accepted wording '{}'",
+            percentage, kind, alias
+        ),
+    }
+}
+
+fn sparse_cosine(a: &BTreeMap<String, f64>, b: &BTreeMap<String, f64>) -> f64 {
+    let dot: f64 = a.iter().filter_map(|(k, x)| b.get(k).map(|y| x * y)).sum();
+    let norm = |m: &BTreeMap<String, f64>| m.values().map(|x| x * x).sum::<f64>().sqrt();
+    let denom = norm(a) * norm(b);
+    if denom == 0.0 {
+        0.0
+    } else {
+        dot / denom
+    }
+}
+
+fn dense_cosine(a: &[f64], b: &[f64]) -> f64 {
+    let dot: f64 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f64]| v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let denom = norm(a) * norm(b);
+    if denom == 0.0 {
+        0.0
+    } else {
+        dot / denom
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer_bank::{AnswerBankEntry, EntryStatus};
+    use crate::questionnaire::matching::Bm25Scorer;
+    use crate::questionnaire::normalize::Normalizer;
+
+    fn entry(id: &str, question: &str) -> AnswerBankEntry {
+        AnswerBankEntry {
+            entry_id: id.to_string(),
+            vault_id: "v".to_string(),
+            question_canonical: question.to_string(),
+            answer_short: "Yes".to_string(),
+            answer_long: "Yes".to_string(),
+            notes: None,
+            evidence_links: vec![],
+            owner: "alice".to_string(),
+            last_reviewed_at: None,
+            tags: vec![],
+            source: "manual".to_string(),
+            content_hash: String::new(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            status: EntryStatus::Approved,
+            last_edited_by: None,
+            translations: vec![],
+        }
+    }
+
+    fn bank() -> Vec<AnswerBankEntry> {
+        vec![
+            entry("a", "Do you encrypt customer data at rest?"),
+            entry("b", "Do you run background checks on employees?"),
+        ]
+    }
+
+    #[test]
+    fn test_char_ngrams_tolerate_typos() {
+        let engine = MatchingEngine::with_scorer(
+            bank(),
+            Normalizer::default(),
+            Arc::new(CharNgramScorer::default()),
+        );
+        let found = engine
+            .get_suggestions("Do you encrpyt custmer data at rest?", 2)
+            .unwrap();
+        assert_eq!(found[0].answer_bank_entry_id, "a");
+        assert!(found[0].score > found[1].score);
+        assert_eq!(found[0].scorer_name, "char_ngram");
+        assert_eq!(found[0].scorer_version, "1+n3");
+        assert!(found[0]
+            .confidence_explanation
+            .contains("similarity to the canonical question"));
+        assert!(CharNgramScorer::new(1).is_err());
+    }
+
+    #[test]
+    fn test_embedding_scores_related_words() {
+        let model = "4 2\n\
+                     encrypt 1.0 0.0\n\
+                     encryption 0.9 0.1\n\
+                     data 0.8 0.2\n\
+                     employees 0.0 1.0\n";
+        let scorer = EmbeddingScorer::parse(model).unwrap();
+        assert_eq!(scorer.dimensions(), 2);
+        assert!(scorer.version().starts_with("1+sha256:"));
+
+        let engine = MatchingEngine::with_scorer(bank(), Normalizer::default(), Arc::new(scorer));
+        let found = engine.get_suggestions("Data encryption?", 2).unwrap();
+        assert_eq!(found[0].answer_bank_entry_id, "a");
+        assert_eq!(found[0].scorer_name, "embedding");
+
+        assert!(EmbeddingScorer::parse("word 1.0 2.0\nother 1.0\n").is_err());
+        assert!(EmbeddingScorer::parse("word one two\n").is_err());
+        assert!(EmbeddingScorer::parse("").is_err());
+    }
+
+    #[test]
+    fn test_blend_is_weighted_mean_with_versioned_parts() {
+        let blend = BlendedScorer::new(vec![
+            (Arc::new(Bm25Scorer), 3.0),
+            (Arc::new(CharNgramScorer::default()), 1.0),
+        ])
+        .unwrap();
+        assert_eq!(blend.name(), "blended");
+        assert_eq!(blend.version(), "bm25@1*3+char_ngram@1+n3*1");
+
+        let question = "Do you encrypt data at rest?";
+        let score_with = |scorer: Arc<dyn Scorer>| {
+            MatchingEngine::with_scorer(bank(), Normalizer::default(), scorer)
+                .get_suggestions(question, 1)
+                .unwrap()[0]
+                .score
+        };
+        let bm25 = score_with(Arc::new(Bm25Scorer));
+        let ngram = score_with(Arc::new(CharNgramScorer::default()));
+        let blended = score_with(Arc::new(blend));
+        assert!((blended - (0.75 * bm25 + 0.25 * ngram)).abs() < 1e-9);
+
+        assert!(BlendedScorer::new(Vec::new()).is_err());
+        assert!(BlendedScorer::new(vec![(Arc::new(Bm25Scorer), 0.0)]).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! Shared helpers for grid-shaped questionnaire sources.
+//!
+//! Formats that decode to plain rows of cells (ODS sheets, DOCX tables) are
+//! profiled here so they produce the same `ColumnProfile` shape as XLSX:
+//! column letters as `col_ref`, first row as labels.
+
+use super::ColumnProfile;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::ids::Ulid;
+use std::path::{Path, PathBuf};
+
+const SAMPLE_LIMIT: usize = 5;
+const ROW_LIMIT: usize = 50;
+
+/// Profile rows where `rows[0]` is the header row.
+///
+/// Mirrors the XLSX profiler: only the first `ROW_LIMIT` rows (header
+/// included) are scanned, and columns without a header fall back to their
+/// letter as label.
+pub(crate) fn profile_rows(rows: &[Vec<String>], format: &str) -> CoreResult<Vec<ColumnProfile>> {
+    let scanned = &rows[..rows.len().min(ROW_LIMIT)];
+
+    let col_count = scanned
+        .iter()
+        .filter_map(|r| r.iter().rposition(|c| !c.trim().is_empty()))
+        .max()
+        .map(|i| i + 1)
+        .ok_or_else(|| {
+            CoreError::new(
+                CoreErrorCode::ImportFailed,
+                format!("{} contained no readable cells", format),
+            )
+        })?;
+
+    let mut cols = Vec::with_capacity(col_count);
+    for idx in 0..col_count {
+        let col_ref = index_to_col_letters(idx);
+        let label = scanned
+            .first()
+            .and_then(|header| header.get(idx))
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| col_ref.clone());
+
+        let mut non_empty_count = 0i64;
+        let mut sample = Vec::new();
+        for row in scanned.iter().skip(1) {
+            let v = row.get(idx).map(|s| s.trim()).unwrap_or("");
+            if v.is_empty() {
+                continue;
+            }
+            non_empty_count += 1;
+            if sample.len() < SAMPLE_LIMIT {
+                sample.push(v.to_string());
+            }
+        }
+
+        cols.push(ColumnProfile {
+            col_ref,
+            ordinal: idx as i64,
+            label,
+            non_empty_count,
+            sample,
+        });
+    }
+
+    Ok(cols)
+}
+
+/// Unzip an office document into a fresh temp directory. Callers remove it.
+pub(crate) fn unzip_to_temp(path: &Path, prefix: &str) -> CoreResult<PathBuf> {
+    let tmp = std::env::temp_dir().join(format!(
+        "{}_{}_{}",
+        prefix,
+        std::process::id(),
+        Ulid::new()?
+    ));
+    let _ = std::fs::remove_dir_all(&tmp);
+    std::fs::create_dir_all(&tmp)?;
+
+    if let Err(e) = crate::util::zip::unzip_to_dir(path, &tmp) {
+        let _ = std::fs::remove_dir_all(&tmp);
+        return Err(e);
+    }
+    Ok(tmp)
+}
+
+/// Drop trailing empty cells of each row and trailing empty rows.
+pub(crate) fn trim_trailing_empty(rows: &mut Vec<Vec<String>>) {
+    for row in rows.iter_mut() {
+        while row.last().map(|c| c.trim().is_empty()).unwrap_or(false) {
+            row.pop();
+        }
+    }
+    while rows.last().map(|r| r.is_empty()).unwrap_or(false) {
+        rows.pop();
+    }
+}
+
+pub(crate) fn col_letters_to_index(s: &str) -> i64 {
+    // A -> 0, B -> 1, Z -> 25, AA -> 26...
+    let mut n: i64 = 0;
+    for c in s.chars() {
+        if !c.is_ascii_alphabetic() {
+            continue;
+        }
+        let v = (c.to_ascii_uppercase() as u8 - b'A' + 1) as i64;
+        n = n * 26 + v;
+    }
+    n - 1
+}
+
+pub(crate) fn index_to_col_letters(mut idx: usize) -> String {
+    // 0 -> A, 1 -> B, 25 -> Z, 26 -> AA...
+    let mut out = String::new();
+    idx += 1;
+    while idx > 0 {
+        let rem = (idx - 1) % 26;
+        out.push((b'A' + rem as u8) as char);
+        idx = (idx - 1) / 26;
+    }
+    out.chars().rev().collect()
+}
@@ -0,0 +1,627 @@
+//! Stored match suggestions for imported questions.
+//!
+//! `MatchingEngine` scores in memory; this module runs it over the questions
+//! of an import and persists the results in `match_suggestion`, so reopening
+//! an import shows the suggestions computed earlier. Accepting a suggestion
+//! makes its answer bank entry the question's answer; rejecting one keeps it
+//! from being suggested again for that question. [`auto_answer_import`] does
+//! all of this for a whole import, accepting suggestions above a threshold.
+
+use super::explain::MatchExplanation;
+use super::matching::{AnswerBankMatcher, MatchSuggestion, RankingModel};
+use super::workflow::{self, QuestionAnswer, QuestionStatus, QuestionnaireQuestion};
+use crate::answer_bank;
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::ids::Ulid;
+use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
+use crate::storage::db::{escape_db_text, unescape_db_text, SqliteDb};
+use crate::storage::AuditEventBatch;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone)]
+pub struct QuestionSuggestion {
+    pub suggestion_id: String,
+    pub question_id: String,
+    pub answer_bank_entry_id: String,
+    pub score: f64,
+    pub normalized_question: String,
+    pub normalized_answer: String,
+    pub confidence_explanation: String,
+    /// Matched spans and per-field scores; `None` for suggestions stored
+    /// before explanations were recorded.
+    pub explanation: Option<MatchExplanation>,
+    /// Scorer that produced the score; `None` for suggestions stored before
+    /// scorers were recorded.
+    pub scorer_name: Option<String>,
+    pub scorer_version: Option<String>,
+    pub accepted: bool,
+    pub accepted_at: Option<String>,
+    pub rejected_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+const SUGGESTION_COLUMNS: &str = "s.id, s.question_id, s.answer_bank_entry_id, s.score, s.normalized_question, s.normalized_answer, IFNULL(s.confidence_explanation,''), IFNULL(s.scorer_name,''), IFNULL(s.scorer_version,''), s.accepted, IFNULL(s.accepted_at,''), IFNULL(s.rejected_at,''), s.created_at, s.updated_at, IFNULL(s.explanation_json,'')";
+
+/// Options for [`auto_answer_import`].
+#[derive(Debug, Clone)]
+pub struct AutoAnswerOptions {
+    /// Best suggestions scoring at or above this (0.0 - 1.0] are accepted.
+    pub threshold: f64,
+    /// Suggestions stored per question for review.
+    pub top_n: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoAnswerOutcome {
+    /// Best suggestion met the threshold and was accepted.
+    AutoFilled,
+    /// Suggestions were stored but none met the threshold with an approved
+    /// entry.
+    NeedsReview,
+    /// Nothing in the answer bank matched.
+    NoMatch,
+}
+
+impl AutoAnswerOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AutoAnswerOutcome::AutoFilled => "auto_filled",
+            AutoAnswerOutcome::NeedsReview => "needs_review",
+            AutoAnswerOutcome::NoMatch => "no_match",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AutoAnswerItem {
+    pub question_id: String,
+    pub row_number: i64,
+    pub outcome: AutoAnswerOutcome,
+    pub best_score: Option<f64>,
+    pub best_entry_id: Option<String>,
+}
+
+/// Coverage report for one auto-answer run.
+///
+/// Only questions without an answer are considered; drafted, approved and
+/// not-applicable questions are counted in `skipped` and left untouched.
+#[derive(Debug, Clone)]
+pub struct AutoAnswerReport {
+    pub import_id: String,
+    pub threshold: f64,
+    pub auto_filled: i64,
+    pub needs_review: i64,
+    pub no_match: i64,
+    pub skipped: i64,
+    pub items: Vec<AutoAnswerItem>,
+}
+
+/// Compute and store up to `top_n` suggestions for every question of an import
+/// that has no answer yet (`unanswered` or `suggested`).
+///
+/// Pending suggestions from an earlier run are replaced; rejected ones are
+/// kept and their entries are not suggested again. Questions move between
+/// `unanswered` and `suggested` depending on whether anything was found.
+pub fn generate_suggestions(
+    db: &SqliteDb,
+    import_id: &str,
+    top_n: usize,
+    actor: &str,
+) -> CoreResult<Vec<QuestionSuggestion>> {
+    validator::validate_chain(db)?;
+
+    let import = super::load_import(db, import_id)?;
+    let pass = run_matching_pass(db, &import.vault_id, import_id, top_n)?;
+
+    let mut events = AuditEventBatch::new(db, &import.vault_id)?;
+    pass.push_events(db, actor, &mut events)?;
+
+    db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", pass.sql, events.sql()))?;
+
+    list_import_suggestions(db, import_id)
+}
+
+/// Match every unanswered question of a mapped import in one pass, accept the
+/// best suggestion where it scores at least `options.threshold` and its entry
+/// is approved, and leave the rest `suggested` for review.
+pub fn auto_answer_import(
+    db: &SqliteDb,
+    import_id: &str,
+    options: &AutoAnswerOptions,
+    actor: &str,
+) -> CoreResult<AutoAnswerReport> {
+    validator::validate_chain(db)?;
+
+    if !(options.threshold > 0.0 && options.threshold <= 1.0) {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "threshold must be in (0, 1]",
+        ));
+    }
+    let import = super::load_import(db, import_id)?;
+    if import.column_map.is_none() {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "questionnaire import has no column map",
+        ));
+    }
+
+    let pass = run_matching_pass(db, &import.vault_id, import_id, options.top_n)?;
+    let mut sql = pass.sql.clone();
+    let mut events = AuditEventBatch::new(db, &import.vault_id)?;
+    pass.push_events(db, actor, &mut events)?;
+
+    let mut report = AutoAnswerReport {
+        import_id: import_id.to_string(),
+        threshold: options.threshold,
+        auto_filled: 0,
+        needs_review: 0,
+        no_match: 0,
+        skipped: pass.skipped,
+        items: Vec::new(),
+    };
+
+    // Draft, in-review and retired entries are only ever suggested.
+    let best_ids: Vec<String> = pass
+        .questions
+        .iter()
+        .filter_map(|(_, found)| found.first())
+        .map(|(_, s)| s.answer_bank_entry_id.clone())
+        .collect();
+    let approved = answer_bank::approved_entry_ids(db, &best_ids)?;
+
+    for (question, found) in &pass.questions {
+        let best = found.first();
+        let outcome = match best {
+            None => AutoAnswerOutcome::NoMatch,
+            Some((_, s))
+                if s.score < options.threshold || !approved.contains(&s.answer_bank_entry_id) =>
+            {
+                AutoAnswerOutcome::NeedsReview
+            }
+            Some((suggestion_id, s)) => {
+                let payload = {
+                    let mut o = CanonicalJson::object();
+                    o.insert(
+                        "suggestion_id",
+                        CanonicalJson::String(suggestion_id.clone()),
+                    );
+                    o.insert(
+                        "question_id",
+                        CanonicalJson::String(question.question_id.clone()),
+                    );
+                    o.insert("import_id", CanonicalJson::String(import_id.to_string()));
+                    o.insert(
+                        "answer_bank_entry_id",
+                        CanonicalJson::String(s.answer_bank_entry_id.clone()),
+                    );
+                    o
+                };
+                events.push(db, actor, "MatchSuggestionAccepted", payload)?;
+                sql.push_str(&workflow::set_answer_sql(
+                    db,
+                    question,
+                    &QuestionAnswer::Entry(s.answer_bank_entry_id.clone()),
+                    actor,
+                    &mut events,
+                )?);
+                sql.push_str(&format!(
+                    "UPDATE match_suggestion SET accepted=1, accepted_at={}, updated_at={} WHERE id={};\n",
+                    db.q(DETERMINISTIC_TIMESTAMP_UTC),
+                    db.q(DETERMINISTIC_TIMESTAMP_UTC),
+                    db.q(suggestion_id),
+                ));
+                AutoAnswerOutcome::AutoFilled
+            }
+        };
+        match outcome {
+            AutoAnswerOutcome::AutoFilled => report.auto_filled += 1,
+            AutoAnswerOutcome::NeedsReview => report.needs_review += 1,
+            AutoAnswerOutcome::NoMatch => report.no_match += 1,
+        }
+        report.items.push(AutoAnswerItem {
+            question_id: question.question_id.clone(),
+            row_number: question.row_number,
+            outcome,
+            best_score: best.map(|(_, s)| s.score),
+            best_entry_id: best.map(|(_, s)| s.answer_bank_entry_id.clone()),
+        });
+    }
+
+    events.push(db, actor, "QuestionnaireAutoAnswered", {
+        let mut o = CanonicalJson::object();
+        o.insert("import_id", CanonicalJson::String(import_id.to_string()));
+        // Canonical JSON has no floats; the threshold is kept in basis points.
+        o.insert(
+            "threshold_bp",
+            CanonicalJson::Number((options.threshold * 10_000.0).round() as i64),
+        );
+        o.insert("auto_filled", CanonicalJson::Number(report.auto_filled));
+        o.insert("needs_review", CanonicalJson::Number(report.needs_review));
+        o.insert("no_match", CanonicalJson::Number(report.no_match));
+        o
+    })?;
+
+    db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", sql, events.sql()))?;
+    Ok(report)
+}
+
+/// Result of matching the open questions of an import, not yet written.
+struct MatchPass {
+    import_id: String,
+    top_n: usize,
+    scorer_name: String,
+    scorer_version: String,
+    sql: String,
+    status_changes: Vec<CanonicalJson>,
+    /// Open questions (with their post-pass status) and the suggestions stored
+    /// for them, best first, each with its new suggestion id.
+    questions: Vec<(QuestionnaireQuestion, Vec<(String, MatchSuggestion)>)>,
+    skipped: i64,
+}
+
+impl MatchPass {
+    fn push_events(
+        &self,
+        db: &SqliteDb,
+        actor: &str,
+        events: &mut AuditEventBatch,
+    ) -> CoreResult<()> {
+        let suggestion_count: usize = self.questions.iter().map(|(_, f)| f.len()).sum();
+        events.push(db, actor, "MatchSuggestionsGenerated", {
+            let mut o = CanonicalJson::object();
+            o.insert("import_id", CanonicalJson::String(self.import_id.clone()));
+            o.insert(
+                "question_count",
+                CanonicalJson::Number(self.questions.len() as i64),
+            );
+            o.insert(
+                "suggestion_count",
+                CanonicalJson::Number(suggestion_count as i64),
+            );
+            o.insert("top_n", CanonicalJson::Number(self.top_n as i64));
+            o.insert("scorer", CanonicalJson::String(self.scorer_name.clone()));
+            o.insert(
+                "scorer_version",
+                CanonicalJson::String(self.scorer_version.clone()),
+            );
+            o
+        })?;
+        for payload in &self.status_changes {
+            events.push(db, actor, "QuestionStatusChanged", payload.clone())?;
+        }
+        Ok(())
+    }
+}
+
+fn run_matching_pass(
+    db: &SqliteDb,
+    vault_id: &str,
+    import_id: &str,
+    top_n: usize,
+) -> CoreResult<MatchPass> {
+    if top_n == 0 {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "top_n must be > 0",
+        ));
+    }
+
+    let questions = workflow::list_questions(db, import_id)?;
+
+    let rejected: HashSet<(String, String)> = db
+        .query_rows_tsv(&format!(
+            "SELECT s.question_id, s.answer_bank_entry_id FROM match_suggestion s JOIN questionnaire_question q ON q.question_id=s.question_id WHERE q.import_id={} AND s.rejected_at IS NOT NULL;",
+            db.q(import_id)
+        ))?
+        .into_iter()
+        .filter(|r| r.len() >= 2)
+        .map(|r| (r[0].clone(), r[1].clone()))
+        .collect();
+
+    let language = super::load_import(db, import_id)?.language;
+    let matcher = AnswerBankMatcher::load(db, RankingModel::default())?.with_locale(&language);
+
+    let now = DETERMINISTIC_TIMESTAMP_UTC;
+    let mut pass = MatchPass {
+        import_id: import_id.to_string(),
+        top_n,
+        scorer_name: matcher.scorer().name().to_string(),
+        scorer_version: matcher.scorer().version().to_string(),
+        sql: String::new(),
+        status_changes: Vec::new(),
+        questions: Vec::new(),
+        skipped: 0,
+    };
+
+    for q in questions {
+        if !matches!(
+            q.status,
+            QuestionStatus::Unanswered | QuestionStatus::Suggested
+        ) {
+            pass.skipped += 1;
+            continue;
+        }
+
+        pass.sql.push_str(&format!(
+            "DELETE FROM match_suggestion WHERE question_id={} AND accepted=0 AND rejected_at IS NULL;\n",
+            db.q(&q.question_id)
+        ));
+
+        let rejected_here = rejected
+            .iter()
+            .filter(|(qid, _)| *qid == q.question_id)
+            .count();
+        let mut found = matcher.get_suggestions(db, &q.question_text, top_n + rejected_here)?;
+        found.retain(|s| {
+            !rejected.contains(&(q.question_id.clone(), s.answer_bank_entry_id.clone()))
+        });
+        found.truncate(top_n);
+
+        let mut stored = Vec::new();
+        for s in found {
+            let suggestion_id = Ulid::new()?.to_string();
+            pass.sql.push_str(&format!(
+                "INSERT INTO match_suggestion (id, vault_id, question_id, answer_bank_entry_id, score, normalized_question, normalized_answer, confidence_explanation, explanation_json, scorer_name, scorer_version, accepted, accepted_at, created_at, updated_at) VALUES ({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, 0, NULL, {}, {});\n",
+                db.q(&suggestion_id),
+                db.q(vault_id),
+                db.q(&q.question_id),
+                db.q(&s.answer_bank_entry_id),
+                s.score,
+                db.q(&escape_db_text(&s.normalized_question)),
+                db.q(&escape_db_text(&s.normalized_answer)),
+                db.q(&escape_db_text(&s.confidence_explanation)),
+                db.q(&escape_db_text(&s.explanation.to_canonical_json().encode())),
+                db.q(&s.scorer_name),
+                db.q(&escape_db_text(&s.scorer_version)),
+                db.q(now),
+                db.q(now),
+            ));
+            stored.push((suggestion_id, s));
+        }
+
+        let status = if stored.is_empty() {
+            QuestionStatus::Unanswered
+        } else {
+            QuestionStatus::Suggested
+        };
+        let mut q = q;
+        if status != q.status {
+            pass.sql.push_str(&format!(
+                "UPDATE questionnaire_question SET status={}, updated_at={} WHERE question_id={};\n",
+                db.q(status.as_str()),
+                db.q(now),
+                db.q(&q.question_id),
+            ));
+            pass.status_changes
+                .push(workflow::status_event_payload(&q, status));
+            q.status = status;
+        }
+        pass.questions.push((q, stored));
+    }
+
+    Ok(pass)
+}
+
+/// All stored suggestions for one question, best first.
+pub fn list_suggestions(db: &SqliteDb, question_id: &str) -> CoreResult<Vec<QuestionSuggestion>> {
+    workflow::get_question(db, question_id)?;
+    query_suggestions(
+        db,
+        &format!(
+            "SELECT {} FROM match_suggestion s WHERE s.question_id={} ORDER BY s.score DESC, s.answer_bank_entry_id ASC;",
+            SUGGESTION_COLUMNS,
+            db.q(question_id)
+        ),
+    )
+}
+
+/// All stored suggestions for an import, grouped by question in source row
+/// order and best first within a question.
+pub fn list_import_suggestions(
+    db: &SqliteDb,
+    import_id: &str,
+) -> CoreResult<Vec<QuestionSuggestion>> {
+    super::load_import(db, import_id)?;
+    query_suggestions(
+        db,
+        &format!(
+            "SELECT {} FROM match_suggestion s JOIN questionnaire_question q ON q.question_id=s.question_id WHERE q.import_id={} ORDER BY q.row_number ASC, s.score DESC, s.answer_bank_entry_id ASC;",
+            SUGGESTION_COLUMNS,
+            db.q(import_id)
+        ),
+    )
+}
+
+pub fn get_suggestion(db: &SqliteDb, suggestion_id: &str) -> CoreResult<QuestionSuggestion> {
+    query_suggestions(
+        db,
+        &format!(
+            "SELECT {} FROM match_suggestion s WHERE s.id={} LIMIT 1;",
+            SUGGESTION_COLUMNS,
+            db.q(suggestion_id)
+        ),
+    )?
+    .into_iter()
+    .next()
+    .ok_or_else(|| CoreError::new(CoreErrorCode::NotFound, "match suggestion not found"))
+}
+
+/// Accept a suggestion: its entry becomes the question's answer (status
+/// `drafted`), any previously accepted suggestion is released, and the
+/// question wording is remembered as an alias of the entry.
+pub fn accept_suggestion(
+    db: &SqliteDb,
+    suggestion_id: &str,
+    actor: &str,
+) -> CoreResult<QuestionSuggestion> {
+    validator::validate_chain(db)?;
+
+    let suggestion = get_suggestion(db, suggestion_id)?;
+    if suggestion.accepted {
+        return Ok(suggestion);
+    }
+    let question = workflow::get_question(db, &suggestion.question_id)?;
+
+    let mut events = AuditEventBatch::new(db, &question.vault_id)?;
+    events.push(
+        db,
+        actor,
+        "MatchSuggestionAccepted",
+        suggestion_event_payload(&suggestion, &question.import_id),
+    )?;
+    let mut answer_sql = workflow::set_answer_sql(
+        db,
+        &question,
+        &QuestionAnswer::Entry(suggestion.answer_bank_entry_id.clone()),
+        actor,
+        &mut events,
+    )?;
+    answer_sql.push_str(&answer_bank::learn_alias_sql(
+        db,
+        &suggestion.answer_bank_entry_id,
+        &question.question_text,
+        &question.question_id,
+        actor,
+        &mut events,
+    )?);
+    let accept_sql = format!(
+        "UPDATE match_suggestion SET accepted=1, accepted_at={}, rejected_at=NULL, updated_at={} WHERE id={};",
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        db.q(suggestion_id),
+    );
+
+    db.exec_batch(&format!(
+        "BEGIN;\n{}{}\n{}COMMIT;",
+        answer_sql,
+        accept_sql,
+        events.sql()
+    ))?;
+    get_suggestion(db, suggestion_id)
+}
+
+/// Reject a pending suggestion so it is not offered again for this question.
+///
+/// Accepted suggestions cannot be rejected; set a different answer instead.
+pub fn reject_suggestion(
+    db: &SqliteDb,
+    suggestion_id: &str,
+    actor: &str,
+) -> CoreResult<QuestionSuggestion> {
+    validator::validate_chain(db)?;
+
+    let suggestion = get_suggestion(db, suggestion_id)?;
+    if suggestion.accepted {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            "suggestion has been accepted; set a different answer instead",
+        ));
+    }
+    if suggestion.rejected_at.is_some() {
+        return Ok(suggestion);
+    }
+    let question = workflow::get_question(db, &suggestion.question_id)?;
+
+    let mut sql = format!(
+        "UPDATE match_suggestion SET rejected_at={}, updated_at={} WHERE id={};\n",
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        db.q(suggestion_id),
+    );
+    let mut events = AuditEventBatch::new(db, &question.vault_id)?;
+    events.push(
+        db,
+        actor,
+        "MatchSuggestionRejected",
+        suggestion_event_payload(&suggestion, &question.import_id),
+    )?;
+
+    // The last pending suggestion going away leaves nothing to review.
+    let others_pending = db
+        .query_optional_string(&format!(
+            "SELECT id FROM match_suggestion WHERE question_id={} AND id<>{} AND accepted=0 AND rejected_at IS NULL LIMIT 1;",
+            db.q(&question.question_id),
+            db.q(suggestion_id)
+        ))?
+        .is_some();
+    if question.status == QuestionStatus::Suggested && !others_pending {
+        sql.push_str(&format!(
+            "UPDATE questionnaire_question SET status={}, updated_at={} WHERE question_id={};\n",
+            db.q(QuestionStatus::Unanswered.as_str()),
+            db.q(DETERMINISTIC_TIMESTAMP_UTC),
+            db.q(&question.question_id),
+        ));
+        events.push(
+            db,
+            actor,
+            "QuestionStatusChanged",
+            workflow::status_event_payload(&question, QuestionStatus::Unanswered),
+        )?;
+    }
+
+    db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", sql, events.sql()))?;
+    get_suggestion(db, suggestion_id)
+}
+
+fn suggestion_event_payload(s: &QuestionSuggestion, import_id: &str) -> CanonicalJson {
+    let mut o = CanonicalJson::object();
+    o.insert(
+        "suggestion_id",
+        CanonicalJson::String(s.suggestion_id.clone()),
+    );
+    o.insert("question_id", CanonicalJson::String(s.question_id.clone()));
+    o.insert("import_id", CanonicalJson::String(import_id.to_string()));
+    o.insert(
+        "answer_bank_entry_id",
+        CanonicalJson::String(s.answer_bank_entry_id.clone()),
+    );
+    o
+}
+
+fn query_suggestions(db: &SqliteDb, sql: &str) -> CoreResult<Vec<QuestionSuggestion>> {
+    let rows = db.query_rows_tsv(sql)?;
+    let mut out = Vec::new();
+    for r in rows {
+        if r.len() < 15 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected match_suggestion row",
+            ));
+        }
+        let score: f64 = r[3]
+            .parse()
+            .map_err(|_| CoreError::new(CoreErrorCode::CorruptVault, "invalid suggestion score"))?;
+        let opt = |s: &str| {
+            if s.is_empty() {
+                None
+            } else {
+                Some(s.to_string())
+            }
+        };
+        out.push(QuestionSuggestion {
+            suggestion_id: r[0].clone(),
+            question_id: r[1].clone(),
+            answer_bank_entry_id: r[2].clone(),
+            score,
+            normalized_question: unescape_db_text(&r[4]),
+            normalized_answer: unescape_db_text(&r[5]),
+            confidence_explanation: unescape_db_text(&r[6]),
+            explanation: match r[14].as_str() {
+                "" => None,
+                json => Some(MatchExplanation::from_json_str(&unescape_db_text(json))?),
+            },
+            scorer_name: opt(&r[7]),
+            scorer_version: opt(&r[8]),
+            accepted: r[9] == "1",
+            accepted_at: opt(&r[10]),
+            rejected_at: opt(&r[11]),
+            created_at: r[12].clone(),
+            updated_at: r[13].clone(),
+        });
+    }
+    Ok(out)
+}
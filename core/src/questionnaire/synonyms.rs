@@ -0,0 +1,134 @@
+//! Vault synonym/acronym dictionary for matching.
+//!
+//! Rows live in `match_synonym` (seeded by migration 0010 with common security
+//! and compliance terms) and feed the [`Normalizer`] used by matching. Terms
+//! and canonical forms are stored tokenized so lookups are exact.
+
+use super::matching::MatchingEngine;
+use super::normalize::Normalizer;
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
+use crate::storage::db::SqliteDb;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchSynonym {
+    pub term: String,
+    pub canonical: String,
+    /// `seed` for shipped rows, `user` once added or edited in the app.
+    pub source: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+pub fn list_synonyms(db: &SqliteDb) -> CoreResult<Vec<MatchSynonym>> {
+    let rows = db.query_rows_tsv(
+        "SELECT term, canonical, source, created_at, updated_at FROM match_synonym ORDER BY term ASC;",
+    )?;
+    Ok(rows
+        .into_iter()
+        .filter(|r| r.len() >= 5)
+        .map(|r| MatchSynonym {
+            term: r[0].clone(),
+            canonical: r[1].clone(),
+            source: r[2].clone(),
+            created_at: r[3].clone(),
+            updated_at: r[4].clone(),
+        })
+        .collect())
+}
+
+/// Add or replace the synonym for `term`.
+pub fn set_synonym(
+    db: &SqliteDb,
+    term: &str,
+    canonical: &str,
+    actor: &str,
+) -> CoreResult<MatchSynonym> {
+    validator::validate_chain(db)?;
+
+    let term = tokenized("term", term)?;
+    let canonical = tokenized("canonical", canonical)?;
+    if term == canonical {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "term and canonical must differ",
+        ));
+    }
+
+    let vault_id = load_vault_id(db)?;
+    let now = DETERMINISTIC_TIMESTAMP_UTC;
+    let upsert_sql = format!(
+        "INSERT INTO match_synonym (term, canonical, source, created_at, updated_at) VALUES ({}, {}, 'user', {}, {}) \
+         ON CONFLICT(term) DO UPDATE SET canonical=excluded.canonical, source='user', updated_at=excluded.updated_at;",
+        db.q(&term),
+        db.q(&canonical),
+        db.q(now),
+        db.q(now),
+    );
+    let event_sql =
+        crate::storage::build_event_insert_sql(db, &vault_id, actor, "MatchSynonymSet", {
+            let mut o = CanonicalJson::object();
+            o.insert("term", CanonicalJson::String(term.clone()));
+            o.insert("canonical", CanonicalJson::String(canonical.clone()));
+            o
+        })?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", upsert_sql, event_sql))?;
+
+    list_synonyms(db)?
+        .into_iter()
+        .find(|s| s.term == term)
+        .ok_or_else(|| CoreError::new(CoreErrorCode::DbError, "synonym not stored"))
+}
+
+pub fn delete_synonym(db: &SqliteDb, term: &str, actor: &str) -> CoreResult<()> {
+    validator::validate_chain(db)?;
+
+    let term = tokenized("term", term)?;
+    let exists = db.query_optional_string(&format!(
+        "SELECT term FROM match_synonym WHERE term={} LIMIT 1;",
+        db.q(&term)
+    ))?;
+    if exists.is_none() {
+        return Err(CoreError::new(
+            CoreErrorCode::NotFound,
+            format!("synonym not found: {term}"),
+        ));
+    }
+
+    let vault_id = load_vault_id(db)?;
+    let delete_sql = format!("DELETE FROM match_synonym WHERE term={};", db.q(&term));
+    let event_sql =
+        crate::storage::build_event_insert_sql(db, &vault_id, actor, "MatchSynonymDeleted", {
+            let mut o = CanonicalJson::object();
+            o.insert("term", CanonicalJson::String(term.clone()));
+            o
+        })?;
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", delete_sql, event_sql))
+}
+
+/// Default normalization pipeline with the vault's synonyms loaded.
+pub fn load_normalizer(db: &SqliteDb) -> CoreResult<Normalizer> {
+    let mut normalizer = Normalizer::default();
+    for s in list_synonyms(db)? {
+        normalizer.add_synonym(&s.term, &s.canonical);
+    }
+    Ok(normalizer)
+}
+
+fn tokenized(field: &str, s: &str) -> CoreResult<String> {
+    let tokens = MatchingEngine::normalize(s);
+    if tokens.is_empty() {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            format!("{field} is required"),
+        ));
+    }
+    Ok(tokens.join(" "))
+}
+
+fn load_vault_id(db: &SqliteDb) -> CoreResult<String> {
+    db.query_optional_string("SELECT vault_id FROM vault LIMIT 1;")?
+        .ok_or_else(|| CoreError::new(CoreErrorCode::CorruptVault, "missing vault row"))
+}
@@ -0,0 +1,677 @@
+//! Per-question answering workflow for imported questionnaires.
+//!
+//! Questions are materialized from the stored import rows when a column map is
+//! set. Each question carries a status, an optional assignee, the chosen
+//! answer (an answer bank entry or a free-text override) and a comment thread.
+//! Every change appends an audit event.
+
+use super::ColumnMap;
+use crate::audit::canonical::CanonicalJson;
+use crate::audit::validator;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::domain::ids::Ulid;
+use crate::domain::time::DETERMINISTIC_TIMESTAMP_UTC;
+use crate::storage::db::{escape_db_text, unescape_db_text, SqliteDb};
+use crate::storage::AuditEventBatch;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionStatus {
+    Unanswered,
+    Suggested,
+    Drafted,
+    Approved,
+    NotApplicable,
+}
+
+impl QuestionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuestionStatus::Unanswered => "unanswered",
+            QuestionStatus::Suggested => "suggested",
+            QuestionStatus::Drafted => "drafted",
+            QuestionStatus::Approved => "approved",
+            QuestionStatus::NotApplicable => "not_applicable",
+        }
+    }
+
+    pub fn parse(s: &str) -> CoreResult<Self> {
+        match s {
+            "unanswered" => Ok(QuestionStatus::Unanswered),
+            "suggested" => Ok(QuestionStatus::Suggested),
+            "drafted" => Ok(QuestionStatus::Drafted),
+            "approved" => Ok(QuestionStatus::Approved),
+            "not_applicable" => Ok(QuestionStatus::NotApplicable),
+            other => Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!("unknown question status: {other}"),
+            )),
+        }
+    }
+
+    fn can_transition_to(&self, to: QuestionStatus) -> bool {
+        use QuestionStatus::*;
+        matches!(
+            (self, to),
+            (Unanswered, Suggested | Drafted | NotApplicable)
+                | (Suggested, Unanswered | Drafted | NotApplicable)
+                | (Drafted, Unanswered | Suggested | Approved | NotApplicable)
+                | (Approved, Drafted)
+                | (NotApplicable, Unanswered)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestionAnswer {
+    /// Answer taken from an answer bank entry.
+    Entry(String),
+    /// Free-text answer written for this question only.
+    Override(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct QuestionnaireQuestion {
+    pub question_id: String,
+    pub vault_id: String,
+    pub import_id: String,
+    pub row_number: i64,
+    pub question_text: String,
+    pub source_answer: Option<String>,
+    pub source_notes: Option<String>,
+    pub status: QuestionStatus,
+    pub assignee: Option<String>,
+    pub answer: Option<QuestionAnswer>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct QuestionComment {
+    pub comment_id: String,
+    pub question_id: String,
+    pub author: String,
+    pub body: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportProgress {
+    pub import_id: String,
+    pub total: i64,
+    pub unanswered: i64,
+    pub suggested: i64,
+    pub drafted: i64,
+    pub approved: i64,
+    pub not_applicable: i64,
+    /// Share of questions that are approved or not applicable (0-100).
+    pub completion_percent: f64,
+    /// Share of questions with at least a draft answer, or not applicable (0-100).
+    pub answered_percent: f64,
+}
+
+const QUESTION_COLUMNS: &str = "question_id, vault_id, import_id, row_number, question_text, IFNULL(source_answer,''), IFNULL(source_notes,''), status, IFNULL(assignee,''), IFNULL(answer_bank_entry_id,''), IFNULL(answer_override,''), created_at, updated_at";
+
+pub fn list_questions(db: &SqliteDb, import_id: &str) -> CoreResult<Vec<QuestionnaireQuestion>> {
+    ensure_import_exists(db, import_id)?;
+    let rows = db.query_rows_tsv(&format!(
+        "SELECT {} FROM questionnaire_question WHERE import_id={} ORDER BY row_number ASC;",
+        QUESTION_COLUMNS,
+        db.q(import_id)
+    ))?;
+    rows.iter().map(|r| parse_question_row(r)).collect()
+}
+
+/// Questions of an import answered with an answer bank entry mapped to the
+/// control, or to any control of `framework` when `control_id` is `None`.
+pub fn list_questions_for_control(
+    db: &SqliteDb,
+    import_id: &str,
+    framework: &str,
+    control_id: Option<&str>,
+) -> CoreResult<Vec<QuestionnaireQuestion>> {
+    ensure_import_exists(db, import_id)?;
+    let filter = crate::answer_bank::control_filter_sql(db, framework, control_id)?;
+    let rows = db.query_rows_tsv(&format!(
+        "SELECT {} FROM questionnaire_question WHERE import_id={} AND answer_bank_entry_id IN (SELECT entry_id FROM answer_bank_control WHERE {}) ORDER BY row_number ASC;",
+        QUESTION_COLUMNS,
+        db.q(import_id),
+        filter
+    ))?;
+    rows.iter().map(|r| parse_question_row(r)).collect()
+}
+
+pub fn get_question(db: &SqliteDb, question_id: &str) -> CoreResult<QuestionnaireQuestion> {
+    let rows = db.query_rows_tsv(&format!(
+        "SELECT {} FROM questionnaire_question WHERE question_id={} LIMIT 1;",
+        QUESTION_COLUMNS,
+        db.q(question_id)
+    ))?;
+    match rows.first() {
+        Some(r) => parse_question_row(r),
+        None => Err(CoreError::new(
+            CoreErrorCode::NotFound,
+            "questionnaire question not found",
+        )),
+    }
+}
+
+pub fn set_question_status(
+    db: &SqliteDb,
+    question_id: &str,
+    status: QuestionStatus,
+    actor: &str,
+) -> CoreResult<QuestionnaireQuestion> {
+    validator::validate_chain(db)?;
+
+    let before = get_question(db, question_id)?;
+    if before.status == status {
+        return Ok(before);
+    }
+    if !before.status.can_transition_to(status) {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            format!(
+                "cannot move question from {} to {}",
+                before.status.as_str(),
+                status.as_str()
+            ),
+        ));
+    }
+    if matches!(status, QuestionStatus::Drafted | QuestionStatus::Approved)
+        && before.answer.is_none()
+    {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            format!("question has no answer to mark as {}", status.as_str()),
+        ));
+    }
+
+    let update_sql = format!(
+        "UPDATE questionnaire_question SET status={}, updated_at={} WHERE question_id={};",
+        db.q(status.as_str()),
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        db.q(question_id),
+    );
+    let event_sql = crate::storage::build_event_insert_sql(
+        db,
+        &before.vault_id,
+        actor,
+        "QuestionStatusChanged",
+        status_event_payload(&before, status),
+    )?;
+
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", update_sql, event_sql))?;
+    get_question(db, question_id)
+}
+
+/// Record the chosen answer for a question and move it to `drafted`.
+///
+/// Approved questions go back to `drafted`: a changed answer needs approving
+/// again. Choosing an answer bank entry remembers the question wording as an
+/// alias of the entry.
+pub fn set_question_answer(
+    db: &SqliteDb,
+    question_id: &str,
+    answer: &QuestionAnswer,
+    actor: &str,
+) -> CoreResult<QuestionnaireQuestion> {
+    validator::validate_chain(db)?;
+
+    let before = get_question(db, question_id)?;
+    let mut events = AuditEventBatch::new(db, &before.vault_id)?;
+    let mut update_sql = set_answer_sql(db, &before, answer, actor, &mut events)?;
+    if let QuestionAnswer::Entry(entry_id) = answer {
+        update_sql.push_str(&crate::answer_bank::learn_alias_sql(
+            db,
+            entry_id.trim(),
+            &before.question_text,
+            &before.question_id,
+            actor,
+            &mut events,
+        )?);
+    }
+
+    db.exec_batch(&format!("BEGIN;\n{}{}COMMIT;", update_sql, events.sql()))?;
+    get_question(db, question_id)
+}
+
+/// SQL (plus audit events) that stores `answer` on a question and moves it to
+/// `drafted`. Stored suggestions for other entries lose their acceptance so
+/// `match_suggestion.accepted` always agrees with the question's answer.
+pub(crate) fn set_answer_sql(
+    db: &SqliteDb,
+    before: &QuestionnaireQuestion,
+    answer: &QuestionAnswer,
+    actor: &str,
+    events: &mut AuditEventBatch,
+) -> CoreResult<String> {
+    if before.status == QuestionStatus::NotApplicable {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            "question is marked not applicable",
+        ));
+    }
+
+    let (entry_sql, override_sql, answer_payload) = match answer {
+        QuestionAnswer::Entry(entry_id) => {
+            let entry_id = entry_id.trim();
+            let exists = db
+                .query_optional_string(&format!(
+                    "SELECT entry_id FROM answer_bank WHERE entry_id={} AND vault_id={} LIMIT 1;",
+                    db.q(entry_id),
+                    db.q(&before.vault_id)
+                ))?
+                .is_some();
+            if !exists {
+                return Err(CoreError::new(
+                    CoreErrorCode::NotFound,
+                    "answer bank entry not found",
+                ));
+            }
+            let mut o = CanonicalJson::object();
+            o.insert(
+                "answer_bank_entry_id",
+                CanonicalJson::String(entry_id.to_string()),
+            );
+            (db.q(entry_id), "NULL".to_string(), o)
+        }
+        QuestionAnswer::Override(text) => {
+            let text = text.trim().replace("\r\n", "\n").replace('\r', "\n");
+            if text.is_empty() {
+                return Err(CoreError::new(
+                    CoreErrorCode::ValidationError,
+                    "answer override is required",
+                ));
+            }
+            // The override text itself stays out of the audit log.
+            let mut o = CanonicalJson::object();
+            o.insert(
+                "override_sha256",
+                CanonicalJson::String(crate::audit::hasher::sha256_hex_bytes(text.as_bytes())?),
+            );
+            ("NULL".to_string(), db.q(&escape_db_text(&text)), o)
+        }
+    };
+
+    let mut sql = format!(
+        "UPDATE questionnaire_question SET answer_bank_entry_id={}, answer_override={}, status={}, updated_at={} WHERE question_id={};\n",
+        entry_sql,
+        override_sql,
+        db.q(QuestionStatus::Drafted.as_str()),
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        db.q(&before.question_id),
+    );
+    sql.push_str(&format!(
+        "UPDATE match_suggestion SET accepted=0, accepted_at=NULL, updated_at={} WHERE question_id={} AND accepted=1 AND answer_bank_entry_id IS NOT {};\n",
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        db.q(&before.question_id),
+        entry_sql,
+    ));
+
+    events.push(db, actor, "QuestionAnswerSet", {
+        let mut o = answer_payload;
+        o.insert(
+            "question_id",
+            CanonicalJson::String(before.question_id.clone()),
+        );
+        o.insert("import_id", CanonicalJson::String(before.import_id.clone()));
+        o
+    })?;
+    if before.status != QuestionStatus::Drafted {
+        events.push(
+            db,
+            actor,
+            "QuestionStatusChanged",
+            status_event_payload(before, QuestionStatus::Drafted),
+        )?;
+    }
+    Ok(sql)
+}
+
+/// Assign a question to someone, or clear the assignee with `None`.
+pub fn assign_question(
+    db: &SqliteDb,
+    question_id: &str,
+    assignee: Option<&str>,
+    actor: &str,
+) -> CoreResult<QuestionnaireQuestion> {
+    validator::validate_chain(db)?;
+
+    let before = get_question(db, question_id)?;
+    let assignee = assignee.map(str::trim).filter(|s| !s.is_empty());
+    if before.assignee.as_deref() == assignee {
+        return Ok(before);
+    }
+
+    let update_sql = format!(
+        "UPDATE questionnaire_question SET assignee={}, updated_at={} WHERE question_id={};",
+        match assignee {
+            Some(a) => db.q(&escape_db_text(a)),
+            None => "NULL".to_string(),
+        },
+        db.q(DETERMINISTIC_TIMESTAMP_UTC),
+        db.q(question_id),
+    );
+    let event_sql =
+        crate::storage::build_event_insert_sql(db, &before.vault_id, actor, "QuestionAssigned", {
+            let mut o = CanonicalJson::object();
+            o.insert(
+                "question_id",
+                CanonicalJson::String(question_id.to_string()),
+            );
+            o.insert("import_id", CanonicalJson::String(before.import_id.clone()));
+            o.insert(
+                "assignee",
+                match assignee {
+                    Some(a) => CanonicalJson::String(a.to_string()),
+                    None => CanonicalJson::Null,
+                },
+            );
+            o
+        })?;
+
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", update_sql, event_sql))?;
+    get_question(db, question_id)
+}
+
+pub fn add_question_comment(
+    db: &SqliteDb,
+    question_id: &str,
+    body: &str,
+    actor: &str,
+) -> CoreResult<QuestionComment> {
+    validator::validate_chain(db)?;
+
+    let question = get_question(db, question_id)?;
+    let body = body.trim().replace("\r\n", "\n").replace('\r', "\n");
+    if body.is_empty() {
+        return Err(CoreError::new(
+            CoreErrorCode::ValidationError,
+            "comment body is required",
+        ));
+    }
+
+    let comment_id = Ulid::new()?.to_string();
+    let created_at = DETERMINISTIC_TIMESTAMP_UTC.to_string();
+    let insert_sql = format!(
+        "INSERT INTO questionnaire_question_comment (comment_id, question_id, author, body, created_at) VALUES ({}, {}, {}, {}, {});",
+        db.q(&comment_id),
+        db.q(question_id),
+        db.q(&escape_db_text(actor)),
+        db.q(&escape_db_text(&body)),
+        db.q(&created_at),
+    );
+    let event_sql = crate::storage::build_event_insert_sql(
+        db,
+        &question.vault_id,
+        actor,
+        "QuestionCommentAdded",
+        {
+            let mut o = CanonicalJson::object();
+            o.insert(
+                "question_id",
+                CanonicalJson::String(question_id.to_string()),
+            );
+            o.insert("comment_id", CanonicalJson::String(comment_id.clone()));
+            o
+        },
+    )?;
+
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", insert_sql, event_sql))?;
+
+    Ok(QuestionComment {
+        comment_id,
+        question_id: question_id.to_string(),
+        author: actor.to_string(),
+        body,
+        created_at,
+    })
+}
+
+pub fn list_question_comments(
+    db: &SqliteDb,
+    question_id: &str,
+) -> CoreResult<Vec<QuestionComment>> {
+    get_question(db, question_id)?;
+    // Timestamps are deterministic, so insertion order breaks ties.
+    let rows = db.query_rows_tsv(&format!(
+        "SELECT comment_id, question_id, author, body, created_at FROM questionnaire_question_comment WHERE question_id={} ORDER BY created_at ASC, rowid ASC;",
+        db.q(question_id)
+    ))?;
+    let mut out = Vec::new();
+    for r in rows {
+        if r.len() < 5 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected questionnaire_question_comment row",
+            ));
+        }
+        out.push(QuestionComment {
+            comment_id: r[0].clone(),
+            question_id: r[1].clone(),
+            author: unescape_db_text(&r[2]),
+            body: unescape_db_text(&r[3]),
+            created_at: r[4].clone(),
+        });
+    }
+    Ok(out)
+}
+
+pub fn import_progress(db: &SqliteDb, import_id: &str) -> CoreResult<ImportProgress> {
+    ensure_import_exists(db, import_id)?;
+    let rows = db.query_rows_tsv(&format!(
+        "SELECT status, COUNT(*) FROM questionnaire_question WHERE import_id={} GROUP BY status ORDER BY status ASC;",
+        db.q(import_id)
+    ))?;
+
+    let mut progress = ImportProgress {
+        import_id: import_id.to_string(),
+        total: 0,
+        unanswered: 0,
+        suggested: 0,
+        drafted: 0,
+        approved: 0,
+        not_applicable: 0,
+        completion_percent: 0.0,
+        answered_percent: 0.0,
+    };
+    for r in rows {
+        if r.len() < 2 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected question status count row",
+            ));
+        }
+        let n: i64 = r[1]
+            .parse()
+            .map_err(|_| CoreError::new(CoreErrorCode::CorruptVault, "invalid status count"))?;
+        let status = QuestionStatus::parse(&r[0])
+            .map_err(|e| CoreError::new(CoreErrorCode::CorruptVault, e.message))?;
+        match status {
+            QuestionStatus::Unanswered => progress.unanswered = n,
+            QuestionStatus::Suggested => progress.suggested = n,
+            QuestionStatus::Drafted => progress.drafted = n,
+            QuestionStatus::Approved => progress.approved = n,
+            QuestionStatus::NotApplicable => progress.not_applicable = n,
+        }
+        progress.total += n;
+    }
+
+    if progress.total > 0 {
+        let pct = |n: i64| n as f64 * 100.0 / progress.total as f64;
+        progress.completion_percent = pct(progress.approved + progress.not_applicable);
+        progress.answered_percent =
+            pct(progress.drafted + progress.approved + progress.not_applicable);
+    }
+    Ok(progress)
+}
+
+/// SQL that (re)builds the questions of an import for a new column map.
+///
+/// While no work has been recorded the questions (and any generated
+/// suggestions) are simply rebuilt. Once any question has progress (status,
+/// assignee, answer, comments or reviewed suggestions) the question column is
+/// locked; only the answer/notes source columns are refreshed.
+pub(crate) fn materialize_questions_sql(
+    db: &SqliteDb,
+    vault_id: &str,
+    import_id: &str,
+    previous: Option<&ColumnMap>,
+    map: &ColumnMap,
+) -> CoreResult<String> {
+    let rows = db.query_rows_tsv(&format!(
+        "SELECT row_number, cells_json FROM questionnaire_import_row WHERE import_id={} ORDER BY row_number ASC;",
+        db.q(import_id)
+    ))?;
+
+    let has_progress = db
+        .query_optional_string(&format!(
+            "SELECT question_id FROM questionnaire_question q WHERE import_id={} AND (status NOT IN ('unanswered','suggested') OR assignee IS NOT NULL OR answer_bank_entry_id IS NOT NULL OR answer_override IS NOT NULL OR EXISTS (SELECT 1 FROM questionnaire_question_comment c WHERE c.question_id=q.question_id) OR EXISTS (SELECT 1 FROM match_suggestion s WHERE s.question_id=q.question_id AND (s.accepted=1 OR s.rejected_at IS NOT NULL))) LIMIT 1;",
+            db.q(import_id)
+        ))?
+        .is_some();
+
+    if has_progress && previous.map(|p| p.question != map.question).unwrap_or(true) {
+        return Err(CoreError::new(
+            CoreErrorCode::Conflict,
+            "question column cannot change once questions have been worked on",
+        ));
+    }
+
+    let mut sql = String::new();
+    if !has_progress {
+        sql.push_str(&format!(
+            "DELETE FROM match_suggestion WHERE question_id IN (SELECT question_id FROM questionnaire_question WHERE import_id={});\n",
+            db.q(import_id)
+        ));
+        sql.push_str(&format!(
+            "DELETE FROM questionnaire_question WHERE import_id={};\n",
+            db.q(import_id)
+        ));
+    }
+
+    let now = DETERMINISTIC_TIMESTAMP_UTC;
+    for r in rows {
+        if r.len() < 2 {
+            return Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                "unexpected questionnaire_import_row row",
+            ));
+        }
+        let row_number: i64 = r[0]
+            .parse()
+            .map_err(|_| CoreError::new(CoreErrorCode::CorruptVault, "invalid row_number"))?;
+        let cells = crate::util::json::JsonValue::parse(&r[1])?;
+        let cells = cells.as_object()?;
+        let cell = |col: &str| -> Option<String> {
+            match cells.get(col) {
+                Some(crate::util::json::JsonValue::String(s)) if !s.trim().is_empty() => {
+                    Some(s.clone())
+                }
+                _ => None,
+            }
+        };
+        let opt_sql = |v: Option<String>| match v {
+            Some(s) => db.q(&escape_db_text(&s)),
+            None => "NULL".to_string(),
+        };
+
+        let answer = cell(&map.answer);
+        let notes = map.notes.as_deref().and_then(cell);
+        if has_progress {
+            sql.push_str(&format!(
+                "UPDATE questionnaire_question SET source_answer={}, source_notes={} WHERE import_id={} AND row_number={};\n",
+                opt_sql(answer),
+                opt_sql(notes),
+                db.q(import_id),
+                row_number,
+            ));
+            continue;
+        }
+
+        let Some(question_text) = cell(&map.question) else {
+            continue;
+        };
+        sql.push_str(&format!(
+            "INSERT INTO questionnaire_question (question_id, vault_id, import_id, row_number, question_text, source_answer, source_notes, status, created_at, updated_at) VALUES ({}, {}, {}, {}, {}, {}, {}, {}, {}, {});\n",
+            db.q(&Ulid::new()?.to_string()),
+            db.q(vault_id),
+            db.q(import_id),
+            row_number,
+            db.q(&escape_db_text(&question_text)),
+            opt_sql(answer),
+            opt_sql(notes),
+            db.q(QuestionStatus::Unanswered.as_str()),
+            db.q(now),
+            db.q(now),
+        ));
+    }
+    Ok(sql)
+}
+
+pub(crate) fn status_event_payload(
+    before: &QuestionnaireQuestion,
+    to: QuestionStatus,
+) -> CanonicalJson {
+    let mut o = CanonicalJson::object();
+    o.insert(
+        "question_id",
+        CanonicalJson::String(before.question_id.clone()),
+    );
+    o.insert("import_id", CanonicalJson::String(before.import_id.clone()));
+    o.insert(
+        "from",
+        CanonicalJson::String(before.status.as_str().to_string()),
+    );
+    o.insert("to", CanonicalJson::String(to.as_str().to_string()));
+    o
+}
+
+fn ensure_import_exists(db: &SqliteDb, import_id: &str) -> CoreResult<()> {
+    db.query_optional_string(&format!(
+        "SELECT import_id FROM questionnaire_import WHERE import_id={} LIMIT 1;",
+        db.q(import_id)
+    ))?
+    .map(|_| ())
+    .ok_or_else(|| CoreError::new(CoreErrorCode::NotFound, "questionnaire import not found"))
+}
+
+fn parse_question_row(r: &[String]) -> CoreResult<QuestionnaireQuestion> {
+    if r.len() < 13 {
+        return Err(CoreError::new(
+            CoreErrorCode::CorruptVault,
+            "unexpected questionnaire_question row",
+        ));
+    }
+    let row_number: i64 = r[3]
+        .parse()
+        .map_err(|_| CoreError::new(CoreErrorCode::CorruptVault, "invalid row_number"))?;
+    let status = QuestionStatus::parse(&r[7])
+        .map_err(|e| CoreError::new(CoreErrorCode::CorruptVault, e.message))?;
+    let opt = |s: &str| {
+        if s.is_empty() {
+            None
+        } else {
+            Some(unescape_db_text(s))
+        }
+    };
+    let answer = if !r[9].is_empty() {
+        Some(QuestionAnswer::Entry(r[9].clone()))
+    } else {
+        opt(&r[10]).map(QuestionAnswer::Override)
+    };
+    Ok(QuestionnaireQuestion {
+        question_id: r[0].clone(),
+        vault_id: r[1].clone(),
+        import_id: r[2].clone(),
+        row_number,
+        question_text: unescape_db_text(&r[4]),
+        source_answer: opt(&r[5]),
+        source_notes: opt(&r[6]),
+        status,
+        assignee: opt(&r[8]),
+        answer,
+        created_at: r[11].clone(),
+        updated_at: r[12].clone(),
+    })
+}
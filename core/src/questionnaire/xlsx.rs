@@ -1,92 +1,153 @@
-use super::ColumnProfile;
+use super::sheet::{self, col_letters_to_index, index_to_col_letters};
+use super::xml::{attr_value, decode_xml_entities, encode_xml_entities, find_from};
 use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
-use crate::domain::ids::Ulid;
-use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
-const SAMPLE_LIMIT: usize = 5;
-const ROW_LIMIT: usize = 50;
-
-pub(crate) fn profile_columns(path: &Path) -> CoreResult<Vec<ColumnProfile>> {
+/// Read the first worksheet as rows of cell text, indexed by sheet row and
+/// column (row 1 / column A at index 0).
+pub(crate) fn read_rows(path: &Path) -> CoreResult<Vec<Vec<String>>> {
     // XLSX is a zip file containing XML parts. For Phase 2 we keep a small,
     // dependency-free parser that is good enough for our sanitized fixtures.
-    let tmp = std::env::temp_dir().join(format!("cs_xlsx_{}_{}", std::process::id(), Ulid::new()?));
+    let tmp = sheet::unzip_to_temp(path, "cs_xlsx")?;
+    let rows = read_first_sheet(&tmp);
     let _ = std::fs::remove_dir_all(&tmp);
-    std::fs::create_dir_all(&tmp)?;
+    rows
+}
 
-    crate::util::zip::unzip_to_dir(path, &tmp)?;
+/// Write rows as a single-sheet workbook with inline string cells, the
+/// smallest layout `read_rows` and spreadsheet apps both accept.
+pub(crate) fn write_rows(path: &Path, sheet_name: &str, rows: &[Vec<String>]) -> CoreResult<()> {
+    let tmp = std::env::temp_dir().join(format!(
+        "cs_xlsx_out_{}_{}",
+        std::process::id(),
+        crate::domain::ids::Ulid::new()?
+    ));
+    let result = write_parts(&tmp, sheet_name, rows).and_then(|_| {
+        crate::util::zip::touch_tree_deterministic(&tmp)?;
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        let out = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            std::env::current_dir()?.join(path)
+        };
+        crate::util::zip::zip_dir_deterministic(&tmp, &out)
+    });
+    let _ = std::fs::remove_dir_all(&tmp);
+    result
+}
 
-    let shared = read_shared_strings(&tmp)?;
-    let sheet_path = pick_sheet_xml(&tmp)?;
-    let sheet_xml = crate::util::fs::read_to_string(&sheet_path)?;
+fn write_parts(root: &Path, sheet_name: &str, rows: &[Vec<String>]) -> CoreResult<()> {
+    use crate::util::fs::write_string;
+
+    const XML_DECL: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+    const MAIN_NS: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+    const PKG_REL_NS: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+    const DOC_REL_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+    write_string(
+        &root.join("[Content_Types].xml"),
+        &format!(
+            "{XML_DECL}<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\"><Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/><Default Extension=\"xml\" ContentType=\"application/xml\"/><Override PartName=\"/xl/workbook.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml\"/><Override PartName=\"/xl/worksheets/sheet1.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml\"/></Types>"
+        ),
+    )?;
+    write_string(
+        &root.join("_rels").join(".rels"),
+        &format!(
+            "{XML_DECL}<Relationships xmlns=\"{PKG_REL_NS}\"><Relationship Id=\"rId1\" Type=\"{DOC_REL_NS}/officeDocument\" Target=\"xl/workbook.xml\"/></Relationships>"
+        ),
+    )?;
+    write_string(
+        &root.join("xl").join("workbook.xml"),
+        &format!(
+            "{XML_DECL}<workbook xmlns=\"{MAIN_NS}\" xmlns:r=\"{DOC_REL_NS}\"><sheets><sheet name=\"{}\" sheetId=\"1\" r:id=\"rId1\"/></sheets></workbook>",
+            encode_xml_entities(sheet_name)
+        ),
+    )?;
+    write_string(
+        &root.join("xl").join("_rels").join("workbook.xml.rels"),
+        &format!(
+            "{XML_DECL}<Relationships xmlns=\"{PKG_REL_NS}\"><Relationship Id=\"rId1\" Type=\"{DOC_REL_NS}/worksheet\" Target=\"worksheets/sheet1.xml\"/></Relationships>"
+        ),
+    )?;
+
+    let mut sheet_xml = format!("{XML_DECL}<worksheet xmlns=\"{MAIN_NS}\"><sheetData>");
+    for (r, row) in rows.iter().enumerate() {
+        sheet_xml.push_str(&format!("<row r=\"{}\">", r + 1));
+        for (c, v) in row.iter().enumerate() {
+            if v.is_empty() {
+                continue;
+            }
+            sheet_xml.push_str(&format!(
+                "<c r=\"{}{}\" t=\"inlineStr\"><is><t>{}</t></is></c>",
+                index_to_col_letters(c),
+                r + 1,
+                encode_xml_entities(v)
+            ));
+        }
+        sheet_xml.push_str("</row>");
+    }
+    sheet_xml.push_str("</sheetData></worksheet>");
+    write_string(
+        &root.join("xl").join("worksheets").join("sheet1.xml"),
+        &sheet_xml,
+    )
+}
 
-    let mut header_by_col: BTreeMap<String, String> = BTreeMap::new();
-    let mut non_empty_by_col: BTreeMap<String, i64> = BTreeMap::new();
-    let mut samples_by_col: BTreeMap<String, Vec<String>> = BTreeMap::new();
-    let mut max_col_idx: i64 = -1;
+fn read_first_sheet(unzipped_root: &Path) -> CoreResult<Vec<Vec<String>>> {
+    let shared = read_shared_strings(unzipped_root)?;
+    let sheet_path = pick_sheet_xml(unzipped_root)?;
+    let sheet_xml = crate::util::fs::read_to_string(&sheet_path)?;
 
-    // Scan rows (1..ROW_LIMIT) and populate maps.
-    let mut row_count = 0usize;
+    let mut rows: Vec<Vec<String>> = Vec::new();
     let mut pos = 0usize;
     while let Some(row_start) = find_from(&sheet_xml, "<row", pos) {
         let row_tag_end = find_from(&sheet_xml, ">", row_start)
             .ok_or_else(|| CoreError::new(CoreErrorCode::ImportFailed, "invalid xlsx row tag"))?;
         let row_tag = &sheet_xml[row_start..=row_tag_end];
-        let row_r = attr_value(row_tag, "r").unwrap_or_else(|| "0".to_string());
-        let row_num: i64 = row_r.parse().unwrap_or(0);
+
+        // Skip look-alikes such as <rowBreaks>.
+        let after = row_tag.as_bytes().get(4).copied().unwrap_or(b'>');
+        if !(after == b'>' || after == b'/' || after.is_ascii_whitespace()) {
+            pos = row_tag_end + 1;
+            continue;
+        }
+        if row_tag.ends_with("/>") {
+            pos = row_tag_end + 1;
+            continue;
+        }
 
         let row_end = find_from(&sheet_xml, "</row>", row_tag_end).ok_or_else(|| {
             CoreError::new(CoreErrorCode::ImportFailed, "invalid xlsx row end tag")
         })?;
         let row_body = &sheet_xml[row_tag_end + 1..row_end];
-
-        parse_cells_in_row(
-            row_num,
-            row_body,
-            &shared,
-            &mut header_by_col,
-            &mut non_empty_by_col,
-            &mut samples_by_col,
-            &mut max_col_idx,
-        )?;
-
         pos = row_end + "</row>".len();
-        row_count += 1;
-        if row_count >= ROW_LIMIT {
-            break;
-        }
-    }
 
-    if max_col_idx < 0 {
-        let _ = std::fs::remove_dir_all(&tmp);
-        return Err(CoreError::new(
-            CoreErrorCode::ImportFailed,
-            "xlsx contained no readable cells",
-        ));
-    }
+        let cells = parse_cells_in_row(row_body, &shared)?;
+        if cells.iter().all(|(_, v)| v.trim().is_empty()) {
+            continue;
+        }
 
-    let mut cols = Vec::new();
-    for idx in 0..=max_col_idx {
-        let col_ref = index_to_col_letters(idx as usize);
-        let label = header_by_col
-            .get(&col_ref)
-            .cloned()
-            .filter(|s| !s.trim().is_empty())
-            .unwrap_or_else(|| col_ref.clone());
-        let non_empty_count = *non_empty_by_col.get(&col_ref).unwrap_or(&0);
-        let sample = samples_by_col.remove(&col_ref).unwrap_or_default();
+        // Rows without an `r` attribute follow the previous row.
+        let row_num = attr_value(row_tag, "r")
+            .and_then(|r| r.parse::<usize>().ok())
+            .filter(|n| *n > rows.len())
+            .unwrap_or(rows.len() + 1);
+        while rows.len() + 1 < row_num {
+            rows.push(Vec::new());
+        }
 
-        cols.push(ColumnProfile {
-            col_ref,
-            ordinal: idx,
-            label,
-            non_empty_count,
-            sample,
-        });
+        let width = cells.iter().map(|(i, _)| i + 1).max().unwrap_or(0);
+        let mut row = vec![String::new(); width];
+        for (i, v) in cells {
+            row[i] = v;
+        }
+        rows.push(row);
     }
 
-    let _ = std::fs::remove_dir_all(&tmp);
-    Ok(cols)
+    sheet::trim_trailing_empty(&mut rows);
+    Ok(rows)
 }
 
 fn pick_sheet_xml(unzipped_root: &Path) -> CoreResult<PathBuf> {
@@ -158,74 +219,44 @@ fn parse_shared_strings(xml: &str) -> Vec<String> {
     out
 }
 
-fn parse_cells_in_row(
-    row_num: i64,
-    row_body: &str,
-    shared: &[String],
-    header_by_col: &mut BTreeMap<String, String>,
-    non_empty_by_col: &mut BTreeMap<String, i64>,
-    samples_by_col: &mut BTreeMap<String, Vec<String>>,
-    max_col_idx: &mut i64,
-) -> CoreResult<()> {
+fn parse_cells_in_row(row_body: &str, shared: &[String]) -> CoreResult<Vec<(usize, String)>> {
+    let mut cells = Vec::new();
+    let mut next_col = 0usize;
     let mut pos = 0usize;
     while let Some(c_start) = find_from(row_body, "<c", pos) {
         let c_tag_end = find_from(row_body, ">", c_start)
             .ok_or_else(|| CoreError::new(CoreErrorCode::ImportFailed, "invalid xlsx cell tag"))?;
         let c_tag = &row_body[c_start..=c_tag_end];
 
-        let cell_ref = match attr_value(c_tag, "r") {
-            Some(v) => v,
-            None => {
-                pos = c_tag_end + 1;
-                continue;
-            }
-        };
-        let col_letters = cell_ref
-            .chars()
-            .take_while(|c| c.is_ascii_alphabetic())
-            .collect::<String>()
-            .to_ascii_uppercase();
-        if col_letters.is_empty() {
+        // Cells without an `r` attribute follow the previous cell.
+        let col_idx = attr_value(c_tag, "r")
+            .map(|cell_ref| {
+                cell_ref
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphabetic())
+                    .collect::<String>()
+            })
+            .filter(|letters| !letters.is_empty())
+            .map(|letters| col_letters_to_index(&letters) as usize)
+            .unwrap_or(next_col);
+        next_col = col_idx + 1;
+
+        // Empty styled cells are self-closing (`<c r="B2" s="1"/>`).
+        if c_tag.ends_with("/>") {
             pos = c_tag_end + 1;
             continue;
         }
 
-        let col_idx = col_letters_to_index(&col_letters);
-        if col_idx > *max_col_idx {
-            *max_col_idx = col_idx;
-        }
-
         let t = attr_value(c_tag, "t").unwrap_or_default();
+        let c_end = find_from(row_body, "</c>", c_tag_end).ok_or_else(|| {
+            CoreError::new(CoreErrorCode::ImportFailed, "invalid xlsx cell end tag")
+        })?;
+        let c_body = &row_body[c_tag_end + 1..c_end];
 
-        let c_end = find_from(row_body, "</c>", c_tag_end).unwrap_or(c_tag_end);
-        let c_body = if c_end > c_tag_end {
-            &row_body[c_tag_end + 1..c_end]
-        } else {
-            ""
-        };
-
-        let value = read_cell_value(c_body, &t, shared);
-        let value = value.trim();
-
-        if row_num == 1 {
-            if !value.is_empty() {
-                header_by_col.insert(col_letters.clone(), value.to_string());
-            }
-        } else if !value.is_empty() {
-            *non_empty_by_col.entry(col_letters.clone()).or_insert(0) += 1;
-            let entry = samples_by_col.entry(col_letters.clone()).or_default();
-            if entry.len() < SAMPLE_LIMIT {
-                entry.push(value.to_string());
-            }
-        }
-
-        pos = if c_end > c_tag_end {
-            c_end + "</c>".len()
-        } else {
-            c_tag_end + 1
-        };
+        cells.push((col_idx, read_cell_value(c_body, &t, shared)));
+        pos = c_end + "</c>".len();
     }
-    Ok(())
+    Ok(cells)
 }
 
 fn read_cell_value(c_body: &str, cell_type: &str, shared: &[String]) -> String {
@@ -259,50 +290,3 @@ fn extract_simple_tag_text(s: &str, tag: &str) -> Option<String> {
     let inner = &s[start + open.len()..start + open.len() + end];
     Some(inner.to_string())
 }
-
-fn attr_value(tag: &str, name: &str) -> Option<String> {
-    // Very small attribute scanner: name="...".
-    let pat = format!("{}=\"", name);
-    let i = tag.find(&pat)?;
-    let rest = &tag[i + pat.len()..];
-    let j = rest.find('"')?;
-    Some(rest[..j].to_string())
-}
-
-fn find_from(hay: &str, needle: &str, from: usize) -> Option<usize> {
-    hay.get(from..)?.find(needle).map(|i| from + i)
-}
-
-fn decode_xml_entities(s: &str) -> String {
-    // Minimal entity decoder for our fixture XML.
-    s.replace("&amp;", "&")
-        .replace("&lt;", "<")
-        .replace("&gt;", ">")
-        .replace("&quot;", "\"")
-        .replace("&#39;", "'")
-}
-
-fn col_letters_to_index(s: &str) -> i64 {
-    // A -> 0, B -> 1, Z -> 25, AA -> 26...
-    let mut n: i64 = 0;
-    for c in s.chars() {
-        if !c.is_ascii_alphabetic() {
-            continue;
-        }
-        let v = (c.to_ascii_uppercase() as u8 - b'A' + 1) as i64;
-        n = n * 26 + v;
-    }
-    n - 1
-}
-
-fn index_to_col_letters(mut idx: usize) -> String {
-    // 0 -> A, 1 -> B, 25 -> Z, 26 -> AA...
-    let mut out = String::new();
-    idx += 1;
-    while idx > 0 {
-        let rem = (idx - 1) % 26;
-        out.push((b'A' + rem as u8) as char);
-        idx = (idx - 1) / 26;
-    }
-    out.chars().rev().collect()
-}
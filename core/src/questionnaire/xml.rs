@@ -0,0 +1,208 @@
+//! Minimal XML scanning shared by the zipped-XML questionnaire importers
+//! (XLSX, ODS, DOCX).
+//!
+//! This is not a conforming XML parser. It tokenizes well-formed office
+//! documents into start/end/text events, which is all the importers need to
+//! walk table structures without pulling in a dependency.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum XmlEvent<'a> {
+    /// Opening tag. `tag` is the raw tag text (including `<` and `>`) so
+    /// callers can read attributes with [`attr_value`].
+    Start {
+        name: &'a str,
+        tag: &'a str,
+        self_closing: bool,
+    },
+    End {
+        name: &'a str,
+    },
+    /// Raw character data; entities are still encoded.
+    Text(&'a str),
+    /// CDATA section contents (no entity decoding applies).
+    CData(&'a str),
+}
+
+pub(crate) struct XmlReader<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> XmlReader<'a> {
+    pub(crate) fn new(s: &'a str) -> Self {
+        Self { s, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for XmlReader<'a> {
+    type Item = XmlEvent<'a>;
+
+    fn next(&mut self) -> Option<XmlEvent<'a>> {
+        loop {
+            let rest = self.s.get(self.pos..)?;
+            if rest.is_empty() {
+                return None;
+            }
+
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.pos += end;
+                return Some(XmlEvent::Text(&rest[..end]));
+            }
+
+            if rest.starts_with("<!--") {
+                let end = rest.find("-->").map(|i| i + 3).unwrap_or(rest.len());
+                self.pos += end;
+                continue;
+            }
+
+            if let Some(body) = rest.strip_prefix("<![CDATA[") {
+                let (text, consumed) = match body.find("]]>") {
+                    Some(end) => (&body[..end], "<![CDATA[".len() + end + "]]>".len()),
+                    None => (body, rest.len()),
+                };
+                self.pos += consumed;
+                return Some(XmlEvent::CData(text));
+            }
+
+            if rest.starts_with("<?") || rest.starts_with("<!") {
+                let end = rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+                self.pos += end;
+                continue;
+            }
+
+            let end = tag_end(rest)?;
+            let tag = &rest[..=end];
+            self.pos += end + 1;
+
+            if let Some(inner) = tag.strip_prefix("</") {
+                let name = inner.trim_end_matches('>').trim();
+                return Some(XmlEvent::End { name });
+            }
+
+            let self_closing = tag.ends_with("/>");
+            let name_end = tag[1..]
+                .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+                .map(|i| i + 1)
+                .unwrap_or(tag.len());
+            return Some(XmlEvent::Start {
+                name: &tag[1..name_end],
+                tag,
+                self_closing,
+            });
+        }
+    }
+}
+
+/// Index of the `>` closing the tag that starts at `s[0]`, skipping quoted
+/// attribute values.
+fn tag_end(s: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '>' => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+pub(crate) fn attr_value(tag: &str, name: &str) -> Option<String> {
+    // Attribute scanner: ` name="..."` or ` name='...'`. Requiring the leading
+    // whitespace keeps `r` from matching inside e.g. `xr:uid`.
+    let mut from = 0usize;
+    while let Some(i) = find_from(tag, name, from) {
+        from = i + name.len();
+        let preceded_by_ws = tag[..i]
+            .chars()
+            .next_back()
+            .map(|c| c.is_whitespace())
+            .unwrap_or(false);
+        let rest = &tag[i + name.len()..];
+        let quote = match rest.strip_prefix('=').and_then(|r| r.chars().next()) {
+            Some(q @ ('"' | '\'')) => q,
+            _ => continue,
+        };
+        if !preceded_by_ws {
+            continue;
+        }
+        let value = &rest[2..];
+        let j = value.find(quote)?;
+        return Some(decode_xml_entities(&value[..j]));
+    }
+    None
+}
+
+pub(crate) fn find_from(hay: &str, needle: &str, from: usize) -> Option<usize> {
+    hay.get(from..)?.find(needle).map(|i| from + i)
+}
+
+pub(crate) fn decode_xml_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp..];
+        let Some(semi) = after.find(';') else {
+            out.push_str(after);
+            return out;
+        };
+        let entity = &after[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                if let Some(hex) = entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                {
+                    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                } else if let Some(dec) = entity.strip_prefix('#') {
+                    dec.parse::<u32>().ok().and_then(char::from_u32)
+                } else {
+                    None
+                }
+            }
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &after[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &after[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Escape text for use in XML content and double-quoted attributes. Control
+/// characters XML 1.0 cannot carry are dropped.
+pub(crate) fn encode_xml_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -27,15 +27,13 @@ impl SqliteDb {
         let caps = shell::capabilities();
         caps.require_sqlite3()?;
 
+        // Scripts are piped via stdin: bulk writes (e.g. questionnaire rows)
+        // easily exceed the per-argument size limit of the OS.
         let cmd = format!("PRAGMA foreign_keys=ON; {}", sql);
-        let out = shell::run_capture(
+        let out = shell::run_capture_stdin(
             "sqlite3",
-            &[
-                "-batch",
-                "-bail",
-                self.path.to_string_lossy().as_ref(),
-                cmd.as_str(),
-            ],
+            &["-batch", "-bail", self.path.to_string_lossy().as_ref()],
+            cmd.as_bytes(),
         )?;
 
         if !out.status.success() {
@@ -157,3 +155,42 @@ impl SqliteDb {
         Ok(())
     }
 }
+
+/// Escape user text for storage.
+///
+/// `query_rows_tsv` is not safe for embedded tabs/newlines, so free text is
+/// stored with lightweight, reversible escaping and unescaped on read.
+pub(crate) fn escape_db_text(s: &str) -> String {
+    // Keep it reversible and deterministic. Order matters.
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+pub(crate) fn unescape_db_text(s: &str) -> String {
+    // Reverse of escape_db_text.
+    let mut out = String::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.peek().copied() {
+            Some('n') => {
+                let _ = chars.next();
+                out.push('\n');
+            }
+            Some('t') => {
+                let _ = chars.next();
+                out.push('\t');
+            }
+            Some('\\') => {
+                let _ = chars.next();
+                out.push('\\');
+            }
+            _ => out.push('\\'),
+        }
+    }
+    out
+}
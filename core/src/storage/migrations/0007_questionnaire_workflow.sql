@@ -0,0 +1,46 @@
+-- 0007_questionnaire_workflow.sql
+
+PRAGMA foreign_keys = ON;
+
+-- Raw data rows captured at import time so questions can be materialized once
+-- a column map is set (the source file itself is not kept in the vault).
+CREATE TABLE IF NOT EXISTS questionnaire_import_row (
+  import_id TEXT NOT NULL,
+  row_number INTEGER NOT NULL, -- 1-based source row (header is row 1)
+  cells_json TEXT NOT NULL,    -- canonical JSON object: col_ref -> cell text (non-empty cells only)
+  PRIMARY KEY(import_id, row_number),
+  FOREIGN KEY(import_id) REFERENCES questionnaire_import(import_id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS questionnaire_question (
+  question_id TEXT PRIMARY KEY,
+  vault_id TEXT NOT NULL,
+  import_id TEXT NOT NULL,
+  row_number INTEGER NOT NULL,
+  question_text TEXT NOT NULL,
+  source_answer TEXT NULL,  -- value found in the mapped answer column, if any
+  source_notes TEXT NULL,   -- value found in the mapped notes column, if any
+  status TEXT NOT NULL DEFAULT 'unanswered', -- 'unanswered' | 'suggested' | 'drafted' | 'approved' | 'not_applicable'
+  assignee TEXT NULL,
+  answer_bank_entry_id TEXT NULL,
+  answer_override TEXT NULL,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  UNIQUE(import_id, row_number),
+  FOREIGN KEY(vault_id) REFERENCES vault(vault_id),
+  FOREIGN KEY(import_id) REFERENCES questionnaire_import(import_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_qna_question_import ON questionnaire_question(import_id, row_number);
+CREATE INDEX IF NOT EXISTS idx_qna_question_status ON questionnaire_question(import_id, status);
+
+CREATE TABLE IF NOT EXISTS questionnaire_question_comment (
+  comment_id TEXT PRIMARY KEY,
+  question_id TEXT NOT NULL,
+  author TEXT NOT NULL,
+  body TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  FOREIGN KEY(question_id) REFERENCES questionnaire_question(question_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_qna_comment_question ON questionnaire_question_comment(question_id);
@@ -0,0 +1,9 @@
+-- 0008_match_suggestion_review.sql
+-- Suggestions are now persisted per imported question (question_id refers to
+-- questionnaire_question). Rejections are recorded so regenerating
+-- suggestions does not bring back entries a reviewer already turned down.
+
+ALTER TABLE match_suggestion ADD COLUMN rejected_at TIMESTAMP NULL;
+
+CREATE INDEX IF NOT EXISTS idx_match_suggestion_entry
+ON match_suggestion(answer_bank_entry_id);
@@ -0,0 +1,7 @@
+-- 0009_questionnaire_revision.sql
+-- An import can be a newer revision of an earlier import of the same
+-- questionnaire (e.g. a customer's v2 with reworded and added questions).
+
+ALTER TABLE questionnaire_import ADD COLUMN parent_import_id TEXT NULL REFERENCES questionnaire_import(import_id);
+
+CREATE INDEX IF NOT EXISTS idx_qna_import_parent ON questionnaire_import(parent_import_id);
@@ -0,0 +1,53 @@
+-- 0010_match_synonym.sql
+-- Synonym/acronym dictionary used to normalize text before matching. The
+-- vault database holds a single vault, so rows apply vault-wide. Terms are
+-- stored tokenized (lowercase, punctuation removed, single spaces) and are
+-- rewritten to `canonical` in both questions and answer bank entries.
+
+CREATE TABLE IF NOT EXISTS match_synonym (
+  term TEXT PRIMARY KEY,
+  canonical TEXT NOT NULL,
+  source TEXT NOT NULL DEFAULT 'seed', -- 'seed' | 'user'
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL
+);
+
+INSERT OR IGNORE INTO match_synonym (term, canonical, source, created_at, updated_at) VALUES
+  ('multi factor authentication', 'mfa', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('multifactor authentication', 'mfa', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('multi factor', 'mfa', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('two factor authentication', 'mfa', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('two factor', 'mfa', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('2fa', 'mfa', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('single sign on', 'sso', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('one time password', 'otp', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('role based access control', 'rbac', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('penetration test', 'pentest', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('penetration testing', 'pentest', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('pen test', 'pentest', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('pen testing', 'pentest', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('ssl', 'tls', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('transport layer security', 'tls', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('virtual private network', 'vpn', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('web application firewall', 'waf', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('intrusion detection system', 'ids', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('endpoint detection and response', 'edr', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('security information and event management', 'siem', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('data loss prevention', 'dlp', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('key management service', 'kms', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('personally identifiable information', 'pii', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('personal data', 'pii', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('protected health information', 'phi', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('business continuity plan', 'bcp', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('business continuity planning', 'bcp', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('disaster recovery', 'dr', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('software development lifecycle', 'sdlc', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('software development life cycle', 'sdlc', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('data processing agreement', 'dpa', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('data processing addendum', 'dpa', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('business associate agreement', 'baa', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('general data protection regulation', 'gdpr', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('soc 2', 'soc2', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('soc ii', 'soc2', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('iso 27001', 'iso27001', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z'),
+  ('iso iec 27001', 'iso27001', 'seed', '2000-01-01T00:00:00Z', '2000-01-01T00:00:00Z');
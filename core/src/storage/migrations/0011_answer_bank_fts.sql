@@ -0,0 +1,23 @@
+-- 0011_answer_bank_fts.sql
+-- Full-text index over answer bank text for search and match candidate
+-- retrieval. Rows share the answer_bank rowid and are written by the answer
+-- bank functions in the same transaction as the entry itself. Existing text
+-- is stored escaped (see escape_db_text); the backfill maps escaped tabs and
+-- newlines to spaces, later writes index the unescaped text.
+
+CREATE VIRTUAL TABLE IF NOT EXISTS answer_bank_fts USING fts5(
+  entry_id UNINDEXED,
+  question_canonical,
+  answer_short,
+  answer_long,
+  tokenize = 'porter unicode61 remove_diacritics 2'
+);
+
+INSERT INTO answer_bank_fts (rowid, entry_id, question_canonical, answer_short, answer_long)
+SELECT
+  rowid,
+  entry_id,
+  REPLACE(REPLACE(question_canonical, '\n', ' '), '\t', ' '),
+  REPLACE(REPLACE(answer_short, '\n', ' '), '\t', ' '),
+  REPLACE(REPLACE(answer_long, '\n', ' '), '\t', ' ')
+FROM answer_bank;
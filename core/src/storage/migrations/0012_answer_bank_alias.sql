@@ -0,0 +1,26 @@
+-- 0012_answer_bank_alias.sql
+-- Question wordings remembered for an answer bank entry. Accepting an entry
+-- for a questionnaire question records the question text here so the same
+-- wording matches the entry with full confidence next time; aliases can also
+-- be added and removed by hand. Foreign keys are not enforced on every
+-- connection, so answer bank deletes remove aliases explicitly.
+
+CREATE TABLE IF NOT EXISTS answer_bank_alias (
+  alias_id TEXT PRIMARY KEY,
+  entry_id TEXT NOT NULL,
+  alias_text TEXT NOT NULL,         -- wording as accepted (escaped like other user text)
+  normalized_text TEXT NOT NULL,    -- lowercase words joined by single spaces
+  source_question_id TEXT NULL,     -- questionnaire_question it was learned from; NULL if added by hand
+  created_by TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  UNIQUE(entry_id, normalized_text),
+  FOREIGN KEY(entry_id) REFERENCES answer_bank(entry_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_answer_bank_alias_entry ON answer_bank_alias(entry_id);
+
+-- Rows share the answer_bank_alias rowid, like answer_bank_fts.
+CREATE VIRTUAL TABLE IF NOT EXISTS answer_bank_alias_fts USING fts5(
+  alias_text,
+  tokenize = 'porter unicode61 remove_diacritics 2'
+);
@@ -0,0 +1,7 @@
+-- 0013_match_suggestion_scorer.sql
+-- Records which scorer (and which version of it) produced each suggestion so
+-- stored scores can be reproduced. Rows written before this migration leave
+-- both columns NULL.
+
+ALTER TABLE match_suggestion ADD COLUMN scorer_name TEXT NULL;
+ALTER TABLE match_suggestion ADD COLUMN scorer_version TEXT NULL;
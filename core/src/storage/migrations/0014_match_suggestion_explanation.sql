@@ -0,0 +1,6 @@
+-- 0014_match_suggestion_explanation.sql
+-- Structured explanation of each suggestion (matched terms with their spans in
+-- the question and entry fields, per-field score breakdown) as canonical
+-- JSON. Rows written before this migration only have confidence_explanation.
+
+ALTER TABLE match_suggestion ADD COLUMN explanation_json TEXT NULL;
@@ -0,0 +1,31 @@
+-- 0015_answer_bank_revision.sql
+-- Full content of every answer bank entry version. Each create, update,
+-- rollback and review appends the entry as it stands after the write,
+-- numbered from 1 per entry, so earlier answers can be read back, compared
+-- and restored.
+-- Revisions outlive their entry: deleting an entry keeps its history.
+-- Existing entries start with their current content as revision 1.
+
+CREATE TABLE IF NOT EXISTS answer_bank_revision (
+  entry_id TEXT NOT NULL,
+  revision INTEGER NOT NULL,
+  kind TEXT NOT NULL,               -- 'created' | 'updated' | 'rolled_back' | 'reviewed'
+  rolled_back_to INTEGER NULL,      -- revision restored by a rollback
+  question_canonical TEXT NOT NULL, -- user text escaped like answer_bank
+  answer_short TEXT NOT NULL,
+  answer_long TEXT NOT NULL,
+  notes TEXT NULL,
+  evidence_links_json TEXT NOT NULL,
+  owner TEXT NOT NULL,
+  last_reviewed_at TEXT NULL,
+  tags_json TEXT NOT NULL,
+  source TEXT NOT NULL,
+  content_hash TEXT NOT NULL,
+  created_by TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  PRIMARY KEY(entry_id, revision)
+);
+
+INSERT OR IGNORE INTO answer_bank_revision (entry_id, revision, kind, rolled_back_to, question_canonical, answer_short, answer_long, notes, evidence_links_json, owner, last_reviewed_at, tags_json, source, content_hash, created_by, created_at)
+SELECT entry_id, 1, 'created', NULL, question_canonical, answer_short, answer_long, notes, evidence_links_json, owner, last_reviewed_at, tags_json, source, content_hash, 'migration', updated_at
+FROM answer_bank;
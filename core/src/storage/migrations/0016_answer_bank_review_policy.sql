@@ -0,0 +1,14 @@
+-- 0016_answer_bank_review_policy.sql
+-- How often answer bank entries must be re-reviewed. An entry's interval is
+-- its own policy if it has one, else the shortest interval of its tags, else
+-- the vault default; entries with none of these never go stale. Marking an
+-- entry reviewed adds an answer_bank_revision of kind 'reviewed'.
+
+CREATE TABLE IF NOT EXISTS answer_bank_review_policy (
+  scope TEXT NOT NULL,              -- 'default' | 'tag' | 'entry'
+  target TEXT NOT NULL,             -- tag or entry_id; '' for the default
+  interval_days INTEGER NOT NULL,
+  updated_by TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  PRIMARY KEY(scope, target)
+);
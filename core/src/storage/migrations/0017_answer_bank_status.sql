@@ -0,0 +1,10 @@
+-- 0017_answer_bank_status.sql
+-- Answer bank approval workflow. New entries start as 'draft'; only
+-- 'approved' entries are used for auto-answer and 'retired' ones are never
+-- matched. Approval must come from someone other than last_edited_by.
+-- Entries that existed before the workflow stay in use as 'approved'.
+
+ALTER TABLE answer_bank ADD COLUMN status TEXT NOT NULL DEFAULT 'approved'; -- 'draft' | 'in_review' | 'approved' | 'retired'
+ALTER TABLE answer_bank ADD COLUMN last_edited_by TEXT NULL; -- actor of the last create/update/rollback
+
+CREATE INDEX IF NOT EXISTS idx_answer_bank_status ON answer_bank(status);
@@ -0,0 +1,12 @@
+-- 0018_vault_variable.sql
+-- Vault-level values for `{{name}}` placeholders in answer bank answers
+-- (company name, regions, RTO/RPO, ...). Answers are stored with their
+-- placeholders; suggestions and rendered exports substitute the current
+-- values, so changing a variable updates every answer that uses it.
+
+CREATE TABLE IF NOT EXISTS vault_variable (
+  name TEXT PRIMARY KEY,            -- lowercase letters, digits and '_'
+  value TEXT NOT NULL,              -- escaped like other user text
+  updated_by TEXT NOT NULL,
+  updated_at TEXT NOT NULL
+);
@@ -0,0 +1,11 @@
+-- 0019_locales.sql
+-- Multilingual answer bank entries and questionnaire languages. An entry's
+-- own question and answers are in the default locale ('en'); translations
+-- hold per-locale variants as a canonical JSON array of
+-- {locale, question_canonical, answer_short, answer_long}, sorted by locale.
+-- Revisions snapshot them with the rest of the entry. Each import records the
+-- language its questions are matched in.
+
+ALTER TABLE answer_bank ADD COLUMN translations_json TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE answer_bank_revision ADD COLUMN translations_json TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE questionnaire_import ADD COLUMN language TEXT NOT NULL DEFAULT 'en';
@@ -0,0 +1,41 @@
+-- 0020_tag_taxonomy.sql
+-- Managed answer bank tags and framework control mappings.
+-- Tags are '/'-separated paths ('security/encryption' is a child of
+-- 'security'); defining a tag defines its ancestors. Entries keep their tags
+-- in answer_bank.tags_json, so tags in use but never defined still work.
+-- Control mappings link an entry to control identifiers of a framework
+-- (SOC 2 CC6.1, ISO 27001 A.8.5, ...), compared case-insensitively.
+
+CREATE TABLE IF NOT EXISTS answer_bank_tag (
+  tag TEXT PRIMARY KEY,
+  description TEXT NULL,            -- escaped like other user text
+  created_by TEXT NOT NULL,
+  created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS answer_bank_control (
+  entry_id TEXT NOT NULL,
+  framework TEXT NOT NULL COLLATE NOCASE,
+  control_id TEXT NOT NULL COLLATE NOCASE,
+  created_by TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  PRIMARY KEY(entry_id, framework, control_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_answer_bank_control_control ON answer_bank_control(framework, control_id);
+
+-- Existing tags become the starting taxonomy, normalized like every tag
+-- write (trimmed, lowercased). SQLite's lower() only folds ASCII, so tags
+-- with other characters or untrimmed levels are left out here; they are
+-- still listed as in use, normalized, and entries pick up the normalized
+-- form the next time their tags are written.
+INSERT OR IGNORE INTO answer_bank_tag (tag, description, created_by, created_at)
+SELECT DISTINCT lower(trim(json_each.value)), NULL, 'migration', '2000-01-01T00:00:00Z'
+FROM answer_bank, json_each(answer_bank.tags_json)
+WHERE trim(json_each.value) <> ''
+  AND json_each.value NOT GLOB '*[^ -~]*'
+  AND json_each.value NOT GLOB '* /*'
+  AND json_each.value NOT GLOB '*/ *'
+  AND trim(json_each.value) NOT GLOB '/*'
+  AND trim(json_each.value) NOT GLOB '*/'
+  AND json_each.value NOT GLOB '*//*';
@@ -0,0 +1,11 @@
+-- 0021_export_signing_key.sql
+-- Public half of the vault's Ed25519 key for signing export pack manifests,
+-- created on the first export and replaced on rotation (one row). Recipients
+-- verify packs against it. The secret key is not stored here: it lives in
+-- the owner-only file export_signing.key next to the vault database.
+
+CREATE TABLE IF NOT EXISTS export_signing_key (
+  public_key_hex TEXT PRIMARY KEY,
+  created_by TEXT NOT NULL,
+  created_at TEXT NOT NULL
+);
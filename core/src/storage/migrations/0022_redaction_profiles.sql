@@ -0,0 +1,13 @@
+-- 0022_redaction_profiles.sql
+-- Named redaction profiles applied when generating export packs. The profile
+-- is stored as its canonical JSON (rules, excluded evidence tags and the
+-- notes/owner switches) so its hash matches what manifests record.
+
+CREATE TABLE IF NOT EXISTS redaction_profile (
+  name TEXT PRIMARY KEY,
+  profile_json TEXT NOT NULL,
+  created_by TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  updated_by TEXT NOT NULL,
+  updated_at TEXT NOT NULL
+);
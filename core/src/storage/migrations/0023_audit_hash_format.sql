@@ -0,0 +1,7 @@
+-- 0023_audit_hash_format.sql
+-- How an audit event's hash was computed. Format 1 hashes the payload JSON
+-- itself; format 2 hashes the payload's SHA-256, so an exported event whose
+-- payload is redacted can still be checked against its stored hash. Events
+-- written before this migration keep format 1.
+
+ALTER TABLE audit_event ADD COLUMN hash_format INTEGER NOT NULL DEFAULT 1;
@@ -186,6 +186,54 @@ pub fn evidence_add(
     })
 }
 
+/// Replace an evidence item's tags. Tags are `/`-separated paths like answer
+/// bank tags; returns them normalized, sorted and deduplicated.
+pub fn evidence_set_tags(
+    db: &SqliteDb,
+    evidence_id: &str,
+    tags: &[String],
+    actor: &str,
+) -> CoreResult<Vec<String>> {
+    validator::validate_chain(db)?;
+    let vault_id = db
+        .query_optional_string(&format!(
+            "SELECT vault_id FROM evidence_item WHERE evidence_id={} AND deleted_at IS NULL;",
+            db.q(evidence_id)
+        ))?
+        .ok_or_else(|| CoreError::new(CoreErrorCode::NotFound, "evidence not found"))?;
+
+    let mut normalized = tags
+        .iter()
+        .map(|t| crate::answer_bank::normalize_tag(t))
+        .collect::<CoreResult<Vec<_>>>()?;
+    normalized.sort();
+    normalized.dedup();
+    let tags_json = CanonicalJson::Array(
+        normalized
+            .iter()
+            .cloned()
+            .map(CanonicalJson::String)
+            .collect(),
+    );
+
+    let event_sql = build_event_insert_sql(db, &vault_id, actor, "EvidenceTagsUpdated", {
+        let mut o = CanonicalJson::object();
+        o.insert(
+            "evidence_id",
+            CanonicalJson::String(evidence_id.to_string()),
+        );
+        o.insert("tags", tags_json.clone());
+        o
+    })?;
+    let update_sql = format!(
+        "UPDATE evidence_item SET tags_json={} WHERE evidence_id={};",
+        db.q(&tags_json.encode()),
+        db.q(evidence_id)
+    );
+    db.exec_batch(&format!("BEGIN;\n{}\n{}\nCOMMIT;", update_sql, event_sql))?;
+    Ok(normalized)
+}
+
 pub fn license_install_from_path(
     db: &SqliteDb,
     vault_root: &Path,
@@ -390,38 +438,103 @@ pub(crate) fn build_event_insert_sql(
     event_type: &str,
     payload: CanonicalJson,
 ) -> CoreResult<String> {
-    use crate::audit::hasher;
+    let prev_hash = db
+        .query_optional_string("SELECT hash FROM audit_event ORDER BY seq DESC LIMIT 1;")?
+        .unwrap_or_else(|| GENESIS_PREV_HASH.to_string());
+
+    let (sql, _hash) =
+        build_chained_event_insert_sql(db, vault_id, actor, event_type, payload, &prev_hash)?;
+    Ok(sql)
+}
+
+/// Several audit events appended in one script.
+///
+/// `build_event_insert_sql` reads the chain head from the database, so events
+/// built before the previous one is committed would share a `prev_hash`. The
+/// batch tracks the head itself so each event chains onto the one before it.
+pub(crate) struct AuditEventBatch {
+    vault_id: String,
+    prev_hash: String,
+    sql: String,
+}
+
+impl AuditEventBatch {
+    pub(crate) fn new(db: &SqliteDb, vault_id: &str) -> CoreResult<Self> {
+        let prev_hash = db
+            .query_optional_string("SELECT hash FROM audit_event ORDER BY seq DESC LIMIT 1;")?
+            .unwrap_or_else(|| GENESIS_PREV_HASH.to_string());
+        Ok(Self {
+            vault_id: vault_id.to_string(),
+            prev_hash,
+            sql: String::new(),
+        })
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        db: &SqliteDb,
+        actor: &str,
+        event_type: &str,
+        payload: CanonicalJson,
+    ) -> CoreResult<()> {
+        let (sql, hash) = build_chained_event_insert_sql(
+            db,
+            &self.vault_id,
+            actor,
+            event_type,
+            payload,
+            &self.prev_hash,
+        )?;
+        self.sql.push_str(&sql);
+        self.sql.push('\n');
+        self.prev_hash = hash;
+        Ok(())
+    }
+
+    pub(crate) fn sql(&self) -> &str {
+        &self.sql
+    }
+}
+
+const GENESIS_PREV_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+fn build_chained_event_insert_sql(
+    db: &SqliteDb,
+    vault_id: &str,
+    actor: &str,
+    event_type: &str,
+    payload: CanonicalJson,
+    prev_hash: &str,
+) -> CoreResult<(String, String)> {
+    use crate::audit::{hasher, validator};
 
     let event_id = Ulid::new()?.to_string();
     let occurred_at = DETERMINISTIC_TIMESTAMP_UTC.to_string();
     let payload_json = payload.to_string();
 
-    let prev_hash = db
-        .query_optional_string("SELECT hash FROM audit_event ORDER BY seq DESC LIMIT 1;")?
-        .unwrap_or_else(|| {
-            "0000000000000000000000000000000000000000000000000000000000000000".to_string()
-        });
-
-    let canonical = crate::audit::validator::canonical_event_string(
+    let hash_format = validator::HASH_FORMAT_PAYLOAD_SHA256;
+    let canonical = validator::canonical_event_string_v2(
         &event_id,
         vault_id,
         &occurred_at,
         actor,
         event_type,
-        &payload_json,
-        &prev_hash,
+        &hasher::sha256_hex_bytes(payload_json.as_bytes())?,
+        prev_hash,
     );
     let hash = hasher::sha256_hex_bytes(canonical.as_bytes())?;
 
-    Ok(format!(
-        "INSERT INTO audit_event (event_id, vault_id, occurred_at, actor, event_type, payload_json, prev_hash, hash) VALUES ({}, {}, {}, {}, {}, {}, {}, {});",
+    let sql = format!(
+        "INSERT INTO audit_event (event_id, vault_id, occurred_at, actor, event_type, payload_json, prev_hash, hash, hash_format) VALUES ({}, {}, {}, {}, {}, {}, {}, {}, {});",
         db.q(&event_id),
         db.q(vault_id),
         db.q(&occurred_at),
         db.q(actor),
         db.q(event_type),
         db.q(&payload_json),
-        db.q(&prev_hash),
+        db.q(prev_hash),
         db.q(&hash),
-    ))
+        hash_format,
+    );
+    Ok((sql, hash))
 }
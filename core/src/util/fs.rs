@@ -55,6 +55,32 @@ pub fn write_string(path: &Path, contents: &str) -> CoreResult<()> {
     Ok(())
 }
 
+/// Like [`write_string`], but the file is only readable by its owner on
+/// Unix (mode 0600) from the moment it is created.
+pub fn write_private_string(path: &Path, contents: &str) -> CoreResult<()> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| CoreError::new(CoreErrorCode::IoError, "path has no parent"))?;
+    ensure_dir(parent)?;
+
+    let tmp_path = tmp_sibling(path);
+    let _ = fs::remove_file(&tmp_path);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut f = options.open(&tmp_path)?;
+    f.write_all(contents.as_bytes())?;
+    f.flush()?;
+    f.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
 fn tmp_sibling(path: &Path) -> PathBuf {
     let file_name = path
         .file_name()
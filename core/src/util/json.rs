@@ -84,6 +84,16 @@ impl<'a> JsonObject<'a> {
         }
     }
 
+    pub fn get_bool(&self, key: &str) -> CoreResult<bool> {
+        match self.get(key) {
+            Some(JsonValue::Bool(b)) => Ok(*b),
+            _ => Err(CoreError::new(
+                CoreErrorCode::CorruptVault,
+                format!("expected bool field {}", key),
+            )),
+        }
+    }
+
     pub fn get_array(&self, key: &str) -> CoreResult<&'a [JsonValue]> {
         match self.get(key) {
             Some(JsonValue::Array(v)) => Ok(v),
@@ -244,29 +254,35 @@ impl<'a> Parser<'a> {
             ));
         }
 
-        let mut out = String::new();
+        // Collect raw bytes so multi-byte UTF-8 sequences survive intact.
+        let mut out: Vec<u8> = Vec::new();
         while let Some(b) = self.next() {
             match b {
-                b'\"' => return Ok(out),
+                b'\"' => {
+                    return String::from_utf8(out).map_err(|_| {
+                        CoreError::new(CoreErrorCode::CorruptVault, "invalid utf-8 in string")
+                    })
+                }
                 b'\\' => {
                     let esc = self
                         .next()
                         .ok_or_else(|| CoreError::new(CoreErrorCode::CorruptVault, "bad escape"))?;
                     match esc {
-                        b'\"' => out.push('"'),
-                        b'\\' => out.push('\\'),
-                        b'/' => out.push('/'),
-                        b'b' => out.push('\u{0008}'),
-                        b'f' => out.push('\u{000C}'),
-                        b'n' => out.push('\n'),
-                        b'r' => out.push('\r'),
-                        b't' => out.push('\t'),
+                        b'\"' => out.push(b'"'),
+                        b'\\' => out.push(b'\\'),
+                        b'/' => out.push(b'/'),
+                        b'b' => out.push(0x08),
+                        b'f' => out.push(0x0C),
+                        b'n' => out.push(b'\n'),
+                        b'r' => out.push(b'\r'),
+                        b't' => out.push(b'\t'),
                         b'u' => {
                             let code = self.parse_hex4()?;
                             let ch = char::from_u32(code).ok_or_else(|| {
                                 CoreError::new(CoreErrorCode::CorruptVault, "bad unicode")
                             })?;
-                            out.push(ch);
+                            let mut buf = [0u8; 4];
+                            out.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
                         }
                         _ => {
                             return Err(CoreError::new(
@@ -276,7 +292,7 @@ impl<'a> Parser<'a> {
                         }
                     }
                 }
-                _ => out.push(b as char),
+                _ => out.push(b),
             }
         }
 
@@ -1 +1,353 @@
-// Redaction profiles are a Phase 5 feature.
+//! Redaction profiles.
+//!
+//! A profile names what to hide when generating an export pack with it:
+//! keyword and regex rules whose matches in answer text are replaced,
+//! evidence tags whose evidence is left out, and whether notes and owner
+//! fields are removed. Profiles are stored in the vault and applied by
+//! `export::pack::generate_pack` (see `export::redaction`).
+//!
+//! Keyword rules match whole words, case-insensitively: a match must not be
+//! directly preceded or followed by a letter or digit. `AWS` hides "AWS",
+//! "aws's" and "aws_key" but not the "aws" in "laws". A keyword that starts
+//! or ends with another character (`C++`) is not bounded on that side.
+//!
+//! Regex rules use the `regex` crate's syntax, which matches in linear time;
+//! back-references and lookaround are not supported and are rejected when the
+//! profile is saved. `(?i)` makes a rule case-insensitive. Empty matches are
+//! never replaced.
+
+use crate::audit::canonical::CanonicalJson;
+use crate::domain::errors::{CoreError, CoreErrorCode, CoreResult};
+use crate::util::json::JsonValue;
+use regex::{Regex, RegexBuilder};
+
+/// Replacement used when a rule does not name one.
+pub const DEFAULT_REPLACEMENT: &str = "[REDACTED]";
+
+const MAX_PATTERN_LEN: usize = 500;
+/// Compiled size allowed per rule, so `a{1000}{1000}` cannot blow up.
+const MAX_COMPILED_SIZE: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionRuleKind {
+    Keyword,
+    Regex,
+}
+
+impl RedactionRuleKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RedactionRuleKind::Keyword => "keyword",
+            RedactionRuleKind::Regex => "regex",
+        }
+    }
+
+    pub fn parse(s: &str) -> CoreResult<Self> {
+        match s {
+            "keyword" => Ok(RedactionRuleKind::Keyword),
+            "regex" => Ok(RedactionRuleKind::Regex),
+            _ => Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                format!("unknown redaction rule kind: {s}"),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionRule {
+    pub kind: RedactionRuleKind,
+    pub pattern: String,
+    /// Text that replaces each match.
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionProfile {
+    pub name: String,
+    /// Applied in order to answer text.
+    pub rules: Vec<RedactionRule>,
+    /// Evidence with one of these tags, or a descendant, is left out.
+    pub excluded_evidence_tags: Vec<String>,
+    pub remove_notes: bool,
+    /// Removes answer bank entry owners and question assignees.
+    pub remove_owners: bool,
+}
+
+impl RedactionProfile {
+    /// Trimmed name, normalized tags; every rule must compile.
+    pub fn normalized(&self) -> CoreResult<RedactionProfile> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() || name.chars().any(char::is_control) {
+            return Err(CoreError::new(
+                CoreErrorCode::ValidationError,
+                "redaction profile name is required",
+            ));
+        }
+        let mut tags = self
+            .excluded_evidence_tags
+            .iter()
+            .map(|t| crate::answer_bank::normalize_tag(t))
+            .collect::<CoreResult<Vec<_>>>()?;
+        tags.sort();
+        tags.dedup();
+        let profile = RedactionProfile {
+            name,
+            rules: self.rules.clone(),
+            excluded_evidence_tags: tags,
+            remove_notes: self.remove_notes,
+            remove_owners: self.remove_owners,
+        };
+        Redactor::new(&profile)?;
+        Ok(profile)
+    }
+
+    pub fn to_canonical_json(&self) -> CanonicalJson {
+        let mut o = CanonicalJson::object();
+        o.insert("name", CanonicalJson::String(self.name.clone()));
+        o.insert(
+            "rules",
+            CanonicalJson::Array(
+                self.rules
+                    .iter()
+                    .map(|r| {
+                        let mut r_o = CanonicalJson::object();
+                        r_o.insert("kind", CanonicalJson::String(r.kind.as_str().to_string()));
+                        r_o.insert("pattern", CanonicalJson::String(r.pattern.clone()));
+                        r_o.insert("replacement", CanonicalJson::String(r.replacement.clone()));
+                        r_o
+                    })
+                    .collect(),
+            ),
+        );
+        o.insert(
+            "excluded_evidence_tags",
+            CanonicalJson::Array(
+                self.excluded_evidence_tags
+                    .iter()
+                    .cloned()
+                    .map(CanonicalJson::String)
+                    .collect(),
+            ),
+        );
+        o.insert("remove_notes", CanonicalJson::Bool(self.remove_notes));
+        o.insert("remove_owners", CanonicalJson::Bool(self.remove_owners));
+        o
+    }
+
+    pub fn from_json_str(s: &str) -> CoreResult<Self> {
+        let v = JsonValue::parse(s)?;
+        let o = v.as_object()?;
+        let mut rules = Vec::new();
+        for r in o.get_array("rules")? {
+            let r = r.as_object()?;
+            rules.push(RedactionRule {
+                kind: RedactionRuleKind::parse(&r.get_string("kind")?)?,
+                pattern: r.get_string("pattern")?,
+                replacement: r.get_string("replacement")?,
+            });
+        }
+        Ok(RedactionProfile {
+            name: o.get_string("name")?,
+            rules,
+            excluded_evidence_tags: o
+                .get_array("excluded_evidence_tags")?
+                .iter()
+                .map(|t| t.as_string())
+                .collect::<CoreResult<_>>()?,
+            remove_notes: o.get_bool("remove_notes")?,
+            remove_owners: o.get_bool("remove_owners")?,
+        })
+    }
+}
+
+/// How often one rule matched in a text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleHit {
+    /// Index of the rule in the profile.
+    pub rule: usize,
+    pub matches: usize,
+}
+
+/// A profile's text rules, compiled.
+pub struct Redactor {
+    rules: Vec<CompiledRule>,
+}
+
+struct CompiledRule {
+    regex: Regex,
+    replacement: String,
+    /// Whether a match must start, and end, at a word boundary.
+    bounded: (bool, bool),
+}
+
+impl CompiledRule {
+    fn accepts(&self, text: &str, start: usize, end: usize) -> bool {
+        let (bounded_start, bounded_end) = self.bounded;
+        (!bounded_start || !text[..start].chars().next_back().is_some_and(is_word))
+            && (!bounded_end || !text[end..].chars().next().is_some_and(is_word))
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric()
+}
+
+impl Redactor {
+    pub fn new(profile: &RedactionProfile) -> CoreResult<Self> {
+        let mut rules = Vec::new();
+        for (i, r) in profile.rules.iter().enumerate() {
+            if r.pattern.is_empty() || r.pattern.chars().count() > MAX_PATTERN_LEN {
+                return Err(invalid_rule(
+                    i,
+                    format!("pattern must be 1-{MAX_PATTERN_LEN} characters"),
+                ));
+            }
+            let pattern = match r.kind {
+                RedactionRuleKind::Keyword => regex::escape(&r.pattern),
+                RedactionRuleKind::Regex => r.pattern.clone(),
+            };
+            let keyword = r.kind == RedactionRuleKind::Keyword;
+            let regex = RegexBuilder::new(&pattern)
+                .case_insensitive(keyword)
+                .size_limit(MAX_COMPILED_SIZE)
+                .build()
+                .map_err(|e| invalid_rule(i, e.to_string()))?;
+            let bounded = if keyword {
+                (
+                    r.pattern.chars().next().is_some_and(is_word),
+                    r.pattern.chars().next_back().is_some_and(is_word),
+                )
+            } else {
+                (false, false)
+            };
+            rules.push(CompiledRule {
+                regex,
+                replacement: r.replacement.clone(),
+                bounded,
+            });
+        }
+        Ok(Redactor { rules })
+    }
+
+    /// Apply every rule in order. Returns the redacted text and the rules
+    /// that matched.
+    pub fn redact(&self, text: &str) -> CoreResult<(String, Vec<RuleHit>)> {
+        let mut text = text.to_string();
+        let mut hits = Vec::new();
+        for (i, rule) in self.rules.iter().enumerate() {
+            let mut out = String::new();
+            let mut pos = 0;
+            let mut matches = 0;
+            for m in rule
+                .regex
+                .find_iter(&text)
+                .filter(|m| !m.is_empty() && rule.accepts(&text, m.start(), m.end()))
+            {
+                out.push_str(&text[pos..m.start()]);
+                out.push_str(&rule.replacement);
+                matches += 1;
+                pos = m.end();
+            }
+            if matches > 0 {
+                out.push_str(&text[pos..]);
+                text = out;
+                hits.push(RuleHit { rule: i, matches });
+            }
+        }
+        Ok((text, hits))
+    }
+}
+
+fn invalid_rule(index: usize, message: String) -> CoreError {
+    CoreError::new(
+        CoreErrorCode::ValidationError,
+        format!("redaction rule {}: {}", index, message),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redact(kind: RedactionRuleKind, pattern: &str, text: &str) -> String {
+        let profile = RedactionProfile {
+            name: "test".to_string(),
+            rules: vec![RedactionRule {
+                kind,
+                pattern: pattern.to_string(),
+                replacement: DEFAULT_REPLACEMENT.to_string(),
+            }],
+            excluded_evidence_tags: vec![],
+            remove_notes: false,
+            remove_owners: false,
+        };
+        Redactor::new(&profile).unwrap().redact(text).unwrap().0
+    }
+
+    #[test]
+    fn test_keywords_match_whole_words_case_insensitively() {
+        let kw = RedactionRuleKind::Keyword;
+        assert_eq!(
+            redact(kw, "acme", "ACME and Acme's cloud, not acmeish"),
+            "[REDACTED] and [REDACTED]'s cloud, not acmeish"
+        );
+        assert_eq!(
+            redact(kw, "AWS", "AWS hosting under the laws of aws_key."),
+            "[REDACTED] hosting under the laws of [REDACTED]_key."
+        );
+        assert_eq!(
+            redact(kw, "C++", "C++ and c++11"),
+            "[REDACTED] and [REDACTED]11"
+        );
+        assert_eq!(redact(kw, "a.b", "axb a.b"), "axb [REDACTED]");
+    }
+
+    #[test]
+    fn test_regex_rules() {
+        let re = RedactionRuleKind::Regex;
+        assert_eq!(
+            redact(re, r"\b[\w.]+@\w+\.com\b", "Mail jane.doe@acme.com now"),
+            "Mail [REDACTED] now"
+        );
+        assert_eq!(
+            redact(re, r"\d{3}-\d{4}", "Call 555-1234 or 55-12"),
+            "Call [REDACTED] or 55-12"
+        );
+        assert_eq!(
+            redact(re, "(?i)(alpha|beta)+ team", "ALPHABETA team"),
+            "[REDACTED]"
+        );
+        assert_eq!(redact(re, "^a.*?c", "abcabc"), "[REDACTED]abc");
+        assert_eq!(redact(re, "x*", "abc"), "abc");
+        assert_eq!(redact(re, "[^a-c ]+", "ab dd c"), "ab [REDACTED] c");
+    }
+
+    #[test]
+    fn test_unsupported_regex_is_rejected() {
+        for pattern in [
+            "(?=a)",
+            r"(a)\1",
+            "(a",
+            "a)",
+            "[a",
+            "*a",
+            r"\p",
+            "a{1000}{1000}",
+        ] {
+            let profile = RedactionProfile {
+                name: "test".to_string(),
+                rules: vec![RedactionRule {
+                    kind: RedactionRuleKind::Regex,
+                    pattern: pattern.to_string(),
+                    replacement: String::new(),
+                }],
+                excluded_evidence_tags: vec![],
+                remove_notes: false,
+                remove_owners: false,
+            };
+            let err = Redactor::new(&profile).err().unwrap();
+            assert_eq!(err.code, CoreErrorCode::ValidationError, "{pattern}");
+        }
+    }
+}
@@ -85,7 +85,8 @@ pub fn unzip_to_dir(zip_path: &Path, out_dir: &Path) -> CoreResult<()> {
     Ok(())
 }
 
-fn list_files_sorted(staging_dir: &Path) -> CoreResult<Vec<String>> {
+/// Relative paths of every file under `dir`, byte-wise sorted.
+pub fn list_files_sorted(staging_dir: &Path) -> CoreResult<Vec<String>> {
     let caps = shell::capabilities();
     caps.require_bash()?;
 
@@ -1,5 +1,10 @@
-use core::answer_bank::{self, AnswerBankCreateInput, AnswerBankUpdatePatch, ListParams};
+use core::answer_bank::{
+    self, AnswerBankColumnMap, AnswerBankCreateInput, AnswerBankUpdatePatch, BulkRowAction,
+    EntryStatus, ListParams, ReviewPolicyScope, RevisionKind,
+};
 use core::domain::errors::{CoreErrorCode, CoreResult};
+use core::questionnaire::matching::{AnswerBankMatcher, RankingModel};
+use core::questionnaire::workflow::{self, QuestionAnswer};
 use core::storage::db::SqliteDb;
 use core::storage::{self, vault_db_path};
 use std::path::PathBuf;
@@ -52,6 +57,7 @@ fn answer_bank_create_update_delete_and_list_are_deterministic_and_audited() ->
                 "a".to_string(),
             ],
             source: "manual".to_string(),
+            translations: vec![],
         },
         "tester",
     )?;
@@ -95,6 +101,7 @@ fn answer_bank_create_update_delete_and_list_are_deterministic_and_audited() ->
             last_reviewed_at: None,
             tags: vec![],
             source: "manual".to_string(),
+            translations: vec![],
         },
         "tester",
     )?;
@@ -118,7 +125,7 @@ fn answer_bank_create_update_delete_and_list_are_deterministic_and_audited() ->
     let updated = answer_bank::ab_update_entry(&db, &created.entry_id, patch, "tester")?;
     assert_ne!(updated.content_hash, created.content_hash);
 
-    // Search should be deterministic (ordered by question_canonical, entry_id).
+    // Search goes through the full-text index kept in sync with updates.
     let searched = answer_bank::ab_search_entries(
         &db,
         "policy",
@@ -148,6 +155,98 @@ fn answer_bank_create_update_delete_and_list_are_deterministic_and_audited() ->
     Ok(())
 }
 
+fn input(question: &str, answer_long: &str) -> AnswerBankCreateInput {
+    AnswerBankCreateInput {
+        question_canonical: question.to_string(),
+        answer_short: "Yes".to_string(),
+        answer_long: answer_long.to_string(),
+        notes: None,
+        evidence_links: vec![],
+        owner: "alice".to_string(),
+        last_reviewed_at: None,
+        tags: vec![],
+        source: "manual".to_string(),
+        translations: vec![],
+    }
+}
+
+#[test]
+fn answer_bank_search_is_ranked_and_follows_writes() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_answer_bank_fts")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let in_answer = answer_bank::ab_create_entry(
+        &db,
+        input(
+            "Do you have a security policy?",
+            "It covers encryption of backups.",
+        ),
+        "tester",
+    )?;
+    let in_question = answer_bank::ab_create_entry(
+        &db,
+        input("Are backups encrypted?", "Yes, nightly."),
+        "tester",
+    )?;
+    let unrelated = answer_bank::ab_create_entry(
+        &db,
+        input("Do you have a firewall?", "Yes\tat the edge."),
+        "tester",
+    )?;
+    let params = || ListParams {
+        limit: 10,
+        offset: 0,
+    };
+    let ids = |v: Vec<answer_bank::AnswerBankEntry>| -> Vec<String> {
+        v.into_iter().map(|e| e.entry_id).collect()
+    };
+
+    // Stemmed, all words required, question matches ranked first.
+    let found = answer_bank::ab_search_entries(&db, "encrypted backup", params())?;
+    assert_eq!(
+        ids(found),
+        vec![in_question.entry_id.clone(), in_answer.entry_id.clone()]
+    );
+    assert!(answer_bank::ab_search_entries(&db, "encrypted firewall", params())?.is_empty());
+    // Word prefixes match; FTS syntax in queries is treated as text.
+    let found = answer_bank::ab_search_entries(&db, "fire\"wall OR", params())?;
+    assert!(found.is_empty());
+    let found = answer_bank::ab_search_entries(&db, "firew", params())?;
+    assert_eq!(ids(found), vec![unrelated.entry_id.clone()]);
+
+    // Candidates match any term; the rarer term in a question ranks first.
+    let found =
+        answer_bank::ab_match_candidates(&db, &["firewall".to_string(), "backups".to_string()], 2)?;
+    assert_eq!(
+        ids(found),
+        vec![unrelated.entry_id.clone(), in_question.entry_id.clone()]
+    );
+
+    answer_bank::ab_update_entry(
+        &db,
+        &unrelated.entry_id,
+        AnswerBankUpdatePatch {
+            question_canonical: Some("Do you run a WAF?".to_string()),
+            ..Default::default()
+        },
+        "tester",
+    )?;
+    assert!(answer_bank::ab_search_entries(&db, "firewall", params())?.is_empty());
+    let found = answer_bank::ab_search_entries(&db, "waf edge", params())?;
+    assert_eq!(ids(found), vec![unrelated.entry_id.clone()]);
+
+    answer_bank::ab_delete_entry(&db, &in_question.entry_id, "tester")?;
+    let found = answer_bank::ab_search_entries(&db, "backups", params())?;
+    assert_eq!(ids(found), vec![in_answer.entry_id.clone()]);
+    assert_eq!(answer_bank::ab_count_entries(&db)?, 2);
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
 #[test]
 fn answer_bank_validation_errors_are_stable() -> CoreResult<()> {
     let vault_root = make_temp_dir("cs_answer_bank_validation")?;
@@ -168,6 +267,7 @@ fn answer_bank_validation_errors_are_stable() -> CoreResult<()> {
             last_reviewed_at: None,
             tags: vec![],
             source: "manual".to_string(),
+            translations: vec![],
         },
         "tester",
     )
@@ -198,3 +298,836 @@ fn answer_bank_validation_errors_are_stable() -> CoreResult<()> {
     let _ = std::fs::remove_dir_all(&vault_root);
     Ok(())
 }
+
+#[test]
+fn answer_bank_revisions_keep_history_diff_and_roll_back() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_answer_bank_revision")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let created = answer_bank::ab_create_entry(
+        &db,
+        input("Do you encrypt data at rest?", "Yes, with AES-128."),
+        "alice",
+    )?;
+    let id = created.entry_id.clone();
+    answer_bank::ab_update_entry(
+        &db,
+        &id,
+        AnswerBankUpdatePatch {
+            answer_long: Some("Yes, with AES-256.".to_string()),
+            tags: Some(vec!["crypto".to_string()]),
+            ..Default::default()
+        },
+        "bob",
+    )?;
+
+    let revisions = answer_bank::ab_list_revisions(&db, &id)?;
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0].revision, 1);
+    assert_eq!(revisions[0].kind, RevisionKind::Created);
+    assert_eq!(revisions[0].answer_long, "Yes, with AES-128.");
+    assert_eq!(revisions[0].content_hash, created.content_hash);
+    assert_eq!(revisions[0].created_by, "alice");
+    assert_eq!(revisions[1].kind, RevisionKind::Updated);
+    assert_eq!(revisions[1].answer_long, "Yes, with AES-256.");
+    assert_eq!(revisions[1].created_by, "bob");
+
+    // Points in time are audit positions: each revision sits at its event.
+    let (created_seq, updated_seq) = (
+        revisions[0].audit_seq.unwrap(),
+        revisions[1].audit_seq.unwrap(),
+    );
+    assert!(created_seq < updated_seq);
+    let at = |seq| answer_bank::ab_revision_at_audit_seq(&db, &id, seq);
+    assert_eq!(at(created_seq)?.revision, 1);
+    assert_eq!(at(updated_seq - 1)?.revision, 1);
+    assert_eq!(at(updated_seq)?.revision, 2);
+    assert_eq!(at(updated_seq + 100)?.revision, 2);
+    assert_eq!(
+        at(created_seq - 1).unwrap_err().code,
+        CoreErrorCode::NotFound
+    );
+    assert_eq!(at(0).unwrap_err().code, CoreErrorCode::ValidationError);
+
+    let diff = answer_bank::ab_diff_revisions(&db, &id, 1, 2)?;
+    let fields: Vec<&str> = diff.changes.iter().map(|c| c.field.as_str()).collect();
+    assert_eq!(fields, vec!["answer_long", "content_hash", "tags"]);
+    assert_eq!(
+        diff.changes[0].before.as_deref(),
+        Some("Yes, with AES-128.")
+    );
+    assert_eq!(diff.changes[0].after.as_deref(), Some("Yes, with AES-256."));
+    assert_eq!(diff.changes[2].before, None);
+    assert_eq!(diff.changes[2].after.as_deref(), Some("crypto"));
+    assert!(answer_bank::ab_diff_revisions(&db, &id, 2, 2)?
+        .changes
+        .is_empty());
+
+    let rolled_back = answer_bank::ab_rollback_entry(&db, &id, 1, "carol")?;
+    assert_eq!(rolled_back.answer_long, "Yes, with AES-128.");
+    assert!(rolled_back.tags.is_empty());
+    assert_eq!(rolled_back.content_hash, created.content_hash);
+    let latest = answer_bank::ab_get_revision(&db, &id, 3)?;
+    assert_eq!(latest.kind, RevisionKind::RolledBack);
+    assert_eq!(latest.rolled_back_to, Some(1));
+    assert_eq!(latest.created_by, "carol");
+    assert_eq!(
+        db.query_optional_string(
+            "SELECT payload_json FROM audit_event WHERE event_type='AnswerBankEntryUpdated' ORDER BY seq DESC LIMIT 1;"
+        )?
+        .map(|p| p.contains("\"rolled_back_to\":1") && p.contains("\"revision\":3")),
+        Some(true)
+    );
+
+    // Rolling back to content the entry already has is refused.
+    let err = answer_bank::ab_rollback_entry(&db, &id, 3, "carol").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::Conflict);
+    let err = answer_bank::ab_get_revision(&db, &id, 9).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+
+    // History outlives the entry.
+    answer_bank::ab_delete_entry(&db, &id, "tester")?;
+    assert_eq!(answer_bank::ab_list_revisions(&db, &id)?.len(), 3);
+    let err = answer_bank::ab_rollback_entry(&db, &id, 2, "carol").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn answer_bank_review_policies_find_overdue_entries_and_down_rank_them() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_answer_bank_review")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let create = |question: &str, owner: &str, tags: &[&str]| {
+        let mut i = input(question, "We encrypt customer data at rest.");
+        i.owner = owner.to_string();
+        i.tags = tags.iter().map(|t| t.to_string()).collect();
+        answer_bank::ab_create_entry(&db, i, "tester")
+    };
+    let crypto = create(
+        "Do you encrypt customer data at rest?",
+        "alice",
+        &["crypto/at-rest"],
+    )?;
+    let backups = create("Do you encrypt customer backups at rest?", "bob", &[])?;
+    let hr = create("Do you run background checks?", "alice", &["hr"])?;
+
+    // No policy: nothing goes stale.
+    assert!(answer_bank::ab_overdue_reviews(&db, "2024-06-01")?.is_empty());
+
+    answer_bank::ab_set_review_policy(&db, ReviewPolicyScope::Default, "", 365, "tester")?;
+    answer_bank::ab_set_review_policy(&db, ReviewPolicyScope::Tag, "crypto", 90, "tester")?;
+    answer_bank::ab_set_review_policy(&db, ReviewPolicyScope::Entry, &hr.entry_id, 30, "tester")?;
+    let policies = answer_bank::ab_list_review_policies(&db)?;
+    let scopes: Vec<ReviewPolicyScope> = policies.iter().map(|p| p.scope).collect();
+    assert_eq!(
+        scopes,
+        vec![
+            ReviewPolicyScope::Default,
+            ReviewPolicyScope::Tag,
+            ReviewPolicyScope::Entry
+        ]
+    );
+
+    let reviewed = answer_bank::ab_mark_reviewed(&db, &crypto.entry_id, "2024-01-01", "carol")?;
+    assert_eq!(reviewed.last_reviewed_at.as_deref(), Some("2024-01-01"));
+    assert_eq!(reviewed.content_hash, crypto.content_hash);
+    let revisions = answer_bank::ab_list_revisions(&db, &crypto.entry_id)?;
+    assert_eq!(
+        revisions.last().map(|r| r.kind),
+        Some(RevisionKind::Reviewed)
+    );
+    answer_bank::ab_mark_reviewed(&db, &backups.entry_id, "2024-01-01", "carol")?;
+    answer_bank::ab_mark_reviewed(&db, &hr.entry_id, "2024-05-20", "carol")?;
+
+    // crypto: interval 90 days of its parent tag, due 2024-03-31. backups: default 365
+    // days. hr: own 30-day policy beats the default, due 2024-06-19.
+    let overdue = answer_bank::ab_overdue_reviews(&db, "2024-06-01")?;
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue[0].owner, "alice");
+    assert_eq!(overdue[0].entries.len(), 1);
+    let entry = &overdue[0].entries[0];
+    assert_eq!(entry.entry_id, crypto.entry_id);
+    assert_eq!(entry.interval_days, 90);
+    assert_eq!(entry.due_on.as_deref(), Some("2024-03-31"));
+    assert_eq!(entry.days_overdue, Some(62));
+
+    let overdue = answer_bank::ab_overdue_reviews(&db, "2025-01-02")?;
+    let owners: Vec<&str> = overdue.iter().map(|o| o.owner.as_str()).collect();
+    assert_eq!(owners, vec!["alice", "bob"]);
+    let alice: Vec<&str> = overdue[0]
+        .entries
+        .iter()
+        .map(|e| e.entry_id.as_str())
+        .collect();
+    // Most overdue first.
+    assert_eq!(alice, vec![crypto.entry_id.as_str(), hr.entry_id.as_str()]);
+
+    // Stale answers are flagged and scaled down when matching.
+    let question = "Do you encrypt customer data at rest?";
+    let fresh = AnswerBankMatcher::load(&db, RankingModel::Bm25)?
+        .with_review_date("2024-02-01")
+        .get_suggestions(&db, question, 3)?;
+    let stale = AnswerBankMatcher::load(&db, RankingModel::Bm25)?
+        .with_review_date("2024-06-01")
+        .get_suggestions(&db, question, 3)?;
+    let find = |s: &[core::questionnaire::matching::MatchSuggestion], id: &str| {
+        s.iter()
+            .find(|m| m.answer_bank_entry_id == id)
+            .cloned()
+            .expect("suggestion for entry")
+    };
+    let before = find(&fresh, &crypto.entry_id);
+    let after = find(&stale, &crypto.entry_id);
+    assert!(!before.stale);
+    assert!(after.stale);
+    assert!((after.score - before.score * 0.8).abs() < 1e-9);
+    assert!(after.confidence_explanation.ends_with("(review overdue)"));
+    assert!(!find(&stale, &backups.entry_id).stale);
+
+    answer_bank::ab_delete_review_policy(&db, ReviewPolicyScope::Tag, "crypto", "tester")?;
+    let err = answer_bank::ab_delete_review_policy(&db, ReviewPolicyScope::Tag, "crypto", "tester")
+        .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+
+    let err = answer_bank::ab_set_review_policy(&db, ReviewPolicyScope::Tag, "crypto", 0, "tester")
+        .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+    let err =
+        answer_bank::ab_set_review_policy(&db, ReviewPolicyScope::Entry, "nope", 30, "tester")
+            .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+    let err = answer_bank::ab_mark_reviewed(&db, &hr.entry_id, "last week", "carol").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+    let err = answer_bank::ab_mark_reviewed(&db, &hr.entry_id, "9999-01-01", "carol").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+
+    assert_event_types_contain(
+        &db,
+        &[
+            "AnswerBankReviewPolicySet",
+            "AnswerBankReviewPolicyDeleted",
+            "AnswerBankEntryReviewed",
+        ],
+    )?;
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn answer_bank_entries_need_approval_by_another_actor() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_answer_bank_status")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let created = answer_bank::ab_create_entry(
+        &db,
+        input("Do you run vulnerability scans?", "Weekly."),
+        "alice",
+    )?;
+    let id = created.entry_id.clone();
+    assert_eq!(created.status, EntryStatus::Draft);
+    assert_eq!(created.last_edited_by.as_deref(), Some("alice"));
+
+    let in_review = answer_bank::ab_set_entry_status(&db, &id, EntryStatus::InReview, "alice")?;
+    assert_eq!(in_review.status, EntryStatus::InReview);
+
+    // The last editor cannot approve their own text.
+    let err =
+        answer_bank::ab_set_entry_status(&db, &id, EntryStatus::Approved, "alice").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::PermissionDenied);
+    let approved = answer_bank::ab_set_entry_status(&db, &id, EntryStatus::Approved, "bob")?;
+    assert_eq!(approved.status, EntryStatus::Approved);
+
+    // Editing approved content sends it back to draft; bob is now the last
+    // editor, so alice may approve.
+    let edited = answer_bank::ab_update_entry(
+        &db,
+        &id,
+        AnswerBankUpdatePatch {
+            answer_long: Some("Daily.".to_string()),
+            ..Default::default()
+        },
+        "bob",
+    )?;
+    assert_eq!(edited.status, EntryStatus::Draft);
+    assert_eq!(edited.last_edited_by.as_deref(), Some("bob"));
+    let err = answer_bank::ab_set_entry_status(&db, &id, EntryStatus::Approved, "bob").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::PermissionDenied);
+    answer_bank::ab_set_entry_status(&db, &id, EntryStatus::Approved, "alice")?;
+
+    // Retired entries are not match candidates and must be redrafted first.
+    let terms = vec!["vulnerability".to_string()];
+    assert_eq!(answer_bank::ab_match_candidates(&db, &terms, 10)?.len(), 1);
+    answer_bank::ab_set_entry_status(&db, &id, EntryStatus::Retired, "alice")?;
+    assert!(answer_bank::ab_match_candidates(&db, &terms, 10)?.is_empty());
+    let err =
+        answer_bank::ab_set_entry_status(&db, &id, EntryStatus::Approved, "carol").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::Conflict);
+    let redrafted = answer_bank::ab_set_entry_status(&db, &id, EntryStatus::Draft, "carol")?;
+    assert_eq!(redrafted.status, EntryStatus::Draft);
+    assert_eq!(answer_bank::ab_match_candidates(&db, &terms, 10)?.len(), 1);
+
+    let transitions = db.query_rows_tsv(
+        "SELECT actor, payload_json FROM audit_event WHERE event_type='AnswerBankEntryStatusChanged' ORDER BY seq ASC;",
+    )?;
+    let summary: Vec<(String, bool)> = transitions
+        .iter()
+        .map(|r| {
+            (
+                r[0].clone(),
+                r[1].contains("\"from\":\"approved\",\"to\":\"draft\""),
+            )
+        })
+        .collect();
+    assert_eq!(summary.len(), 6);
+    assert_eq!(summary[2], ("bob".to_string(), true));
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn answer_bank_bulk_import_previews_upserts_and_round_trips_exports() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_answer_bank_bulk")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let existing =
+        answer_bank::ab_create_entry(&db, input("Do you encrypt data at rest?", "Yes"), "tester")?;
+    let kept =
+        answer_bank::ab_create_entry(&db, input("Do you run backups?", "Nightly."), "tester")?;
+
+    let csv_path = vault_root.join("library.csv");
+    std::fs::write(
+        &csv_path,
+        format!(
+            "ID,Question,Answer,Owner,Tags,Source\n\
+             ,Do you have an incident response plan?,\"Yes.\nTested yearly.\",bob,ir; policy,\n\
+             ,Do you encrypt data at rest?,Yes,,,manual\n\
+             {id},Do you encrypt data at rest?,AES-256 with managed keys.,carol,crypto,manual\n\
+             NOPE,Do you log access?,Yes.,,,\n\
+             ,Do you have an incident response plan?,\"Yes.\nTested yearly.\",bob,policy;ir,\n\
+             ,Do you pen test?,,,,\n\
+             {kept},Do you run backups?,Nightly.,alice,,manual\n",
+            id = existing.entry_id,
+            kept = kept.entry_id
+        ),
+    )?;
+
+    let cols = answer_bank::ab_profile_import_file(&csv_path)?;
+    assert_eq!(cols.len(), 6);
+    let map = AnswerBankColumnMap {
+        question: "Question".to_string(),
+        answer_long: "Answer".to_string(),
+        entry_id: Some("ID".to_string()),
+        owner: Some("Owner".to_string()),
+        tags: Some("Tags".to_string()),
+        source: Some("Source".to_string()),
+        ..Default::default()
+    };
+
+    let bad_map = AnswerBankColumnMap {
+        notes: Some("Answer".to_string()),
+        ..map.clone()
+    };
+    let err = answer_bank::ab_bulk_import(&db, &csv_path, &bad_map, true, "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+
+    let events_before = db.query_optional_string("SELECT COUNT(*) FROM audit_event;")?;
+    let preview = answer_bank::ab_bulk_import(&db, &csv_path, &map, true, "tester")?;
+    assert!(preview.dry_run);
+    let actions: Vec<(i64, BulkRowAction)> = preview
+        .rows
+        .iter()
+        .map(|r| (r.row_number, r.action))
+        .collect();
+    assert_eq!(
+        actions,
+        vec![
+            (2, BulkRowAction::Create),
+            (3, BulkRowAction::Duplicate),
+            (4, BulkRowAction::Update),
+            (5, BulkRowAction::Conflict),
+            (6, BulkRowAction::Duplicate),
+            (7, BulkRowAction::Conflict),
+            (8, BulkRowAction::Unchanged),
+        ]
+    );
+    assert_eq!(
+        (
+            preview.created,
+            preview.updated,
+            preview.unchanged,
+            preview.duplicates,
+            preview.conflicts
+        ),
+        (1, 1, 1, 2, 2)
+    );
+    assert_eq!(preview.rows[0].entry_id, None);
+    assert_eq!(
+        preview.rows[1].entry_id.as_deref(),
+        Some(existing.entry_id.as_str())
+    );
+    assert_eq!(
+        preview.rows[4].message.as_deref(),
+        Some("same content as row 2")
+    );
+    assert_eq!(preview.rows[3].message.as_deref(), Some("unknown entry_id"));
+    assert_eq!(
+        db.query_optional_string("SELECT COUNT(*) FROM audit_event;")?,
+        events_before
+    );
+    assert_eq!(answer_bank::ab_count_entries(&db)?, 2);
+
+    let applied = answer_bank::ab_bulk_import(&db, &csv_path, &map, false, "importer")?;
+    assert_eq!((applied.created, applied.updated), (1, 1));
+    assert_eq!(answer_bank::ab_count_entries(&db)?, 3);
+
+    let created_id = applied.rows[0].entry_id.clone().expect("created entry id");
+    let created = answer_bank::ab_get_entry(&db, &created_id)?;
+    assert_eq!(created.answer_long, "Yes.\nTested yearly.");
+    assert_eq!(created.answer_short, created.answer_long);
+    assert_eq!(created.owner, "bob");
+    assert_eq!(created.tags, vec!["ir".to_string(), "policy".to_string()]);
+    assert_eq!(created.source, "import");
+    assert_eq!(created.status, EntryStatus::Draft);
+    assert_eq!(
+        applied.rows[4].entry_id.as_deref(),
+        Some(created_id.as_str())
+    );
+
+    let updated = answer_bank::ab_get_entry(&db, &existing.entry_id)?;
+    assert_eq!(updated.answer_long, "AES-256 with managed keys.");
+    assert_eq!(updated.answer_short, "Yes");
+    assert_eq!(updated.owner, "carol");
+    assert_eq!(updated.last_edited_by.as_deref(), Some("importer"));
+    assert_eq!(
+        answer_bank::ab_list_revisions(&db, &existing.entry_id)?.len(),
+        2
+    );
+    assert_event_types_contain(&db, &["AnswerBankBulkImported"])?;
+    core::audit::validator::validate_chain(&db)?;
+
+    // Exports carry every entry and read back as unchanged rows.
+    for name in ["bank.csv", "bank.xlsx"] {
+        let out = vault_root.join("export").join(name);
+        let summary = answer_bank::ab_export_entries(&db, &out, false)?;
+        assert_eq!(summary.entry_count, 3);
+        assert_eq!(summary.sha256.len(), 64);
+
+        let cols = answer_bank::ab_profile_import_file(&out)?;
+        let map = answer_bank::ab_guess_column_map(&cols).expect("export headers map");
+        assert!(map.entry_id.is_some() && map.evidence_links.is_some());
+        let report = answer_bank::ab_bulk_import(&db, &out, &map, true, "tester")?;
+        assert_eq!(report.unchanged, 3, "{name}: {:?}", report.rows);
+        assert_eq!(report.rows.len(), 3);
+    }
+
+    let err = answer_bank::ab_export_entries(&db, &vault_root.join("bank.ods"), false).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::UnsupportedFormat);
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn answer_bank_near_duplicates_are_grouped_and_merged() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_answer_bank_dupes")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let create = |question: &str, tag: &str, evidence: &str| {
+        answer_bank::ab_create_entry(
+            &db,
+            AnswerBankCreateInput {
+                tags: vec![tag.to_string()],
+                evidence_links: vec![evidence.to_string()],
+                ..input(question, "Yes, with AES-256.")
+            },
+            "tester",
+        )
+    };
+    let a = create("Do you encrypt data at rest?", "crypto", "ev1")?;
+    let b = create("Is data encrypted at rest?", "storage", "ev2")?;
+    let c = create("Is customer data encrypted at rest?", "crypto", "ev1")?;
+    let other = create("Do you run backups?", "bcp", "ev3")?;
+    answer_bank::ab_set_entry_status(&db, &a.entry_id, EntryStatus::Approved, "approver")?;
+    answer_bank::ab_add_alias(&db, &b.entry_id, "Are disks encrypted?", "tester")?;
+
+    let groups = answer_bank::ab_find_duplicates(&db, answer_bank::DEFAULT_DUPLICATE_THRESHOLD)?;
+    assert_eq!(groups.len(), 1);
+    let ids: Vec<&str> = groups[0]
+        .entries
+        .iter()
+        .map(|e| e.entry_id.as_str())
+        .collect();
+    // The approved entry is suggested as the one to keep.
+    assert_eq!(
+        ids,
+        vec![
+            a.entry_id.as_str(),
+            b.entry_id.as_str(),
+            c.entry_id.as_str()
+        ]
+    );
+    assert_eq!(groups[0].similarity, 1.0);
+    assert!(!ids.contains(&other.entry_id.as_str()));
+    assert!(answer_bank::ab_find_duplicates(&db, 0.0).is_err());
+
+    let err = answer_bank::ab_merge_entries(
+        &db,
+        &a.entry_id,
+        std::slice::from_ref(&a.entry_id),
+        "tester",
+    )
+    .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+
+    let merged_ids = vec![b.entry_id.clone(), c.entry_id.clone()];
+    let kept = answer_bank::ab_merge_entries(&db, &a.entry_id, &merged_ids, "merger")?;
+    assert_eq!(kept.tags, vec!["crypto".to_string(), "storage".to_string()]);
+    assert_eq!(
+        kept.evidence_links,
+        vec!["ev1".to_string(), "ev2".to_string()]
+    );
+    assert_eq!(kept.status, EntryStatus::Draft);
+
+    let aliases: Vec<String> = answer_bank::ab_list_aliases(&db, &a.entry_id)?
+        .into_iter()
+        .map(|alias| alias.alias_text)
+        .collect();
+    assert_eq!(
+        aliases,
+        vec![
+            "Are disks encrypted?".to_string(),
+            "Is customer data encrypted at rest?".to_string(),
+            "Is data encrypted at rest?".to_string(),
+        ]
+    );
+    assert!(answer_bank::ab_list_aliases(&db, &b.entry_id)?.is_empty());
+    for id in &merged_ids {
+        assert_eq!(
+            answer_bank::ab_get_entry(&db, id)?.status,
+            EntryStatus::Retired
+        );
+    }
+
+    let candidates = answer_bank::ab_match_candidates(&db, &["disks".to_string()], 10)?;
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].entry_id, a.entry_id);
+    assert!(answer_bank::ab_find_duplicates(&db, 0.6)?.is_empty());
+
+    let err = answer_bank::ab_merge_entries(
+        &db,
+        &a.entry_id,
+        std::slice::from_ref(&b.entry_id),
+        "merger",
+    )
+    .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::Conflict);
+
+    let merge_events = db.query_rows_tsv(
+        "SELECT actor, payload_json FROM audit_event WHERE event_type='AnswerBankEntriesMerged';",
+    )?;
+    assert_eq!(merge_events.len(), 1);
+    assert_eq!(merge_events[0][0], "merger");
+    assert!(merge_events[0][1].contains(&c.entry_id));
+    core::audit::validator::validate_chain(&db)?;
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn answer_bank_variables_render_in_suggestions_and_exports_and_are_audited() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_answer_bank_variables")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let entry = answer_bank::ab_create_entry(
+        &db,
+        AnswerBankCreateInput {
+            answer_short: "Yes, {{company_name}}.".to_string(),
+            ..input(
+                "Do you encrypt data at rest?",
+                "{{ company_name }} encrypts data at rest; see {{policy_url}}. {{Not A Var}}",
+            )
+        },
+        "tester",
+    )?;
+
+    // Unknown placeholders stay as written and are reported.
+    let rendered = answer_bank::ab_render_entry(&db, &entry.entry_id)?;
+    assert_eq!(rendered.answer_short, "Yes, {{company_name}}.");
+    assert_eq!(rendered.unresolved, vec!["company_name", "policy_url"]);
+    let unresolved = answer_bank::ab_unresolved_variables(&db)?;
+    assert_eq!(unresolved.len(), 1);
+    assert_eq!(unresolved[0].entry_id, entry.entry_id);
+
+    answer_bank::ab_set_variable(&db, "company_name", "Acme Corp", "tester")?;
+    answer_bank::ab_set_variable(&db, "policy_url", "https://acme.test/p", "tester")?;
+    answer_bank::ab_set_variable(&db, "company_name", "Acme Inc", "tester")?;
+    let vars = answer_bank::ab_list_variables(&db)?;
+    assert_eq!(vars.len(), 2);
+    assert_eq!(vars[0].value, "Acme Inc");
+
+    let rendered = answer_bank::ab_render_entry(&db, &entry.entry_id)?;
+    assert_eq!(rendered.answer_short, "Yes, Acme Inc.");
+    assert_eq!(
+        rendered.answer_long,
+        "Acme Inc encrypts data at rest; see https://acme.test/p. {{Not A Var}}"
+    );
+    assert!(rendered.unresolved.is_empty());
+    assert!(answer_bank::ab_unresolved_variables(&db)?.is_empty());
+
+    // Stored answers keep the placeholders.
+    let stored = answer_bank::ab_get_entry(&db, &entry.entry_id)?;
+    assert_eq!(stored.answer_short, "Yes, {{company_name}}.");
+
+    let suggestions = AnswerBankMatcher::load(&db, RankingModel::Bm25)?.get_suggestions(
+        &db,
+        "Is data encrypted at rest?",
+        3,
+    )?;
+    assert_eq!(suggestions[0].answer_bank_entry_id, entry.entry_id);
+    assert_eq!(suggestions[0].answer_short, "Yes, Acme Inc.");
+    assert!(suggestions[0].unresolved_variables.is_empty());
+
+    // Raw exports round-trip the templates; rendered exports resolve them.
+    let raw = vault_root.join("export").join("raw.csv");
+    answer_bank::ab_export_entries(&db, &raw, false)?;
+    assert!(std::fs::read_to_string(&raw)?.contains("{{company_name}}"));
+    answer_bank::ab_delete_variable(&db, "policy_url", "tester")?;
+    let out = vault_root.join("export").join("rendered.csv");
+    let summary = answer_bank::ab_export_entries(&db, &out, true)?;
+    assert_eq!(summary.unresolved_variables, vec!["policy_url"]);
+    let text = std::fs::read_to_string(&out)?;
+    assert!(text.contains("Acme Inc encrypts data at rest; see {{policy_url}}."));
+
+    let err = answer_bank::ab_set_variable(&db, "Company Name", "x", "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+    let err = answer_bank::ab_set_variable(&db, "company_name", "  ", "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+    let err = answer_bank::ab_delete_variable(&db, "policy_url", "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+
+    assert_event_types_contain(&db, &["VaultVariableSet", "VaultVariableDeleted"])?;
+    let sets = db.query_optional_string(
+        "SELECT COUNT(*) FROM audit_event WHERE event_type='VaultVariableSet';",
+    )?;
+    assert_eq!(sets.as_deref(), Some("3"));
+    core::audit::validator::validate_chain(&db)?;
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn answer_bank_tags_form_a_taxonomy_and_entries_map_to_controls() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_answer_bank_taxonomy")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let at_rest = answer_bank::ab_create_entry(
+        &db,
+        AnswerBankCreateInput {
+            tags: vec!["security/encryption/at-rest".to_string()],
+            ..input("Do you encrypt data at rest?", "AES-256 everywhere.")
+        },
+        "alice",
+    )?;
+    let in_transit = answer_bank::ab_create_entry(
+        &db,
+        AnswerBankCreateInput {
+            tags: vec![
+                "Security/Encryption".to_string(),
+                "network".to_string(),
+                " security / encryption".to_string(),
+            ],
+            ..input("Do you encrypt data in transit?", "TLS 1.2 or later.")
+        },
+        "alice",
+    )?;
+    // Every tag write is normalized: case and spacing variants are one tag.
+    assert_eq!(in_transit.tags, vec!["network", "security/encryption"]);
+    answer_bank::ab_set_entry_status(&db, &in_transit.entry_id, EntryStatus::InReview, "alice")?;
+    answer_bank::ab_set_entry_status(&db, &in_transit.entry_id, EntryStatus::Approved, "bob")?;
+    answer_bank::ab_set_review_policy(
+        &db,
+        ReviewPolicyScope::Tag,
+        "SECURITY/Encryption",
+        90,
+        "tester",
+    )?;
+
+    // Tags in use are listed with their ancestors and counts.
+    answer_bank::ab_define_tag(&db, " Security / Encryption ", Some("Crypto"), "tester")?;
+    let tags = answer_bank::ab_list_tags(&db)?;
+    let names: Vec<&str> = tags.iter().map(|t| t.tag.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "network",
+            "security",
+            "security/encryption",
+            "security/encryption/at-rest"
+        ]
+    );
+    let crypto = &tags[2];
+    assert!(crypto.defined);
+    assert_eq!(crypto.description.as_deref(), Some("Crypto"));
+    assert_eq!(crypto.parent.as_deref(), Some("security"));
+    assert_eq!((crypto.entry_count, crypto.total_count), (1, 2));
+    assert!(tags[1].defined, "ancestors are defined with the tag");
+    assert!(!tags[0].defined);
+
+    // Renaming moves descendants, entries, evidence and tag policies along.
+    let key_doc = vault_root.join("keys.txt");
+    std::fs::write(&key_doc, "key management")?;
+    let key_doc = storage::evidence_add(&db, &vault_root, &key_doc, "tester")?;
+    storage::evidence_set_tags(
+        &db,
+        &key_doc.evidence_id,
+        &[
+            "security/encryption/keys".to_string(),
+            "network".to_string(),
+        ],
+        "tester",
+    )?;
+    let report = answer_bank::ab_rename_tag(&db, "security/encryption", "crypto", "tester")?;
+    assert_eq!(
+        report.renamed_tags,
+        vec![
+            "security/encryption",
+            "security/encryption/at-rest",
+            "security/encryption/keys"
+        ]
+    );
+    assert_eq!(report.entry_ids.len(), 2);
+    assert_eq!(report.evidence_ids, vec![key_doc.evidence_id.clone()]);
+    let evidence_tags = db.query_optional_string(&format!(
+        "SELECT tags_json FROM evidence_item WHERE evidence_id='{}';",
+        key_doc.evidence_id
+    ))?;
+    assert_eq!(
+        evidence_tags.as_deref(),
+        Some(r#"["crypto/keys","network"]"#)
+    );
+    let moved = answer_bank::ab_get_entry(&db, &at_rest.entry_id)?;
+    assert_eq!(moved.tags, vec!["crypto/at-rest"]);
+    assert_ne!(moved.content_hash, at_rest.content_hash);
+    let moved = answer_bank::ab_get_entry(&db, &in_transit.entry_id)?;
+    assert_eq!(moved.tags, vec!["crypto", "network"]);
+    assert_eq!(moved.status, EntryStatus::Approved);
+    assert_eq!(
+        answer_bank::ab_list_revisions(&db, &in_transit.entry_id)?.len(),
+        2
+    );
+    let policies = answer_bank::ab_list_review_policies(&db)?;
+    assert_eq!(policies[0].target, "crypto");
+    let crypto = answer_bank::ab_list_tags(&db)?
+        .into_iter()
+        .find(|t| t.tag == "crypto")
+        .unwrap();
+    assert_eq!(crypto.description.as_deref(), Some("Crypto"));
+    assert_eq!(crypto.total_count, 2);
+
+    let err = answer_bank::ab_rename_tag(&db, "crypto", "crypto/x", "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+    let err = answer_bank::ab_rename_tag(&db, "nope", "other", "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+    let err = answer_bank::ab_delete_tag(&db, "crypto", "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::Conflict);
+    answer_bank::ab_delete_tag(&db, "security", "tester")?;
+
+    // Control mappings filter the bank and answered questions.
+    answer_bank::ab_map_control(&db, &at_rest.entry_id, "SOC 2", "CC6.1", "tester")?;
+    answer_bank::ab_map_control(&db, &at_rest.entry_id, "ISO  27001", "A.8.24", "tester")?;
+    answer_bank::ab_map_control(&db, &in_transit.entry_id, "SOC 2", "CC6.7", "tester")?;
+    let err = answer_bank::ab_map_control(&db, &at_rest.entry_id, "soc 2", "cc6.1", "tester")
+        .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::Conflict);
+
+    let controls = answer_bank::ab_list_entry_controls(&db, &at_rest.entry_id)?;
+    assert_eq!(controls[0].framework, "ISO 27001");
+    assert_eq!(answer_bank::ab_list_controls(&db)?.len(), 3);
+    let soc2 = answer_bank::ab_entries_for_control(&db, "soc 2", None)?;
+    assert_eq!(soc2.len(), 2);
+    let cc61 = answer_bank::ab_entries_for_control(&db, "SOC 2", Some("cc6.1"))?;
+    assert_eq!(cc61.len(), 1);
+    assert_eq!(cc61[0].entry_id, at_rest.entry_id);
+
+    let src = vault_root.join("q.csv");
+    std::fs::write(
+        &src,
+        "Question,Answer\nIs data encrypted at rest?,\nIs data encrypted in transit?,\n",
+    )?;
+    let imp = core::questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    core::questionnaire::set_column_map(
+        &db,
+        &imp.import_id,
+        &core::questionnaire::ColumnMap {
+            question: "Question".to_string(),
+            answer: "Answer".to_string(),
+            notes: None,
+        },
+        "tester",
+    )?;
+    let qs = workflow::list_questions(&db, &imp.import_id)?;
+    for (q, entry) in qs.iter().zip([&at_rest, &in_transit]) {
+        workflow::set_question_answer(
+            &db,
+            &q.question_id,
+            &QuestionAnswer::Entry(entry.entry_id.clone()),
+            "tester",
+        )?;
+    }
+    let answered =
+        workflow::list_questions_for_control(&db, &imp.import_id, "SOC 2", Some("CC6.1"))?;
+    assert_eq!(answered.len(), 1);
+    assert_eq!(answered[0].question_id, qs[0].question_id);
+    assert_eq!(
+        workflow::list_questions_for_control(&db, &imp.import_id, "SOC 2", None)?.len(),
+        2
+    );
+
+    answer_bank::ab_unmap_control(&db, &at_rest.entry_id, "soc 2", "CC6.1", "tester")?;
+    let err = answer_bank::ab_unmap_control(&db, &at_rest.entry_id, "SOC 2", "CC6.1", "tester")
+        .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+
+    assert_event_types_contain(
+        &db,
+        &[
+            "AnswerBankTagDefined",
+            "AnswerBankTagRenamed",
+            "AnswerBankTagDeleted",
+            "AnswerBankControlMapped",
+            "AnswerBankControlUnmapped",
+        ],
+    )?;
+    core::audit::validator::validate_chain(&db)?;
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
@@ -0,0 +1,951 @@
+use core::answer_bank::{self, AnswerBankCreateInput};
+use core::domain::errors::{CoreErrorCode, CoreResult};
+use core::export::manifest::ExportManifest;
+use core::export::pack;
+use core::export::redaction::{self, REDACTION_REPORT_JSON};
+use core::export::scope::{ExportScope, ScopeControl};
+use core::export::signing::{self, ManifestSignature};
+use core::questionnaire::workflow::{self, QuestionAnswer};
+use core::questionnaire::{self, ColumnMap};
+use core::storage::db::SqliteDb;
+use core::storage::{self, vault_db_path};
+use core::util::json::JsonValue;
+use core::util::redact::{RedactionProfile, RedactionRule, RedactionRuleKind};
+use core::util::zip;
+use ed25519_dalek::{Signer, SigningKey};
+use std::path::{Path, PathBuf};
+
+fn make_temp_dir(prefix: &str) -> CoreResult<PathBuf> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let dir = std::env::temp_dir().join(format!("{}_{}_{}", prefix, std::process::id(), ts));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn input(question: &str, answer_long: &str) -> AnswerBankCreateInput {
+    AnswerBankCreateInput {
+        question_canonical: question.to_string(),
+        answer_short: "Yes".to_string(),
+        answer_long: answer_long.to_string(),
+        notes: None,
+        evidence_links: vec![],
+        owner: "alice".to_string(),
+        last_reviewed_at: None,
+        tags: vec![],
+        source: "manual".to_string(),
+        translations: vec![],
+    }
+}
+
+fn read_manifest(dir: &Path) -> CoreResult<ExportManifest> {
+    ExportManifest::from_json_str(&std::fs::read_to_string(dir.join("manifest.json"))?)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Sign an unpacked pack's manifest with `key`, as its holder would.
+fn resign(dir: &Path, key: &SigningKey) -> CoreResult<String> {
+    let manifest = std::fs::read_to_string(dir.join("manifest.json"))?;
+    let public_key_hex = hex(&key.verifying_key().to_bytes());
+    let sig = ManifestSignature {
+        algorithm: signing::SIGNATURE_ALGORITHM.to_string(),
+        public_key_hex: public_key_hex.clone(),
+        signature_hex: hex(&key.sign(manifest.as_bytes()).to_bytes()),
+    };
+    std::fs::write(dir.join(signing::SIGNATURE_FILE), sig.to_json_string())?;
+    Ok(public_key_hex)
+}
+
+/// Rewrite a file inside an unpacked pack, fix up its manifest hash and
+/// re-sign with `key`, as someone regenerating the manifest would.
+fn tamper(dir: &Path, path: &str, from: &str, to: &str, key: &SigningKey) -> CoreResult<()> {
+    let p = dir.join(path);
+    let text = std::fs::read_to_string(&p)?.replacen(from, to, 1);
+    std::fs::write(&p, &text)?;
+    let mut manifest = read_manifest(dir)?;
+    for f in manifest.files.iter_mut().filter(|f| f.path == path) {
+        f.sha256 = core::audit::hasher::sha256_hex_file(&p)?;
+        f.size = text.len() as i64;
+    }
+    std::fs::write(dir.join("manifest.json"), manifest.to_json_string())?;
+    resign(dir, key)?;
+    Ok(())
+}
+
+/// Import a CSV questionnaire with one question per answer and answer them.
+fn answered_import(
+    db: &SqliteDb,
+    vault_root: &Path,
+    name: &str,
+    answers: &[QuestionAnswer],
+) -> CoreResult<String> {
+    let src = vault_root.join(name);
+    let mut csv = "Question,Answer\n".to_string();
+    for i in 0..answers.len() {
+        csv.push_str(&format!("Question {} of {}?,\n", i + 1, name));
+    }
+    std::fs::write(&src, csv)?;
+    let imp = questionnaire::import_questionnaire(db, vault_root, &src, "tester")?;
+    questionnaire::set_column_map(
+        db,
+        &imp.import_id,
+        &ColumnMap {
+            question: "Question".to_string(),
+            answer: "Answer".to_string(),
+            notes: None,
+        },
+        "tester",
+    )?;
+    let qs = workflow::list_questions(db, &imp.import_id)?;
+    for (q, a) in qs.iter().zip(answers) {
+        workflow::set_question_answer(db, &q.question_id, a, "tester")?;
+    }
+    Ok(imp.import_id)
+}
+
+fn section_files(pack: &pack::ExportPack, name: &str) -> Vec<String> {
+    pack.manifest
+        .sections
+        .iter()
+        .find(|s| s.name == name)
+        .map(|s| s.files.clone())
+        .unwrap_or_default()
+}
+
+#[test]
+fn export_pack_sections_are_listed_in_the_manifest_and_validated() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_export_sections")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let policy = vault_root.join("policy.txt");
+    std::fs::write(&policy, "Encryption policy")?;
+    let evidence = storage::evidence_add(&db, &vault_root, &policy, "tester")?;
+
+    let entry = answer_bank::ab_create_entry(
+        &db,
+        AnswerBankCreateInput {
+            evidence_links: vec![evidence.evidence_id.clone()],
+            ..input(
+                "Do you encrypt data at rest?",
+                "{{company}} encrypts data with AES-256.",
+            )
+        },
+        "alice",
+    )?;
+    answer_bank::ab_set_variable(&db, "company", "Acme", "tester")?;
+    answer_bank::ab_map_control(&db, &entry.entry_id, "SOC 2", "CC6.1", "tester")?;
+
+    let src = vault_root.join("q.csv");
+    std::fs::write(
+        &src,
+        "Question,Answer\nIs data encrypted at rest?,\nDo you have a DPO?,\n",
+    )?;
+    let imp = questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    questionnaire::set_column_map(
+        &db,
+        &imp.import_id,
+        &ColumnMap {
+            question: "Question".to_string(),
+            answer: "Answer".to_string(),
+            notes: None,
+        },
+        "tester",
+    )?;
+    let qs = workflow::list_questions(&db, &imp.import_id)?;
+    workflow::set_question_answer(
+        &db,
+        &qs[0].question_id,
+        &QuestionAnswer::Entry(entry.entry_id.clone()),
+        "tester",
+    )?;
+    workflow::set_question_answer(
+        &db,
+        &qs[1].question_id,
+        &QuestionAnswer::Override("Yes, Jane Doe & team.".to_string()),
+        "tester",
+    )?;
+
+    let out_zip = vault_root.join("exports").join("pack.zip");
+    let generated = pack::generate_pack(
+        &vault_root,
+        &out_zip,
+        &ExportScope::default(),
+        None,
+        "tester",
+    )?;
+    let vault_key = generated.signature.public_key_hex.clone();
+    assert_eq!(generated.manifest.version, pack::MANIFEST_VERSION);
+    let names: Vec<&str> = generated
+        .manifest
+        .sections
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "answer_bank",
+            "audit_log",
+            "column_maps",
+            "evidence",
+            "index",
+            "questionnaires"
+        ]
+    );
+    let questionnaire_file = format!("questionnaires/{}.json", imp.import_id);
+    let listed: Vec<&str> = generated
+        .manifest
+        .files
+        .iter()
+        .map(|f| f.path.as_str())
+        .collect();
+    for path in [
+        "answer_bank/answer_bank.csv",
+        "answer_bank/answer_bank.json",
+        "audit_log/audit_log.jsonl",
+        "questionnaires/column_maps.json",
+        questionnaire_file.as_str(),
+        evidence.relative_path.as_str(),
+        "index.html",
+        "index.md",
+    ] {
+        assert!(listed.contains(&path), "{path} missing from manifest");
+    }
+    pack::validate_pack(&out_zip, std::slice::from_ref(&vault_key))?;
+
+    // Same vault, same pack. The first export recorded the new signing key
+    // after it was written, so compare two later ones.
+    let again = vault_root.join("exports").join("again.zip");
+    pack::generate_pack(&vault_root, &again, &ExportScope::default(), None, "tester")?;
+    let third = vault_root.join("exports").join("third.zip");
+    pack::generate_pack(&vault_root, &third, &ExportScope::default(), None, "tester")?;
+    assert_ne!(std::fs::read(&out_zip)?, std::fs::read(&again)?);
+    assert_eq!(std::fs::read(&again)?, std::fs::read(&third)?);
+
+    let dir = make_temp_dir("cs_export_sections_unpacked")?;
+    zip::unzip_to_dir(&out_zip, &dir)?;
+    let answers = std::fs::read_to_string(dir.join(&questionnaire_file))?;
+    assert!(answers.contains("Acme encrypts data with AES-256."));
+    assert!(answers.contains("Yes, Jane Doe & team."));
+    let bank = std::fs::read_to_string(dir.join("answer_bank/answer_bank.json"))?;
+    assert!(bank.contains("\"control_id\":\"CC6.1\""));
+    assert!(bank.contains("{{company}} encrypts"));
+    let log = std::fs::read_to_string(dir.join("audit_log/audit_log.jsonl"))?;
+    assert!(log
+        .lines()
+        .any(|l| l.contains("\"event_type\":\"AnswerBankControlMapped\"")));
+
+    // The HTML index links answers to their evidence and lists file hashes.
+    let html = std::fs::read_to_string(dir.join("index.html"))?;
+    assert!(html.contains(&format!("<section id=\"q-{}\">", imp.import_id)));
+    assert!(html.contains("Acme encrypts data with AES-256."));
+    assert!(html.contains("Yes, Jane Doe &amp; team."));
+    assert!(html.contains(&format!(
+        "<a href=\"{}\">policy.txt</a>",
+        evidence.relative_path
+    )));
+    assert!(html.contains(&format!("<code>{}</code>", evidence.sha256)));
+    let bank_file = generated
+        .manifest
+        .files
+        .iter()
+        .find(|f| f.path == "answer_bank/answer_bank.json")
+        .unwrap();
+    assert!(html.contains(&bank_file.sha256));
+    assert!(!html.contains("<script") && !html.contains("http"));
+
+    // Edits with a regenerated, re-signed manifest still fail the section
+    // checks.
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let trusted = vec![hex(&key.verifying_key().to_bytes())];
+    let rezip = |name: &str| -> CoreResult<PathBuf> {
+        let out = vault_root.join("exports").join(name);
+        zip::zip_dir_deterministic(&dir, &out)?;
+        Ok(out)
+    };
+    tamper(
+        &dir,
+        "answer_bank/answer_bank.json",
+        "AES-256",
+        "AES-128",
+        &key,
+    )?;
+    let err = pack::validate_pack(&rezip("bank.zip")?, &trusted).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::HashMismatch);
+    tamper(
+        &dir,
+        "answer_bank/answer_bank.json",
+        "AES-128",
+        "AES-256",
+        &key,
+    )?;
+    pack::validate_pack(&rezip("restored.zip")?, &trusted)?;
+
+    tamper(
+        &dir,
+        "audit_log/audit_log.jsonl",
+        "\"actor\":\"alice\"",
+        "\"actor\":\"mallory\"",
+        &key,
+    )?;
+    let err = pack::validate_pack(&rezip("log.zip")?, &trusted).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::HashMismatch);
+
+    let _ = std::fs::remove_dir_all(&dir);
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn export_packs_are_signed_by_the_vault_and_bind_the_audit_head() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_export_signed")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+    assert_eq!(signing::vault_signing_public_key(&db)?, None);
+
+    let out_zip = vault_root.join("exports").join("pack.zip");
+    let generated = pack::generate_pack(
+        &vault_root,
+        &out_zip,
+        &ExportScope::default(),
+        None,
+        "tester",
+    )?;
+    let vault_key = signing::vault_signing_public_key(&db)?.expect("key created on export");
+    assert_eq!(generated.signature.public_key_hex, vault_key);
+
+    // The key is recorded after the pack is written, right after its head.
+    let head =
+        db.query_rows_tsv("SELECT seq, event_type FROM audit_event ORDER BY seq DESC LIMIT 2;")?;
+    let manifest_head = generated.manifest.audit_head.clone().unwrap();
+    assert_eq!(head[0][1], "ExportSigningKeyCreated");
+    assert_eq!(manifest_head.seq.to_string(), head[1][0]);
+
+    // Only the public key is in the database; the secret is in an owner-only
+    // file next to it.
+    let columns = db.query_rows_tsv("SELECT name FROM pragma_table_info('export_signing_key');")?;
+    assert!(columns.iter().all(|c| !c[0].contains("secret")));
+    let key_file = signing::signing_key_path(&db);
+    assert_eq!(key_file, vault_root.join(signing::SIGNING_KEY_FILE));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&key_file)?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    let verified = pack::validate_pack(&out_zip, std::slice::from_ref(&vault_key))?;
+    assert_eq!(verified, generated.signature);
+    let err = pack::validate_pack(&out_zip, &[]).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::PermissionDenied);
+
+    // The key is kept: later packs are signed by it and carry the new head.
+    answer_bank::ab_create_entry(&db, input("Do you log access?", "Yes."), "alice")?;
+    let later = pack::generate_pack(
+        &vault_root,
+        &vault_root.join("later.zip"),
+        &ExportScope::default(),
+        None,
+        "tester",
+    )?;
+    assert_eq!(later.signature.public_key_hex, vault_key);
+    assert_ne!(later.manifest.audit_head.unwrap(), manifest_head);
+
+    let dir = make_temp_dir("cs_export_signed_unpacked")?;
+    zip::unzip_to_dir(&out_zip, &dir)?;
+    let rezip = |name: &str| -> CoreResult<PathBuf> {
+        let out = vault_root.join("exports").join(name);
+        zip::zip_dir_deterministic(&dir, &out)?;
+        Ok(out)
+    };
+
+    // A manifest edited without the vault key fails its signature.
+    let manifest = std::fs::read_to_string(dir.join("manifest.json"))?;
+    std::fs::write(
+        dir.join("manifest.json"),
+        manifest.replacen("\"seq\":", "\"seq\":1", 1),
+    )?;
+    let err =
+        pack::validate_pack(&rezip("edited.zip")?, std::slice::from_ref(&vault_key)).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::HashMismatch);
+
+    // Re-signing with another key only passes if that key is trusted, and
+    // then the audit log no longer ends at the claimed head.
+    let other = resign(&dir, &SigningKey::from_bytes(&[9u8; 32]))?;
+    let err =
+        pack::validate_pack(&rezip("resigned.zip")?, std::slice::from_ref(&vault_key)).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::PermissionDenied);
+    let err =
+        pack::validate_pack(&rezip("resigned2.zip")?, &[vault_key.clone(), other]).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::HashMismatch);
+
+    // Files outside the manifest and unsigned packs are rejected.
+    std::fs::write(dir.join("manifest.json"), &manifest)?;
+    std::fs::remove_file(dir.join(signing::SIGNATURE_FILE))?;
+    let err =
+        pack::validate_pack(&rezip("unsigned.zip")?, std::slice::from_ref(&vault_key)).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::PermissionDenied);
+    let _ = std::fs::remove_dir_all(&dir);
+    zip::unzip_to_dir(&out_zip, &dir)?;
+    std::fs::write(dir.join("extra.txt"), "not in the manifest")?;
+    let err =
+        pack::validate_pack(&rezip("extra.zip")?, std::slice::from_ref(&vault_key)).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::HashMismatch);
+
+    // Rotation replaces the key; a missing or foreign key file stops exports
+    // until the key is rotated.
+    let rotated = signing::rotate_signing_key(&db, "tester")?;
+    assert_ne!(rotated, vault_key);
+    assert_eq!(
+        signing::vault_signing_public_key(&db)?,
+        Some(rotated.clone())
+    );
+    let events = db.query_optional_string(
+        "SELECT COUNT(*) FROM audit_event WHERE event_type='ExportSigningKeyRotated';",
+    )?;
+    assert_eq!(events.as_deref(), Some("1"));
+    let next = pack::generate_pack(
+        &vault_root,
+        &vault_root.join("next.zip"),
+        &ExportScope::default(),
+        None,
+        "tester",
+    )?;
+    assert_eq!(next.signature.public_key_hex, rotated);
+    std::fs::remove_file(&key_file)?;
+    let err = pack::generate_pack(
+        &vault_root,
+        &vault_root.join("nokey.zip"),
+        &ExportScope::default(),
+        None,
+        "tester",
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+    signing::rotate_signing_key(&db, "tester")?;
+    assert!(key_file.exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn export_scope_limits_the_pack_and_is_recorded_in_the_manifest() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_export_scope")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let mut evidence = Vec::new();
+    for name in ["backup.txt", "policy.txt", "public.txt"] {
+        let p = vault_root.join(name);
+        std::fs::write(&p, format!("{} contents", name))?;
+        evidence.push(storage::evidence_add(&db, &vault_root, &p, "tester")?);
+    }
+    let (backup, policy, public) = (&evidence[0], &evidence[1], &evidence[2]);
+    let tags = storage::evidence_set_tags(
+        &db,
+        &public.evidence_id,
+        &[" public / web ".to_string(), "public/web".to_string()],
+        "tester",
+    )?;
+    assert_eq!(tags, vec!["public/web".to_string()]);
+    let err = storage::evidence_set_tags(&db, "missing", &[], "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+
+    let encryption = answer_bank::ab_create_entry(
+        &db,
+        AnswerBankCreateInput {
+            evidence_links: vec![policy.evidence_id.clone()],
+            tags: vec!["security/encryption".to_string()],
+            ..input("Do you encrypt data at rest?", "Yes, with AES-256.")
+        },
+        "alice",
+    )?;
+    answer_bank::ab_map_control(&db, &encryption.entry_id, "SOC 2", "CC6.1", "tester")?;
+    let backups = answer_bank::ab_create_entry(
+        &db,
+        AnswerBankCreateInput {
+            evidence_links: vec![backup.evidence_id.clone()],
+            tags: vec!["operations".to_string()],
+            ..input("Do you back up data?", "Daily.")
+        },
+        "alice",
+    )?;
+    let other = answer_bank::ab_create_entry(&db, input("Do you have a DPO?", "Yes."), "alice")?;
+
+    let customer = answered_import(
+        &db,
+        &vault_root,
+        "customer.csv",
+        &[
+            QuestionAnswer::Entry(encryption.entry_id.clone()),
+            QuestionAnswer::Entry(backups.entry_id.clone()),
+            QuestionAnswer::Override("Not applicable.".to_string()),
+        ],
+    )?;
+    let unrelated = answered_import(
+        &db,
+        &vault_root,
+        "unrelated.csv",
+        &[QuestionAnswer::Entry(other.entry_id.clone())],
+    )?;
+
+    let exports = vault_root.join("exports");
+    let generate = |name: &str, scope: ExportScope| -> CoreResult<pack::ExportPack> {
+        let out = exports.join(name);
+        let generated = pack::generate_pack(&vault_root, &out, &scope, None, "tester")?;
+        let key = generated.signature.public_key_hex.clone();
+        pack::validate_pack(&out, &[key])?;
+        Ok(generated)
+    };
+
+    // One customer's questionnaire: its answers and the evidence they link to.
+    let scope = ExportScope {
+        import_ids: vec![format!(" {} ", customer), customer.clone()],
+        ..ExportScope::default()
+    };
+    let customer_pack = generate("customer.zip", scope)?;
+    let recorded = customer_pack.manifest.scope.clone().unwrap();
+    assert_eq!(recorded.import_ids, vec![customer.clone()]);
+    assert_eq!(
+        section_files(&customer_pack, "questionnaires"),
+        vec![format!("questionnaires/{}.json", customer)]
+    );
+    assert_eq!(
+        section_files(&customer_pack, "evidence"),
+        vec![backup.relative_path.clone(), policy.relative_path.clone()]
+    );
+    let dir = make_temp_dir("cs_export_scope_unpacked")?;
+    zip::unzip_to_dir(&customer_pack.zip_path, &dir)?;
+    let bank = std::fs::read_to_string(dir.join("answer_bank/answer_bank.json"))?;
+    assert!(bank.contains(&encryption.entry_id) && bank.contains(&backups.entry_id));
+    assert!(!bank.contains(&other.entry_id));
+    let maps = std::fs::read_to_string(dir.join("questionnaires/column_maps.json"))?;
+    assert!(maps.contains(&customer) && !maps.contains(&unrelated));
+    // The vault-wide audit log is left out unless the scope asks for it.
+    assert!(section_files(&customer_pack, "audit_log").is_empty());
+    assert!(!dir.join("audit_log/audit_log.jsonl").exists());
+    assert!(customer_pack.manifest.audit_head.is_some());
+    let with_log = generate(
+        "customer_log.zip",
+        ExportScope {
+            import_ids: vec![customer.clone()],
+            full_audit_log: true,
+            ..ExportScope::default()
+        },
+    )?;
+    assert!(with_log.manifest.scope.as_ref().unwrap().full_audit_log);
+    assert_eq!(
+        section_files(&with_log, "audit_log"),
+        vec!["audit_log/audit_log.jsonl".to_string()]
+    );
+    let log_dir = make_temp_dir("cs_export_scope_log")?;
+    zip::unzip_to_dir(&with_log.zip_path, &log_dir)?;
+    let manifest = std::fs::read_to_string(log_dir.join("manifest.json"))?;
+    std::fs::write(
+        log_dir.join("manifest.json"),
+        manifest.replacen("\"full_audit_log\":true,", "", 1),
+    )?;
+    let trusted = resign(&log_dir, &SigningKey::from_bytes(&[8u8; 32]))?;
+    let unasked = exports.join("unasked_log.zip");
+    zip::zip_dir_deterministic(&log_dir, &unasked)?;
+    let err = pack::validate_pack(&unasked, &[trusted]).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::CorruptVault);
+    let _ = std::fs::remove_dir_all(&log_dir);
+
+    // A signed manifest claiming another scope fails validation.
+    let manifest = std::fs::read_to_string(dir.join("manifest.json"))?;
+    std::fs::write(
+        dir.join("manifest.json"),
+        manifest.replacen(
+            &format!("[\"{}\"]", customer),
+            &format!("[\"{}\"]", unrelated),
+            1,
+        ),
+    )?;
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let trusted = resign(&dir, &key)?;
+    let rescoped = exports.join("rescoped.zip");
+    zip::zip_dir_deterministic(&dir, &rescoped)?;
+    let err = pack::validate_pack(&rescoped, &[trusted]).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::CorruptVault);
+
+    // Narrowed by tag, questions answered outside the scope lose their answer.
+    let tagged = generate(
+        "tagged.zip",
+        ExportScope {
+            import_ids: vec![customer.clone()],
+            answer_bank_tags: vec!["security".to_string()],
+            ..ExportScope::default()
+        },
+    )?;
+    assert_eq!(
+        section_files(&tagged, "evidence"),
+        vec![policy.relative_path.clone()]
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+    zip::unzip_to_dir(&tagged.zip_path, &dir)?;
+    let q = std::fs::read_to_string(dir.join(format!("questionnaires/{}.json", customer)))?;
+    assert_eq!(q.matches("\"question_id\"").count(), 3);
+    assert_eq!(q.matches("\"answer_withheld\":true").count(), 1);
+    assert!(q.contains(&encryption.entry_id) && q.contains("Not applicable."));
+    assert!(!q.contains(&backups.entry_id) && !q.contains("Daily."));
+    let index = std::fs::read_to_string(dir.join("index.html"))?;
+    assert!(index.contains("withheld (outside this pack's scope)"));
+
+    // By control and evidence tag.
+    let by_control = generate(
+        "control.zip",
+        ExportScope {
+            controls: vec![ScopeControl {
+                framework: "SOC  2".to_string(),
+                control_id: None,
+            }],
+            ..ExportScope::default()
+        },
+    )?;
+    assert_eq!(
+        by_control.manifest.scope.as_ref().unwrap().controls[0].framework,
+        "SOC 2"
+    );
+    assert_eq!(
+        section_files(&by_control, "evidence"),
+        vec![policy.relative_path.clone()]
+    );
+    let by_tag = generate(
+        "public.zip",
+        ExportScope {
+            evidence_tags: vec!["public".to_string()],
+            ..ExportScope::default()
+        },
+    )?;
+    assert_eq!(
+        section_files(&by_tag, "evidence"),
+        vec![public.relative_path.clone()]
+    );
+    assert_eq!(section_files(&by_tag, "questionnaires").len(), 2);
+
+    // By when evidence was added, as audit positions: only what came after
+    // an earlier pack's audit head.
+    let later_src = vault_root.join("later.txt");
+    std::fs::write(&later_src, "later contents")?;
+    let later = storage::evidence_add(&db, &vault_root, &later_src, "tester")?;
+    let since = customer_pack.manifest.audit_head.as_ref().unwrap().seq + 1;
+    let since_pack = generate(
+        "since.zip",
+        ExportScope {
+            evidence_added_from_seq: Some(since),
+            ..ExportScope::default()
+        },
+    )?;
+    assert_eq!(
+        since_pack
+            .manifest
+            .scope
+            .as_ref()
+            .unwrap()
+            .evidence_added_from_seq,
+        Some(since)
+    );
+    assert_eq!(
+        section_files(&since_pack, "evidence"),
+        vec![later.relative_path.clone()]
+    );
+    let before = generate(
+        "before.zip",
+        ExportScope {
+            evidence_added_to_seq: Some(since - 1),
+            ..ExportScope::default()
+        },
+    )?;
+    assert_eq!(section_files(&before, "evidence").len(), 3);
+    let err = generate(
+        "backwards.zip",
+        ExportScope {
+            evidence_added_from_seq: Some(since),
+            evidence_added_to_seq: Some(since - 1),
+            ..ExportScope::default()
+        },
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+
+    let err = generate(
+        "missing.zip",
+        ExportScope {
+            import_ids: vec!["missing".to_string()],
+            ..ExportScope::default()
+        },
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+
+    let _ = std::fs::remove_dir_all(&dir);
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn export_packs_apply_redaction_profiles_and_report_them() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_export_redaction")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let mut evidence = Vec::new();
+    for name in ["policy.txt", "falcon-salaries.txt", "falcon-roadmap.txt"] {
+        let p = vault_root.join(name);
+        std::fs::write(&p, format!("{} contents", name))?;
+        evidence.push(storage::evidence_add(&db, &vault_root, &p, "tester")?);
+    }
+    let (policy, salaries, roadmap) = (&evidence[0], &evidence[1], &evidence[2]);
+    storage::evidence_set_tags(
+        &db,
+        &salaries.evidence_id,
+        &["internal/hr".to_string()],
+        "tester",
+    )?;
+
+    let entry = answer_bank::ab_create_entry(
+        &db,
+        AnswerBankCreateInput {
+            notes: Some("Ask the Falcon team".to_string()),
+            evidence_links: vec![policy.evidence_id.clone(), salaries.evidence_id.clone()],
+            ..input(
+                "Does Falcon handle security incidents?",
+                "Project Falcon, reachable at sec.team@acme.com.",
+            )
+        },
+        "alice",
+    )?;
+    let import_id = answered_import(
+        &db,
+        &vault_root,
+        "falcon-customer.csv",
+        &[
+            QuestionAnswer::Entry(entry.entry_id.clone()),
+            QuestionAnswer::Override("Ask FALCON via jane@acme.com".to_string()),
+        ],
+    )?;
+    let qs = workflow::list_questions(&db, &import_id)?;
+    workflow::assign_question(&db, &qs[0].question_id, Some("Bob"), "tester")?;
+    answer_bank::ab_set_variable(&db, "hosting", "Acme \"Cloud\"\n  in the EU", "tester")?;
+
+    let rule = |kind, pattern: &str, replacement: &str| RedactionRule {
+        kind,
+        pattern: pattern.to_string(),
+        replacement: replacement.to_string(),
+    };
+    let profile = RedactionProfile {
+        name: " customer ".to_string(),
+        rules: vec![
+            rule(RedactionRuleKind::Keyword, "falcon", "[PROJECT]"),
+            rule(RedactionRuleKind::Regex, r"[\w.]+@\w+\.com", "[EMAIL]"),
+            rule(
+                RedactionRuleKind::Keyword,
+                "Acme \"Cloud\"",
+                "[VENDOR \"\\]",
+            ),
+            rule(RedactionRuleKind::Regex, r"\n\s+", " "),
+            rule(RedactionRuleKind::Keyword, "value", "[X]"),
+        ],
+        excluded_evidence_tags: vec!["internal".to_string()],
+        remove_notes: true,
+        remove_owners: true,
+    };
+    let saved = redaction::redaction_save_profile(&db, &profile, "tester")?;
+    assert_eq!(saved.name, "customer");
+    assert_eq!(redaction::redaction_get_profile(&db, "customer")?, saved);
+
+    // Invalid rules are rejected on save; profiles can be listed and deleted.
+    let err = redaction::redaction_save_profile(
+        &db,
+        &RedactionProfile {
+            name: "broken".to_string(),
+            rules: vec![rule(RedactionRuleKind::Regex, "(a", "")],
+            ..profile.clone()
+        },
+        "tester",
+    )
+    .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+    redaction::redaction_save_profile(
+        &db,
+        &RedactionProfile {
+            name: "spare".to_string(),
+            ..profile.clone()
+        },
+        "tester",
+    )?;
+    let names: Vec<String> = redaction::redaction_list_profiles(&db)?
+        .into_iter()
+        .map(|p| p.name)
+        .collect();
+    assert_eq!(names, vec!["customer".to_string(), "spare".to_string()]);
+    redaction::redaction_delete_profile(&db, "spare", "tester")?;
+    let err = redaction::redaction_delete_profile(&db, "spare", "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+
+    let out_zip = vault_root.join("exports").join("redacted.zip");
+    let err = pack::generate_pack(
+        &vault_root,
+        &out_zip,
+        &ExportScope::default(),
+        Some("spare"),
+        "tester",
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+    let generated = pack::generate_pack(
+        &vault_root,
+        &out_zip,
+        &ExportScope::default(),
+        Some("customer"),
+        "tester",
+    )?;
+    let key = generated.signature.public_key_hex.clone();
+    pack::validate_pack(&out_zip, std::slice::from_ref(&key))?;
+
+    let recorded = generated.manifest.redaction.clone().unwrap();
+    assert_eq!(recorded.profile, "customer");
+    assert_eq!(recorded.profile_sha256, redaction::profile_sha256(&saved)?);
+    assert_eq!(
+        recorded.unredacted,
+        vec!["audit_actors", "column_maps", "evidence_contents", "tags"]
+    );
+    assert_eq!(
+        section_files(&generated, "redactions"),
+        vec![REDACTION_REPORT_JSON.to_string()]
+    );
+    // Evidence names are redacted in the pack; contents are copied as is.
+    let roadmap_path = roadmap
+        .relative_path
+        .replace("falcon-roadmap", "[PROJECT]-roadmap");
+    let mut expected = vec![policy.relative_path.clone(), roadmap_path.clone()];
+    expected.sort();
+    assert_eq!(section_files(&generated, "evidence"), expected);
+
+    let dir = make_temp_dir("cs_export_redaction_unpacked")?;
+    zip::unzip_to_dir(&out_zip, &dir)?;
+    let read = |path: &str| std::fs::read_to_string(dir.join(path));
+    let bank = read("answer_bank/answer_bank.json")?;
+    assert!(bank.contains("Project [PROJECT], reachable at [EMAIL]."));
+    assert!(bank.contains("Does [PROJECT] handle security incidents?"));
+    assert!(!bank.contains("Ask the Falcon team") && !bank.contains("alice"));
+    let q = read(&format!("questionnaires/{}.json", import_id))?;
+    assert!(q.contains("Ask [PROJECT] via [EMAIL]"));
+    assert!(q.contains("Question 1 of [PROJECT]-customer.csv?"));
+    assert!(!q.to_lowercase().contains("falcon"));
+    assert!(!q.contains("acme.com") && !q.contains("Bob"));
+    assert_eq!(read(&roadmap_path)?, "falcon-roadmap.txt contents");
+    for index in ["index.html", "index.md"] {
+        let text = read(index)?.to_lowercase();
+        assert!(
+            !text.contains("falcon") && !text.contains("salaries"),
+            "{index}"
+        );
+    }
+
+    let report = read(REDACTION_REPORT_JSON)?;
+    assert!(report.contains("\"profile\":\"customer\""));
+    assert!(report.contains(
+        "\"unredacted\":[\"audit_actors\",\"column_maps\",\"evidence_contents\",\"tags\"]"
+    ));
+    for expected in [
+        format!(
+            "{{\"action\":\"excluded\",\"id\":\"{}\",\"target\":\"evidence\"}}",
+            salaries.evidence_id
+        ),
+        format!(
+            "{{\"action\":\"removed\",\"field\":\"notes\",\"id\":\"{}\",\"target\":\"answer_bank_entry\"}}",
+            entry.entry_id
+        ),
+        format!(
+            "{{\"action\":\"removed\",\"field\":\"assignee\",\"id\":\"{}\",\"target\":\"question\"}}",
+            qs[0].question_id
+        ),
+        format!(
+            "{{\"action\":\"replaced\",\"field\":\"answer\",\"id\":\"{}\",\"matches\":1,\"rule\":1,\"target\":\"question\"}}",
+            qs[1].question_id
+        ),
+    ] {
+        assert!(report.contains(&expected), "missing {}", expected);
+    }
+    assert!(report.contains("\"action\":\"removed\",\"field\":\"payload.assignee\""));
+    assert!(!report.to_lowercase().contains("falcon") && !report.contains("acme.com"));
+    assert!(!report.contains("Bob"));
+
+    // The pack's audit log hides redacted payload text, here the excluded
+    // evidence's file name; the vault records the export afterwards.
+    let log = read("audit_log/audit_log.jsonl")?;
+    assert!(log.contains("\"payload_redacted\":true"));
+    assert!(!log.contains("falcon-") && !log.contains("Bob"));
+    // Rules see payload text as written, not JSON-escaped, and leave keys
+    // and the JSON itself intact.
+    let variable_set = log
+        .lines()
+        .map(|line| JsonValue::parse(line).unwrap())
+        .find(|v| v.as_object().unwrap().get_string("event_type").unwrap() == "VaultVariableSet")
+        .unwrap();
+    let payload = variable_set.as_object()?.get_string("payload")?;
+    let payload = JsonValue::parse(&payload)?;
+    assert_eq!(
+        payload.as_object()?.get_string("value")?,
+        "[VENDOR \"\\] in the EU"
+    );
+    let last = db.query_rows_tsv(
+        "SELECT event_type, payload_json FROM audit_event ORDER BY seq DESC LIMIT 1;",
+    )?;
+    assert_eq!(last[0][0], "ExportRedacted");
+    assert!(last[0][1].contains(&recorded.profile_sha256));
+
+    // A report that does not match the manifest fails validation.
+    let signer = SigningKey::from_bytes(&[5u8; 32]);
+    tamper(
+        &dir,
+        REDACTION_REPORT_JSON,
+        "\"profile\":\"customer\"",
+        "\"profile\":\"other\"",
+        &signer,
+    )?;
+    let trusted = hex(&signer.verifying_key().to_bytes());
+    let tampered = vault_root.join("exports").join("tampered.zip");
+    zip::zip_dir_deterministic(&dir, &tampered)?;
+    let err = pack::validate_pack(&tampered, std::slice::from_ref(&trusted)).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::CorruptVault);
+
+    // A redacted event's hash still covers its original payload's SHA-256.
+    assert!(log.contains("\"hash_format\":2"));
+    let dir2 = make_temp_dir("cs_export_redaction_tampered")?;
+    zip::unzip_to_dir(&out_zip, &dir2)?;
+    tamper(
+        &dir2,
+        "audit_log/audit_log.jsonl",
+        "\"payload_redacted\":true,\"payload_sha256\":\"",
+        "\"payload_redacted\":true,\"payload_sha256\":\"0",
+        &signer,
+    )?;
+    zip::zip_dir_deterministic(&dir2, &tampered)?;
+    let err = pack::validate_pack(&tampered, &[trusted]).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::HashMismatch);
+
+    let _ = std::fs::remove_dir_all(&dir);
+    let _ = std::fs::remove_dir_all(&dir2);
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
@@ -0,0 +1,644 @@
+use core::answer_bank::{
+    self, AnswerBankCreateInput, AnswerBankEntry, AnswerBankUpdatePatch, EntryStatus, LocalizedText,
+};
+use core::domain::errors::{CoreErrorCode, CoreResult};
+use core::questionnaire::matching::{AnswerBankMatcher, RankingModel};
+use core::questionnaire::suggestions::{self, AutoAnswerOptions, AutoAnswerOutcome};
+use core::questionnaire::synonyms;
+use core::questionnaire::workflow::{self, QuestionAnswer, QuestionStatus};
+use core::questionnaire::{self, ColumnMap};
+use core::storage::db::SqliteDb;
+use core::storage::{self, vault_db_path};
+use std::path::PathBuf;
+
+fn make_temp_dir(prefix: &str) -> CoreResult<PathBuf> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let dir = std::env::temp_dir().join(format!("{}_{}_{}", prefix, std::process::id(), ts));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Approved entry: created by "tester", approved by "approver".
+fn create_entry(db: &SqliteDb, question: &str, answer: &str) -> CoreResult<AnswerBankEntry> {
+    let entry = answer_bank::ab_create_entry(
+        db,
+        AnswerBankCreateInput {
+            question_canonical: question.to_string(),
+            answer_short: answer.to_string(),
+            answer_long: answer.to_string(),
+            notes: None,
+            evidence_links: vec![],
+            owner: "alice".to_string(),
+            last_reviewed_at: None,
+            tags: vec![],
+            source: "manual".to_string(),
+            translations: vec![],
+        },
+        "tester",
+    )?;
+    answer_bank::ab_set_entry_status(db, &entry.entry_id, EntryStatus::Approved, "approver")
+}
+
+/// Vault with three answer bank entries and a mapped two-question import.
+fn setup(prefix: &str) -> CoreResult<(PathBuf, SqliteDb, String, Vec<AnswerBankEntry>)> {
+    let vault_root = make_temp_dir(prefix)?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let entries = vec![
+        create_entry(&db, "Is data encrypted at rest?", "Yes, AES-256")?,
+        create_entry(&db, "Is data encrypted in transit?", "Yes, TLS 1.2+")?,
+        create_entry(&db, "Do you have a firewall?", "Yes")?,
+    ];
+
+    let src = vault_root.join("q.csv");
+    std::fs::write(
+        &src,
+        "Question,Answer\nIs customer data encrypted at rest?,\nDescribe SOC2 scope.,\n",
+    )?;
+    let imp = questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    questionnaire::set_column_map(
+        &db,
+        &imp.import_id,
+        &ColumnMap {
+            question: "Question".to_string(),
+            answer: "Answer".to_string(),
+            notes: None,
+        },
+        "tester",
+    )?;
+    Ok((vault_root, db, imp.import_id, entries))
+}
+
+#[test]
+fn generated_suggestions_are_stored_and_reloaded() -> CoreResult<()> {
+    let (vault_root, db, import_id, entries) = setup("cs_match_store")?;
+    let qs = workflow::list_questions(&db, &import_id)?;
+
+    let generated = suggestions::generate_suggestions(&db, &import_id, 2, "tester")?;
+    assert_eq!(generated.len(), 2);
+    assert!(generated.iter().all(|s| s.question_id == qs[0].question_id));
+    assert_eq!(generated[0].answer_bank_entry_id, entries[0].entry_id);
+    assert!(generated[0].score > generated[1].score);
+    assert!(!generated[0].accepted);
+
+    // Reopening the import reads the same rows back.
+    let reloaded = suggestions::list_import_suggestions(&db, &import_id)?;
+    let ids = |v: &[suggestions::QuestionSuggestion]| -> Vec<String> {
+        v.iter().map(|s| s.suggestion_id.clone()).collect()
+    };
+    assert_eq!(ids(&reloaded), ids(&generated));
+    assert_eq!(reloaded[0].score, generated[0].score);
+    assert_eq!(
+        reloaded[0].confidence_explanation,
+        generated[0].confidence_explanation
+    );
+    // The scorer is stored with each row so scores can be reproduced.
+    assert_eq!(reloaded[0].scorer_name.as_deref(), Some("bm25"));
+    assert_eq!(reloaded[0].scorer_version.as_deref(), Some("1"));
+    let explanation = reloaded[0]
+        .explanation
+        .as_ref()
+        .expect("explanation stored");
+    assert_eq!(explanation.summary, reloaded[0].confidence_explanation);
+    assert!(!explanation.terms.is_empty());
+    assert!(!explanation.field_scores.is_empty());
+
+    assert_eq!(
+        workflow::get_question(&db, &qs[0].question_id)?.status,
+        QuestionStatus::Suggested
+    );
+    // Nothing matched the second question, so it stays unanswered.
+    assert!(suggestions::list_suggestions(&db, &qs[1].question_id)?.is_empty());
+    assert_eq!(
+        workflow::get_question(&db, &qs[1].question_id)?.status,
+        QuestionStatus::Unanswered
+    );
+
+    // Regenerating replaces pending suggestions instead of piling them up.
+    let again = suggestions::generate_suggestions(&db, &import_id, 2, "tester")?;
+    assert_eq!(again.len(), 2);
+    assert_eq!(
+        suggestions::list_import_suggestions(&db, &import_id)?.len(),
+        2
+    );
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn accept_and_reject_update_question_and_audit_log() -> CoreResult<()> {
+    let (vault_root, db, import_id, entries) = setup("cs_match_accept")?;
+    let qs = workflow::list_questions(&db, &import_id)?;
+    let q1 = &qs[0].question_id;
+
+    let generated = suggestions::generate_suggestions(&db, &import_id, 2, "tester")?;
+    let (best, second) = (&generated[0], &generated[1]);
+
+    // Rejecting a suggestion hides its entry from later runs.
+    let rejected = suggestions::reject_suggestion(&db, &second.suggestion_id, "reviewer")?;
+    assert!(rejected.rejected_at.is_some());
+    assert!(!rejected.accepted);
+    let regenerated = suggestions::generate_suggestions(&db, &import_id, 3, "tester")?;
+    let pending: Vec<&str> = regenerated
+        .iter()
+        .filter(|s| s.rejected_at.is_none())
+        .map(|s| s.answer_bank_entry_id.as_str())
+        .collect();
+    assert!(!pending.contains(&second.answer_bank_entry_id.as_str()));
+    assert!(regenerated
+        .iter()
+        .any(|s| s.suggestion_id == second.suggestion_id));
+
+    let best = regenerated
+        .iter()
+        .find(|s| s.answer_bank_entry_id == best.answer_bank_entry_id)
+        .unwrap();
+    let accepted = suggestions::accept_suggestion(&db, &best.suggestion_id, "reviewer")?;
+    assert!(accepted.accepted);
+    assert!(accepted.accepted_at.is_some());
+
+    let q = workflow::get_question(&db, q1)?;
+    assert_eq!(q.status, QuestionStatus::Drafted);
+    assert_eq!(
+        q.answer,
+        Some(QuestionAnswer::Entry(entries[0].entry_id.clone()))
+    );
+
+    // Accepted suggestions cannot be rejected, and their entry cannot be
+    // deleted while it answers the question.
+    let err = suggestions::reject_suggestion(&db, &best.suggestion_id, "reviewer").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::Conflict);
+    let err = answer_bank::ab_delete_entry(&db, &entries[0].entry_id, "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::Conflict);
+
+    // Choosing another answer releases the acceptance.
+    workflow::set_question_answer(
+        &db,
+        q1,
+        &QuestionAnswer::Override("Yes, with AES-256".to_string()),
+        "reviewer",
+    )?;
+    assert!(!suggestions::get_suggestion(&db, &best.suggestion_id)?.accepted);
+
+    // Entries that were only suggested can be deleted; their suggestions go too.
+    answer_bank::ab_delete_entry(&db, &entries[1].entry_id, "tester")?;
+    assert!(suggestions::list_suggestions(&db, q1)?
+        .iter()
+        .all(|s| s.answer_bank_entry_id != entries[1].entry_id));
+
+    let rows = db.query_rows_tsv("SELECT event_type FROM audit_event ORDER BY seq ASC;")?;
+    let types: Vec<String> = rows.into_iter().map(|r| r[0].clone()).collect();
+    for want in [
+        "MatchSuggestionsGenerated",
+        "MatchSuggestionRejected",
+        "MatchSuggestionAccepted",
+        "QuestionAnswerSet",
+    ] {
+        assert!(
+            types.iter().any(|t| t == want),
+            "missing audit event {want}"
+        );
+    }
+    core::audit::validator::validate_chain(&db)?;
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn auto_answer_accepts_above_threshold_and_reports_coverage() -> CoreResult<()> {
+    let (vault_root, db, _, entries) = setup("cs_match_auto")?;
+
+    let src = vault_root.join("auto.csv");
+    std::fs::write(
+        &src,
+        "Question,Answer\nIs customer data encrypted at rest?,\nIs there a firewall on every office network?,\nDescribe SOC2 scope.,\nDo you do pen tests?,\n",
+    )?;
+    let imp = questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    let options = AutoAnswerOptions {
+        threshold: 0.5,
+        top_n: 3,
+    };
+
+    let err = suggestions::auto_answer_import(&db, &imp.import_id, &options, "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+
+    questionnaire::set_column_map(
+        &db,
+        &imp.import_id,
+        &ColumnMap {
+            question: "Question".to_string(),
+            answer: "Answer".to_string(),
+            notes: None,
+        },
+        "tester",
+    )?;
+    let qs = workflow::list_questions(&db, &imp.import_id)?;
+    workflow::set_question_status(
+        &db,
+        &qs[3].question_id,
+        QuestionStatus::NotApplicable,
+        "tester",
+    )?;
+
+    let err = suggestions::auto_answer_import(
+        &db,
+        &imp.import_id,
+        &AutoAnswerOptions {
+            threshold: 1.5,
+            top_n: 3,
+        },
+        "tester",
+    )
+    .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+
+    // Entries that are not approved are only suggested.
+    answer_bank::ab_set_entry_status(&db, &entries[0].entry_id, EntryStatus::Draft, "tester")?;
+    let report = suggestions::auto_answer_import(&db, &imp.import_id, &options, "tester")?;
+    assert_eq!((report.auto_filled, report.needs_review), (0, 2));
+    assert_eq!(report.items[0].outcome, AutoAnswerOutcome::NeedsReview);
+    assert_eq!(
+        report.items[0].best_entry_id.as_deref(),
+        Some(entries[0].entry_id.as_str())
+    );
+    answer_bank::ab_set_entry_status(&db, &entries[0].entry_id, EntryStatus::Approved, "approver")?;
+
+    let report = suggestions::auto_answer_import(&db, &imp.import_id, &options, "tester")?;
+    assert_eq!(
+        (
+            report.auto_filled,
+            report.needs_review,
+            report.no_match,
+            report.skipped
+        ),
+        (1, 1, 1, 1)
+    );
+    let outcomes: Vec<(i64, AutoAnswerOutcome)> = report
+        .items
+        .iter()
+        .map(|i| (i.row_number, i.outcome))
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            (2, AutoAnswerOutcome::AutoFilled),
+            (3, AutoAnswerOutcome::NeedsReview),
+            (4, AutoAnswerOutcome::NoMatch),
+        ]
+    );
+    assert_eq!(
+        report.items[0].best_entry_id.as_deref(),
+        Some(entries[0].entry_id.as_str())
+    );
+
+    let qs = workflow::list_questions(&db, &imp.import_id)?;
+    let statuses: Vec<QuestionStatus> = qs.iter().map(|q| q.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            QuestionStatus::Drafted,
+            QuestionStatus::Suggested,
+            QuestionStatus::Unanswered,
+            QuestionStatus::NotApplicable,
+        ]
+    );
+    assert_eq!(
+        qs[0].answer,
+        Some(QuestionAnswer::Entry(entries[0].entry_id.clone()))
+    );
+    let stored = suggestions::list_suggestions(&db, &qs[0].question_id)?;
+    assert!(stored[0].accepted);
+    assert!(stored.iter().skip(1).all(|s| !s.accepted));
+
+    // A second run leaves the auto-filled question alone.
+    let again = suggestions::auto_answer_import(&db, &imp.import_id, &options, "tester")?;
+    assert_eq!((again.auto_filled, again.skipped), (0, 2));
+
+    core::audit::validator::validate_chain(&db)?;
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn synonyms_are_seeded_editable_and_used_for_matching() -> CoreResult<()> {
+    let (vault_root, db, _, _) = setup("cs_match_synonyms")?;
+    let mfa = create_entry(
+        &db,
+        "Do you require multi-factor authentication for remote access?",
+        "Yes, for all staff",
+    )?;
+
+    let seeded = synonyms::list_synonyms(&db)?;
+    assert!(seeded
+        .iter()
+        .any(|s| s.term == "2fa" && s.canonical == "mfa" && s.source == "seed"));
+
+    let src = vault_root.join("syn.csv");
+    std::fs::write(
+        &src,
+        "Question,Answer\nIs 2FA required for remote access?,\nIs SAML federation supported?,\n",
+    )?;
+    let imp = questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    questionnaire::set_column_map(
+        &db,
+        &imp.import_id,
+        &ColumnMap {
+            question: "Question".to_string(),
+            answer: "Answer".to_string(),
+            notes: None,
+        },
+        "tester",
+    )?;
+    let qs = workflow::list_questions(&db, &imp.import_id)?;
+
+    let generated = suggestions::generate_suggestions(&db, &imp.import_id, 1, "tester")?;
+    let top = generated
+        .iter()
+        .find(|s| s.question_id == qs[0].question_id)
+        .unwrap();
+    assert_eq!(top.answer_bank_entry_id, mfa.entry_id);
+    assert!(top.normalized_question.split(' ').any(|t| t == "mfa"));
+    assert!(generated.iter().all(|s| s.question_id != qs[1].question_id));
+
+    // A user synonym links the acronym to an entry's wording.
+    let sso = create_entry(&db, "Do you support single sign-on?", "Yes, via Okta")?;
+    let added = synonyms::set_synonym(&db, "SAML federation", "SSO", "tester")?;
+    assert_eq!(
+        (
+            added.term.as_str(),
+            added.canonical.as_str(),
+            added.source.as_str()
+        ),
+        ("saml federation", "sso", "user")
+    );
+    let generated = suggestions::generate_suggestions(&db, &imp.import_id, 1, "tester")?;
+    let top = generated
+        .iter()
+        .find(|s| s.question_id == qs[1].question_id)
+        .unwrap();
+    assert_eq!(top.answer_bank_entry_id, sso.entry_id);
+
+    let err = synonyms::set_synonym(&db, "sso", "SSO!", "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+    synonyms::delete_synonym(&db, "saml federation", "tester")?;
+    let err = synonyms::delete_synonym(&db, "saml federation", "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+    assert_eq!(synonyms::list_synonyms(&db)?.len(), seeded.len());
+
+    core::audit::validator::validate_chain(&db)?;
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn accepted_wording_is_remembered_as_an_alias() -> CoreResult<()> {
+    let (vault_root, db, import_id, entries) = setup("cs_match_alias")?;
+    let qs = workflow::list_questions(&db, &import_id)?;
+
+    let generated = suggestions::generate_suggestions(&db, &import_id, 1, "tester")?;
+    assert!(generated[0].score < 1.0);
+    suggestions::accept_suggestion(&db, &generated[0].suggestion_id, "reviewer")?;
+
+    let aliases = answer_bank::ab_list_aliases(&db, &entries[0].entry_id)?;
+    assert_eq!(aliases.len(), 1);
+    assert_eq!(aliases[0].alias_text, "Is customer data encrypted at rest?");
+    assert_eq!(
+        aliases[0].source_question_id.as_deref(),
+        Some(qs[0].question_id.as_str())
+    );
+
+    // The next questionnaire with the same wording matches with full confidence.
+    let src = vault_root.join("next.csv");
+    std::fs::write(
+        &src,
+        "Question,Answer\nIS CUSTOMER DATA ENCRYPTED AT REST,\nWhat is your company's legal name?,\n",
+    )?;
+    let next = questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    questionnaire::set_column_map(
+        &db,
+        &next.import_id,
+        &ColumnMap {
+            question: "Question".to_string(),
+            answer: "Answer".to_string(),
+            notes: None,
+        },
+        "tester",
+    )?;
+    let generated = suggestions::generate_suggestions(&db, &next.import_id, 1, "tester")?;
+    assert_eq!(generated[0].answer_bank_entry_id, entries[0].entry_id);
+    assert_eq!(generated[0].score, 1.0);
+
+    // Auto-answer does not teach aliases; answering by hand does.
+    let next_qs = workflow::list_questions(&db, &next.import_id)?;
+    let options = AutoAnswerOptions {
+        threshold: 0.9,
+        top_n: 1,
+    };
+    suggestions::auto_answer_import(&db, &next.import_id, &options, "tester")?;
+    assert_eq!(
+        answer_bank::ab_list_aliases(&db, &entries[0].entry_id)?.len(),
+        1
+    );
+    workflow::set_question_answer(
+        &db,
+        &next_qs[1].question_id,
+        &QuestionAnswer::Entry(entries[2].entry_id.clone()),
+        "reviewer",
+    )?;
+    assert_eq!(
+        answer_bank::ab_list_aliases(&db, &entries[2].entry_id)?[0].alias_text,
+        "What is your company's legal name?"
+    );
+
+    // Aliases can be managed by hand and pull in entries that share no words.
+    let err = answer_bank::ab_add_alias(
+        &db,
+        &entries[0].entry_id,
+        "is customer data encrypted at rest",
+        "tester",
+    )
+    .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::Conflict);
+    let manual = answer_bank::ab_add_alias(
+        &db,
+        &entries[1].entry_id,
+        "Are connections protected on the wire?",
+        "tester",
+    )?;
+    assert_eq!(manual.source_question_id, None);
+    let src = vault_root.join("tls.csv");
+    std::fs::write(
+        &src,
+        "Question,Answer\nAre connections protected on the wire?,\n",
+    )?;
+    let tls = questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    questionnaire::set_column_map(
+        &db,
+        &tls.import_id,
+        &ColumnMap {
+            question: "Question".to_string(),
+            answer: "Answer".to_string(),
+            notes: None,
+        },
+        "tester",
+    )?;
+    let generated = suggestions::generate_suggestions(&db, &tls.import_id, 1, "tester")?;
+    assert_eq!(generated[0].answer_bank_entry_id, entries[1].entry_id);
+    assert_eq!(generated[0].score, 1.0);
+
+    answer_bank::ab_delete_alias(&db, &manual.alias_id, "tester")?;
+    assert!(answer_bank::ab_list_aliases(&db, &entries[1].entry_id)?.is_empty());
+    let err = answer_bank::ab_delete_alias(&db, &manual.alias_id, "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+    let generated = suggestions::generate_suggestions(&db, &tls.import_id, 1, "tester")?;
+    assert!(generated.is_empty());
+
+    core::audit::validator::validate_chain(&db)?;
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+fn de(question: &str, short: &str, long: &str) -> LocalizedText {
+    LocalizedText {
+        locale: "de".to_string(),
+        question_canonical: question.to_string(),
+        answer_short: short.to_string(),
+        answer_long: long.to_string(),
+    }
+}
+
+#[test]
+fn imports_are_matched_in_their_language_with_default_fallback() -> CoreResult<()> {
+    let (vault_root, db, _, entries) = setup("cs_match_locale")?;
+
+    let translated = answer_bank::ab_update_entry(
+        &db,
+        &entries[0].entry_id,
+        AnswerBankUpdatePatch {
+            translations: Some(vec![
+                LocalizedText {
+                    locale: "FR".to_string(),
+                    question_canonical: "Les données sont-elles chiffrées au repos ?".to_string(),
+                    answer_short: "Oui, AES-256".to_string(),
+                    answer_long: "Oui, AES-256".to_string(),
+                },
+                de(
+                    "Werden Daten im Ruhezustand verschlüsselt?",
+                    "Ja, AES-256",
+                    " Alle Daten sind mit AES-256 verschlüsselt. ",
+                ),
+            ]),
+            ..Default::default()
+        },
+        "tester",
+    )?;
+    // Stored normalized and sorted by locale; the hash covers every locale.
+    let locales: Vec<&str> = translated
+        .translations
+        .iter()
+        .map(|t| t.locale.as_str())
+        .collect();
+    assert_eq!(locales, vec!["de", "fr"]);
+    assert_eq!(
+        translated.translations[0].answer_long,
+        "Alle Daten sind mit AES-256 verschlüsselt."
+    );
+    assert_ne!(translated.content_hash, entries[0].content_hash);
+    assert_eq!(translated.text_in("de").answer_short, "Ja, AES-256");
+    assert_eq!(translated.text_in("es").answer_short, "Yes, AES-256");
+
+    let reordered = answer_bank::ab_create_entry(
+        &db,
+        AnswerBankCreateInput {
+            question_canonical: translated.question_canonical.clone(),
+            answer_short: translated.answer_short.clone(),
+            answer_long: translated.answer_long.clone(),
+            notes: None,
+            evidence_links: vec![],
+            owner: "bob".to_string(),
+            last_reviewed_at: None,
+            tags: vec![],
+            source: "manual".to_string(),
+            translations: translated.translations.iter().rev().cloned().collect(),
+        },
+        "tester",
+    )?;
+    assert_eq!(reordered.content_hash, translated.content_hash);
+    answer_bank::ab_delete_entry(&db, &reordered.entry_id, "tester")?;
+
+    let diff = answer_bank::ab_diff_revisions(&db, &entries[0].entry_id, 1, 2)?;
+    assert!(diff
+        .changes
+        .iter()
+        .any(|c| c.field == "answer_short[de]" && c.after.as_deref() == Some("Ja, AES-256")));
+
+    // A German import: the translated entry matches in German, the others in
+    // their default text.
+    let src = vault_root.join("fragen.csv");
+    std::fs::write(
+        &src,
+        "Frage,Antwort\nSind Kundendaten im Ruhezustand verschlüsselt?,\nIs data encrypted in transit?,\n",
+    )?;
+    let imp = questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    assert_eq!(imp.language, answer_bank::DEFAULT_LOCALE);
+    questionnaire::set_column_map(
+        &db,
+        &imp.import_id,
+        &ColumnMap {
+            question: "Frage".to_string(),
+            answer: "Antwort".to_string(),
+            notes: None,
+        },
+        "tester",
+    )?;
+    let imp = questionnaire::set_import_language(&db, &imp.import_id, " DE ", "tester")?;
+    assert_eq!(imp.language, "de");
+    let err =
+        questionnaire::set_import_language(&db, &imp.import_id, "german!", "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+
+    let qs = workflow::list_questions(&db, &imp.import_id)?;
+    let generated = suggestions::generate_suggestions(&db, &imp.import_id, 1, "tester")?;
+    let top = |question_id: &str| {
+        generated
+            .iter()
+            .find(|s| s.question_id == question_id)
+            .map(|s| s.answer_bank_entry_id.clone())
+    };
+    assert_eq!(top(&qs[0].question_id), Some(entries[0].entry_id.clone()));
+    assert_eq!(top(&qs[1].question_id), Some(entries[1].entry_id.clone()));
+
+    let german = AnswerBankMatcher::load(&db, RankingModel::Bm25)?
+        .with_locale("de")
+        .get_suggestions(&db, "Sind Kundendaten im Ruhezustand verschlüsselt?", 1)?;
+    assert_eq!(german[0].answer_short, "Ja, AES-256");
+
+    let err = answer_bank::ab_update_entry(
+        &db,
+        &entries[0].entry_id,
+        AnswerBankUpdatePatch {
+            translations: Some(vec![de("a", "b", "c"), de("d", "e", "f")]),
+            ..Default::default()
+        },
+        "tester",
+    )
+    .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+
+    // Rolling back restores the untranslated content.
+    let restored = answer_bank::ab_rollback_entry(&db, &entries[0].entry_id, 1, "tester")?;
+    assert!(restored.translations.is_empty());
+    assert_eq!(restored.content_hash, entries[0].content_hash);
+
+    core::audit::validator::validate_chain(&db)?;
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
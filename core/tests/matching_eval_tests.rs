@@ -0,0 +1,103 @@
+use core::domain::errors::CoreResult;
+use core::questionnaire::evaluation::{self, EvalBaseline, EvalFixture, EvalReport};
+use core::questionnaire::matching::RankingModel;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Rank cut-off for precision and recall.
+const K: usize = 3;
+/// Baseline name of the pre-BM25 ranking the other configurations are
+/// compared against.
+const LEGACY: &str = "jaccard_legacy";
+
+/// Set to rewrite the stored baseline from the current rankings, after
+/// checking that a change really improves (or knowingly trades off) them.
+const UPDATE_ENV: &str = "UPDATE_MATCHING_BASELINE";
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("matching")
+        .join(name)
+}
+
+fn read_fixture(name: &str) -> String {
+    std::fs::read_to_string(fixture_path(name)).expect("read matching fixture")
+}
+
+fn misses(report: &EvalReport) -> String {
+    report
+        .cases
+        .iter()
+        .filter(|c| c.first_relevant_rank != Some(1))
+        .map(|c| {
+            format!(
+                "  {:?}: expected {:?}, top {} {:?}, first relevant at {:?}",
+                c.question, c.expected, report.k, c.top_k, c.first_relevant_rank
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn matching_does_not_regress_against_baseline() -> CoreResult<()> {
+    let fixture = EvalFixture::from_json_str(&read_fixture("answer_bank.json"))?;
+    let gold = evaluation::parse_gold_set(&read_fixture("gold.json"))?;
+
+    let mut reports = BTreeMap::new();
+    for model in [
+        RankingModel::Bm25,
+        RankingModel::Jaccard,
+        RankingModel::CharNgram,
+        RankingModel::Blended,
+    ] {
+        let report = evaluation::evaluate(&fixture.engine(model.scorer()), &gold, K)?;
+        reports.insert(model.as_str().to_string(), report);
+    }
+    reports.insert(
+        LEGACY.to_string(),
+        evaluation::evaluate(&fixture.legacy_engine(), &gold, K)?,
+    );
+
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        let baseline = EvalBaseline {
+            k: K,
+            configs: reports
+                .iter()
+                .map(|(name, r)| (name.clone(), r.metrics))
+                .collect(),
+        };
+        std::fs::write(
+            fixture_path("baseline.json"),
+            format!("{}\n", baseline.to_canonical_json().encode()),
+        )
+        .expect("write baseline");
+        return Ok(());
+    }
+
+    let baseline = EvalBaseline::from_json_str(&read_fixture("baseline.json"))?;
+    let mut failures = Vec::new();
+    for (name, report) in &reports {
+        let regressed = baseline.regressions(name, report, 0.0);
+        if !regressed.is_empty() {
+            failures.push(format!(
+                "{}\nmisses:\n{}",
+                regressed.join("\n"),
+                misses(report)
+            ));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "matching regressed (rerun with {UPDATE_ENV}=1 to accept):\n{}",
+        failures.join("\n")
+    );
+
+    // The default model should keep ranking most gold questions first, and
+    // better than the pre-BM25 ranking did.
+    assert!(reports["bm25"].metrics.mrr >= 0.8);
+    assert!(reports["bm25"].metrics.mrr > reports[LEGACY].metrics.mrr);
+    Ok(())
+}
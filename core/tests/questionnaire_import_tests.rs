@@ -0,0 +1,242 @@
+use core::domain::errors::{CoreErrorCode, CoreResult};
+use core::questionnaire;
+use core::storage::db::SqliteDb;
+use core::storage::{self, vault_db_path};
+use std::path::{Path, PathBuf};
+
+fn make_temp_dir(prefix: &str) -> CoreResult<PathBuf> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let dir = std::env::temp_dir().join(format!("{}_{}_{}", prefix, std::process::id(), ts));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Zip `parts` (relative path, contents) into `out` using the zip CLI.
+fn write_zip(out: &Path, parts: &[(&str, &str)]) -> CoreResult<()> {
+    let staging = out.with_extension("parts");
+    for (rel, contents) in parts {
+        let p = staging.join(rel);
+        std::fs::create_dir_all(p.parent().unwrap())?;
+        std::fs::write(&p, contents)?;
+    }
+    let mut args = vec![
+        "-X".to_string(),
+        "-q".to_string(),
+        out.display().to_string(),
+    ];
+    args.extend(parts.iter().map(|(rel, _)| rel.to_string()));
+    let status = std::process::Command::new("zip")
+        .current_dir(&staging)
+        .args(&args)
+        .status()?;
+    assert!(status.success(), "zip failed");
+    std::fs::remove_dir_all(&staging)?;
+    Ok(())
+}
+
+const ODS_CONTENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0">
+<office:body><office:spreadsheet>
+<table:table table:name="Questions">
+<table:table-column table:number-columns-repeated="3"/>
+<table:table-row>
+<table:table-cell office:value-type="string"><text:p>Question</text:p></table:table-cell>
+<table:table-cell office:value-type="string"><text:p>Answer</text:p></table:table-cell>
+<table:table-cell office:value-type="string"><text:p>Notes</text:p></table:table-cell>
+<table:table-cell table:number-columns-repeated="1020"/>
+</table:table-row>
+<table:table-row>
+<table:table-cell office:value-type="string"><text:p>Do you encrypt data <text:span>at rest</text:span>?</text:p></table:table-cell>
+<table:table-cell/>
+<table:table-cell office:value-type="string"><text:p>AES&amp;KMS</text:p></table:table-cell>
+</table:table-row>
+<table:table-row>
+<table:table-cell office:value-type="string"><text:p>Is MFA<text:s text:c="2"/>enforced?</text:p></table:table-cell>
+<table:table-cell office:value-type="string"><text:p>Yes</text:p></table:table-cell>
+</table:table-row>
+<table:table-row table:number-rows-repeated="1048570"><table:table-cell table:number-columns-repeated="1024"/></table:table-row>
+</table:table>
+<table:table table:name="Ignored">
+<table:table-row><table:table-cell office:value-type="string"><text:p>Other sheet</text:p></table:table-cell></table:table-row>
+</table:table>
+</office:spreadsheet></office:body></office:document-content>"#;
+
+const DOCX_DOCUMENT: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+<w:p><w:r><w:t>Vendor Security Questionnaire</w:t></w:r></w:p>
+<w:tbl>
+<w:tr><w:tc><w:p><w:r><w:t>Vendor</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Acme</w:t></w:r></w:p></w:tc></w:tr>
+</w:tbl>
+<w:tbl>
+<w:tr><w:tc><w:p><w:r><w:t>ID</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Question</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Response</w:t></w:r></w:p></w:tc></w:tr>
+<w:tr><w:tc><w:p><w:r><w:t>1.1</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t xml:space="preserve">Do you have an </w:t></w:r><w:r><w:t>access control policy?</w:t></w:r></w:p></w:tc><w:tc><w:p/></w:tc></w:tr>
+<w:tr><w:tc><w:tcPr><w:gridSpan w:val="2"/></w:tcPr><w:p><w:r><w:t>Section 2</w:t></w:r></w:p></w:tc><w:tc><w:p/></w:tc></w:tr>
+</w:tbl>
+<w:p/>
+<w:tbl>
+<w:tr><w:tc><w:p><w:r><w:t>ID</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Question</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Response</w:t></w:r></w:p></w:tc></w:tr>
+<w:tr><w:tc><w:p><w:r><w:t>2.1</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Describe your backup process.</w:t></w:r></w:p><w:p><w:r><w:t>Include RPO.</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Daily &lt;encrypted&gt;</w:t></w:r></w:p></w:tc></w:tr>
+</w:tbl>
+</w:body></w:document>"#;
+
+#[test]
+fn import_ods_profiles_first_sheet_columns() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_qna_ods")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let src = vault_root.join("questions.ods");
+    write_zip(
+        &src,
+        &[
+            ("mimetype", "application/vnd.oasis.opendocument.spreadsheet"),
+            ("content.xml", ODS_CONTENT),
+        ],
+    )?;
+
+    let imp = questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    assert_eq!(imp.format, "ods");
+    assert_eq!(imp.status, "imported");
+
+    let cols = questionnaire::list_columns(&db, &imp.import_id)?;
+    let refs: Vec<&str> = cols.iter().map(|c| c.col_ref.as_str()).collect();
+    assert_eq!(refs, vec!["A", "B", "C"]);
+    let labels: Vec<&str> = cols.iter().map(|c| c.label.as_str()).collect();
+    assert_eq!(labels, vec!["Question", "Answer", "Notes"]);
+
+    assert_eq!(cols[0].non_empty_count, 2);
+    assert_eq!(
+        cols[0].sample,
+        vec![
+            "Do you encrypt data at rest?".to_string(),
+            "Is MFA  enforced?".to_string()
+        ]
+    );
+    assert_eq!(cols[1].non_empty_count, 1);
+    assert_eq!(cols[2].sample, vec!["AES&KMS".to_string()]);
+
+    // Repeated non-empty cells and rows are bounded, by count and by the text
+    // they expand to.
+    let long = format!(
+        r#"<table:table-row table:number-rows-repeated="300"><table:table-cell table:number-columns-repeated="16384"><text:p>{}</text:p></table:table-cell></table:table-row>"#,
+        "x".repeat(10_000)
+    );
+    for (name, row) in [
+        (
+            "wide.ods",
+            r#"<table:table-row><table:table-cell table:number-columns-repeated="100000000"><text:p>x</text:p></table:table-cell></table:table-row>"#,
+        ),
+        (
+            "tall.ods",
+            r#"<table:table-row table:number-rows-repeated="100000000"><table:table-cell><text:p>x</text:p></table:table-cell></table:table-row>"#,
+        ),
+        (
+            "dense.ods",
+            r#"<table:table-row table:number-rows-repeated="1000"><table:table-cell table:number-columns-repeated="10000"><text:p>x</text:p></table:table-cell></table:table-row>"#,
+        ),
+        ("long.ods", long.as_str()),
+        (
+            "spaces.ods",
+            r#"<table:table-row><table:table-cell><text:p>x<text:s text:c="1000000000"/></text:p></table:table-cell></table:table-row>"#,
+        ),
+    ] {
+        let src = vault_root.join(name);
+        let content = format!(
+            r#"<office:document-content><office:body><office:spreadsheet><table:table table:name="Big">{}</table:table></office:spreadsheet></office:body></office:document-content>"#,
+            row
+        );
+        write_zip(&src, &[("content.xml", &content)])?;
+        let err =
+            questionnaire::import_questionnaire(&db, &vault_root, &src, "tester").unwrap_err();
+        assert_eq!(err.code, CoreErrorCode::ValidationError, "{}", name);
+    }
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn import_docx_profiles_question_tables() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_qna_docx")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let src = vault_root.join("questions.docx");
+    write_zip(&src, &[("word/document.xml", DOCX_DOCUMENT)])?;
+
+    let imp = questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    assert_eq!(imp.format, "docx");
+
+    let cols = questionnaire::list_columns(&db, &imp.import_id)?;
+    let labels: Vec<&str> = cols.iter().map(|c| c.label.as_str()).collect();
+    assert_eq!(labels, vec!["ID", "Question", "Response"]);
+
+    // The metadata table is skipped; the second section table (same header)
+    // is appended; the merged "Section 2" row occupies columns A-B.
+    assert_eq!(
+        cols[0].sample,
+        vec![
+            "1.1".to_string(),
+            "Section 2".to_string(),
+            "2.1".to_string()
+        ]
+    );
+    assert_eq!(
+        cols[1].sample,
+        vec![
+            "Do you have an access control policy?".to_string(),
+            "Describe your backup process.\nInclude RPO.".to_string()
+        ]
+    );
+    assert_eq!(cols[2].sample, vec!["Daily <encrypted>".to_string()]);
+
+    // Column maps work the same as for spreadsheet imports.
+    let mapped = questionnaire::set_column_map(
+        &db,
+        &imp.import_id,
+        &questionnaire::ColumnMap {
+            question: "B".to_string(),
+            answer: "C".to_string(),
+            notes: None,
+        },
+        "tester",
+    )?;
+    assert_eq!(mapped.status, "mapped");
+    assert!(questionnaire::validate_column_map(&db, &imp.import_id, None)?.ok);
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn import_rejects_unknown_formats_and_tableless_docx() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_qna_formats")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let txt = vault_root.join("questions.txt");
+    std::fs::write(&txt, "Question\n")?;
+    let err = questionnaire::import_questionnaire(&db, &vault_root, &txt, "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::UnsupportedFormat);
+
+    let docx = vault_root.join("empty.docx");
+    write_zip(
+        &docx,
+        &[(
+            "word/document.xml",
+            r#"<w:document xmlns:w="w"><w:body><w:p><w:r><w:t>No tables</w:t></w:r></w:p></w:body></w:document>"#,
+        )],
+    )?;
+    let err = questionnaire::import_questionnaire(&db, &vault_root, &docx, "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ImportFailed);
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use core::domain::errors::{CoreErrorCode, CoreResult};
+use core::questionnaire::revision::{self, RevisionChange};
+use core::questionnaire::workflow::{self, QuestionAnswer, QuestionStatus};
+use core::questionnaire::{self, ColumnMap};
+use core::storage::db::SqliteDb;
+use core::storage::{self, vault_db_path};
+use std::path::PathBuf;
+
+fn make_temp_dir(prefix: &str) -> CoreResult<PathBuf> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let dir = std::env::temp_dir().join(format!("{}_{}_{}", prefix, std::process::id(), ts));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+const V1: &str = "Question,Answer\n\
+Do you encrypt data at rest?,\n\
+Is MFA enforced for all staff?,\n\
+Do you have a firewall?,\n\
+Describe your backup process.,\n";
+
+const V2: &str = "Question,Answer\n\
+Do you encrypt data at rest?,\n\
+Do you run a bug bounty program?,\n\
+Is MFA enforced for all employees?,\n\
+DO YOU HAVE A FIREWALL!,\n";
+
+fn map() -> ColumnMap {
+    ColumnMap {
+        question: "Question".to_string(),
+        answer: "Answer".to_string(),
+        notes: None,
+    }
+}
+
+#[test]
+fn revision_diff_and_carry_over() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_qna_revision")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let v1_path = vault_root.join("v1.csv");
+    std::fs::write(&v1_path, V1)?;
+    let v1 = questionnaire::import_questionnaire(&db, &vault_root, &v1_path, "tester")?;
+    questionnaire::set_column_map(&db, &v1.import_id, &map(), "tester")?;
+    let err = revision::diff_revision(&db, &v1.import_id).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+
+    let qs = workflow::list_questions(&db, &v1.import_id)?;
+    workflow::set_question_answer(
+        &db,
+        &qs[0].question_id,
+        &QuestionAnswer::Override("Yes, AES-256".to_string()),
+        "tester",
+    )?;
+    workflow::set_question_status(&db, &qs[0].question_id, QuestionStatus::Approved, "tester")?;
+    workflow::assign_question(&db, &qs[1].question_id, Some("bob"), "tester")?;
+    workflow::set_question_status(
+        &db,
+        &qs[2].question_id,
+        QuestionStatus::NotApplicable,
+        "tester",
+    )?;
+
+    // Same columns, so the map is reused and work is carried over on import.
+    let v2_path = vault_root.join("v2.csv");
+    std::fs::write(&v2_path, V2)?;
+    let v2 = revision::import_revision(&db, &v1.import_id, &v2_path, "tester")?;
+    assert_eq!(v2.parent_import_id.as_deref(), Some(v1.import_id.as_str()));
+    assert_eq!(v2.status, "mapped");
+    assert_eq!(v2.column_map, Some(map()));
+
+    let diff = revision::diff_revision(&db, &v2.import_id)?;
+    assert_eq!(
+        (diff.unchanged, diff.changed, diff.added, diff.removed),
+        (2, 1, 1, 1)
+    );
+    let got: Vec<(RevisionChange, Option<i64>, Option<i64>)> = diff
+        .items
+        .iter()
+        .map(|i| (i.change, i.row_number, i.parent_row_number))
+        .collect();
+    assert_eq!(
+        got,
+        vec![
+            (RevisionChange::Unchanged, Some(2), Some(2)),
+            (RevisionChange::Added, Some(3), None),
+            (RevisionChange::Changed, Some(4), Some(3)),
+            (RevisionChange::Unchanged, Some(5), Some(4)),
+            (RevisionChange::Removed, None, Some(5)),
+        ]
+    );
+    assert_eq!(
+        diff.items[4].parent_question_text.as_deref(),
+        Some("Describe your backup process.")
+    );
+
+    let v2_qs = workflow::list_questions(&db, &v2.import_id)?;
+    assert_eq!(v2_qs[0].status, QuestionStatus::Approved);
+    assert_eq!(
+        v2_qs[0].answer,
+        Some(QuestionAnswer::Override("Yes, AES-256".to_string()))
+    );
+    assert_eq!(v2_qs[1].status, QuestionStatus::Unanswered);
+    // Reworded questions need a fresh look; nothing is carried over.
+    assert_eq!(v2_qs[2].status, QuestionStatus::Unanswered);
+    assert_eq!(v2_qs[2].assignee, None);
+    assert_eq!(v2_qs[3].status, QuestionStatus::NotApplicable);
+
+    // Carrying over again is a no-op.
+    let again = revision::carry_over_revision(&db, &v2.import_id, "tester")?;
+    assert_eq!(again.carried_over, 0);
+
+    let rows = db.query_rows_tsv(
+        "SELECT COUNT(*) FROM audit_event WHERE event_type='QuestionCarriedOver';",
+    )?;
+    assert_eq!(rows[0][0], "2");
+    core::audit::validator::validate_chain(&db)?;
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn revision_with_new_columns_waits_for_a_map() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_qna_revision_cols")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let v1_path = vault_root.join("v1.csv");
+    std::fs::write(&v1_path, V1)?;
+    let v1 = questionnaire::import_questionnaire(&db, &vault_root, &v1_path, "tester")?;
+    questionnaire::set_column_map(&db, &v1.import_id, &map(), "tester")?;
+    let qs = workflow::list_questions(&db, &v1.import_id)?;
+    workflow::assign_question(&db, &qs[1].question_id, Some("bob"), "tester")?;
+
+    let v2_path = vault_root.join("v2.csv");
+    std::fs::write(
+        &v2_path,
+        "Control question,Vendor response\nIs MFA enforced for all staff?,\n",
+    )?;
+    let v2 = revision::import_revision(&db, &v1.import_id, &v2_path, "tester")?;
+    assert_eq!(v2.status, "imported");
+    assert_eq!(v2.column_map, None);
+    let err = revision::diff_revision(&db, &v2.import_id).unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+
+    questionnaire::set_column_map(
+        &db,
+        &v2.import_id,
+        &ColumnMap {
+            question: "Control question".to_string(),
+            answer: "Vendor response".to_string(),
+            notes: None,
+        },
+        "tester",
+    )?;
+    let report = revision::carry_over_revision(&db, &v2.import_id, "tester")?;
+    assert_eq!(report.carried_over, 1);
+    let v2_qs = workflow::list_questions(&db, &v2.import_id)?;
+    assert_eq!(v2_qs[0].assignee.as_deref(), Some("bob"));
+    assert_eq!(v2_qs[0].status, QuestionStatus::Unanswered);
+
+    let diff = revision::diff_revision(&db, &v2.import_id)?;
+    assert_eq!((diff.unchanged, diff.removed), (1, 3));
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
@@ -0,0 +1,253 @@
+use core::answer_bank::{self, AnswerBankCreateInput};
+use core::domain::errors::{CoreErrorCode, CoreResult};
+use core::questionnaire::workflow::{self, QuestionAnswer, QuestionStatus};
+use core::questionnaire::{self, ColumnMap};
+use core::storage::db::SqliteDb;
+use core::storage::{self, vault_db_path};
+use std::path::PathBuf;
+
+fn make_temp_dir(prefix: &str) -> CoreResult<PathBuf> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let dir = std::env::temp_dir().join(format!("{}_{}_{}", prefix, std::process::id(), ts));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn event_types(db: &SqliteDb) -> CoreResult<Vec<String>> {
+    let rows = db.query_rows_tsv("SELECT event_type FROM audit_event ORDER BY seq ASC;")?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|r| r.first().cloned())
+        .collect())
+}
+
+fn map(question: &str, answer: &str, notes: Option<&str>) -> ColumnMap {
+    ColumnMap {
+        question: question.to_string(),
+        answer: answer.to_string(),
+        notes: notes.map(|s| s.to_string()),
+    }
+}
+
+const CSV: &str = "Question,Answer,Notes\n\
+Do you encrypt data at rest?,Yes,AES-256\n\
+\n\
+,orphan answer,\n\
+\"Is MFA enforced, for all staff?\",,\n\
+Do you run a café bug bounty?,,\n";
+
+#[test]
+fn column_map_materializes_one_question_per_row() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_qna_wf_rows")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let src = vault_root.join("q.csv");
+    std::fs::write(&src, CSV)?;
+    let imp = questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    assert!(workflow::list_questions(&db, &imp.import_id)?.is_empty());
+
+    questionnaire::set_column_map(
+        &db,
+        &imp.import_id,
+        &map("Question", "Answer", Some("Notes")),
+        "tester",
+    )?;
+
+    // Blank rows and rows without question text are skipped; row numbers
+    // refer to the source (header is row 1).
+    let qs = workflow::list_questions(&db, &imp.import_id)?;
+    let got: Vec<(i64, &str)> = qs
+        .iter()
+        .map(|q| (q.row_number, q.question_text.as_str()))
+        .collect();
+    assert_eq!(
+        got,
+        vec![
+            (2, "Do you encrypt data at rest?"),
+            (5, "Is MFA enforced, for all staff?"),
+            (6, "Do you run a café bug bounty?"),
+        ]
+    );
+    assert_eq!(qs[0].source_answer.as_deref(), Some("Yes"));
+    assert_eq!(qs[0].source_notes.as_deref(), Some("AES-256"));
+    assert_eq!(qs[1].source_answer, None);
+    assert!(qs.iter().all(|q| q.status == QuestionStatus::Unanswered));
+
+    // Remapping before any work rebuilds the questions.
+    questionnaire::set_column_map(&db, &imp.import_id, &map("Answer", "Notes", None), "tester")?;
+    let qs = workflow::list_questions(&db, &imp.import_id)?;
+    let texts: Vec<&str> = qs.iter().map(|q| q.question_text.as_str()).collect();
+    assert_eq!(texts, vec!["Yes", "orphan answer"]);
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
+
+#[test]
+fn question_workflow_transitions_are_validated_and_audited() -> CoreResult<()> {
+    let vault_root = make_temp_dir("cs_qna_wf_status")?;
+    storage::vault_create(&vault_root, "TestVault", "tester")?;
+    let db = SqliteDb::new(&vault_db_path(&vault_root));
+    db.migrate()?;
+
+    let src = vault_root.join("q.csv");
+    std::fs::write(&src, CSV)?;
+    let imp = questionnaire::import_questionnaire(&db, &vault_root, &src, "tester")?;
+    questionnaire::set_column_map(
+        &db,
+        &imp.import_id,
+        &map("Question", "Answer", Some("Notes")),
+        "tester",
+    )?;
+    let qs = workflow::list_questions(&db, &imp.import_id)?;
+    let (q1, q2, q3) = (&qs[0].question_id, &qs[1].question_id, &qs[2].question_id);
+
+    let entry = answer_bank::ab_create_entry(
+        &db,
+        AnswerBankCreateInput {
+            question_canonical: "Is data encrypted at rest?".to_string(),
+            answer_short: "Yes".to_string(),
+            answer_long: "All data is encrypted with AES-256.".to_string(),
+            notes: None,
+            evidence_links: vec![],
+            owner: "alice".to_string(),
+            last_reviewed_at: None,
+            tags: vec![],
+            source: "manual".to_string(),
+            translations: vec![],
+        },
+        "tester",
+    )?;
+
+    // Drafting requires an answer; unanswered questions cannot jump to approved.
+    let err =
+        workflow::set_question_status(&db, q1, QuestionStatus::Drafted, "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+    let err =
+        workflow::set_question_status(&db, q1, QuestionStatus::Approved, "tester").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::Conflict);
+
+    let q = workflow::set_question_answer(
+        &db,
+        q1,
+        &QuestionAnswer::Entry(entry.entry_id.clone()),
+        "tester",
+    )?;
+    assert_eq!(q.status, QuestionStatus::Drafted);
+    assert_eq!(
+        q.answer,
+        Some(QuestionAnswer::Entry(entry.entry_id.clone()))
+    );
+    let q = workflow::set_question_status(&db, q1, QuestionStatus::Approved, "reviewer")?;
+    assert_eq!(q.status, QuestionStatus::Approved);
+
+    let q = workflow::set_question_answer(
+        &db,
+        q2,
+        &QuestionAnswer::Override("Yes,\tfor\r\nall staff".to_string()),
+        "tester",
+    )?;
+    assert_eq!(
+        q.answer,
+        Some(QuestionAnswer::Override("Yes,\tfor\nall staff".to_string()))
+    );
+    let err = workflow::set_question_answer(
+        &db,
+        q2,
+        &QuestionAnswer::Entry("missing".to_string()),
+        "tester",
+    )
+    .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::NotFound);
+
+    workflow::set_question_status(&db, q3, QuestionStatus::NotApplicable, "tester")?;
+    let err = workflow::set_question_answer(
+        &db,
+        q3,
+        &QuestionAnswer::Override("n/a".to_string()),
+        "tester",
+    )
+    .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::Conflict);
+
+    let q = workflow::assign_question(&db, q2, Some(" bob "), "tester")?;
+    assert_eq!(q.assignee.as_deref(), Some("bob"));
+    let q = workflow::assign_question(&db, q2, None, "tester")?;
+    assert_eq!(q.assignee, None);
+
+    workflow::add_question_comment(&db, q2, "Check with IT\nfirst", "alice")?;
+    workflow::add_question_comment(&db, q2, "Confirmed.", "bob")?;
+    let err = workflow::add_question_comment(&db, q2, "  ", "bob").unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::ValidationError);
+    let comments = workflow::list_question_comments(&db, q2)?;
+    let got: Vec<(&str, &str)> = comments
+        .iter()
+        .map(|c| (c.author.as_str(), c.body.as_str()))
+        .collect();
+    assert_eq!(
+        got,
+        vec![("alice", "Check with IT\nfirst"), ("bob", "Confirmed.")]
+    );
+
+    let p = workflow::import_progress(&db, &imp.import_id)?;
+    assert_eq!(
+        (
+            p.total,
+            p.unanswered,
+            p.drafted,
+            p.approved,
+            p.not_applicable
+        ),
+        (3, 0, 1, 1, 1)
+    );
+    assert!((p.completion_percent - 200.0 / 3.0).abs() < 1e-9);
+    assert!((p.answered_percent - 100.0).abs() < 1e-9);
+
+    // Once work exists the question column is locked, but the answer and
+    // notes source columns can still be remapped.
+    let err =
+        questionnaire::set_column_map(&db, &imp.import_id, &map("Notes", "Answer", None), "tester")
+            .unwrap_err();
+    assert_eq!(err.code, CoreErrorCode::Conflict);
+    questionnaire::set_column_map(
+        &db,
+        &imp.import_id,
+        &map("Question", "Notes", None),
+        "tester",
+    )?;
+    let q = workflow::get_question(&db, q1)?;
+    assert_eq!(q.status, QuestionStatus::Approved);
+    assert_eq!(q.source_answer.as_deref(), Some("AES-256"));
+    assert_eq!(q.source_notes, None);
+
+    let types = event_types(&db)?;
+    for want in [
+        "QuestionAnswerSet",
+        "QuestionStatusChanged",
+        "QuestionAssigned",
+        "QuestionCommentAdded",
+    ] {
+        assert!(
+            types.iter().any(|t| t == want),
+            "missing audit event {want}"
+        );
+    }
+    // Answer + implied status change are chained in one write.
+    assert_eq!(
+        types
+            .iter()
+            .filter(|t| t.as_str() == "QuestionStatusChanged")
+            .count(),
+        4
+    );
+    core::audit::validator::validate_chain(&db)?;
+
+    let _ = std::fs::remove_dir_all(&vault_root);
+    Ok(())
+}
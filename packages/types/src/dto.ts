@@ -44,9 +44,37 @@ export type QuestionnaireImportDto = {
   source_filename: string;
   source_sha256: string;
   imported_at: string;
-  format: 'csv' | 'xlsx';
+  format: 'csv' | 'xlsx' | 'ods' | 'docx';
   status: 'imported' | 'mapped' | string;
   column_map?: ColumnMapDto;
+  parent_import_id?: string; // set when imported as a revision of another import
+  language: string;          // locale questions are matched in, e.g. 'en' | 'de'
+};
+
+export type RevisionDiffItemDto = {
+  change: 'added' | 'removed' | 'changed' | 'unchanged';
+  question_id?: string;
+  row_number?: number;
+  question_text?: string;
+  parent_question_id?: string;
+  parent_row_number?: number;
+  parent_question_text?: string;
+};
+
+export type RevisionDiffDto = {
+  import_id: string;
+  parent_import_id: string;
+  added: number;
+  removed: number;
+  changed: number;
+  unchanged: number;
+  items: RevisionDiffItemDto[];
+};
+
+export type CarryOverReportDto = {
+  import_id: string;
+  parent_import_id: string;
+  carried_over: number;
 };
 
 export type ColumnMapValidationIssueDto = {
@@ -60,6 +88,49 @@ export type ColumnMapValidationDto = {
   issues: ColumnMapValidationIssueDto[];
 };
 
+export type QuestionStatus =
+  | 'unanswered'
+  | 'suggested'
+  | 'drafted'
+  | 'approved'
+  | 'not_applicable';
+
+export type QuestionnaireQuestionDto = {
+  question_id: string;
+  vault_id: string;
+  import_id: string;
+  row_number: number;     // 1-based source row (header is row 1)
+  question_text: string;
+  source_answer?: string;
+  source_notes?: string;
+  status: QuestionStatus;
+  assignee?: string;
+  answer_bank_entry_id?: string;
+  answer_override?: string;
+  created_at: string;
+  updated_at: string;
+};
+
+export type QuestionCommentDto = {
+  comment_id: string;
+  question_id: string;
+  author: string;
+  body: string;
+  created_at: string;
+};
+
+export type ImportProgressDto = {
+  import_id: string;
+  total: number;
+  unanswered: number;
+  suggested: number;
+  drafted: number;
+  approved: number;
+  not_applicable: number;
+  completion_percent: number;   // 0 - 100, approved + not applicable
+  answered_percent: number;     // 0 - 100, drafted + approved + not applicable
+};
+
 // Phase 2.3 (Answer Bank)
 export type AnswerBankEntryDto = {
   entry_id: string;
@@ -76,6 +147,207 @@ export type AnswerBankEntryDto = {
   content_hash: string;
   created_at: string;
   updated_at: string;
+  status: EntryStatus;     // only 'approved' entries auto-answer
+  last_edited_by?: string; // approval must come from someone else
+  translations: LocalizedTextDto[]; // the entry's own text is 'en'
+};
+
+// Question and answers in one locale, sorted by locale on an entry.
+export type LocalizedTextDto = {
+  locale: string;          // lowercase language tag, e.g. 'de', 'fr-ca'
+  question_canonical: string;
+  answer_short: string;
+  answer_long: string;
+};
+
+export type EntryStatus = 'draft' | 'in_review' | 'approved' | 'retired';
+
+export type AnswerBankAliasDto = {
+  alias_id: string;
+  entry_id: string;
+  alias_text: string;
+  source_question_id?: string;
+  created_by: string;
+  created_at: string;
+};
+
+export type RevisionKind = 'created' | 'updated' | 'rolled_back' | 'reviewed';
+
+export type AnswerBankRevisionDto = {
+  entry_id: string;
+  revision: number;
+  kind: RevisionKind;
+  rolled_back_to?: number;
+  question_canonical: string;
+  answer_short: string;
+  answer_long: string;
+  notes?: string;
+  evidence_links: string[];
+  owner: string;
+  last_reviewed_at?: string;
+  tags: string[];
+  source: string;
+  content_hash: string;
+  created_by: string;
+  created_at: string;      // fixed vault timestamp; use audit_seq for ordering in time
+  translations: LocalizedTextDto[];
+  audit_seq?: number;      // seq of the audit event that recorded the revision
+};
+
+export type RevisionFieldChangeDto = {
+  field: string;           // translated fields as e.g. 'answer_long[de]'
+  before?: string;
+  after?: string;
+};
+
+export type AnswerBankRevisionDiffDto = {
+  entry_id: string;
+  from_revision: number;
+  to_revision: number;
+  changes: RevisionFieldChangeDto[];
+};
+
+export type ReviewPolicyScope = 'default' | 'tag' | 'entry';
+
+export type ReviewPolicyDto = {
+  scope: ReviewPolicyScope;
+  target: string;          // tag or entry_id; '' for the vault default
+  interval_days: number;
+  updated_by: string;
+  updated_at: string;
+};
+
+export type OverdueReviewDto = {
+  entry_id: string;
+  question_canonical: string;
+  last_reviewed_at?: string;
+  interval_days: number;
+  due_on?: string;         // absent when never reviewed
+  days_overdue?: number;
+};
+
+export type OwnerOverdueReviewsDto = {
+  owner: string;
+  entries: OverdueReviewDto[];
+};
+
+export type DuplicateGroupDto = {
+  entries: AnswerBankEntryDto[]; // suggested entry to keep first
+  similarity: number;            // highest question similarity in the group, 0..1
+};
+
+// Bulk import/export. Column refs are CSV header names or XLSX column letters.
+export type AnswerBankColumnMapDto = {
+  question: string;
+  answer_long: string;
+  answer_short?: string;
+  entry_id?: string;       // rows naming an entry update it
+  notes?: string;
+  owner?: string;
+  tags?: string;           // ';'-separated in cells
+  evidence_links?: string; // ';'-separated in cells
+  source?: string;
+};
+
+export type AnswerBankImportProfileDto = {
+  // Same shape as questionnaire column profiles.
+  columns: {
+    col_ref: string;
+    ordinal: number;
+    label: string;
+    non_empty_count: number;
+    sample: string[];
+  }[];
+  suggested_map?: AnswerBankColumnMapDto; // from export-style header labels
+};
+
+export type BulkRowAction = 'create' | 'update' | 'unchanged' | 'duplicate' | 'conflict';
+
+export type BulkImportRowDto = {
+  row_number: number;
+  action: BulkRowAction;
+  entry_id?: string;       // absent for dry-run creates
+  question: string;
+  message?: string;
+};
+
+export type AnswerBankImportReportDto = {
+  source_filename: string;
+  source_sha256: string;
+  dry_run: boolean;
+  created: number;
+  updated: number;
+  unchanged: number;
+  duplicates: number;
+  conflicts: number;
+  rows: BulkImportRowDto[];
+};
+
+export type AnswerBankExportSummaryDto = {
+  format: 'csv' | 'xlsx';
+  entry_count: number;
+  sha256: string;
+  unresolved_variables: string[]; // placeholders left in a rendered export
+};
+
+// Vault variables fill {{name}} placeholders in answers when rendered.
+export type VaultVariableDto = {
+  name: string;            // lowercase snake_case, e.g. "company_name"
+  value: string;
+  updated_by: string;
+  updated_at: string;
+};
+
+export type RenderedEntryDto = {
+  entry_id: string;
+  answer_short: string;
+  answer_long: string;
+  unresolved: string[];    // placeholders without a variable, left as written
+};
+
+export type UnresolvedVariablesDto = {
+  entry_id: string;
+  question_canonical: string;
+  names: string[];
+};
+
+// Tags are '/'-separated paths; "security/encryption" is a child of "security".
+export type AnswerBankTagDto = {
+  tag: string;
+  description: string | null;
+  created_by: string;
+  created_at: string;
+};
+
+export type TagUsageDto = {
+  tag: string;
+  parent: string | null;
+  description: string | null;
+  defined: boolean;        // in the taxonomy, not only on entries
+  entry_count: number;     // entries with exactly this tag
+  total_count: number;     // entries with this tag or a descendant
+};
+
+export type TagRenameReportDto = {
+  from: string;
+  to: string;
+  renamed_tags: string[];  // old names: the tag and its descendants
+  entry_ids: string[];
+  evidence_ids: string[];
+};
+
+export type ControlMappingDto = {
+  entry_id: string;
+  framework: string;       // e.g. "SOC 2", "ISO 27001"
+  control_id: string;      // e.g. "CC6.1", "A.8.5"
+  created_by: string;
+  created_at: string;
+};
+
+export type ControlUsageDto = {
+  framework: string;
+  control_id: string;
+  entry_count: number;
 };
 
 export type AnswerBankCreateInputDto = {
@@ -88,6 +360,7 @@ export type AnswerBankCreateInputDto = {
   last_reviewed_at?: string;
   tags: string[];
   source: 'manual' | 'import' | 'match' | string;
+  translations?: LocalizedTextDto[];
 };
 
 export type AnswerBankUpdatePatchDto = Partial<
@@ -106,12 +379,100 @@ export type AnswerBankListParamsDto = {
 };
 
 // Phase 2.4 (Matching Algorithm)
+export type RankingModel = 'bm25' | 'jaccard' | 'char_ngram' | 'blended'; // bm25 is the default
+
+// Structured match explanation. Offsets are character offsets, end exclusive.
+export type TermOrigin = 'exact' | 'stem' | 'synonym';
+export type MatchField = 'question' | 'answer_short' | 'answer_long' | 'alias';
+
+export type TextSpanDto = {
+  start: number;
+  end: number;
+  text: string;            // as written, e.g. "encrypted" for term "encrypt"
+  origin: TermOrigin;
+};
+
+export type FieldSpanDto = {
+  field: MatchField;
+  alias_index?: number;    // set for field 'alias'
+  span: TextSpanDto;
+};
+
+export type TermMatchDto = {
+  term: string;
+  question_spans: TextSpanDto[];
+  field_spans: FieldSpanDto[];
+};
+
+export type FieldScoreDto = {
+  field: MatchField;
+  score: number;
+};
+
+export type MatchExplanationDto = {
+  summary: string;
+  terms: TermMatchDto[];
+  field_scores: FieldScoreDto[];
+};
+
 export type MatchSuggestionDto = {
   answer_bank_entry_id: string;
   score: number;           // 0.0 - 1.0
   normalized_question: string;
   normalized_answer: string;
   confidence_explanation: string;
+  explanation: MatchExplanationDto;
+  scorer_name: string;     // scorer that produced the score
+  scorer_version: string;
+  stale: boolean;          // entry overdue for review; score scaled down
+  answer_short: string;    // answers with vault variables resolved
+  answer_long: string;
+  unresolved_variables: string[];
+};
+
+// Suggestions stored per imported question (match_suggestion table).
+// Rows stored before scorers/explanations were recorded lack those fields.
+export type QuestionSuggestionDto = Omit<
+  MatchSuggestionDto,
+  'explanation' | 'scorer_name' | 'scorer_version' | 'stale' | 'answer_short' | 'answer_long' | 'unresolved_variables'
+> & {
+  explanation?: MatchExplanationDto;
+  scorer_name?: string;
+  scorer_version?: string;
+  suggestion_id: string;
+  question_id: string;
+  accepted: boolean;
+  accepted_at?: string;
+  rejected_at?: string;
+  created_at: string;
+  updated_at: string;
+};
+
+export type AutoAnswerItemDto = {
+  question_id: string;
+  row_number: number;
+  outcome: 'auto_filled' | 'needs_review' | 'no_match';
+  best_score?: number;
+  best_entry_id?: string;
+};
+
+export type AutoAnswerReportDto = {
+  import_id: string;
+  threshold: number;       // 0.0 - 1.0
+  auto_filled: number;
+  needs_review: number;
+  no_match: number;
+  skipped: number;         // already answered or not applicable
+  items: AutoAnswerItemDto[];
+};
+
+// Vault synonym/acronym dictionary used to normalize text before matching.
+export type MatchSynonymDto = {
+  term: string;        // tokenized phrase, e.g. "multi factor authentication"
+  canonical: string;   // e.g. "mfa"
+  source: 'seed' | 'user';
+  created_at: string;
+  updated_at: string;
 };
 
 export type MatchingInputDto = {
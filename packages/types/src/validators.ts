@@ -103,7 +103,8 @@ export function isAnswerBankEntryDto(value: unknown): value is AnswerBankEntryDt
     typeof v.source === 'string' &&
     typeof v.content_hash === 'string' &&
     typeof v.created_at === 'string' &&
-    typeof v.updated_at === 'string'
+    typeof v.updated_at === 'string' &&
+    typeof v.status === 'string'
   );
 }
 